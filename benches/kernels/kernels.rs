@@ -0,0 +1,181 @@
+/*! Bulk operation benchmarks.
+
+These measure the operations that walk a whole slice, and that are specialized
+on the slice’s domain: the partial elements at its edges are handled bit by
+bit, and the elements between them are handled whole. Each operation is run on
+a slice that begins on an element boundary, and on one that does not, so that
+both the fast path and the cost of the edges are visible.
+
+Run these before and after changes to the domain or access code, or to the
+feature flags, to compare the results. They use `criterion`, which runs on the
+stable compiler and keeps the previous run for comparison. `criterion` needs a
+newer compiler than `bitvec` itself, so these live in their own crate:
+
+```sh
+cd benches/kernels
+cargo bench
+cargo bench --features bench-internals
+```
+
+The `bench-internals` feature adds benchmarks of the inner loops themselves,
+from the hidden `bitvec::kernels` module. Downstream crates can enable the same
+feature to time those loops with their own `BitOrder` types.
+!*/
+
+use bitvec::prelude::*;
+use criterion::{
+	black_box,
+	criterion_group,
+	criterion_main,
+	Criterion,
+};
+
+/// The number of elements in each benchmark buffer.
+const ELTS: usize = 64;
+
+/// A buffer with a mix of set and cleared bits in every element.
+fn data() -> [usize; ELTS] {
+	let mut out = [0usize; ELTS];
+	for (n, elt) in out.iter_mut().enumerate() {
+		*elt = n.wrapping_mul(0x9E37_79B9).rotate_left(n as u32);
+	}
+	out
+}
+
+fn count_ones(c: &mut Criterion) {
+	let src = data();
+	let bits = src.bits::<Local>();
+	c.bench_function("count_ones_aligned", |b| {
+		b.iter(|| black_box(bits).count_ones())
+	});
+	let bits = &src.bits::<Local>()[3 .. 4000];
+	c.bench_function("count_ones_unaligned", |b| {
+		b.iter(|| black_box(bits).count_ones())
+	});
+}
+
+fn iteration(c: &mut Criterion) {
+	let src = data();
+	let bits = src.bits::<Local>();
+	c.bench_function("iter_aligned", |b| {
+		b.iter(|| black_box(bits).iter().filter(|b| **b).count())
+	});
+	let bits = &src.bits::<Local>()[3 .. 4000];
+	c.bench_function("iter_unaligned", |b| {
+		b.iter(|| black_box(bits).iter().filter(|b| **b).count())
+	});
+}
+
+fn boolean(c: &mut Criterion) {
+	let mut dst = data();
+	let src = data();
+	let rhs = src.bits::<Local>();
+	c.bench_function("and_assign", |b| {
+		b.iter(|| *dst.bits_mut::<Local>() &= black_box(rhs).iter().copied())
+	});
+	c.bench_function("or_assign", |b| {
+		b.iter(|| *dst.bits_mut::<Local>() |= black_box(rhs).iter().copied())
+	});
+	c.bench_function("xor_assign", |b| {
+		b.iter(|| *dst.bits_mut::<Local>() ^= black_box(rhs).iter().copied())
+	});
+
+	let srcs = [data(), data(), data(), data(), data(), data(), data(), data()];
+	let ops = [
+		srcs[0].bits::<Local>(),
+		srcs[1].bits::<Local>(),
+		srcs[2].bits::<Local>(),
+		srcs[3].bits::<Local>(),
+		srcs[4].bits::<Local>(),
+		srcs[5].bits::<Local>(),
+		srcs[6].bits::<Local>(),
+		srcs[7].bits::<Local>(),
+	];
+	c.bench_function("and_many", |b| {
+		b.iter(|| {
+			dst.bits_mut::<Local>().and_many(black_box(&ops).iter().copied())
+		})
+	});
+}
+
+fn shifts(c: &mut Criterion) {
+	let mut dst = data();
+	c.bench_function("shl_assign", |b| {
+		b.iter(|| *dst.bits_mut::<Local>() <<= black_box(13))
+	});
+	c.bench_function("shr_assign", |b| {
+		b.iter(|| *dst.bits_mut::<Local>() >>= black_box(13))
+	});
+	c.bench_function("shl_assign_elements", |b| {
+		b.iter(|| *dst.bits_mut::<Local>() <<= black_box(200))
+	});
+}
+
+fn copies(c: &mut Criterion) {
+	let mut dst = [0usize; ELTS];
+	let src = data();
+	c.bench_function("copy_aligned", |b| {
+		let (to, from) = (dst.bits_mut::<Local>(), src.bits::<Local>());
+		b.iter(|| to.copy_from_slice(black_box(from)))
+	});
+	c.bench_function("copy_same_offset", |b| {
+		let to = &mut dst.bits_mut::<Local>()[3 .. 4000];
+		let from = &src.bits::<Local>()[3 .. 4000];
+		b.iter(|| to.copy_from_slice(black_box(from)))
+	});
+	c.bench_function("copy_misaligned", |b| {
+		let to = &mut dst.bits_mut::<Local>()[3 .. 4000];
+		let from = &src.bits::<Local>()[5 .. 4002];
+		b.iter(|| to.copy_from_slice(black_box(from)))
+	});
+	c.bench_function("set_all_unaligned", |b| {
+		let bits = &mut dst.bits_mut::<Local>()[3 .. 4000];
+		b.iter(|| black_box(&mut *bits).set_all(true))
+	});
+}
+
+#[cfg(feature = "bench-internals")]
+fn inner_loops(c: &mut Criterion) {
+	use bitvec::kernels;
+
+	let src = data();
+	let elts = src.bits::<Local>().as_total_slice();
+	c.bench_function("kernel_count_body", |b| {
+		b.iter(|| kernels::count_body::<usize>(black_box(elts)))
+	});
+	c.bench_function("kernel_count_edge", |b| {
+		b.iter(|| {
+			kernels::count_edge::<Local, usize>(black_box(src[1]), 3 .. 61)
+		})
+	});
+
+	let mut dst = [0usize; ELTS];
+	let dst = dst.bits_mut::<Local>().as_total_slice();
+	c.bench_function("kernel_copy_body", |b| {
+		b.iter(|| kernels::copy_body::<usize>(dst, black_box(elts)))
+	});
+
+	let mut buf = data();
+	c.bench_function("kernel_shift_elements_down", |b| {
+		b.iter(|| kernels::shift_elements_down(&mut buf, black_box(3)))
+	});
+	c.bench_function("kernel_shift_elements_up", |b| {
+		b.iter(|| kernels::shift_elements_up(&mut buf, black_box(3)))
+	});
+}
+
+#[cfg(not(feature = "bench-internals"))]
+criterion_group!(benches, count_ones, iteration, boolean, shifts, copies);
+
+#[cfg(feature = "bench-internals")]
+criterion_group!(
+	benches,
+	count_ones,
+	iteration,
+	boolean,
+	shifts,
+	copies,
+	inner_loops,
+);
+
+criterion_main!(benches);
@@ -14,6 +14,12 @@ fi
 
 $CARGO clean
 $CARGO build $CARGO_TARGET --all-features
+# The `futures` module uses the host `std`, rather than the SGX `std` that the
+# default features select.
+$CARGO build $CARGO_TARGET --no-default-features --features atomic,futures
 if [ -z $DISABLE_TESTS ]; then
     $CARGO test $CARGO_TARGET --all-features
+    $CARGO test $CARGO_TARGET --no-default-features --features atomic,futures
+    # Without `atomic`, memory is accessed through `Cell` rather than atomics.
+    $CARGO test $CARGO_TARGET --no-default-features --features alloc
 fi
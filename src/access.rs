@@ -16,19 +16,62 @@ builds, or `AtomicT` in atomic builds.
 use crate::{
 	indices::BitIdx,
 	order::BitOrder,
+	pointer::BitPtr,
 	store::BitStore,
 };
 
 use core::{
+	cell::Cell,
 	fmt::Debug,
+	ptr,
 	sync::atomic::Ordering,
 };
 
+#[cfg(feature = "atomic")]
+use core::sync::atomic;
+
 use radium::{
 	marker::BitOps,
 	Radium,
 };
 
+/** Resolves a bit of a handle to its memory element, for an unchecked accessor.
+
+The single-bit `_unchecked` accessors reach memory only through this function.
+When the `debug-unchecked` feature is enabled, or the `unchecked-release`
+feature is enabled in a build with debug assertions, it audits the handle, the
+index, and the element and bit that the index resolves to, before any memory is
+touched. Otherwise, it only computes the location.
+
+# Parameters
+
+- `bitptr`: The handle being accessed.
+- `index`: The bit index, from the start of the handle.
+- `op`: The name of the accessor, for the diagnostic.
+
+# Returns
+
+The element containing the bit at `index`, and the position of that bit inside
+it.
+
+# Safety
+
+`index` must be less than `bitptr.len()`, and the returned reference must not
+outlive the memory that `bitptr` describes.
+**/
+#[inline(always)]
+pub(crate) unsafe fn unchecked_element<'a, T>(
+	bitptr: BitPtr<T>,
+	index: usize,
+	op: &str,
+) -> (&'a T::Access, BitIdx<T>)
+where T: BitStore {
+	bitptr.audit_index(index, op);
+	let (elt, bit) = bitptr.head().offset(index as isize);
+	bitptr.audit_bit(elt, bit, op);
+	(&*bitptr.pointer().a().offset(elt), bit)
+}
+
 /** Access interface for shared/mutable memory access.
 
 `&BitSlice` and `&mut BitSlice` contexts must route through their `Access`
@@ -161,6 +204,65 @@ where T: BitStore + BitOps + Sized
 		}
 	}
 
+	/// Set a single bit in an element to some value, and return its previous
+	/// value.
+	///
+	/// This is a single read-modify-write operation, and so is atomic in
+	/// atomic builds.
+	///
+	/// # Type Parameters
+	///
+	/// - `O`: A `BitOrder` implementation which translates `place` into a
+	///   usable bit-mask.
+	///
+	/// # Parameters
+	///
+	/// - `&self`: A shared reference to underlying memory.
+	/// - `place`: A semantic bit index in the `self` element.
+	/// - `value`: The value to which the bit controlled by `place` shall be
+	///   set.
+	///
+	/// # Returns
+	///
+	/// The value of the bit before it was set.
+	#[inline]
+	fn replace_bit<O>(&self, place: BitIdx<T>, value: bool) -> bool
+	where O: BitOrder {
+		let mask = *O::mask(place);
+		let old = if value {
+			self.fetch_or(mask, Ordering::Relaxed)
+		}
+		else {
+			self.fetch_and(!mask, Ordering::Relaxed)
+		};
+		old & mask != T::FALSE
+	}
+
+	/// Invert a single bit in an element, and return its previous value.
+	///
+	/// This is a single read-modify-write operation, and so is atomic in
+	/// atomic builds.
+	///
+	/// # Type Parameters
+	///
+	/// - `O`: A `BitOrder` implementation which translates `place` into a
+	///   usable bit-mask.
+	///
+	/// # Parameters
+	///
+	/// - `&self`: A shared reference to underlying memory.
+	/// - `place`: A semantic bit index in the `self` element.
+	///
+	/// # Returns
+	///
+	/// The value of the bit before it was inverted.
+	#[inline]
+	fn toggle_bit<O>(&self, place: BitIdx<T>) -> bool
+	where O: BitOrder {
+		let mask = *O::mask(place);
+		self.fetch_xor(mask, Ordering::Relaxed) & mask != T::FALSE
+	}
+
 	/// Read a value out of a contended memory element and into a local scope.
 	///
 	/// # Parameters
@@ -185,6 +287,53 @@ where T: BitStore + BitOps + Sized
 		Radium::store(self, value, Ordering::Relaxed)
 	}
 
+	/// Reads the element with a volatile load.
+	///
+	/// The compiler may not elide, duplicate, or reorder this read with
+	/// respect to other volatile accesses. This is required when the element
+	/// is a memory-mapped device register, where each read can have effects.
+	///
+	/// # Parameters
+	///
+	/// - `&self`: A shared reference to underlying memory.
+	///
+	/// # Returns
+	///
+	/// The value of `*self`, as read from memory.
+	///
+	/// # Safety
+	///
+	/// This is not atomic, even in atomic builds. No other thread may write to
+	/// `*self` during the read.
+	unsafe fn load_volatile(&self) -> T {
+		ptr::read_volatile(self.as_ptr())
+	}
+
+	/// Writes the element with a volatile store.
+	///
+	/// The compiler may not elide, duplicate, or reorder this write with
+	/// respect to other volatile accesses.
+	///
+	/// # Parameters
+	///
+	/// - `&self`: A shared reference to underlying memory.
+	/// - `value`: The new value to write into `*self`.
+	///
+	/// # Safety
+	///
+	/// This is not atomic, even in atomic builds. No other thread may read or
+	/// write `*self` during the write.
+	unsafe fn store_volatile(&self, value: T) {
+		ptr::write_volatile(self.as_ptr(), value)
+	}
+
+	/// Produces a raw pointer to the element inside the shared-mutable
+	/// wrapper.
+	///
+	/// The pointer is derived from the wrapper’s interior mutability, so it may
+	/// be written through while `&self` is live.
+	fn as_ptr(&self) -> *mut T;
+
 	/// Converts a slice of `BitAccess` to a mutable slice of `BitStore`.
 	///
 	/// # Safety
@@ -196,9 +345,41 @@ where T: BitStore + BitOps + Sized
 	}
 }
 
-impl<T, R> BitAccess<T> for R
+impl<T> BitAccess<T> for Cell<T>
 where
 	T: BitStore + BitOps,
-	R: Debug + Radium<T>,
+	Self: Radium<T>,
 {
+	#[inline(always)]
+	fn as_ptr(&self) -> *mut T {
+		Cell::as_ptr(self)
+	}
+}
+
+/// Batch implementation of `BitAccess` for the atomic types.
+#[cfg(feature = "atomic")]
+macro_rules! bitaccess {
+	($($t:ty => $atom:ty;)*) => { $(
+		impl BitAccess<$t> for $atom {
+			#[inline(always)]
+			fn as_ptr(&self) -> *mut $t {
+				//  The atomic has the same layout as `$t`, and holds it in an
+				//  `UnsafeCell`, so the pointer may be written through.
+				self as *const Self as *mut $t
+			}
+		}
+	)* };
+}
+
+#[cfg(feature = "atomic")]
+bitaccess! {
+	u8 => atomic::AtomicU8;
+	u16 => atomic::AtomicU16;
+	u32 => atomic::AtomicU32;
+	usize => atomic::AtomicUsize;
+}
+
+#[cfg(all(feature = "atomic", target_pointer_width = "64"))]
+bitaccess! {
+	u64 => atomic::AtomicU64;
 }
@@ -0,0 +1,914 @@
+/*! Bit buffers in caller-chosen memory
+
+`BitVec` and `BitBox` always draw their memory from the global allocator. Code
+that must control where its buffers live, such as an enclave that keeps secrets
+in a dedicated region, or a parser that places its scratch space in a bump
+arena, cannot use them.
+
+This module provides the parallel types [`BitVecIn`] and [`BitBoxIn`], which
+take their memory from any implementor of [`BitAllocator`]. They dereference to
+`BitSlice`, so every slice method is available on them, and they provide the
+growth and conversion methods of the global types.
+
+This module does not require the `alloc` feature, and so can be used by
+`#![no_std]` programs that have no global allocator at all. With that feature
+enabled, [`Global`] forwards to the global allocator, and buffers that use it
+convert into `BitVec` and `BitBox` without copying.
+
+[`BitAllocator`]: trait.BitAllocator.html
+[`BitBoxIn`]: struct.BitBoxIn.html
+[`BitVecIn`]: struct.BitVecIn.html
+[`Global`]: struct.Global.html
+!*/
+
+use crate::{
+	indices::Indexable,
+	order::BitOrder,
+	pointer::BitPtr,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[cfg(feature = "alloc")]
+use crate::{
+	boxed::BitBox,
+	vec::BitVec,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use core::{
+	alloc::Layout,
+	cmp,
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
+	marker::PhantomData,
+	mem,
+	ops::{
+		Deref,
+		DerefMut,
+	},
+	ptr::{
+		self,
+		NonNull,
+	},
+	slice,
+};
+
+/** A source of memory for bit buffers.
+
+# Safety
+
+`allocate` must return either `None`, or a block of memory that is valid for
+reads and writes of `layout.size()` bytes, is aligned to `layout.align()`, and
+remains valid until it is passed to `deallocate` or `grow`. The buffers in this
+module never request a block of zero bytes.
+
+# Examples
+
+A bump arena over a fixed buffer, which never releases memory:
+
+```rust
+use bitvec::{allocator::*, prelude::*};
+use core::{
+    alloc::Layout,
+    cell::{Cell, UnsafeCell},
+    ptr::NonNull,
+};
+
+struct Bump {
+    buf: UnsafeCell<[u64; 4]>,
+    used: Cell<usize>,
+}
+
+unsafe impl BitAllocator for Bump {
+    fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+        let base = self.buf.get() as usize;
+        let mask = layout.align() - 1;
+        let start = (base + self.used.get() + mask) & !mask;
+        let end = start + layout.size();
+        if end > base + 32 {
+            return None;
+        }
+        self.used.set(end - base);
+        NonNull::new(start as *mut u8)
+    }
+
+    unsafe fn deallocate(&self, _: NonNull<u8>, _: Layout) {}
+}
+
+let arena = Bump { buf: UnsafeCell::new([0; 4]), used: Cell::new(0) };
+let mut bv = BitVecIn::<Msb0, u8, _>::new_in(&arena);
+bv.extend_from_bitslice(bits![1, 0, 1, 1]);
+assert_eq!(bv.as_bitslice(), bits![1, 0, 1, 1]);
+
+//  The arena runs out of room.
+assert!(bv.try_reserve(1000).is_err());
+```
+**/
+pub unsafe trait BitAllocator {
+	/// Allocates a block of memory.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `layout`: The size and alignment of the block. The size is never
+	///   zero.
+	///
+	/// # Returns
+	///
+	/// The start of the block, or `None` if the allocator cannot provide it.
+	fn allocate(&self, layout: Layout) -> Option<NonNull<u8>>;
+
+	/// Releases a block of memory.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `ptr`: The start of a block produced by this allocator.
+	/// - `layout`: The layout with which the block was produced.
+	///
+	/// # Safety
+	///
+	/// `ptr` must have been produced by this allocator with `layout`, and not
+	/// yet released.
+	unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout);
+
+	/// Moves the contents of a block into a larger one.
+	///
+	/// The default implementation allocates a new block, copies the old block
+	/// into it, and releases the old block.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `ptr`: The start of a block produced by this allocator.
+	/// - `old`: The layout with which the block was produced.
+	/// - `new`: The layout of the block to produce. Its size is not less than
+	///   the old size, and its alignment is the same.
+	///
+	/// # Returns
+	///
+	/// The start of a block holding the contents of the old block, or `None`
+	/// if the allocator cannot provide it. The old block is still valid when
+	/// this returns `None`.
+	///
+	/// # Safety
+	///
+	/// `ptr` must have been produced by this allocator with `old`, and not yet
+	/// released.
+	unsafe fn grow(
+		&self,
+		ptr: NonNull<u8>,
+		old: Layout,
+		new: Layout,
+	) -> Option<NonNull<u8>>
+	{
+		let out = self.allocate(new)?;
+		ptr::copy_nonoverlapping(ptr.as_ptr(), out.as_ptr(), old.size());
+		self.deallocate(ptr, old);
+		Some(out)
+	}
+}
+
+unsafe impl<A> BitAllocator for &A
+where A: BitAllocator + ?Sized
+{
+	fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+		(**self).allocate(layout)
+	}
+
+	unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+		(**self).deallocate(ptr, layout)
+	}
+
+	unsafe fn grow(
+		&self,
+		ptr: NonNull<u8>,
+		old: Layout,
+		new: Layout,
+	) -> Option<NonNull<u8>>
+	{
+		(**self).grow(ptr, old, new)
+	}
+}
+
+/** The global allocator, as used by `BitVec` and `BitBox`.
+
+Buffers that use it can be converted into, and from, the global types without
+copying.
+**/
+#[cfg(feature = "alloc")]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Global;
+
+#[cfg(feature = "alloc")]
+unsafe impl BitAllocator for Global {
+	fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+		NonNull::new(unsafe { alloc::alloc::alloc(layout) })
+	}
+
+	unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+		alloc::alloc::dealloc(ptr.as_ptr(), layout)
+	}
+
+	unsafe fn grow(
+		&self,
+		ptr: NonNull<u8>,
+		old: Layout,
+		new: Layout,
+	) -> Option<NonNull<u8>>
+	{
+		NonNull::new(alloc::alloc::realloc(ptr.as_ptr(), old, new.size()))
+	}
+}
+
+/** The error produced when a buffer cannot grow.
+
+This is returned by [`BitVecIn::try_reserve`].
+
+[`BitVecIn::try_reserve`]: struct.BitVecIn.html#method.try_reserve
+**/
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AllocError {
+	/// The requested capacity has more bits than a `BitSlice` can address, or
+	/// more bytes than a buffer can hold.
+	CapacityOverflow,
+	/// The allocator could not provide a buffer.
+	Exhausted {
+		/// The layout that was requested.
+		layout: Layout,
+	},
+}
+
+impl AllocError {
+	/// Gets the layout of the block that could not be allocated.
+	///
+	/// # Returns
+	///
+	/// The requested layout, or `None` if the capacity overflowed before a
+	/// layout could be computed.
+	pub fn layout(&self) -> Option<Layout> {
+		match *self {
+			AllocError::CapacityOverflow => None,
+			AllocError::Exhausted { layout } => Some(layout),
+		}
+	}
+}
+
+impl Display for AllocError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match *self {
+			AllocError::CapacityOverflow => fmt.write_str("Capacity overflow"),
+			AllocError::Exhausted { layout } => write!(
+				fmt,
+				"Allocation of {} bytes, aligned to {}, failed",
+				layout.size(),
+				layout.align(),
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocError {
+}
+
+/** A growable bit vector whose memory comes from an allocator of the caller’s
+choice.
+
+This is the counterpart of [`BitVec`] for memory that does not come from the
+global allocator. Its live region always begins at the first bit of its first
+element.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within elements.
+- `T: BitStore`: The element type of the buffer.
+- `A: BitAllocator`: The source of the buffer. Pass a reference to share one
+  allocator, such as an arena, between many buffers.
+
+[`BitVec`]: ../vec/struct.BitVec.html
+**/
+pub struct BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	/// Phantom `BitOrder` member to satisfy the constraint checker.
+	_order: PhantomData<O>,
+	/// The first element of the buffer. This is dangling while `capacity` is
+	/// zero.
+	data: NonNull<T>,
+	/// The number of live bits.
+	len: usize,
+	/// The number of *elements* in the buffer.
+	capacity: usize,
+	/// The source of the buffer.
+	alloc: A,
+}
+
+impl<O, T, A> BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	/// Constructs an empty vector, which will allocate from `alloc`.
+	///
+	/// The vector does not allocate until bits are pushed onto it.
+	pub fn new_in(alloc: A) -> Self {
+		Self {
+			_order: PhantomData,
+			data: NonNull::dangling(),
+			len: 0,
+			capacity: 0,
+			alloc,
+		}
+	}
+
+	/// Constructs an empty vector with room for at least `capacity` bits.
+	///
+	/// # Panics
+	///
+	/// This panics if the allocator cannot provide the buffer.
+	pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+		let mut out = Self::new_in(alloc);
+		out.reserve(capacity);
+		out
+	}
+
+	/// Copies a bit slice into a new vector.
+	///
+	/// # Panics
+	///
+	/// This panics if the allocator cannot provide the buffer.
+	pub fn from_bitslice_in(slice: &BitSlice<O, T>, alloc: A) -> Self {
+		let mut out = Self::with_capacity_in(slice.len(), alloc);
+		out.extend_from_bitslice(slice);
+		out
+	}
+
+	/// Gets the allocator that provides the buffer.
+	pub fn allocator(&self) -> &A {
+		&self.alloc
+	}
+
+	/// Returns the number of bits the vector can hold without reallocating.
+	pub fn capacity(&self) -> usize {
+		self.capacity * T::BITS as usize
+	}
+
+	/// Reserves capacity for at least `additional` more bits.
+	///
+	/// # Panics
+	///
+	/// This panics if the new capacity overflows `BitPtr::<T>::MAX_BITS`, or
+	/// if the allocator cannot provide the buffer.
+	pub fn reserve(&mut self, additional: usize) {
+		if let Err(err) = self.try_reserve(additional) {
+			panic!("{}", err);
+		}
+	}
+
+	/// Reserves capacity for at least `additional` more bits, reporting
+	/// failure rather than panicking.
+	///
+	/// On failure, the vector is unchanged.
+	///
+	/// # Returns
+	///
+	/// `Err(AllocError::CapacityOverflow)` if the new capacity overflows
+	/// `BitPtr::<T>::MAX_BITS` or the address space, and
+	/// `Err(AllocError::Exhausted)` if the allocator cannot provide the buffer.
+	pub fn try_reserve(&mut self, additional: usize) -> Result<(), AllocError> {
+		let bits = self
+			.len
+			.checked_add(additional)
+			.filter(|&bits| bits <= BitPtr::<T>::MAX_BITS)
+			.ok_or(AllocError::CapacityOverflow)?;
+		let (elts, _) = 0u8.idx::<T>().span(bits);
+		if elts <= self.capacity {
+			return Ok(());
+		}
+		//  Grow geometrically, so that repeated pushes are amortized, unless
+		//  the doubled buffer would not fit in the address space.
+		let mut capacity = cmp::max(elts, self.capacity * 2);
+		if Self::try_layout(capacity).is_none() {
+			capacity = elts;
+		}
+		let layout =
+			Self::try_layout(capacity).ok_or(AllocError::CapacityOverflow)?;
+		let data = if self.capacity == 0 {
+			self.alloc.allocate(layout)
+		}
+		else {
+			let old = Self::layout(self.capacity);
+			unsafe { self.alloc.grow(self.data.cast(), old, layout) }
+		};
+		let data = data.ok_or(AllocError::Exhausted { layout })?;
+		self.data = data.cast();
+		self.capacity = capacity;
+		Ok(())
+	}
+
+	/// Appends a bit to the back of the vector.
+	///
+	/// # Panics
+	///
+	/// This panics if the allocator cannot provide the buffer.
+	pub fn push(&mut self, value: bool) {
+		self.reserve(1);
+		let len = self.len;
+		//  A new element is cleared before its first bit goes live.
+		if len & T::MASK as usize == 0 {
+			unsafe {
+				self.data.as_ptr().add(len >> T::INDX).write(T::FALSE);
+			}
+		}
+		self.len += 1;
+		self.as_mut_bitslice().set(len, value);
+	}
+
+	/// Removes the last bit from the vector, and returns it.
+	pub fn pop(&mut self) -> Option<bool> {
+		if self.len == 0 {
+			return None;
+		}
+		let out = self.as_bitslice()[self.len - 1];
+		self.len -= 1;
+		Some(out)
+	}
+
+	/// Shortens the vector to `len` bits. This has no effect if the vector is
+	/// already shorter.
+	pub fn truncate(&mut self, len: usize) {
+		self.len = cmp::min(self.len, len);
+	}
+
+	/// Removes every bit from the vector, keeping its buffer.
+	pub fn clear(&mut self) {
+		self.len = 0;
+	}
+
+	/// Resizes the vector to `new_len` bits, filling any new bits with
+	/// `value`.
+	///
+	/// # Panics
+	///
+	/// This panics if the allocator cannot provide the buffer.
+	pub fn resize(&mut self, new_len: usize, value: bool) {
+		if new_len <= self.len {
+			self.truncate(new_len);
+			return;
+		}
+		self.reserve(new_len - self.len);
+		for _ in self.len .. new_len {
+			self.push(value);
+		}
+	}
+
+	/// Appends a bit slice of any type onto the vector.
+	///
+	/// # Panics
+	///
+	/// This panics if the allocator cannot provide the buffer.
+	pub fn extend_from_bitslice<P, U>(&mut self, other: &BitSlice<P, U>)
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		self.reserve(other.len());
+		for bit in other.iter().copied() {
+			self.push(bit);
+		}
+	}
+
+	/// Views the vector as a bit slice.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		&BitSlice::from_slice(self.as_slice())[.. self.len]
+	}
+
+	/// Views the vector as a mutable bit slice.
+	pub fn as_mut_bitslice(&mut self) -> &mut BitSlice<O, T> {
+		let len = self.len;
+		&mut BitSlice::from_slice_mut(self.as_mut_slice())[.. len]
+	}
+
+	/// Views the elements that hold the live bits.
+	///
+	/// The last element may have dead bits, whose values are unspecified.
+	pub fn as_slice(&self) -> &[T] {
+		let (elts, _) = 0u8.idx::<T>().span(self.len);
+		unsafe { slice::from_raw_parts(self.data.as_ptr(), elts) }
+	}
+
+	/// Views the elements that hold the live bits, mutably.
+	///
+	/// The last element may have dead bits, whose values are unspecified.
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		let (elts, _) = 0u8.idx::<T>().span(self.len);
+		unsafe { slice::from_raw_parts_mut(self.data.as_ptr(), elts) }
+	}
+
+	/// Freezes the vector into a box, which keeps its buffer and allocator.
+	pub fn into_boxed_bitslice(self) -> BitBoxIn<O, T, A> {
+		BitBoxIn { inner: self }
+	}
+
+	/// Computes the layout of a buffer of `capacity` elements.
+	fn layout(capacity: usize) -> Layout {
+		Self::try_layout(capacity).expect("Capacity overflow")
+	}
+
+	/// Computes the layout of a buffer of `capacity` elements, if its size
+	/// fits in the address space.
+	fn try_layout(capacity: usize) -> Option<Layout> {
+		//  `Layout::array` requires Rust 1.44.
+		mem::size_of::<T>()
+			.checked_mul(capacity)
+			.and_then(|size| {
+				Layout::from_size_align(size, mem::align_of::<T>()).ok()
+			})
+	}
+
+	/// Takes the buffer out of the vector, leaving the allocator.
+	///
+	/// # Returns
+	///
+	/// The buffer’s data pointer, live bit count, and element capacity.
+	fn into_raw_parts(self) -> (NonNull<T>, usize, usize) {
+		let out = (self.data, self.len, self.capacity);
+		//  The allocator is dropped, but the buffer is not released.
+		let mut this = mem::ManuallyDrop::new(self);
+		unsafe {
+			ptr::drop_in_place(&mut this.alloc);
+		}
+		out
+	}
+}
+
+impl<O, T, A> Clone for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator + Clone,
+{
+	fn clone(&self) -> Self {
+		Self::from_bitslice_in(self.as_bitslice(), self.alloc.clone())
+	}
+}
+
+impl<O, T, A> Debug for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("BitVecIn<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str(", ")?;
+		fmt.write_str(T::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+impl<O, T, A> Deref for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	type Target = BitSlice<O, T>;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<O, T, A> DerefMut for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.as_mut_bitslice()
+	}
+}
+
+impl<O, T, A> Drop for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	fn drop(&mut self) {
+		if self.capacity != 0 {
+			let layout = Self::layout(self.capacity);
+			unsafe {
+				self.alloc.deallocate(self.data.cast(), layout);
+			}
+		}
+	}
+}
+
+impl<O, T, A> Extend<bool> for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	fn extend<I>(&mut self, src: I)
+	where I: IntoIterator<Item = bool> {
+		let iter = src.into_iter();
+		self.reserve(iter.size_hint().0);
+		for bit in iter {
+			self.push(bit);
+		}
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<O, T> From<BitVecIn<O, T, Global>> for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Moves the buffer into a `BitVec`, without copying.
+	fn from(src: BitVecIn<O, T, Global>) -> Self {
+		let (data, len, capacity) = src.into_raw_parts();
+		let (elts, _) = 0u8.idx::<T>().span(len);
+		//  `Global` allocates with the same layout as `Vec`.
+		let vec = unsafe { Vec::from_raw_parts(data.as_ptr(), elts, capacity) };
+		let mut out = BitVec::from_vec(vec);
+		out.truncate(len);
+		out
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<O, T> From<BitVec<O, T>> for BitVecIn<O, T, Global>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from(mut src: BitVec<O, T>) -> Self {
+		src.force_align();
+		let len = src.len();
+		let mut vec = mem::ManuallyDrop::new(src.into_vec());
+		let capacity = vec.capacity();
+		let data = match capacity {
+			0 => NonNull::dangling(),
+			_ => unsafe { NonNull::new_unchecked(vec.as_mut_ptr()) },
+		};
+		Self {
+			_order: PhantomData,
+			data,
+			len,
+			capacity,
+			alloc: Global,
+		}
+	}
+}
+
+unsafe impl<O, T, A> Send for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator + Send,
+{
+}
+
+unsafe impl<O, T, A> Sync for BitVecIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator + Sync,
+{
+}
+
+/** A fixed-size bit buffer whose memory comes from an allocator of the
+caller’s choice.
+
+This is the counterpart of [`BitBox`] for memory that does not come from the
+global allocator. It is produced by [`BitVecIn::into_boxed_bitslice`], and can
+be thawed back into a vector with [`into_bitvec`].
+
+[`BitBox`]: ../boxed/struct.BitBox.html
+[`BitVecIn::into_boxed_bitslice`]: struct.BitVecIn.html#method.into_boxed_bitslice
+[`into_bitvec`]: #method.into_bitvec
+**/
+pub struct BitBoxIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	/// The buffer, which is never grown while boxed.
+	inner: BitVecIn<O, T, A>,
+}
+
+impl<O, T, A> BitBoxIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	/// Copies a bit slice into a new box.
+	///
+	/// # Panics
+	///
+	/// This panics if the allocator cannot provide the buffer.
+	pub fn from_bitslice_in(slice: &BitSlice<O, T>, alloc: A) -> Self {
+		BitVecIn::from_bitslice_in(slice, alloc).into_boxed_bitslice()
+	}
+
+	/// Gets the allocator that provides the buffer.
+	pub fn allocator(&self) -> &A {
+		self.inner.allocator()
+	}
+
+	/// Views the box as a bit slice.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		self.inner.as_bitslice()
+	}
+
+	/// Views the box as a mutable bit slice.
+	pub fn as_mut_bitslice(&mut self) -> &mut BitSlice<O, T> {
+		self.inner.as_mut_bitslice()
+	}
+
+	/// Thaws the box into a growable vector, which keeps its buffer and
+	/// allocator.
+	pub fn into_bitvec(self) -> BitVecIn<O, T, A> {
+		self.inner
+	}
+}
+
+impl<O, T, A> Clone for BitBoxIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator + Clone,
+{
+	fn clone(&self) -> Self {
+		Self {
+			inner: self.inner.clone(),
+		}
+	}
+}
+
+impl<O, T, A> Debug for BitBoxIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("BitBoxIn<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str(", ")?;
+		fmt.write_str(T::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+impl<O, T, A> Deref for BitBoxIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	type Target = BitSlice<O, T>;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<O, T, A> DerefMut for BitBoxIn<O, T, A>
+where
+	O: BitOrder,
+	T: BitStore,
+	A: BitAllocator,
+{
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.as_mut_bitslice()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<O, T> From<BitBoxIn<O, T, Global>> for BitBox<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from(src: BitBoxIn<O, T, Global>) -> Self {
+		BitVec::from(src.inner).into_boxed_bitslice()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+	use alloc::borrow::ToOwned;
+	use core::cell::Cell;
+
+	/// Forwards to the global allocator, and counts the live blocks.
+	#[cfg(feature = "alloc")]
+	#[derive(Default)]
+	struct Counter {
+		live: Cell<isize>,
+	}
+
+	#[cfg(feature = "alloc")]
+	unsafe impl BitAllocator for Counter {
+		fn allocate(&self, layout: Layout) -> Option<NonNull<u8>> {
+			self.live.set(self.live.get() + 1);
+			Global.allocate(layout)
+		}
+
+		unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+			self.live.set(self.live.get() - 1);
+			Global.deallocate(ptr, layout)
+		}
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn counted() {
+		//  The copy ends seven bits into its last `u16` element.
+		let data = [0u32, !0, 0xAAAA_AAAA];
+		let bits = &data.bits::<Msb0>()[3 .. 90];
+		let counter = Counter::default();
+		{
+			let mut bv = BitVecIn::<Msb0, u16, _>::new_in(&counter);
+			bv.extend(bits[.. 0].iter().copied());
+			assert!(bv.is_empty());
+			assert_eq!(counter.live.get(), 0);
+			bv.extend(bits.iter().copied());
+			assert_eq!(bv.as_bitslice(), bits);
+			assert_eq!(counter.live.get(), 1);
+
+			bv.resize(200, true);
+			assert_eq!(bv[.. 87], *bits);
+			assert_eq!(bv[87 ..].count_ones(), 113);
+			assert_eq!(bv.pop(), Some(true));
+			bv.truncate(87);
+
+			let boxed = bv.into_boxed_bitslice();
+			let copy = boxed.clone();
+			assert_eq!(counter.live.get(), 2);
+			assert_eq!(copy.as_bitslice(), bits);
+		}
+		assert_eq!(counter.live.get(), 0);
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn global_round_trip() {
+		//  The first and last bits of the span, and the bits on either side of
+		//  the element boundary, are set.
+		let data = [0x0400_0001u32, 0x8000_0010];
+		let bits = &data.bits::<Msb0>()[5 .. 60];
+		assert_eq!(bits.count_ones(), 4);
+		let bv = BitVecIn::<Msb0, u32, _>::from_bitslice_in(bits, Global);
+		let global = BitVec::from(bv);
+		assert_eq!(global, bits);
+		let back = BitVecIn::from(bits.to_owned());
+		assert_eq!(back.as_bitslice(), bits);
+		let boxed = BitBox::from(back.into_boxed_bitslice());
+		assert_eq!(boxed.as_bitslice(), bits);
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn reserve_overflow() {
+		let data = [0xA5u8; 3];
+		let bits = &data.bits::<Msb0>()[2 .. 20];
+		let mut bv = BitVecIn::<Msb0, u8, _>::from_bitslice_in(bits, Global);
+		assert_eq!(bv.try_reserve(!0), Err(AllocError::CapacityOverflow));
+		assert_eq!(
+			bv.try_reserve(BitPtr::<u8>::MAX_BITS),
+			Err(AllocError::CapacityOverflow),
+		);
+		assert_eq!(AllocError::CapacityOverflow.layout(), None);
+		assert_eq!(bv.as_bitslice(), bits);
+		assert!(bv.try_reserve(100).is_ok());
+		assert_eq!(bv.as_bitslice(), bits);
+	}
+}
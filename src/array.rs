@@ -0,0 +1,550 @@
+/*! Fixed-capacity bit vectors, without allocation
+
+Embedded firmware and enclaves often cannot allocate, but still need a bit
+sequence whose length changes at runtime. [`BitArrayVec`] keeps its bits in an
+array stored inline, and offers the growable API of `BitVec` within that fixed
+capacity.
+
+The array is named by its type: a `BitArrayVec<Msb0, [u8; 4]>` holds up to
+thirty-two bits in four bytes. Any array of `BitStore` elements whose length
+implements [`BitBuffer`] may be used, which covers every length up to 32, and
+the powers of two up to 4096.
+
+# Capacity
+
+The capacity is a whole number of storage elements, given by the array type,
+rather than a number of bits given as a const generic parameter. Const generics
+require Rust 1.51, and this crate supports Rust 1.36, so a type cannot be
+parameterized by an integer; an array type is the only way to fix the size of
+inline storage. To hold at least `n` bits, use an array of
+`ceil(n / T::BITS)` elements of `T`. Choosing `u8` elements wastes at most
+seven bits. Pushes past the requested `n` are not rejected; they fill the rest
+of the last element before [`is_full`] reports the vector full.
+
+[`BitArrayVec`]: struct.BitArrayVec.html
+[`is_full`]: struct.BitArrayVec.html#method.is_full
+[`BitBuffer`]: trait.BitBuffer.html
+!*/
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use core::{
+	cmp::Ordering,
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
+	hash::{
+		Hash,
+		Hasher,
+	},
+	iter::FromIterator,
+	marker::PhantomData,
+	ops::{
+		Deref,
+		DerefMut,
+	},
+};
+
+/** An array of `BitStore` elements, used as the fixed storage of a
+`BitArrayVec`.
+
+This is implemented for arrays of every length up to 32, and of the powers of
+two up to 4096.
+**/
+pub trait BitBuffer: Copy {
+	/// The element type of the array.
+	type Store: BitStore;
+
+	/// Produces an array of zeroed elements.
+	fn zeroed() -> Self;
+
+	/// Views the array as a slice.
+	fn as_elements(&self) -> &[Self::Store];
+
+	/// Views the array as a mutable slice.
+	fn as_elements_mut(&mut self) -> &mut [Self::Store];
+}
+
+/// Implements `BitBuffer` on arrays of each listed length.
+macro_rules! buffer {
+	($($n:expr),* $(,)?) => { $(
+		impl<T> BitBuffer for [T; $n]
+		where T: BitStore {
+			type Store = T;
+
+			fn zeroed() -> Self {
+				[T::FALSE; $n]
+			}
+
+			fn as_elements(&self) -> &[T] {
+				&self[..]
+			}
+
+			fn as_elements_mut(&mut self) -> &mut [T] {
+				&mut self[..]
+			}
+		}
+	)* };
+}
+
+buffer![
+	0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+	21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 64, 128, 256, 512, 1024,
+	2048, 4096,
+];
+
+/** A bit vector with a fixed capacity, stored inline in an array.
+
+This has the `push`, `pop`, and `extend` API of `BitVec`, and dereferences to
+`BitSlice` for everything else. It never allocates, so it is available without
+the `alloc` feature. Operations that would grow it past its capacity panic, and
+each has a `try_` counterpart that returns a [`CapacityError`] instead.
+
+The capacity is that of the whole array `A`, in bits. It is not a bit count
+given as a const generic parameter, which the minimum supported Rust version
+does not have; see the [module documentation] for how to size the array.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within each storage element.
+- `A: BitBuffer`: The array in which the bits are stored.
+
+# Examples
+
+```rust
+use bitvec::{array::BitArrayVec, prelude::*};
+
+let mut bv = BitArrayVec::<Msb0, [u8; 2]>::new();
+assert_eq!(bv.capacity(), 16);
+bv.extend([true, false, true].iter().copied());
+bv.push(true);
+assert_eq!(bv, bits![1, 0, 1, 1]);
+assert_eq!(bv.pop(), Some(true));
+
+assert!(bv.try_extend_from_bitslice(bits![0; 13]).is_ok());
+assert!(bv.is_full());
+assert!(bv.try_push(false).is_err());
+assert_eq!(bv.as_slice(), &[0b1010_0000, 0]);
+```
+
+[`CapacityError`]: struct.CapacityError.html
+[module documentation]: index.html#capacity
+**/
+#[derive(Clone, Copy)]
+pub struct BitArrayVec<O = Local, A = [usize; 1]>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	/// The storage. Bits past `len` are always zero.
+	buf: A,
+	/// The number of live bits.
+	len: usize,
+	/// The ordering of bits within the storage elements.
+	_ord: PhantomData<O>,
+}
+
+impl<O, A> BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	/// Constructs an empty vector.
+	pub fn new() -> Self {
+		Self {
+			buf: A::zeroed(),
+			len: 0,
+			_ord: PhantomData,
+		}
+	}
+
+	/// Copies a bit slice into a new vector.
+	///
+	/// # Returns
+	///
+	/// The vector, or an error if `slice` is longer than the capacity.
+	pub fn from_bitslice<O2, T2>(
+		slice: &BitSlice<O2, T2>,
+	) -> Result<Self, CapacityError>
+	where
+		O2: BitOrder,
+		T2: BitStore,
+	{
+		let mut out = Self::new();
+		out.try_extend_from_bitslice(slice)?;
+		Ok(out)
+	}
+
+	/// Gets the number of bits that the vector can hold.
+	pub fn capacity(&self) -> usize {
+		self.buf.as_elements().len() * <A::Store as BitStore>::BITS as usize
+	}
+
+	/// Tests if the vector is at its capacity.
+	pub fn is_full(&self) -> bool {
+		self.len == self.capacity()
+	}
+
+	/// Views the live bits.
+	pub fn as_bitslice(&self) -> &BitSlice<O, A::Store> {
+		&BitSlice::from_slice(self.buf.as_elements())[.. self.len]
+	}
+
+	/// Views the live bits mutably.
+	pub fn as_mut_bitslice(&mut self) -> &mut BitSlice<O, A::Store> {
+		let len = self.len;
+		&mut BitSlice::from_slice_mut(self.buf.as_elements_mut())[.. len]
+	}
+
+	/// Views the elements that hold the live bits.
+	pub fn as_slice(&self) -> &[A::Store] {
+		self.as_bitslice().as_slice()
+	}
+
+	/// Takes the storage array out of the vector.
+	///
+	/// The bits past the length of the vector are zero.
+	pub fn into_inner(self) -> A {
+		self.buf
+	}
+
+	/// Appends a bit to the back of the vector.
+	///
+	/// # Panics
+	///
+	/// This panics if the vector is full.
+	pub fn push(&mut self, value: bool) {
+		if self.try_push(value).is_err() {
+			panic!("Capacity overflow: {}", self.capacity());
+		}
+	}
+
+	/// Appends a bit to the back of the vector, if there is room for it.
+	///
+	/// # Returns
+	///
+	/// An error if the vector is full, in which case it is not modified.
+	pub fn try_push(&mut self, value: bool) -> Result<(), CapacityError> {
+		if self.is_full() {
+			return Err(CapacityError {
+				capacity: self.capacity(),
+			});
+		}
+		let len = self.len;
+		BitSlice::<O, _>::from_slice_mut(self.buf.as_elements_mut())
+			.set(len, value);
+		self.len += 1;
+		Ok(())
+	}
+
+	/// Removes the last bit of the vector, and returns it.
+	pub fn pop(&mut self) -> Option<bool> {
+		let len = self.len.checked_sub(1)?;
+		let out = self.as_bitslice()[len];
+		self.truncate(len);
+		Some(out)
+	}
+
+	/// Shortens the vector to `len` bits, if it is longer.
+	pub fn truncate(&mut self, len: usize) {
+		if len < self.len {
+			self.as_mut_bitslice()[len ..].set_all(false);
+			self.len = len;
+		}
+	}
+
+	/// Removes every bit from the vector.
+	pub fn clear(&mut self) {
+		self.truncate(0);
+	}
+
+	/// Appends the bits of a slice to the back of the vector.
+	///
+	/// # Panics
+	///
+	/// This panics if the bits do not fit.
+	pub fn extend_from_bitslice<O2, T2>(&mut self, other: &BitSlice<O2, T2>)
+	where
+		O2: BitOrder,
+		T2: BitStore,
+	{
+		if self.try_extend_from_bitslice(other).is_err() {
+			panic!("Capacity overflow: {}", self.capacity());
+		}
+	}
+
+	/// Appends the bits of a slice to the back of the vector, if they fit.
+	///
+	/// # Returns
+	///
+	/// An error if the bits do not fit, in which case the vector is not
+	/// modified.
+	pub fn try_extend_from_bitslice<O2, T2>(
+		&mut self,
+		other: &BitSlice<O2, T2>,
+	) -> Result<(), CapacityError>
+	where
+		O2: BitOrder,
+		T2: BitStore,
+	{
+		let (start, end) = (self.len, self.len + other.len());
+		if end > self.capacity() {
+			return Err(CapacityError {
+				capacity: self.capacity(),
+			});
+		}
+		let bits = BitSlice::<O, _>::from_slice_mut(self.buf.as_elements_mut());
+		for (idx, bit) in (start .. end).zip(other.iter()) {
+			bits.set(idx, *bit);
+		}
+		self.len = end;
+		Ok(())
+	}
+}
+
+impl<O, A> Debug for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("BitArrayVec<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str(", ")?;
+		fmt.write_str(<A::Store as BitStore>::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+impl<O, A> Default for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<O, A> Deref for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	type Target = BitSlice<O, A::Store>;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<O, A> DerefMut for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.as_mut_bitslice()
+	}
+}
+
+impl<O, A> Display for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+impl<O, A> Eq for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+}
+
+/// Appends bits from an iterator.
+///
+/// # Panics
+///
+/// This panics if the iterator produces more bits than fit.
+impl<O, A> Extend<bool> for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn extend<I>(&mut self, src: I)
+	where I: IntoIterator<Item = bool> {
+		for bit in src {
+			self.push(bit);
+		}
+	}
+}
+
+/// Collects bits from an iterator.
+///
+/// # Panics
+///
+/// This panics if the iterator produces more bits than fit.
+impl<O, A> FromIterator<bool> for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn from_iter<I>(src: I) -> Self
+	where I: IntoIterator<Item = bool> {
+		let mut out = Self::new();
+		out.extend(src);
+		out
+	}
+}
+
+impl<O, A> Hash for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn hash<H>(&self, hasher: &mut H)
+	where H: Hasher {
+		self.as_bitslice().hash(hasher)
+	}
+}
+
+impl<O, A> Ord for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.as_bitslice().cmp(other.as_bitslice())
+	}
+}
+
+impl<O, A, O2, A2> PartialEq<BitArrayVec<O2, A2>> for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+	O2: BitOrder,
+	A2: BitBuffer,
+{
+	fn eq(&self, rhs: &BitArrayVec<O2, A2>) -> bool {
+		self.as_bitslice().eq(rhs.as_bitslice())
+	}
+}
+
+impl<O, A, O2, T2> PartialEq<BitSlice<O2, T2>> for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+	O2: BitOrder,
+	T2: BitStore,
+{
+	fn eq(&self, rhs: &BitSlice<O2, T2>) -> bool {
+		self.as_bitslice().eq(rhs)
+	}
+}
+
+impl<O, A, O2, T2> PartialEq<&BitSlice<O2, T2>> for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+	O2: BitOrder,
+	T2: BitStore,
+{
+	fn eq(&self, rhs: &&BitSlice<O2, T2>) -> bool {
+		self.as_bitslice().eq(*rhs)
+	}
+}
+
+impl<O, A> PartialOrd for BitArrayVec<O, A>
+where
+	O: BitOrder,
+	A: BitBuffer,
+{
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+/** The error produced when a `BitArrayVec` cannot hold any more bits.
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct CapacityError {
+	/// The capacity of the vector.
+	capacity: usize,
+}
+
+impl CapacityError {
+	/// Gets the capacity of the vector, in bits.
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+}
+
+impl Display for CapacityError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(fmt, "Capacity of {} bits exceeded", self.capacity)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityError {
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Lsb0,
+		slice::AsBits,
+	};
+
+	#[test]
+	fn fixed_capacity() {
+		//  Every bit is set, so a popped or truncated bit left in the storage
+		//  shows up in it.
+		let data = [!0u32; 2];
+		let bits = &data.bits::<Lsb0>()[3 .. 50];
+		let mut bv = BitArrayVec::<Lsb0, [u16; 3]>::from_bitslice(bits).unwrap();
+		assert_eq!(bv, *bits);
+		assert_eq!(bv.try_extend_from_bitslice(bits), Err(CapacityError {
+			capacity: 48,
+		}));
+		assert_eq!(bv.len(), 47);
+		bv.push(true);
+		assert!(bv.try_push(true).is_err());
+
+		//  Popped and truncated bits are cleared from the storage.
+		assert_eq!(bv.pop(), Some(true));
+		bv.truncate(20);
+		let inner = bv.into_inner();
+		assert_eq!(inner.bits::<Lsb0>()[.. 20], bits[.. 20]);
+		assert!(inner.bits::<Lsb0>()[20 ..].not_any());
+
+		bv.clear();
+		assert!(bv.is_empty());
+		assert_eq!(bv.pop(), None);
+		let empty = BitArrayVec::<Lsb0, [u8; 0]>::new();
+		assert!(empty.is_full());
+	}
+
+	#[test]
+	#[should_panic(expected = "Capacity overflow: 8")]
+	fn overflow() {
+		let bits = (0 .. 9).map(|n| n % 2 == 0);
+		let _ = bits.collect::<BitArrayVec<Lsb0, [u8; 1]>>();
+	}
+}
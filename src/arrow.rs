@@ -0,0 +1,235 @@
+/*! Interoperation with Apache Arrow validity bitmaps
+
+Arrow stores boolean columns, and the validity of the values in every other
+column, as bitmaps with a fixed layout:
+
+- bits are numbered from the least significant bit of each byte, which is the
+  [`Lsb0`] ordering over `u8`;
+- a bitmap of `n` bits occupies at least `(n + 7) / 8` bytes, and the bits past
+  `n` in its last byte are ignored;
+- a column that has been sliced keeps its original buffer, and records the bit
+  offset at which it begins;
+- buffers that are written out are padded to a multiple of 64 bytes.
+
+The [`arrow-buffer`] crate represents such a bitmap as a `BooleanBuffer`, and a
+validity bitmap as a `NullBuffer` around one. [`ArrowBits`] views either as the
+`BitSlice<Lsb0, u8>` that its offset and length select, without copying, and
+[`to_boolean_buffer`] and [`to_null_buffer`] copy any `BitSlice` into a new,
+padded, Arrow buffer. [`view`] applies the same rules to a raw byte buffer, such
+as one received across the Arrow C data interface.
+
+`arrow-buffer` is built against the host `std` and requires Rust 1.62 or later,
+so this module is not available in SGX enclave builds, or on the 1.36 compiler
+that the rest of this crate supports.
+
+[`ArrowBits`]: trait.ArrowBits.html
+[`Lsb0`]: ../order/struct.Lsb0.html
+[`arrow-buffer`]: https://docs.rs/arrow-buffer/53/arrow_buffer/
+[`to_boolean_buffer`]: fn.to_boolean_buffer.html
+[`to_null_buffer`]: fn.to_null_buffer.html
+[`view`]: fn.view.html
+!*/
+
+#![cfg(all(feature = "arrow", feature = "alloc"))]
+
+use crate::{
+	order::{
+		BitOrder,
+		Lsb0,
+	},
+	slice::{
+		AsBits,
+		BitSlice,
+	},
+	store::BitStore,
+};
+
+use core::cmp;
+
+use ::arrow_buffer::{
+	BooleanBuffer,
+	MutableBuffer,
+	NullBuffer,
+};
+
+/// The alignment, in bytes, to which Arrow pads the buffers it writes.
+pub const PADDING: usize = 64;
+
+/** Views an Arrow bitmap as a `BitSlice`.
+
+# Examples
+
+```rust
+use arrow_buffer::NullBuffer;
+use bitvec::{arrow::ArrowBits, prelude::*};
+
+let nulls = NullBuffer::from(vec![true, false, true, true, false]);
+let valid = nulls.slice(1, 4);
+assert_eq!(valid.arrow_bits(), bits![0, 1, 1, 0]);
+assert_eq!(valid.arrow_bits().count_zeros(), valid.null_count());
+```
+**/
+pub trait ArrowBits {
+	/// Views the bits of the bitmap, from its offset and for its length.
+	fn arrow_bits(&self) -> &BitSlice<Lsb0, u8>;
+}
+
+impl ArrowBits for BooleanBuffer {
+	fn arrow_bits(&self) -> &BitSlice<Lsb0, u8> {
+		let offset = self.offset();
+		&self.values().bits::<Lsb0>()[offset .. offset + self.len()]
+	}
+}
+
+impl ArrowBits for NullBuffer {
+	fn arrow_bits(&self) -> &BitSlice<Lsb0, u8> {
+		self.inner().arrow_bits()
+	}
+}
+
+/// Views the bits of a raw Arrow bitmap.
+///
+/// # Parameters
+///
+/// - `bytes`: The buffer that holds the bitmap, from its first byte.
+/// - `offset`: The index of the first bit of the bitmap in `bytes`, as
+///   recorded for a sliced column.
+/// - `len`: The number of bits in the bitmap.
+///
+/// # Returns
+///
+/// The `len` bits of `bytes` beginning at bit `offset`, or `None` if `bytes`
+/// is too short to hold them.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{arrow, prelude::*};
+///
+/// let bytes = [0b1011_0100u8, 0b0000_0001];
+/// let bits = arrow::view(&bytes, 2, 7).unwrap();
+/// assert_eq!(bits, bits![1, 0, 1, 1, 0, 1, 1]);
+/// assert!(arrow::view(&bytes, 10, 7).is_none());
+/// ```
+pub fn view(
+	bytes: &[u8],
+	offset: usize,
+	len: usize,
+) -> Option<&BitSlice<Lsb0, u8>>
+{
+	let end = offset.checked_add(len)?;
+	if end > bytes.len().saturating_mul(8) {
+		return None;
+	}
+	Some(&bytes.bits::<Lsb0>()[offset .. end])
+}
+
+/// Computes the number of bytes that Arrow requires for a bitmap.
+///
+/// # Parameters
+///
+/// - `bits`: The number of bits in the bitmap.
+///
+/// # Returns
+///
+/// The number of bytes that hold `bits` bits, rounded up to a multiple of
+/// [`PADDING`].
+///
+/// [`PADDING`]: constant.PADDING.html
+pub fn padded_len(bits: usize) -> usize {
+	let bytes = bits / 8 + cmp::min(bits % 8, 1);
+	match bytes % PADDING {
+		0 => bytes,
+		rem => bytes + PADDING - rem,
+	}
+}
+
+/// Copies a bit slice into a new Arrow bitmap.
+///
+/// The bits are renumbered into the [`Lsb0`] ordering over bytes, beginning at
+/// offset zero, and the buffer is zero-filled out to [`padded_len`] bytes.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{arrow::{self, ArrowBits}, prelude::*};
+///
+/// let bits = bits![Msb0, u16; 1, 1, 0, 1, 0, 0, 1, 0, 1];
+/// let buffer = arrow::to_boolean_buffer(bits);
+/// assert_eq!(buffer.len(), 9);
+/// assert_eq!(buffer.values().len(), 64);
+/// assert_eq!(&buffer.values()[.. 2], &[0b0100_1011, 0b0000_0001]);
+/// assert_eq!(buffer.arrow_bits(), bits);
+/// ```
+///
+/// [`Lsb0`]: ../order/struct.Lsb0.html
+/// [`padded_len`]: fn.padded_len.html
+pub fn to_boolean_buffer<O, T>(bits: &BitSlice<O, T>) -> BooleanBuffer
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let len = bits.len();
+	let mut buffer = MutableBuffer::from_len_zeroed(padded_len(len));
+	buffer.as_slice_mut().bits_mut::<Lsb0>()[.. len].clone_from_slice(bits);
+	BooleanBuffer::new(buffer.into(), 0, len)
+}
+
+/// Copies a bit slice into a new Arrow validity bitmap.
+///
+/// Set bits in `bits` mark valid values, and unset bits mark nulls. The
+/// buffer is laid out as by [`to_boolean_buffer`].
+///
+/// [`to_boolean_buffer`]: fn.to_boolean_buffer.html
+pub fn to_null_buffer<O, T>(bits: &BitSlice<O, T>) -> NullBuffer
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	NullBuffer::new(to_boolean_buffer(bits))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Msb0;
+
+	#[test]
+	fn known_layout() {
+		//  Arrow numbers the bits of each byte from the least significant.
+		let bits = &0xB080u16.bits::<Msb0>()[.. 9];
+		let buffer = to_boolean_buffer(bits);
+		assert_eq!(&buffer.values()[.. 2], &[0b0000_1101, 0b0000_0001]);
+		assert_eq!(to_null_buffer(bits).null_count(), 5);
+	}
+
+	#[test]
+	fn round_trip() {
+		//  Runs and single set bits at the element edges, in spans that
+		//  start and end inside the bytes of the Arrow buffer.
+		let data = [0x8000_0001u32, 0x0000_FFFF, 0xAAAA_AAAA];
+		for &(start, end) in &[(0, 0), (3, 11), (5, 96), (31, 65)] {
+			let bits = &data.bits::<Msb0>()[start .. end];
+			let buffer = to_boolean_buffer(bits);
+			assert_eq!(buffer.values().len() % PADDING, 0);
+			assert_eq!(buffer.arrow_bits(), bits);
+			for (idx, bit) in bits.iter().enumerate() {
+				assert_eq!(buffer.value(idx), *bit);
+			}
+
+			let nulls = to_null_buffer(bits);
+			assert_eq!(nulls.null_count(), bits.count_zeros());
+			if bits.len() > 4 {
+				let sliced = nulls.slice(3, bits.len() - 4);
+				assert_eq!(sliced.arrow_bits(), &bits[3 .. bits.len() - 1]);
+				let raw = view(sliced.validity(), sliced.offset(), sliced.len());
+				assert_eq!(raw, Some(sliced.arrow_bits()));
+			}
+		}
+		assert_eq!(padded_len(0), 0);
+		assert_eq!(padded_len(1), 64);
+		assert_eq!(padded_len(512), 64);
+		assert_eq!(padded_len(513), 128);
+		assert!(view(&[0u8; 2], !0, 2).is_none());
+	}
+}
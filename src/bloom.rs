@@ -0,0 +1,296 @@
+/*! Bloom filters
+
+A Bloom filter is a set that stores only a fixed-size array of bits, and answers
+membership queries with no false negatives and a tunable rate of false
+positives. It is one of the most common uses of a bit vector, so this module
+provides one, in [`BloomFilter`].
+
+The filter derives all of its hash functions from one [`BuildHasher`], by
+seeding each hasher with the index of the hash function before writing the item
+into it. The filter bits are an ordinary `BitVec`, which can be taken out of the
+filter for storage or transmission and put back into a new filter later, as long
+as the same `BuildHasher` and number of hash functions are used.
+
+[`BloomFilter`]: struct.BloomFilter.html
+[`BuildHasher`]: https://doc.rust-lang.org/core/hash/trait.BuildHasher.html
+!*/
+
+#![cfg(all(feature = "bloom", feature = "alloc"))]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::hash::{
+	BuildHasher,
+	Hash,
+	Hasher,
+};
+
+/** A probabilistic set, backed by a `BitVec`.
+
+Each item sets `hashes` bits in the filter, chosen by hashing the item. An item
+is reported as present when all of its bits are set. Items that were inserted
+are always reported as present, but an item that was not inserted may also be
+reported as present if other insertions happened to set all of its bits.
+
+# Type Parameters
+
+- `S: BuildHasher`: The source of the filter’s hash functions. Filters can only
+  be merged, or restored from their bits, when they use equivalent builders.
+- `O: BitOrder`: The ordering of bits within each storage element.
+- `T: BitStore`: The storage element type.
+
+# Examples
+
+```rust
+use bitvec::{bloom::BloomFilter, prelude::*};
+use std::{collections::hash_map::DefaultHasher, hash::BuildHasherDefault};
+
+type Hasher = BuildHasherDefault<DefaultHasher>;
+
+let mut seen = BloomFilter::<Hasher>::with_hasher(1024, 3, Hasher::default());
+assert!(seen.insert("alpha"));
+assert!(!seen.insert("alpha"));
+assert!(seen.contains("alpha"));
+
+//  The raw bits round-trip through storage.
+let (bits, hashes, hasher) = seen.into_parts();
+let restored = BloomFilter::from_parts(bits, hashes, hasher);
+assert!(restored.contains("alpha"));
+```
+**/
+#[derive(Clone, Debug)]
+pub struct BloomFilter<S, O = Local, T = usize>
+where
+	S: BuildHasher,
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The filter bits.
+	bits: BitVec<O, T>,
+	/// The number of bits each item sets.
+	hashes: usize,
+	/// The source of the hash functions.
+	hasher: S,
+}
+
+impl<S, O, T> BloomFilter<S, O, T>
+where
+	S: BuildHasher,
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Creates an empty filter.
+	///
+	/// # Parameters
+	///
+	/// - `bits`: The number of bits in the filter.
+	/// - `hashes`: The number of hash functions, and so the number of bits
+	///   each item sets.
+	/// - `hasher`: The source of the hash functions.
+	///
+	/// # Panics
+	///
+	/// This panics if `bits` or `hashes` is zero.
+	pub fn with_hasher(bits: usize, hashes: usize, hasher: S) -> Self {
+		Self::from_parts(BitVec::repeat(false, bits), hashes, hasher)
+	}
+
+	/// Rebuilds a filter from its bits.
+	///
+	/// The bits are moved to the front of their first element, if they are not
+	/// there already, so that the elements of any two filters of the same size
+	/// line up.
+	///
+	/// # Parameters
+	///
+	/// - `bits`: The bits of a filter, as produced by [`into_parts`] or
+	///   [`as_bitslice`].
+	/// - `hashes`: The number of hash functions the filter used.
+	/// - `hasher`: A builder equivalent to the one the filter used.
+	///
+	/// # Panics
+	///
+	/// This panics if `bits` is empty or `hashes` is zero.
+	///
+	/// [`as_bitslice`]: #method.as_bitslice
+	/// [`into_parts`]: #method.into_parts
+	pub fn from_parts(mut bits: BitVec<O, T>, hashes: usize, hasher: S) -> Self {
+		assert!(!bits.is_empty(), "A Bloom filter must have at least one bit");
+		assert!(hashes > 0, "A Bloom filter must have at least one hash");
+		bits.force_align();
+		Self {
+			bits,
+			hashes,
+			hasher,
+		}
+	}
+
+	/// Unwraps the filter into its bits, number of hash functions, and hash
+	/// builder.
+	pub fn into_parts(self) -> (BitVec<O, T>, usize, S) {
+		(self.bits, self.hashes, self.hasher)
+	}
+
+	/// Views the filter bits.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		&self.bits
+	}
+
+	/// Counts the number of hash functions.
+	pub fn hashes(&self) -> usize {
+		self.hashes
+	}
+
+	/// Inserts an item into the filter.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `item`: The item to insert.
+	///
+	/// # Returns
+	///
+	/// `true` if the filter changed, which means that `item` was certainly not
+	/// present before. `false` if `item` was, or may have been, present.
+	pub fn insert<Q>(&mut self, item: &Q) -> bool
+	where Q: Hash + ?Sized {
+		let mut changed = false;
+		for n in 0 .. self.hashes {
+			let idx = self.index(n, item);
+			changed |= !self.bits.replace(idx, true);
+		}
+		changed
+	}
+
+	/// Tests whether an item may be in the filter.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `item`: The item to look up.
+	///
+	/// # Returns
+	///
+	/// `false` if `item` was certainly never inserted. `true` if it was, or if
+	/// the bits it would set are all set by other items.
+	pub fn contains<Q>(&self, item: &Q) -> bool
+	where Q: Hash + ?Sized {
+		(0 .. self.hashes).all(|n| self.bits[self.index(n, item)])
+	}
+
+	/// Adds every item in another filter to this one.
+	///
+	/// The filters are merged a whole element at a time.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `other`: A filter built with the same size, number of hash functions,
+	///   and hash builder as `self`.
+	///
+	/// # Panics
+	///
+	/// This panics if `other` has a different number of bits or hash
+	/// functions. Filters whose hash builders differ cannot be detected, and
+	/// merge into a filter that gives meaningless answers.
+	pub fn merge(&mut self, other: &Self) {
+		assert_eq!(
+			(self.bits.len(), self.hashes),
+			(other.bits.len(), other.hashes),
+			"Cannot merge Bloom filters of different shapes",
+		);
+		//  Both filters begin at the front of their first element, so their
+		//  elements line up.
+		let src = other.bits.as_slice();
+		for (dst, src) in self.bits.as_mut_slice().iter_mut().zip(src) {
+			*dst |= *src;
+		}
+	}
+
+	/// Removes every item from the filter.
+	pub fn clear(&mut self) {
+		self.bits.set_all(false);
+	}
+
+	/// Computes the bit that the `n`th hash function selects for an item.
+	fn index<Q>(&self, n: usize, item: &Q) -> usize
+	where Q: Hash + ?Sized {
+		let mut hasher = self.hasher.build_hasher();
+		hasher.write_usize(n);
+		item.hash(&mut hasher);
+		(hasher.finish() % self.bits.len() as u64) as usize
+	}
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+	use std::{
+		collections::hash_map::DefaultHasher,
+		hash::BuildHasherDefault,
+	};
+
+	type Filter = BloomFilter<BuildHasherDefault<DefaultHasher>, Msb0, u8>;
+
+	fn filter() -> Filter {
+		Filter::with_hasher(256, 4, Default::default())
+	}
+
+	#[test]
+	fn insert_contains() {
+		let mut bloom = filter();
+		assert!(!bloom.contains(&0u32));
+		for n in 0 .. 20u32 {
+			bloom.insert(&n);
+		}
+		assert!((0 .. 20u32).all(|n| bloom.contains(&n)));
+		assert!(bloom.as_bitslice().count_ones() <= 80);
+
+		bloom.clear();
+		assert!(bloom.as_bitslice().not_any());
+		assert!(!bloom.contains(&0u32));
+	}
+
+	#[test]
+	fn merge() {
+		let (mut a, mut b) = (filter(), filter());
+		a.insert("left");
+		b.insert("right");
+		a.merge(&b);
+		assert!(a.contains("left"));
+		assert!(a.contains("right"));
+
+		//  A filter rebuilt from bits that do not begin on an element boundary
+		//  still merges bit for bit.
+		let mut raw = [0u8; 33];
+		raw[32] = 0b0010_0000;
+		let bits = BitVec::from_bitslice(&raw.bits::<Msb0>()[3 .. 259]);
+		let c = Filter::from_parts(bits, 4, Default::default());
+		let mut d = filter();
+		d.merge(&c);
+		a.merge(&c);
+		assert_eq!(d.as_bitslice().count_ones(), 1);
+		assert!(d.as_bitslice()[255]);
+		assert!(a.as_bitslice()[255]);
+		assert!(a.contains("left") && a.contains("right"));
+	}
+
+	#[test]
+	#[should_panic]
+	fn merge_mismatch() {
+		let mut a = filter();
+		a.merge(&Filter::with_hasher(256, 3, Default::default()));
+	}
+}
@@ -7,10 +7,9 @@ use crate::{
 	store::BitStore,
 };
 
-use std::{
-    boxed::Box,
-    slice,
-};
+use alloc::boxed::Box;
+
+use core::slice;
 
 use core::ops::{
 	Add,
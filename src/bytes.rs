@@ -0,0 +1,286 @@
+/*! Bit-level views of `bytes` buffers
+
+Network stacks built on the [`bytes`] crate receive their data as reference
+counted `Bytes` and `BytesMut` buffers. Both of these dereference to `[u8]`, so
+a borrowed view of their bits is available through [`AsBits`] without copying:
+
+```rust
+use bitvec::prelude::*;
+use bytes::{Bytes, BytesMut};
+
+let frame = Bytes::from_static(&[0b1010_0000, 0x0F]);
+assert!(frame.bits::<Msb0>()[0]);
+
+let mut buf = BytesMut::from(&[0u8; 2][..]);
+buf.bits_mut::<Msb0>().set(15, true);
+assert_eq!(&buf[..], &[0, 1]);
+```
+
+A borrowed view cannot outlive the buffer it borrows, however. This module
+provides [`BitVecBytes`], which owns a share of a `Bytes` buffer and dereferences
+to the bits it covers, so that parsers can hold and split bit regions of
+received data without copying it into a `BitVec`.
+
+[`AsBits`]: ../slice/trait.AsBits.html
+[`BitVecBytes`]: struct.BitVecBytes.html
+[`bytes`]: https://docs.rs/bytes/1/bytes/
+!*/
+
+#![cfg(all(feature = "bytes", feature = "alloc"))]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::{
+		AsBits,
+		BitSlice,
+	},
+	vec::BitVec,
+};
+
+use core::{
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
+	marker::PhantomData,
+	ops::{
+		Deref,
+		Range,
+	},
+};
+
+use ::bytes::{
+	Bytes,
+	BytesMut,
+};
+
+/** A bit sequence that shares ownership of a `Bytes` buffer.
+
+This is an immutable, cheaply cloned, handle to a region of bits inside a
+reference counted byte buffer. Cloning it, or taking a subregion of it with
+[`slice`], only adjusts reference counts; the bytes themselves are never copied.
+
+The region need not begin or end on a byte boundary.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering used to index bits within each byte.
+
+# Examples
+
+```rust
+use bitvec::{bytes::BitVecBytes, prelude::*};
+use bytes::Bytes;
+
+let packet = BitVecBytes::<Msb0>::from(Bytes::from_static(&[0x45, 0x00, 0x05]));
+//  The version and header-length nibbles of an IPv4 header.
+let version = packet.slice(0 .. 4);
+let ihl = packet.slice(4 .. 8);
+assert_eq!(version.load::<u8>(), 4);
+assert_eq!(ihl.load::<u8>(), 5);
+```
+
+[`slice`]: #method.slice
+**/
+#[derive(Clone)]
+pub struct BitVecBytes<O = Local>
+where O: BitOrder
+{
+	/// The bytes that hold the live bits, trimmed to the first and last of
+	/// them.
+	bytes: Bytes,
+	/// The index of the first live bit in the first byte.
+	head: u8,
+	/// The number of live bits.
+	len: usize,
+	_order: PhantomData<O>,
+}
+
+impl<O> BitVecBytes<O>
+where O: BitOrder
+{
+	/// Covers every bit of a buffer.
+	///
+	/// # Parameters
+	///
+	/// - `bytes`: A buffer, which is not copied.
+	pub fn new(bytes: Bytes) -> Self {
+		let len = bytes.len() * 8;
+		Self {
+			bytes,
+			head: 0,
+			len,
+			_order: PhantomData,
+		}
+	}
+
+	/// Takes a subregion of the sequence, sharing the same buffer.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `range`: The bits of `self` to keep.
+	///
+	/// # Returns
+	///
+	/// A handle to the bits of `self` in `range`. The buffer under it is
+	/// trimmed to the bytes those bits touch.
+	///
+	/// # Panics
+	///
+	/// This panics if `range` is decreasing, or extends past the end of `self`.
+	pub fn slice(&self, range: Range<usize>) -> Self {
+		let Range { start, end } = range;
+		assert!(
+			start <= end && end <= self.len,
+			"Range {:?} out of bounds: {}",
+			start .. end,
+			self.len,
+		);
+		let len = end - start;
+		let start = self.head as usize + start;
+		let lo = start / 8;
+		let hi = if len == 0 { lo } else { (start + len - 1) / 8 + 1 };
+		//  An empty region has no bytes, so it cannot begin inside one.
+		let head = if len == 0 { 0 } else { (start % 8) as u8 };
+		Self {
+			bytes: self.bytes.slice(lo .. hi),
+			head,
+			len,
+			_order: PhantomData,
+		}
+	}
+
+	/// Views the bits of the sequence.
+	pub fn as_bitslice(&self) -> &BitSlice<O, u8> {
+		&self.bytes.bits::<O>()[self.head as usize ..][.. self.len]
+	}
+
+	/// Unwraps the sequence into its buffer.
+	///
+	/// # Returns
+	///
+	/// The bytes which hold the live bits of `self`. The first and last bytes
+	/// may contain dead bits outside the region `self` covered.
+	pub fn into_bytes(self) -> Bytes {
+		self.bytes
+	}
+}
+
+impl<O> Deref for BitVecBytes<O>
+where O: BitOrder
+{
+	type Target = BitSlice<O, u8>;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<O> AsRef<BitSlice<O, u8>> for BitVecBytes<O>
+where O: BitOrder
+{
+	fn as_ref(&self) -> &BitSlice<O, u8> {
+		self.as_bitslice()
+	}
+}
+
+impl<O> From<Bytes> for BitVecBytes<O>
+where O: BitOrder
+{
+	fn from(bytes: Bytes) -> Self {
+		Self::new(bytes)
+	}
+}
+
+impl<O> From<BytesMut> for BitVecBytes<O>
+where O: BitOrder
+{
+	/// Freezes a mutable buffer, without copying it.
+	fn from(bytes: BytesMut) -> Self {
+		Self::new(bytes.freeze())
+	}
+}
+
+impl<O> From<BitVec<O, u8>> for BitVecBytes<O>
+where O: BitOrder
+{
+	/// Moves the allocation of a bit vector into a shared buffer, without
+	/// copying it. The bit vector’s head offset is preserved.
+	fn from(bv: BitVec<O, u8>) -> Self {
+		let bitptr = bv.bitptr();
+		let len = bitptr.len();
+		let head = if len == 0 { 0 } else { *bitptr.head() };
+		let mut bytes = Bytes::from(bv.into_vec());
+		bytes.truncate(bitptr.elements());
+		Self {
+			bytes,
+			head,
+			len,
+			_order: PhantomData,
+		}
+	}
+}
+
+impl<O> Debug for BitVecBytes<O>
+where O: BitOrder
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("BitVecBytes<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Msb0;
+
+	use alloc::borrow::ToOwned;
+
+	#[test]
+	fn from_bitvec() {
+		let data = [0x0Fu8, 0xF0, 0x00];
+		let bv = data.bits::<Msb0>()[4 .. 12].to_owned();
+		let expected = bv.clone();
+
+		let bits = BitVecBytes::from(bv);
+		assert_eq!(bits.as_bitslice(), expected.as_bitslice());
+		assert!(bits.all());
+		assert_eq!(bits.into_bytes().len(), 2);
+	}
+
+	#[test]
+	fn slice() {
+		let bits = BitVecBytes::<Msb0>::from(Bytes::from_static(&[0x0F, 0xF0]));
+		let middle = bits.slice(4 .. 12);
+		assert!(middle.all());
+		assert_eq!(middle.slice(2 .. 6).len(), 4);
+		assert!(bits.slice(8 .. 8).is_empty());
+		assert_eq!(bits.slice(8 .. 8).into_bytes().len(), 0);
+		assert_eq!(middle.into_bytes(), Bytes::from_static(&[0x0F, 0xF0]));
+		assert_eq!(bits.slice(9 .. 12).into_bytes().len(), 1);
+	}
+
+	#[test]
+	fn slice_empty() {
+		let bits = BitVecBytes::<Msb0>::from(Bytes::from_static(&[0x0F, 0xF0]));
+		for start in 0 ..= 16 {
+			let empty = bits.slice(start .. start);
+			assert!(empty.is_empty());
+			assert_eq!(empty.as_bitslice().len(), 0);
+			assert!(empty.slice(0 .. 0).is_empty());
+		}
+
+		let mut bv = [0xFFu8; 2].bits::<Msb0>()[3 .. 5].to_owned();
+		bv.clear();
+		assert!(BitVecBytes::from(bv).is_empty());
+	}
+}
@@ -0,0 +1,288 @@
+/*! A portable byte encoding of bit sequences
+
+The memory behind a `BitSlice` depends on its `BitOrder` and `BitStore` type
+parameters, on the head offset of the slice, and, for storage wider than a
+byte, on the byte order of the host. Writing that memory out does not produce
+the same bytes for the same bits in every program, and so it cannot be hashed,
+signed, or stored for another program to read.
+
+[`BitSlice::to_canonical_bytes`] encodes only the bits of a slice, as:
+
+- the number of bits, as a 64-bit big-endian integer, in eight bytes;
+- the bits, eight to a byte, with the first bit of each byte in its most
+  significant bit, in as few bytes as hold them all. The bits of the last byte
+  after the end of the sequence are zero.
+
+[`BitVec::from_canonical_bytes`] decodes this into a vector of any ordering and
+storage type. It accepts only the exact encoding of some sequence, and so every
+sequence has one encoding, and every accepted encoding one sequence.
+
+[`BitSlice::to_canonical_bytes`]: ../slice/struct.BitSlice.html#method.to_canonical_bytes
+[`BitVec::from_canonical_bytes`]: ../vec/struct.BitVec.html#method.from_canonical_bytes
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::{
+		BitOrder,
+		Msb0,
+	},
+	pointer::BitPtr,
+	slice::{
+		AsBits,
+		BitSlice,
+	},
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+	cmp,
+	convert::TryFrom,
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+};
+
+/// The number of bytes in the length header of the encoding.
+pub const HEADER: usize = 8;
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Encodes the bits of the slice in the canonical byte format.
+	///
+	/// The encoding depends only on the sequence of bits, and not on the
+	/// ordering or storage type of the slice, its alignment in memory, or the
+	/// host on which it is produced. See the [`canonical`] module for the
+	/// format.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0x1234u16];
+	/// let bytes = data.bits::<Lsb0>()[2 .. 13].to_canonical_bytes();
+	/// assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 11, 0b1011_0001, 0b0010_0000]);
+	/// assert_eq!(bitvec![1, 0, 1].to_canonical_bytes()[8 ..], [0b1010_0000]);
+	/// ```
+	///
+	/// [`canonical`]: ../canonical/index.html
+	pub fn to_canonical_bytes(&self) -> Vec<u8> {
+		let len = self.len();
+		let mut out = Vec::with_capacity(HEADER + payload_len(len));
+		out.extend_from_slice(&(len as u64).to_be_bytes());
+		out.resize(HEADER + payload_len(len), 0);
+		out[HEADER ..].bits_mut::<Msb0>()[.. len].clone_from_slice(self);
+		out
+	}
+}
+
+impl<O, T> BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Decodes a bit sequence from the canonical byte format.
+	///
+	/// # Parameters
+	///
+	/// - `bytes`: The whole of an encoding produced by
+	///   [`BitSlice::to_canonical_bytes`].
+	///
+	/// # Returns
+	///
+	/// A vector holding the encoded bits.
+	///
+	/// # Errors
+	///
+	/// This fails if `bytes` is not exactly the encoding of some sequence: if
+	/// it is shorter or longer than its header requires, or if any bit after
+	/// the end of the sequence is set. It also fails if the sequence is too
+	/// long for a vector on this target.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::{canonical::CanonicalError, prelude::*};
+	///
+	/// let bytes = bits![Lsb0, u32; 1, 1, 0, 1].to_canonical_bytes();
+	/// let bv = BitVec::<Msb0, u8>::from_canonical_bytes(&bytes).unwrap();
+	/// assert_eq!(bv, bits![1, 1, 0, 1]);
+	///
+	/// let mut padded = bytes.clone();
+	/// padded[8] |= 1;
+	/// assert_eq!(
+	///     BitVec::<Msb0, u8>::from_canonical_bytes(&padded),
+	///     Err(CanonicalError::Padding),
+	/// );
+	/// ```
+	///
+	/// [`BitSlice::to_canonical_bytes`]: ../slice/struct.BitSlice.html#method.to_canonical_bytes
+	pub fn from_canonical_bytes(bytes: &[u8]) -> Result<Self, CanonicalError> {
+		if bytes.len() < HEADER {
+			return Err(CanonicalError::Header { found: bytes.len() });
+		}
+		let (header, payload) = bytes.split_at(HEADER);
+		let mut len = [0; HEADER];
+		len.copy_from_slice(header);
+		let bits = u64::from_be_bytes(len);
+
+		let expected = bits / 8 + cmp::min(bits % 8, 1);
+		if expected != payload.len() as u64 {
+			return Err(CanonicalError::Length {
+				expected,
+				found: payload.len(),
+			});
+		}
+		let len = usize::try_from(bits)
+			.ok()
+			.filter(|&len| len <= BitPtr::<T>::MAX_BITS)
+			.ok_or(CanonicalError::Capacity { bits })?;
+
+		let payload = payload.bits::<Msb0>();
+		if payload[len ..].any() {
+			return Err(CanonicalError::Padding);
+		}
+		let mut out = Self::repeat(false, len);
+		out.clone_from_slice(&payload[.. len]);
+		Ok(out)
+	}
+}
+
+/** The reasons that bytes can fail to decode from the canonical format.
+
+This is returned by [`BitVec::from_canonical_bytes`].
+
+[`BitVec::from_canonical_bytes`]: ../vec/struct.BitVec.html#method.from_canonical_bytes
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum CanonicalError {
+	/// The bytes are too few to hold the length header.
+	Header {
+		/// The number of bytes given.
+		found: usize,
+	},
+	/// The bytes after the header are not the number that the header requires.
+	Length {
+		/// The number of payload bytes that the header requires.
+		expected: u64,
+		/// The number of payload bytes given.
+		found: usize,
+	},
+	/// A bit after the end of the sequence, in its last byte, is set.
+	Padding,
+	/// The sequence is longer than a vector can hold on this target.
+	Capacity {
+		/// The number of bits in the sequence.
+		bits: u64,
+	},
+}
+
+impl Display for CanonicalError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			CanonicalError::Header { found } => write!(
+				fmt,
+				"Canonical bits need a {}-byte header, but only {} bytes are \
+				 available",
+				HEADER, found,
+			),
+			CanonicalError::Length { expected, found } => write!(
+				fmt,
+				"Canonical bits need {} payload bytes, but {} were given",
+				expected, found,
+			),
+			CanonicalError::Padding => {
+				fmt.write_str("Canonical bits have set bits in their padding")
+			},
+			CanonicalError::Capacity { bits } => write!(
+				fmt,
+				"Canonical bits hold {} bits, which is too many for a vector",
+				bits,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CanonicalError {
+}
+
+/// Computes the number of payload bytes that hold a number of bits.
+fn payload_len(bits: usize) -> usize {
+	bits / 8 + cmp::min(bits % 8, 1)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Lsb0;
+
+	#[test]
+	fn round_trip() {
+		//  Runs that cross byte and element boundaries.
+		let data = [0x1B00_FF00u32, 0x0000_FFFF, 0x8000_0001];
+		let msb0 = data.bits::<Msb0>();
+		for &(start, end) in &[(0, 0), (0, 96), (3, 11), (5, 13), (31, 65)] {
+			let bits = &msb0[start .. end];
+			let bytes = bits.to_canonical_bytes();
+			assert_eq!(bytes.len(), HEADER + payload_len(bits.len()));
+
+			//  The same bits, held in other storage, encode the same way.
+			let lsb0 =
+				BitVec::<Lsb0, u16>::from_canonical_bytes(&bytes).unwrap();
+			assert_eq!(lsb0, bits);
+			assert_eq!(lsb0.to_canonical_bytes(), bytes);
+			let back = BitVec::<Msb0, u8>::from_canonical_bytes(&bytes);
+			assert_eq!(back.unwrap(), bits);
+		}
+
+		assert_eq!(msb0[.. 0].to_canonical_bytes(), [0; 8]);
+		//  The bits after the end of a partial last byte are zero.
+		let bytes = msb0[3 .. 10].to_canonical_bytes();
+		assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 7, 0b1101_1000]);
+		let bytes = msb0[3 .. 11].to_canonical_bytes();
+		assert_eq!(bytes, [0, 0, 0, 0, 0, 0, 0, 8, 0b1101_1000]);
+		let decode = BitVec::<Lsb0, u8>::from_canonical_bytes;
+		assert_eq!(decode(&bytes[.. 5]), Err(CanonicalError::Header {
+			found: 5,
+		}));
+		assert_eq!(
+			decode(&bytes[.. 8]),
+			Err(CanonicalError::Length {
+				expected: 1,
+				found: 0,
+			}),
+		);
+		let mut long = bytes.clone();
+		long.push(0);
+		assert_eq!(
+			decode(&long),
+			Err(CanonicalError::Length {
+				expected: 1,
+				found: 2,
+			}),
+		);
+		let mut header = bytes;
+		header[0] = 0x80;
+		assert_eq!(
+			decode(&header),
+			Err(CanonicalError::Length {
+				expected: (1 << 60) + 1,
+				found: 1,
+			}),
+		);
+		let mut padded = msb0[3 .. 10].to_canonical_bytes();
+		padded[8] |= 1;
+		assert_eq!(decode(&padded), Err(CanonicalError::Padding));
+	}
+}
@@ -0,0 +1,152 @@
+/*! Internet checksums over bit regions
+
+Network protocols protect their headers with the 16-bit ones-complement sum of
+[RFC 1071]. Byte-oriented implementations of it require their input to begin
+and end on byte boundaries, but packets built or parsed with `bitvec` often
+carry payloads that do not: a field of a few bits, or a payload that follows
+one, starts part of the way through a byte.
+
+This module adds the checksum to `BitSlice<_, u8>`. The bits of the slice are
+read in order, as a big-endian stream: the first bit of the slice is the most
+significant bit of the first 16-bit word. A slice whose length is not a multiple
+of sixteen is padded with zero bits at its end, which matches the RFC’s padding
+of an odd trailing byte when the slice is a whole number of bytes.
+
+[RFC 1071]: https://tools.ietf.org/html/rfc1071
+!*/
+
+use crate::{
+	indices::Indexable,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+impl<O> BitSlice<O, u8>
+where O: BitOrder
+{
+	/// Computes the 16-bit ones-complement sum of the slice.
+	///
+	/// This is the sum before its final complement. Sums of separate regions,
+	/// such as a pseudo-header and a segment, can be combined by adding them
+	/// and folding the carry back into the low sixteen bits, as long as every
+	/// region but the last has a length that is a multiple of sixteen bits.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The ones-complement sum of the sixteen-bit words of the slice.
+	///
+	/// # Examples
+	///
+	/// This is the example in section 3 of RFC 1071.
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0x00u8, 0x01, 0xF2, 0x03, 0xF4, 0xF5, 0xF6, 0xF7];
+	/// assert_eq!(data.bits::<Msb0>().ones_complement_sum(), 0xDDF2);
+	/// ```
+	pub fn ones_complement_sum(&self) -> u16 {
+		//  The octets are summed in a wide accumulator, and the carries are
+		//  folded in only once, at the end.
+		let mut sum = self
+			.chunks(8)
+			.map(octet)
+			.enumerate()
+			.map(|(n, byte)| (byte as u64) << (if n & 1 == 0 { 8 } else { 0 }))
+			.sum::<u64>();
+		while sum >> 16 != 0 {
+			sum = (sum & 0xFFFF) + (sum >> 16);
+		}
+		sum as u16
+	}
+
+	/// Computes the RFC 1071 Internet checksum of the slice.
+	///
+	/// This is the complement of [`ones_complement_sum`]. A region that holds
+	/// its own correct checksum has a ones-complement sum of `0xFFFF`, and so
+	/// a checksum of zero.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The checksum of the slice, to be written most significant bit first.
+	///
+	/// # Examples
+	///
+	/// The slice need not begin or end on a byte boundary.
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut packet = [0u8; 5];
+	/// let bits = &mut packet.bits_mut::<Msb0>()[3 .. 35];
+	/// bits[.. 16].store_be(0x1234u16);
+	/// let sum = bits.internet_checksum();
+	/// assert_eq!(sum, !0x1234);
+	/// bits[16 ..].store_be(sum);
+	/// assert_eq!(bits.internet_checksum(), 0);
+	/// ```
+	///
+	/// [`ones_complement_sum`]: #method.ones_complement_sum
+	pub fn internet_checksum(&self) -> u16 {
+		!self.ones_complement_sum()
+	}
+}
+
+/// Reads up to eight bits as an octet, with the first bit most significant and
+/// any missing bits at the end cleared.
+fn octet<O>(bits: &BitSlice<O, u8>) -> u8
+where O: BitOrder {
+	//  A whole byte can be converted as a unit.
+	if let [byte] = bits.as_slice() {
+		return (0 .. 8).fold(0, |acc, n| {
+			acc << 1 | byte.get::<O>((n as u8).idx()) as u8
+		});
+	}
+	bits.iter()
+		.fold(0u8, |acc, bit| acc << 1 | *bit as u8)
+		.wrapping_shl(8 - bits.len() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+
+	#[test]
+	fn misaligned() {
+		let data = [0x00u8, 0x01, 0xF2, 0x03, 0xF4, 0xF5, 0xF6, 0xF7, 0x00];
+		//  Shifting the data right by some bits within a larger buffer does
+		//  not change the sum of the region that covers it.
+		for shift in 0 .. 8 {
+			let mut buf = [0u8; 10];
+			buf.bits_mut::<Msb0>()[shift .. shift + 72]
+				.copy_from_slice(data.bits::<Msb0>());
+			let bits = &buf.bits::<Msb0>()[shift .. shift + 64];
+			assert_eq!(bits.ones_complement_sum(), 0xDDF2);
+		}
+
+		//  An odd trailing byte, and an odd trailing bit count, are padded.
+		let bits = data.bits::<Msb0>();
+		assert_eq!(bits[.. 24].ones_complement_sum(), 0xF201);
+		assert_eq!(bits[.. 20].ones_complement_sum(), 0xF001);
+		assert_eq!(bits[.. 0].ones_complement_sum(), 0);
+
+		//  `Lsb0` slices are read in their own bit order.
+		let rev = [0x80u8, 0x00];
+		assert_eq!(rev.bits::<Lsb0>().ones_complement_sum(), 0x0100);
+	}
+}
@@ -0,0 +1,133 @@
+/*! Cross-correlation of bit streams
+
+A receiver that knows a synchronization pattern finds where a frame begins by
+sliding the pattern along the received stream, and counting how many bits agree
+at each offset. Comparing bit by bit costs one operation per bit of the pattern
+at every offset.
+
+[`BitSlice::correlate`] counts the agreeing bits at each offset with whole
+memory elements instead. A window of the stream only lines up with the pattern
+element for element when both begin at the same bit of their first element, so
+the pattern is copied once for each starting bit that the windows use, and each
+window is compared against the matching copy.
+
+[`BitSlice::correlate`]: ../slice/struct.BitSlice.html#method.correlate
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::vec::Vec;
+
+use core::cmp;
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Counts the bits that agree between a pattern and each window of the
+	/// slice, at each offset from the front.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `needle`: The pattern to slide along `self`. Its ordering and storage
+	///   type need not match those of `self`.
+	/// - `max_lag`: The largest offset at which to compare the pattern.
+	///
+	/// # Returns
+	///
+	/// A vector whose element `n` is the number of bits of `needle` equal to
+	/// the bits of `self[n .. n + needle.len()]`. It has an element for each
+	/// offset up to `max_lag` at which `needle` fits within `self`, so it is
+	/// empty if `needle` is longer than `self`. Subtracting an element from
+	/// `needle.len()` gives the Hamming distance at that offset.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let stream = bits![Msb0, u8; 0, 1, 1, 0, 1, 0, 0, 1, 1, 0];
+	/// let sync = bits![1, 0, 0, 1];
+	/// let scores = stream.correlate(sync, 10);
+	/// assert_eq!(scores, [0, 3, 2, 1, 4, 2, 0]);
+	///
+	/// let best = (0 .. scores.len()).max_by_key(|&n| scores[n]);
+	/// assert_eq!(best, Some(4));
+	/// ```
+	pub fn correlate<O2, T2>(
+		&self,
+		needle: &BitSlice<O2, T2>,
+		max_lag: usize,
+	) -> Vec<usize>
+	where
+		O2: BitOrder,
+		T2: BitStore,
+	{
+		let len = needle.len();
+		let lags = match self.len().checked_sub(len) {
+			Some(last) => cmp::min(last, max_lag) + 1,
+			None => return Vec::new(),
+		};
+		//  Copies of the pattern, by the bit at which they begin in their
+		//  first element. They are built as windows come to need them.
+		let mut aligned: Vec<Option<BitVec<O, T>>> =
+			(0 .. T::BITS).map(|_| None).collect();
+		(0 .. lags)
+			.map(|lag| {
+				let window = &self[lag .. lag + len];
+				let head = *window.bitptr().head() as usize;
+				let copy = aligned[head].get_or_insert_with(|| {
+					let mut copy = BitVec::repeat(false, head + len);
+					copy[head ..].clone_from_slice(needle);
+					copy
+				});
+				len - window.xor_count_ones(&copy[head ..])
+			})
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+
+	#[test]
+	fn all_lags() {
+		//  The 13-bit Barker code, placed in a silent stream. It agrees with
+		//  the stream in all 13 bits at its own lag, and in fewer elsewhere.
+		let barker = &0x159Fu16.bits::<Lsb0>()[.. 13];
+		let mut data = [0u32; 4];
+		data.bits_mut::<Msb0>()[43 .. 56].clone_from_slice(barker);
+		let stream = &data.bits::<Msb0>()[3 ..];
+		let scores = stream.correlate(barker, 90);
+		assert_eq!(scores.len(), 91);
+		for (lag, &score) in scores.iter().enumerate() {
+			let window = &stream[lag .. lag + barker.len()];
+			let naive = window.iter().zip(barker).filter(|(a, b)| a == b);
+			assert_eq!(score, naive.count());
+			assert_eq!(score == 13, lag == 40);
+		}
+
+		//  The needle is found where it was taken from.
+		let needle = &stream[40 .. 72];
+		let scores = stream.correlate(needle, !0);
+		assert_eq!(scores.len(), stream.len() - 31);
+		assert_eq!(scores[40], 32);
+		assert!(stream[.. 10].correlate(needle, 5).is_empty());
+	}
+}
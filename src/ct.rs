@@ -0,0 +1,251 @@
+/*! Constant-time comparison and selection of bits
+
+The ordinary comparisons and searches of `BitSlice` stop at the first bit that
+decides their answer: `==` returns at the first differing element, and `any`
+and `all` at the first bit that is set or cleared. The time they take reveals
+where that bit is, which is unacceptable when the bits are key material, MACs,
+or any other secret.
+
+This module provides equality and conditional assignment through the
+[`subtle`] crate’s `Choice` type, with the following guarantee: the sequence
+of instructions executed, and of memory addresses touched, depends only on the
+lengths of the slices and on where they begin within their storage elements,
+and never on the values of their bits or of the `Choice`. Nothing in these
+paths branches or returns early on bit values, and their results are
+accumulated with bitwise arithmetic until they are turned into a `Choice`.
+
+The lengths and alignments of the slices are treated as public, as the `subtle`
+implementations for ordinary slices treat their lengths.
+
+[`subtle`]: https://docs.rs/subtle/2/subtle/
+!*/
+
+#![cfg(feature = "subtle")]
+
+use crate::{
+	access::BitAccess,
+	domain::Domain,
+	oblivious::mask_of,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use ::subtle::{
+	Choice,
+	ConstantTimeEq,
+};
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Tests two slices for equality in constant time.
+	///
+	/// Slices of different lengths are unequal, and this returns at once when
+	/// the lengths differ. Otherwise, every bit of both slices is examined,
+	/// whatever their values.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `other`: Another slice of the same type, which may begin at a
+	///   different bit within its storage.
+	///
+	/// # Returns
+	///
+	/// A `Choice` of `1` if the slices hold the same bits, and `0` otherwise.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let key = [0xA5u8, 0x3C];
+	/// let tag = [0x52u8, 0x9E];
+	/// let key = &key.bits::<Msb0>()[.. 14];
+	/// let tag = tag.bits::<Msb0>();
+	/// assert!(bool::from(key.ct_eq(&tag[1 .. 15])));
+	/// assert!(!bool::from(key.ct_eq(&tag[.. 14])));
+	/// ```
+	pub fn ct_eq(&self, other: &Self) -> Choice {
+		if self.len() != other.len() {
+			return Choice::from(0);
+		}
+		//  Every differing bit is set in `diff`, which is only tested once all
+		//  of the bits have been folded into it.
+		let mut diff = T::FALSE;
+		if self.bitptr().head() == other.bitptr().head() {
+			match (self.domain(), other.domain()) {
+				(Domain::Enclave(a), Domain::Enclave(b)) => {
+					diff = differ(a.load(), b.load());
+				},
+				(
+					Domain::Region {
+						head: ah,
+						body: ab,
+						tail: at,
+					},
+					Domain::Region {
+						head: bh,
+						body: bb,
+						tail: bt,
+					},
+				) => {
+					if let (Some(a), Some(b)) = (ah, bh) {
+						diff |= differ(a.load(), b.load());
+					}
+					for (&a, &b) in ab.iter().zip(bb) {
+						diff |= differ(a, b);
+					}
+					if let (Some(a), Some(b)) = (at, bt) {
+						diff |= differ(a.load(), b.load());
+					}
+				},
+				_ => unreachable!("Equal slices have equal domains"),
+			}
+		}
+		else {
+			for idx in 0 .. self.len() {
+				diff |= differ(self.load_bit(idx), other.load_bit(idx));
+			}
+		}
+		(T::count_ones(diff) as u64).ct_eq(&0)
+	}
+
+	/// Copies another slice into this one if a `Choice` is set, in constant
+	/// time.
+	///
+	/// Every bit of `self` is written, with its own value when `choice` is
+	/// `0`, and with the value of the matching bit of `src` when it is `1`.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `src`: The bits to copy. This may begin at a different bit within
+	///   its storage than `self` does.
+	/// - `choice`: Whether to copy `src` into `self`.
+	///
+	/// # Panics
+	///
+	/// This panics if `src` is not the same length as `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	/// use subtle::Choice;
+	///
+	/// let mut data = [0u8; 2];
+	/// let src = [0xFFu8];
+	/// let bits = &mut data.bits_mut::<Msb0>()[4 .. 12];
+	/// bits.conditional_assign(src.bits(), Choice::from(0));
+	/// assert_eq!(data, [0, 0]);
+	///
+	/// let bits = &mut data.bits_mut::<Msb0>()[4 .. 12];
+	/// bits.conditional_assign(src.bits(), Choice::from(1));
+	/// assert_eq!(data, [0x0F, 0xF0]);
+	/// ```
+	pub fn conditional_assign(&mut self, src: &Self, choice: Choice) {
+		assert_eq!(
+			self.len(),
+			src.len(),
+			"Conditional assignment requires slices of equal length",
+		);
+		let mask = mask_of(T::from(choice.unwrap_u8()));
+		if self.bitptr().head() == src.bitptr().head() {
+			self.zip_words_mut(src, src, |dst, src, _| {
+				(dst & !mask) | (src & mask)
+			});
+			return;
+		}
+		let bitptr = self.bitptr();
+		for idx in 0 .. self.len() {
+			let (elt, bit) = bitptr.head().offset(idx as isize);
+			let elem = unsafe { &*bitptr.pointer().a().offset(elt) };
+			let place = *O::mask(bit);
+			//  Clear the bit only when it will be replaced, then write the
+			//  source bit through the same mask.
+			elem.clear_bits(!(place & mask));
+			elem.set_bits((src.load_bit(idx) << *O::at::<T>(bit)) & mask);
+		}
+	}
+}
+
+impl<O, T> ConstantTimeEq for BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn ct_eq(&self, other: &Self) -> Choice {
+		BitSlice::ct_eq(self, other)
+	}
+}
+
+/// Marks the bits in which two elements differ.
+fn differ<T>(a: T, b: T) -> T
+where T: BitStore {
+	(a | b) & !(a & b)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+
+	#[test]
+	fn constant_time() {
+		let x = [0x0000_FFFFu32, 0xFF00_FF00, 0x0F0F_0F0F];
+		let xb = x.bits::<Lsb0>();
+		for &(from, len) in &[(0, 96), (0, 1), (5, 20), (5, 80), (50, 30)] {
+			let span = from .. from + len;
+			let last = span.end - 1;
+
+			//  Equal operands, in separate storage.
+			let mut y = x;
+			let ct = xb[span.clone()].ct_eq(&y.bits::<Lsb0>()[span.clone()]);
+			assert!(bool::from(ct), "{:?}", span);
+
+			//  Operands that differ only in their last bit.
+			y.bits_mut::<Lsb0>().set(last, !xb[last]);
+			let yb = y.bits::<Lsb0>();
+			let ct = xb[span.clone()].ct_eq(&yb[span.clone()]);
+			assert!(!bool::from(ct), "{:?}", span);
+			assert_ne!(xb[span.clone()], yb[span.clone()]);
+			assert!(bool::from(xb[from .. last].ct_eq(&yb[from .. last])));
+		}
+		assert!(bool::from(xb[.. 0].ct_eq(&xb[50 .. 50])));
+		assert!(bool::from(xb[4 .. 12].ct_eq(&xb[4 .. 12])));
+		assert!(!bool::from(xb[4 .. 12].ct_eq(&xb[4 .. 13])));
+		assert!(!bool::from(xb[3 .. 30].ct_eq(&xb[4 .. 31])));
+		let mut y = x;
+		y.bits_mut::<Lsb0>().set(95, !xb[95]);
+		let ct = ConstantTimeEq::ct_eq(&xb[40 ..], &y.bits::<Lsb0>()[40 ..]);
+		assert!(!bool::from(ct));
+
+		let data = [0x0F0Fu16, 0x3355];
+		let src = data.bits::<Msb0>();
+		for &(dst_from, src_from) in &[(3, 3), (0, 7), (9, 1)] {
+			for &choice in &[0, 1] {
+				let mut out = [0xA5A5u16, 0xC3C3, 0x5A5A];
+				let orig = out;
+				let span = dst_from .. dst_from + 20;
+				let src = &src[src_from .. src_from + 20];
+				out.bits_mut::<Msb0>()[span.clone()]
+					.conditional_assign(src, Choice::from(choice));
+				let (out, orig) = (out.bits::<Msb0>(), orig.bits::<Msb0>());
+				let want = if choice == 1 { src } else { &orig[span.clone()] };
+				assert_eq!(out[span], want);
+				assert_eq!(out[.. dst_from], orig[.. dst_from]);
+				assert_eq!(out[dst_from + 20 ..], orig[dst_from + 20 ..]);
+			}
+		}
+	}
+}
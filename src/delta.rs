@@ -0,0 +1,380 @@
+/*! Deltas between versions of a bitmap
+
+Replication streams and undo logs that record a bitmap after each change store
+the whole buffer every time, even when only a handful of bits changed. A
+[`BitDelta`] records only the positions at which two versions of a bitmap
+differ, as runs of changed bits separated by gaps of unchanged ones, so that its
+size follows the number of changes rather than the size of the map.
+
+[`BitSlice::diff`] computes the delta between two slices of equal length, and
+[`BitSlice::apply_delta`] applies it to a slice by inverting the bits that it
+names. A delta is its own inverse: applying it to either of the versions that
+produced it gives the other one, so the same record serves to redo a change and
+to undo it.
+
+[`BitDelta`]: struct.BitDelta.html
+[`BitSlice::apply_delta`]: ../slice/struct.BitSlice.html#method.apply_delta
+[`BitSlice::diff`]: ../slice/struct.BitSlice.html#method.diff
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	fields,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+};
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Computes the positions at which two slices differ.
+	///
+	/// The slices are compared 64 bits at a time, by exclusive-or, and each
+	/// gap and run is measured by counting the leading zeros or ones of the
+	/// difference, so long stretches of equal or changed bits cost one step
+	/// per 64 bits. Slices in the `Msb0` or `Lsb0` layout are loaded a whole
+	/// element at a time, whatever bit they begin on.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `other`: A slice of the same length as `self`. Its ordering and
+	///   storage type need not match those of `self`.
+	///
+	/// # Returns
+	///
+	/// A delta which, when applied to `self`, makes it equal to `other`.
+	///
+	/// # Panics
+	///
+	/// This panics if `other` is not the same length as `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let old = bitvec![Msb0, u8; 0, 0, 1, 1, 0, 0, 0, 1];
+	/// let new = bitvec![Msb0, u8; 0, 1, 0, 1, 0, 0, 0, 0];
+	/// let delta = old.diff(&new);
+	/// assert_eq!(delta.runs(), &[(1, 2), (4, 1)]);
+	///
+	/// let mut bits = old.clone();
+	/// bits.apply_delta(&delta).unwrap();
+	/// assert_eq!(bits, new);
+	/// //  Applying it again undoes the change.
+	/// bits.apply_delta(&delta).unwrap();
+	/// assert_eq!(bits, old);
+	/// ```
+	pub fn diff<O2, T2>(&self, other: &BitSlice<O2, T2>) -> BitDelta
+	where
+		O2: BitOrder,
+		T2: BitStore,
+	{
+		assert_eq!(
+			self.len(),
+			other.len(),
+			"Cannot compute the delta between slices of different lengths",
+		);
+		let mut runs = Vec::new();
+		let (mut gap, mut run) = (0, 0);
+		//  Whether the bits being counted are in a run, rather than a gap.
+		let mut changed = false;
+		for (a, b) in self.chunks(64).zip(other.chunks(64)) {
+			let mut rest = a.len();
+			//  The first bit of the chunk is the most significant of `diff`.
+			let diff = fields::load_msb_first(a) ^ fields::load_msb_first(b);
+			let mut diff = diff << (64 - rest);
+			while rest > 0 {
+				let same = if changed { !diff } else { diff };
+				let count = cmp::min(same.leading_zeros() as usize, rest);
+				if changed {
+					run += count;
+				}
+				else {
+					gap += count;
+				}
+				rest -= count;
+				diff = diff.checked_shl(count as u32).unwrap_or(0);
+				if rest == 0 {
+					break;
+				}
+				//  The next bit ends the current gap or run.
+				if changed {
+					runs.push((gap, run));
+					gap = 0;
+					run = 0;
+				}
+				changed = !changed;
+			}
+		}
+		if run > 0 {
+			runs.push((gap, run));
+		}
+		BitDelta {
+			len: self.len(),
+			runs,
+		}
+	}
+
+	/// Inverts every bit that a delta names.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `delta`: A delta computed between two slices of the same length as
+	///   `self`.
+	///
+	/// # Returns
+	///
+	/// An error if `delta` was computed for slices of a different length, in
+	/// which case `self` is not modified.
+	pub fn apply_delta(&mut self, delta: &BitDelta) -> Result<(), DeltaError> {
+		if delta.len != self.len() {
+			return Err(DeltaError {
+				expected: delta.len,
+				found: self.len(),
+			});
+		}
+		let mut idx = 0;
+		for &(gap, run) in &delta.runs {
+			idx += gap;
+			let _ = !&mut self[idx .. idx + run];
+			idx += run;
+		}
+		Ok(())
+	}
+}
+
+/** The positions at which two versions of a bitmap differ.
+
+This is produced by [`BitSlice::diff`], and consumed by
+[`BitSlice::apply_delta`]. It is stored as a list of runs, each of which is a
+count of unchanged bits followed by a count of changed bits. Runs never touch,
+so every run but the first has a non-zero gap before it, and no run is empty.
+
+[`BitSlice::apply_delta`]: ../slice/struct.BitSlice.html#method.apply_delta
+[`BitSlice::diff`]: ../slice/struct.BitSlice.html#method.diff
+**/
+#[derive(Clone, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BitDelta {
+	/// The length of the slices between which the delta was computed.
+	len: usize,
+	/// Pairs of the number of unchanged bits, then the number of changed bits.
+	runs: Vec<(usize, usize)>,
+}
+
+//  `len` is the length of the slices the delta applies to, not the number of
+//  changes in it, so there is no `is_empty` to match it.
+#[allow(clippy::len_without_is_empty)]
+impl BitDelta {
+	/// Rebuilds a delta from its parts, as produced by [`len`] and [`runs`].
+	///
+	/// # Parameters
+	///
+	/// - `len`: The length of the slices between which the delta applies.
+	/// - `runs`: Pairs of the number of unchanged bits, then the number of
+	///   changed bits.
+	///
+	/// # Returns
+	///
+	/// The delta, or `None` if the runs reach past `len`, touch, or are empty.
+	///
+	/// [`len`]: #method.len
+	/// [`runs`]: #method.runs
+	pub fn from_runs(len: usize, runs: Vec<(usize, usize)>) -> Option<Self> {
+		let mut end = 0usize;
+		for (n, &(gap, run)) in runs.iter().enumerate() {
+			if run == 0 || (gap == 0 && n > 0) {
+				return None;
+			}
+			end = end.checked_add(gap)?.checked_add(run)?;
+		}
+		if end > len {
+			return None;
+		}
+		Some(Self { len, runs })
+	}
+
+	/// Gets the length of the slices between which the delta applies.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Tests if the delta changes no bits, and so leaves any slice it is
+	/// applied to unchanged.
+	pub fn is_identity(&self) -> bool {
+		self.runs.is_empty()
+	}
+
+	/// Gets the runs of the delta.
+	///
+	/// # Returns
+	///
+	/// Pairs of the number of unchanged bits, then the number of changed bits,
+	/// in order from the front of the slice.
+	pub fn runs(&self) -> &[(usize, usize)] {
+		&self.runs
+	}
+
+	/// Counts the bits that the delta changes.
+	pub fn count_changed(&self) -> usize {
+		self.runs.iter().map(|&(_, run)| run).sum()
+	}
+
+	/// Iterates over the indices of the bits that the delta changes.
+	pub fn changed(&self) -> impl Iterator<Item = usize> + '_ {
+		let mut start = 0;
+		self.runs.iter().flat_map(move |&(gap, run)| {
+			start += gap + run;
+			start - run .. start
+		})
+	}
+}
+
+/** The error produced when a delta is applied to a slice of the wrong length.
+
+This is returned by [`BitSlice::apply_delta`], and `expected` and `found` are
+the lengths of the delta and of the slice.
+
+[`BitSlice::apply_delta`]: ../slice/struct.BitSlice.html#method.apply_delta
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct DeltaError {
+	/// The length of the slices for which the delta was computed.
+	expected: usize,
+	/// The length of the slice to which it was applied.
+	found: usize,
+}
+
+impl DeltaError {
+	/// Gets the length of the slices for which the delta was computed.
+	pub fn expected(&self) -> usize {
+		self.expected
+	}
+
+	/// Gets the length of the slice to which the delta was applied.
+	pub fn found(&self) -> usize {
+		self.found
+	}
+}
+
+impl Display for DeltaError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(
+			fmt,
+			"Delta for {} bits applied to a slice of {} bits",
+			self.expected, self.found,
+		)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeltaError {
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+	use alloc::{
+		borrow::ToOwned,
+		vec,
+	};
+
+	#[test]
+	fn round_trip() {
+		//  The new data flips one bit, and a run of five bits that crosses an
+		//  element boundary.
+		let old = [0xFFFF_0000u32, 0x0F0F_0F0F, 0x0000_FFFF];
+		let new = [0xFFFF_0000u32, 0x0F0E_0F0E, 0xF000_FFFF];
+		let (a, b) = (&old.bits::<Msb0>()[5 ..], &new.bits::<Msb0>()[5 ..]);
+		let delta = a.diff(b);
+		assert_eq!(delta.count_changed(), 6);
+		assert!(!delta.is_identity());
+		assert_eq!(delta.len(), 91);
+		let changed = delta.changed().collect::<Vec<_>>();
+		assert_eq!(changed, [42, 58, 59, 60, 61, 62]);
+		assert_eq!(delta.runs(), &[(42, 1), (15, 5)]);
+		let runs = delta.runs().to_vec();
+		assert_eq!(BitDelta::from_runs(a.len(), runs), Some(delta.clone()));
+
+		let mut data = old;
+		let bits = &mut data.bits_mut::<Msb0>()[5 ..];
+		bits.apply_delta(&delta).unwrap();
+		assert_eq!(bits, b);
+		bits.apply_delta(&delta).unwrap();
+		assert_eq!(data, old);
+
+		//  Deltas cross orderings, and stop at the end of the slice.
+		let lsb = a.iter().copied().collect::<crate::vec::BitVec<Lsb0, u8>>();
+		let delta = lsb.diff(a);
+		assert!(delta.is_identity());
+		let err = lsb[1 ..].to_owned().apply_delta(&delta).unwrap_err();
+		assert_eq!((err.expected(), err.found()), (91, 90));
+
+		let empty = &a[.. 0];
+		let delta = empty.diff(empty);
+		assert!(delta.is_identity());
+		assert_eq!(delta.len(), 0);
+
+		assert!(BitDelta::from_runs(10, vec![(2, 3), (0, 1)]).is_none());
+		assert!(BitDelta::from_runs(10, vec![(2, 3), (5, 1)]).is_none());
+		assert!(BitDelta::from_runs(10, vec![(0, 3), (6, 1)]).is_some());
+	}
+
+	#[test]
+	#[cfg(target_pointer_width = "64")]
+	fn long_runs() {
+		//  The runs cross the 64-bit chunks in which the slices are compared,
+		//  and the slices begin at different bits of different element types.
+		let mut old = [0u16; 20];
+		let mut new = [0u64; 6];
+		let a = &mut old.bits_mut::<Lsb0>()[3 ..][.. 300];
+		let b = &mut new.bits_mut::<Msb0>()[11 ..][.. 300];
+		for (n, &(start, len)) in
+			[(0, 1), (5, 64), (70, 130), (200, 1), (202, 1), (299, 1)]
+				.iter()
+				.enumerate()
+		{
+			let range = start .. start + len;
+			a[range.clone()].set_all(n % 2 == 0);
+			b[range].set_all(true);
+		}
+		let delta = a.diff(b);
+		let expected = a
+			.iter()
+			.zip(b.iter())
+			.enumerate()
+			.filter(|(_, (x, y))| x != y)
+			.map(|(n, _)| n)
+			.collect::<Vec<_>>();
+		assert_eq!(delta.changed().collect::<Vec<_>>(), expected);
+		assert_eq!(delta.runs(), &[(5, 64), (131, 1), (98, 1)]);
+
+		a.apply_delta(&delta).unwrap();
+		assert_eq!(a, b);
+		assert!(a.diff(b).is_identity());
+	}
+}
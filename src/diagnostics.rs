@@ -0,0 +1,96 @@
+/*! Read-only introspection of slice handles.
+
+`&BitSlice` references are not ordinary slice references: their pointer and
+length words are packed with the index of the first live bit, and do not carry
+their meaning on their own. When a slice crosses an FFI boundary, or is shared
+between components that disagree about its alignment, it is useful to see what
+a handle actually describes. This module, enabled by the `diagnostics` feature,
+decodes a handle into plain values for logging and assertions.
+
+None of these values can be used to reconstruct or modify a handle.
+!*/
+
+#![cfg(feature = "diagnostics")]
+
+use crate::{
+	domain::BitDomain,
+	pointer::BitPtr,
+	store::BitStore,
+};
+
+/** The decoded components of a `BitSlice` handle.
+
+This is produced by [`BitSlice::bitptr_parts`].
+
+[`BitSlice::bitptr_parts`]: ../slice/struct.BitSlice.html#method.bitptr_parts
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct BitPtrParts<T>
+where T: BitStore
+{
+	/// The address of the first element containing live bits. Empty slices
+	/// that do not refer to any memory have a dangling address.
+	pub address: *const T,
+	/// The index, within the first element, of the first live bit.
+	pub head: u8,
+	/// The number of live bits.
+	pub bits: usize,
+	/// The number of elements that contain at least one live bit.
+	pub elements: usize,
+	/// How the live bits are laid out over those elements.
+	pub domain: DomainKind,
+}
+
+impl<T> BitPtrParts<T>
+where T: BitStore
+{
+	/// Decodes a pointer structure.
+	pub(crate) fn new(bitptr: BitPtr<T>) -> Self {
+		Self {
+			address: bitptr.pointer().r(),
+			head: *bitptr.head(),
+			bits: bitptr.len(),
+			elements: bitptr.elements(),
+			domain: DomainKind::from(bitptr.domain()),
+		}
+	}
+}
+
+/** The shapes that a slice’s live bits can take over its elements.
+
+Each variant names the elements that are only partially live, which are the
+elements that the slice may share with other handles.
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DomainKind {
+	/// The slice has no live bits.
+	Empty,
+	/// The slice lies within one element, and reaches neither of its edges.
+	Minor,
+	/// The slice begins and ends partway through elements, which are distinct.
+	Major,
+	/// The slice begins partway through an element and ends on an element
+	/// boundary.
+	PartialHead,
+	/// The slice begins on an element boundary and ends partway through an
+	/// element.
+	PartialTail,
+	/// The slice begins and ends on element boundaries, and fully owns every
+	/// element it touches.
+	Spanning,
+}
+
+impl<'a, T> From<BitDomain<'a, T>> for DomainKind
+where T: 'a + BitStore
+{
+	fn from(domain: BitDomain<'a, T>) -> Self {
+		match domain {
+			BitDomain::Empty => DomainKind::Empty,
+			BitDomain::Minor(..) => DomainKind::Minor,
+			BitDomain::Major(..) => DomainKind::Major,
+			BitDomain::PartialHead(..) => DomainKind::PartialHead,
+			BitDomain::PartialTail(..) => DomainKind::PartialTail,
+			BitDomain::Spanning(..) => DomainKind::Spanning,
+		}
+	}
+}
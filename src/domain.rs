@@ -5,17 +5,34 @@ representative states depending on the span of governed elements and live bits.
 
 This module provides representations of the domain states for ease of use by
 handle operations.
+
+The [`Domain`] and [`DomainMut`] views, produced by [`BitSlice::domain`] and
+[`BitSlice::domain_mut`], expose this decomposition publicly. They allow code
+outside the crate to operate on the fully-owned interior elements of a slice
+directly, as ordinary integers, and to touch only the live bits of the partial
+elements at either edge.
+
+[`BitSlice::domain`]: ../slice/struct.BitSlice.html#method.domain
+[`BitSlice::domain_mut`]: ../slice/struct.BitSlice.html#method.domain_mut
+[`Domain`]: enum.Domain.html
+[`DomainMut`]: enum.DomainMut.html
 !*/
 
 use crate::{
+	access::BitAccess,
 	indices::{
 		BitIdx,
 		BitTail,
+		Indexable,
 	},
+	order::BitOrder,
 	pointer::BitPtr,
+	slice::BitSlice,
 	store::BitStore,
 };
 
+use core::marker::PhantomData;
+
 use either::Either;
 
 /** Alias for the region components produced by the `BitDomain::splat` method.
@@ -259,6 +276,411 @@ where T: 'a + BitStore
 	}
 }
 
+/** A read-only view of the memory elements underneath a `BitSlice`.
+
+A bit slice may begin and end partway through a memory element. This view
+separates the elements it fully owns, which can be read as plain integers, from
+the partially-owned edge elements, which may be shared with other slices and
+must be read through a mask.
+
+# Lifetimes
+
+- `'a`: The lifetime of the `BitSlice` this views.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering used to compute edge masks.
+- `T: BitStore`: The element type of the underlying memory.
+
+# Examples
+
+This counts the set bits in a slice, processing whole elements at a time.
+
+```rust
+use bitvec::{domain::Domain, prelude::*};
+
+let data = [0xF0u8, 0x0F, 0xFF];
+let bits = &data.bits::<Msb0>()[2 .. 20];
+let count = match bits.domain() {
+    Domain::Enclave(elem) => elem.load().count_ones(),
+    Domain::Region { head, body, tail } => {
+        head.map_or(0, |e| e.load().count_ones())
+            + body.iter().map(|e| e.count_ones()).sum::<u32>()
+            + tail.map_or(0, |e| e.load().count_ones())
+    },
+};
+assert_eq!(count as usize, bits.count_ones());
+```
+**/
+#[derive(Debug)]
+pub enum Domain<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The slice lies within a single element, and reaches neither edge of it.
+	Enclave(PartialElement<'a, O, T>),
+	/// The slice covers zero or more whole elements, and may begin or end
+	/// partway through an element.
+	Region {
+		/// The partially-owned element at the front of the slice, if the slice
+		/// does not begin at the start of an element.
+		head: Option<PartialElement<'a, O, T>>,
+		/// The elements wholly owned by the slice. This may be empty.
+		body: &'a [T],
+		/// The partially-owned element at the back of the slice, if the slice
+		/// does not end at the end of an element.
+		tail: Option<PartialElement<'a, O, T>>,
+	},
+}
+
+impl<'a, O, T> Domain<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	pub(crate) fn new(bits: &'a BitSlice<O, T>) -> Self {
+		match bits.bitptr().domain().splat() {
+			Either::Right((h, e, t)) => {
+				Domain::Enclave(PartialElement::new(e, h, *t))
+			},
+			Either::Left((h, b, t)) => Domain::Region {
+				head: h.map(|(h, e)| PartialElement::new(e, h, T::BITS)),
+				body: b.map_or(&[], |b| unsafe { &*BitAccess::as_slice_mut(b) }),
+				tail: t.map(|(e, t)| PartialElement::new(e, 0u8.idx(), *t)),
+			},
+		}
+	}
+}
+
+/** A mutable view of the memory elements underneath a `BitSlice`.
+
+This is the write-capable counterpart of [`Domain`]. The wholly-owned body
+elements are yielded as an ordinary mutable slice, and the partial edge elements
+can only be written through their mask, so that bits outside the slice are never
+disturbed.
+
+# Lifetimes
+
+- `'a`: The lifetime of the `BitSlice` this views.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering used to compute edge masks.
+- `T: BitStore`: The element type of the underlying memory.
+
+# Examples
+
+```rust
+use bitvec::{domain::DomainMut, prelude::*};
+
+let mut data = [0u8; 3];
+let bits = &mut data.bits_mut::<Msb0>()[4 .. 20];
+if let DomainMut::Region { head, body, tail } = bits.domain_mut() {
+    head.unwrap().store(!0);
+    for elt in body {
+        *elt = 0xA5;
+    }
+    tail.unwrap().store(!0);
+}
+assert_eq!(data, [0x0F, 0xA5, 0xF0]);
+```
+
+[`Domain`]: enum.Domain.html
+**/
+#[derive(Debug)]
+pub enum DomainMut<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The slice lies within a single element, and reaches neither edge of it.
+	Enclave(PartialElementMut<'a, O, T>),
+	/// The slice covers zero or more whole elements, and may begin or end
+	/// partway through an element.
+	Region {
+		/// The partially-owned element at the front of the slice, if the slice
+		/// does not begin at the start of an element.
+		head: Option<PartialElementMut<'a, O, T>>,
+		/// The elements wholly owned by the slice. This may be empty.
+		body: &'a mut [T],
+		/// The partially-owned element at the back of the slice, if the slice
+		/// does not end at the end of an element.
+		tail: Option<PartialElementMut<'a, O, T>>,
+	},
+}
+
+impl<'a, O, T> DomainMut<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	pub(crate) fn new(bits: &'a mut BitSlice<O, T>) -> Self {
+		match bits.bitptr().domain().splat() {
+			Either::Right((h, e, t)) => DomainMut::Enclave(PartialElementMut {
+				inner: PartialElement::new(e, h, *t),
+				_mut: PhantomData,
+			}),
+			Either::Left((h, b, t)) => DomainMut::Region {
+				head: h.map(|(h, e)| PartialElementMut {
+					inner: PartialElement::new(e, h, T::BITS),
+					_mut: PhantomData,
+				}),
+				body: b.map_or(&mut [], |b| unsafe { BitAccess::as_slice_mut(b) }),
+				tail: t.map(|(e, t)| PartialElementMut {
+					inner: PartialElement::new(e, 0u8.idx(), *t),
+					_mut: PhantomData,
+				}),
+			},
+		}
+	}
+}
+
+/** A read-only handle to the live bits of a partially-owned memory element.
+
+Other slices may own the remaining bits of the element, and may be writing to
+them concurrently. This handle only ever reads the element as a whole, and
+masks away the bits that do not belong to its slice.
+**/
+#[derive(Clone, Copy, Debug)]
+pub struct PartialElement<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The shared element.
+	elem: &'a T::Access,
+	/// The bits of `elem` that belong to the slice.
+	mask: T,
+	/// The index of the first live bit.
+	head: BitIdx<T>,
+	/// The index after the last live bit.
+	tail: u8,
+	/// The ordering used to compute `mask`.
+	_order: PhantomData<O>,
+}
+
+impl<'a, O, T> PartialElement<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	fn new(elem: &'a T::Access, head: BitIdx<T>, tail: u8) -> Self {
+		Self {
+			elem,
+			mask: live_mask::<O, T>(*head, tail),
+			head,
+			tail,
+			_order: PhantomData,
+		}
+	}
+
+	/// Reads the element, clearing every bit that is not part of the slice.
+	pub fn load(&self) -> T {
+		self.elem.load() & self.mask
+	}
+
+	/// Produces the mask of bits in the element that are part of the slice.
+	pub fn mask(&self) -> T {
+		self.mask
+	}
+
+	/// Gets the index of the first bit in the element that is part of the
+	/// slice.
+	pub fn head(&self) -> BitIdx<T> {
+		self.head
+	}
+
+	/// Gets the index one past the last bit in the element that is part of the
+	/// slice. This is in the range `1 ..= T::BITS`.
+	pub fn tail(&self) -> u8 {
+		self.tail
+	}
+}
+
+/** A write-capable handle to the live bits of a partially-owned memory element.
+
+Writes through this handle only modify the bits selected by its mask. The rest
+of the element is left untouched, even if another slice is writing to it at the
+same time.
+**/
+#[derive(Debug)]
+pub struct PartialElementMut<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The shared read handle.
+	inner: PartialElement<'a, O, T>,
+	/// Mark this as derived from an exclusive borrow.
+	_mut: PhantomData<&'a mut T>,
+}
+
+impl<'a, O, T> PartialElementMut<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// Reads the element, clearing every bit that is not part of the slice.
+	pub fn load(&self) -> T {
+		self.inner.load()
+	}
+
+	/// Writes a value into the bits of the element that are part of the
+	/// slice. Bits of `value` outside the mask are discarded.
+	pub fn store(&mut self, value: T) {
+		let mask = self.inner.mask;
+		self.inner.elem.clear_bits(!mask);
+		self.inner.elem.set_bits(value & mask);
+	}
+
+	/// Produces the mask of bits in the element that are part of the slice.
+	pub fn mask(&self) -> T {
+		self.inner.mask
+	}
+
+	/// Gets the index of the first bit in the element that is part of the
+	/// slice.
+	pub fn head(&self) -> BitIdx<T> {
+		self.inner.head
+	}
+
+	/// Gets the index one past the last bit in the element that is part of the
+	/// slice. This is in the range `1 ..= T::BITS`.
+	pub fn tail(&self) -> u8 {
+		self.inner.tail
+	}
+}
+
+/** An iterator over every memory element underneath a `BitSlice`, paired with
+the mask of the element’s bits that are part of the slice.
+
+This is produced by [`BitSlice::raw_elements`], and is the lowest-level view
+this crate offers. Unlike [`Domain`], it does not distinguish the edge elements
+from the body: each element is yielded through its shared-mutability access type
+along with its mask, which is all ones for elements wholly inside the slice.
+Kernels that combine several passes over the memory, such as an `AND` followed
+by a population count, can be written as one loop over this iterator.
+
+# Lifetimes
+
+- `'a`: The lifetime of the `BitSlice` this views.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering used to compute the masks.
+- `T: BitStore`: The element type of the underlying memory.
+
+[`BitSlice::raw_elements`]: ../slice/struct.BitSlice.html#method.raw_elements
+[`Domain`]: enum.Domain.html
+**/
+#[cfg(feature = "unsafe-word-ops")]
+#[derive(Clone, Debug)]
+pub struct RawElements<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The partially-owned element at the front, if it has not been yielded.
+	head: Option<(&'a T::Access, T)>,
+	/// The wholly-owned elements that have not been yielded.
+	body: core::slice::Iter<'a, T::Access>,
+	/// The partially-owned element at the back, if it has not been yielded.
+	tail: Option<(&'a T::Access, T)>,
+	/// The ordering used to compute the masks.
+	_order: PhantomData<O>,
+}
+
+#[cfg(feature = "unsafe-word-ops")]
+impl<'a, O, T> RawElements<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	pub(crate) fn new(bits: &'a BitSlice<O, T>) -> Self {
+		let (head, body, tail) = match bits.bitptr().domain().splat() {
+			Either::Right((h, e, t)) => {
+				(Some((e, live_mask::<O, T>(*h, *t))), &[][..], None)
+			},
+			Either::Left((h, b, t)) => (
+				h.map(|(h, e)| (e, live_mask::<O, T>(*h, T::BITS))),
+				b.unwrap_or(&[]),
+				t.map(|(e, t)| (e, live_mask::<O, T>(0, *t))),
+			),
+		};
+		Self {
+			head,
+			body: body.iter(),
+			tail,
+			_order: PhantomData,
+		}
+	}
+}
+
+#[cfg(feature = "unsafe-word-ops")]
+impl<'a, O, T> Iterator for RawElements<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = (&'a T::Access, T);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.head
+			.take()
+			.or_else(|| self.body.next().map(|e| (e, T::TRUE)))
+			.or_else(|| self.tail.take())
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.head.is_some() as usize
+			+ self.body.len()
+			+ self.tail.is_some() as usize;
+		(len, Some(len))
+	}
+}
+
+#[cfg(feature = "unsafe-word-ops")]
+impl<'a, O, T> DoubleEndedIterator for RawElements<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.tail
+			.take()
+			.or_else(|| self.body.next_back().map(|e| (e, T::TRUE)))
+			.or_else(|| self.head.take())
+	}
+}
+
+#[cfg(feature = "unsafe-word-ops")]
+impl<'a, O, T> ExactSizeIterator for RawElements<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+#[cfg(feature = "unsafe-word-ops")]
+impl<'a, O, T> core::iter::FusedIterator for RawElements<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+/// Computes the mask that selects the bits `head .. tail` of an element.
+fn live_mask<O, T>(head: u8, tail: u8) -> T
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	(head .. tail)
+		.map(|n| *O::mask(n.idx()))
+		.fold(T::FALSE, |m, b| m | b)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -318,4 +740,28 @@ mod tests {
 
 		assert!(bp.domain().is_spanning());
 	}
+
+	#[test]
+	#[cfg(feature = "unsafe-word-ops")]
+	fn raw_elements() {
+		use crate::{
+			order::Msb0,
+			slice::AsBits,
+		};
+		use alloc::vec::Vec;
+
+		let data = [0u8; 4];
+		let masks = |bits: &BitSlice<Msb0, u8>| {
+			unsafe { bits.raw_elements() }.map(|(_, m)| m).collect::<Vec<_>>()
+		};
+		let bits = data.bits::<Msb0>();
+		assert_eq!(masks(&bits[2 .. 6]), [0x3C]);
+		assert_eq!(masks(&bits[3 .. 29]), [0x1F, 0xFF, 0xFF, 0xF8]);
+		assert_eq!(masks(&bits[8 .. 24]), [0xFF, 0xFF]);
+		assert!(masks(&bits[8 .. 8]).is_empty());
+
+		let iter = unsafe { bits[3 .. 29].raw_elements() };
+		assert_eq!(iter.len(), 4);
+		assert_eq!(iter.rev().map(|(_, m)| m).next(), Some(0xF8));
+	}
 }
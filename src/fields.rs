@@ -36,11 +36,23 @@ The provided `BitOrder` implementors `Lsb0` and `Msb0` use the local machine’s
 byte ordering. Other cursors *may* implement ordering of bytes within `T`
 elements differently, for instance by calling `.to_be_bytes` before store and
 `from_be_bytes` after load,
+
+# Unaligned Loads
+
+Packet parsers spend much of their time loading fields that straddle several
+bytes. The `fast_unaligned` feature loads the bytes that a field fully owns with
+a single unaligned read of the one-, two-, four-, or eight-byte word that covers
+them, and then shifts and masks that word down to those bytes. The word may
+overlap the partial elements at either edge of the field, but their bits are
+discarded, and those elements are still loaded on their own. This applies to
+every `load` method of `Lsb0` and `Msb0` slices of bytes, and of wider elements
+whose order matches the target’s byte order. Stores are unaffected.
 !*/
 
 use crate::{
 	access::BitAccess,
 	order::{
+		BitOrder,
 		Lsb0,
 		Msb0,
 	},
@@ -50,6 +62,11 @@ use crate::{
 
 use core::{
 	cmp,
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
 	mem,
 	ptr,
 };
@@ -59,7 +76,6 @@ use either::Either;
 #[cfg(feature = "alloc")]
 use crate::{
 	boxed::BitBox,
-	order::BitOrder,
 	vec::BitVec,
 };
 
@@ -302,6 +318,7 @@ where T: BitStore
 			accumulator.
 			*/
 			Either::Left((head, body, tail)) => {
+				let edges = (head.is_some(), tail.is_some());
 				let mut accum = 0usize;
 
 				//  If the tail exists, it contains the most significant chunk
@@ -313,11 +330,7 @@ where T: BitStore
 				//  Read the body elements, from high address to low, into the
 				//  accumulator.
 				if let Some(elts) = body {
-					for elt in elts.iter().rev() {
-						let val: usize = resize(elt.load());
-						accum <<= T::BITS;
-						accum |= val;
-					}
+					accum = load_body::<T>(accum, elts, edges, false);
 				}
 				//  If the head exists, it contains the least significant chunk
 				//  of the value, on the MSedge side.
@@ -364,6 +377,7 @@ where T: BitStore
 			accumulator.
 			*/
 			Either::Left((head, body, tail)) => {
+				let edges = (head.is_some(), tail.is_some());
 				let mut accum = 0usize;
 
 				//  If the head exists, it contains the most significant chunk
@@ -375,11 +389,7 @@ where T: BitStore
 				//  Read the body elements, from low address to high, into the
 				//  accumulator.
 				if let Some(elts) = body {
-					for elt in elts.iter() {
-						let val: usize = resize(elt.load());
-						accum <<= T::BITS;
-						accum |= val;
-					}
+					accum = load_body::<T>(accum, elts, edges, true);
 				}
 				//  If the tail exists, it contains the least significant chunk
 				//  of the value, on the LSedge side.
@@ -566,6 +576,7 @@ where T: BitStore
 			accumulator.
 			*/
 			Either::Left((head, body, tail)) => {
+				let edges = (head.is_some(), tail.is_some());
 				let mut accum = 0usize;
 
 				//  If the tail exists, it contains the most significant chunk
@@ -579,11 +590,7 @@ where T: BitStore
 				//  Read the body elements, from high address to low, into the
 				//  accumulator.
 				if let Some(elts) = body {
-					for elt in elts.iter().rev() {
-						let val: usize = resize(elt.load());
-						accum <<= T::BITS;
-						accum |= val;
-					}
+					accum = load_body::<T>(accum, elts, edges, false);
 				}
 				//  If the head exists, it contains the least significant chunk
 				//  of the value, on the LSedge side.
@@ -628,6 +635,7 @@ where T: BitStore
 			accumulator.
 			*/
 			Either::Left((head, body, tail)) => {
+				let edges = (head.is_some(), tail.is_some());
 				let mut accum = 0usize;
 
 				//  If the head exists, it contains the most significant chunk
@@ -641,11 +649,7 @@ where T: BitStore
 				//  Read the body elements, from low address to high, into the
 				//  accumulator.
 				if let Some(elts) = body {
-					for elt in elts.iter() {
-						let val: usize = resize(elt.load());
-						accum <<= T::BITS;
-						accum |= val;
-					}
+					accum = load_body::<T>(accum, elts, edges, true);
 				}
 				//  If the tail exists, it contains the least significant chunk
 				//  of the value, on the MSedge side.
@@ -864,6 +868,157 @@ where
 	}
 }
 
+/** A named field at a fixed position inside a fixed-width bit record.
+
+Implementors are zero-sized marker types that describe where a field lives in a
+packed record: the record is `WIDTH` bits wide, and the field occupies
+`START .. START + LEN`. The [`bit_range!`] macro is the intended way to declare
+these types, as it verifies at compile time that the field is non-empty and
+lies entirely within the record.
+
+Once the field is known to fit in the record, the only remaining check at
+runtime is that the slice handed to the accessors is exactly `WIDTH` bits long.
+
+This is expressed with associated constants rather than const generic
+parameters, so that it is available on the compilers this crate supports.
+
+# Examples
+
+```rust
+use bitvec::{bit_range, fields::BitRange, prelude::*};
+
+bit_range!(
+    /// The four-bit version number at the front of the header.
+    pub Version: 16 => 0, 4
+);
+bit_range!(pub Length: 16 => 4, 12);
+
+let mut header = [0u8; 2];
+let bits = header.bits_mut::<Msb0>();
+Version::store(bits, 4u8);
+Length::store(bits, 0x123u16);
+assert_eq!(header[0] & 0xF0, 0x40);
+
+let bits = header.bits::<Msb0>();
+assert_eq!(Version::load::<_, _, u8>(bits), 4);
+assert_eq!(Length::load::<_, _, u16>(bits), 0x123);
+```
+
+[`bit_range!`]: ../macro.bit_range.html
+**/
+pub trait BitRange {
+	/// The index of the first bit of the field within its record.
+	const START: usize;
+	/// The number of bits in the field.
+	const LEN: usize;
+	/// The total number of bits in the record that contains the field.
+	const WIDTH: usize;
+
+	/// Selects the field out of a record.
+	///
+	/// # Panics
+	///
+	/// This panics if `record` is not exactly `WIDTH` bits long.
+	fn get<O, T>(record: &BitSlice<O, T>) -> &BitSlice<O, T>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		assert_eq!(
+			record.len(),
+			Self::WIDTH,
+			"Record width does not match the field descriptor",
+		);
+		unsafe { record.get_unchecked(Self::START .. Self::START + Self::LEN) }
+	}
+
+	/// Selects the field out of a mutable record.
+	///
+	/// # Panics
+	///
+	/// This panics if `record` is not exactly `WIDTH` bits long.
+	fn get_mut<O, T>(record: &mut BitSlice<O, T>) -> &mut BitSlice<O, T>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		assert_eq!(
+			record.len(),
+			Self::WIDTH,
+			"Record width does not match the field descriptor",
+		);
+		unsafe {
+			record.get_unchecked_mut(Self::START .. Self::START + Self::LEN)
+		}
+	}
+
+	/// Loads the field out of a record.
+	///
+	/// # Panics
+	///
+	/// This panics if `record` is not exactly `WIDTH` bits long, or if `U` is
+	/// narrower than the field.
+	fn load<O, T, U>(record: &BitSlice<O, T>) -> U
+	where
+		O: BitOrder,
+		T: BitStore,
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		Self::get(record).load()
+	}
+
+	/// Stores a value into the field of a record.
+	///
+	/// Only the low `LEN` bits of `value` are written.
+	///
+	/// # Panics
+	///
+	/// This panics if `record` is not exactly `WIDTH` bits long, or if `U` is
+	/// narrower than the field.
+	fn store<O, T, U>(record: &mut BitSlice<O, T>, value: U)
+	where
+		O: BitOrder,
+		T: BitStore,
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		Self::get_mut(record).store(value)
+	}
+}
+
+/** Two’s-complement integers that have an unsigned `BitStore` counterpart.
+
+This is used by [`BitSlice::as_int`] to name a signed result type, as the
+`BitStore` types through which bits are loaded are all unsigned.
+
+[`BitSlice::as_int`]: ../slice/struct.BitSlice.html#method.as_int
+**/
+pub trait Signed: Copy {
+	/// The unsigned type of the same width.
+	type Unsigned: BitStore;
+
+	/// Reinterprets the bits of the unsigned counterpart as a signed value.
+	fn from_unsigned(value: Self::Unsigned) -> Self;
+}
+
+macro_rules! signed {
+	($( $s:ty => $u:ty ),* $(,)?) => { $(
+		impl Signed for $s {
+			type Unsigned = $u;
+
+			fn from_unsigned(value: $u) -> Self {
+				value as $s
+			}
+		}
+	)* };
+}
+
+signed![i8 => u8, i16 => u16, i32 => u32, isize => usize];
+
+#[cfg(target_pointer_width = "64")]
+signed![i64 => u64];
+
 /** Safely computes an LS-edge bitmask for a value of some length.
 
 The shift operators panic when the shift amount equals or exceeds the type
@@ -970,6 +1125,236 @@ where
 	out
 }
 
+/** Shifts the fully-owned elements of a field into an accumulator.
+
+# Parameters
+
+- `accum`: The more significant bits of the field, already loaded.
+- `elts`: The elements that the field fully owns.
+- `edges`: Whether the field has a partial element immediately below and above
+  `elts` in memory.
+- `big`: Whether the element at the lowest address is the most significant.
+
+# Returns
+
+`accum`, shifted up by the width of `elts`, with the elements loaded into the
+vacated low bits.
+
+# Fast Path
+
+With the `fast_unaligned` feature, elements that fit in a `usize` are read with
+one unaligned load of the smallest power-of-two word that covers their bytes,
+which is then shifted and masked down to them. This is only the same value when
+the bytes of the elements run in the same order as the elements do, which is
+always true of single-byte elements, and otherwise requires the target byte
+order to match `big`.
+
+A covering word wider than `elts` borrows its spare bytes from the partial edge
+elements, which are part of the same allocation; their bits are masked off and
+never reach the accumulator. Elements that do not qualify, or whose covering
+word would reach past both edges, take the element-wise path.
+**/
+#[inline]
+#[cfg_attr(not(feature = "fast_unaligned"), allow(unused_variables))]
+fn load_body<T>(
+	accum: usize,
+	elts: &[T::Access],
+	edges: (bool, bool),
+	big: bool,
+) -> usize
+where
+	T: BitStore,
+{
+	#[cfg(feature = "fast_unaligned")]
+	{
+		let bytes = mem::size_of_val(elts);
+		let native = big == cfg!(target_endian = "big");
+		let bytewise = mem::size_of::<T>() == 1 || native;
+		let width = bytes.next_power_of_two();
+		let spare = |edge: bool| if edge { mem::size_of::<T>() } else { 0 };
+		let (below, above) = (spare(edges.0), spare(edges.1));
+		if (1 ..= mem::size_of::<usize>()).contains(&bytes)
+			&& bytewise
+			&& below + bytes + above >= width
+		{
+			//  Take the spare bytes from below the elements first, and the
+			//  rest from above them.
+			let pre = cmp::min(below, width - bytes);
+			let post = width - bytes - pre;
+			let mut buf = [0u8; 8];
+			//  The covering word lies within the field’s elements. Only the
+			//  bytes of `elts` survive the mask, and the field fully owns
+			//  them, so no other handle can be writing to them.
+			unsafe {
+				let src = (elts.as_ptr() as *const u8).sub(pre);
+				match width {
+					8 => buf = ptr::read_unaligned(src as *const [u8; 8]),
+					4 => {
+						let part = ptr::read_unaligned(src as *const [u8; 4]);
+						buf[.. 4].copy_from_slice(&part);
+					},
+					2 => {
+						let part = ptr::read_unaligned(src as *const [u8; 2]);
+						buf[.. 2].copy_from_slice(&part);
+					},
+					_ => buf[0] = *src,
+				}
+			}
+			let word = if big {
+				u64::from_be_bytes(buf) >> (64 - 8 * width as u32) >> (8 * post)
+			}
+			else {
+				u64::from_le_bytes(buf) >> (8 * pre)
+			};
+			let val = word & (!0u64 >> (64 - 8 * bytes as u32));
+			return accum.checked_shl(8 * bytes as u32).unwrap_or(0)
+				| val as usize;
+		}
+	}
+	load_elements::<T>(accum, elts, big)
+}
+
+/** Shifts the fully-owned elements of a field into an accumulator, one element
+at a time.
+
+This is the portable path of [`load_body`], and takes the same parameters
+except for `edges`.
+
+[`load_body`]: fn.load_body.html
+**/
+fn load_elements<T>(accum: usize, elts: &[T::Access], big: bool) -> usize
+where T: BitStore {
+	let step = |accum: usize, elt: &T::Access| {
+		let val: usize = resize(elt.load());
+		accum.checked_shl(T::BITS as u32).unwrap_or(0) | val
+	};
+	if big {
+		elts.iter().fold(accum, step)
+	}
+	else {
+		elts.iter().rev().fold(accum, step)
+	}
+}
+
+/** Loads a run of bits as an integer, with its first bit most significant.
+
+This is the order used by the [`pack`] module and the register-staging types
+built on it, and is independent of the `BitOrder` of the slice. Orderings that
+lay their bits out as `Msb0` or `Lsb0` do are loaded a whole element at a time
+through [`BitField`]; any other ordering is read one bit at a time.
+
+# Parameters
+
+- `bits`: The bits to load. This must be no more than 64 bits long.
+
+# Returns
+
+The bits of `bits`, in the low `bits.len()` bits of the integer. An empty slice
+loads as zero.
+
+[`BitField`]: trait.BitField.html
+[`pack`]: ../pack/index.html
+**/
+pub(crate) fn load_msb_first<O, T>(bits: &BitSlice<O, T>) -> u64
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let len = bits.len();
+	if len == 0 {
+		return 0;
+	}
+	//  The `BitField` loads accumulate in a `usize`, so longer runs are split.
+	let word = mem::size_of::<usize>() * 8;
+	if len > word {
+		let (front, back) = bits.split_at(len - word);
+		return load_msb_first(front).checked_shl(word as u32).unwrap_or(0)
+			| load_msb_first(back);
+	}
+	//  An ordering with the same layout as `Msb0` or `Lsb0` covers the same
+	//  bits as it, even in partial elements, so the handle can be viewed
+	//  under that ordering. The run now fits in a `usize`, which is a
+	//  `BitStore` on every target, unlike `u64`.
+	match BitSlice::<O, T>::linear_order() {
+		Some(false) => {
+			bits.bitptr().into_bitslice::<Msb0>().load_be::<usize>() as u64
+		},
+		Some(true) => {
+			let value = bits.bitptr().into_bitslice::<Lsb0>().load_le::<usize>();
+			(BitStore::reverse_bits(value) >> (word - len)) as u64
+		},
+		None => bits.iter().fold(0, |accum, &bit| accum << 1 | bit as u64),
+	}
+}
+
+/** Stores an integer into a run of bits, with its first bit most significant.
+
+This is the inverse of [`load_msb_first`].
+
+# Parameters
+
+- `bits`: The bits to overwrite. This must be no more than 64 bits long.
+- `value`: The integer whose low `bits.len()` bits are stored. Its higher bits
+  are ignored.
+
+[`load_msb_first`]: fn.load_msb_first.html
+**/
+pub(crate) fn store_msb_first<O, T>(bits: &mut BitSlice<O, T>, value: u64)
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let len = bits.len();
+	if len == 0 {
+		return;
+	}
+	let word = mem::size_of::<usize>() * 8;
+	if len > word {
+		let (front, back) = bits.split_at_mut(len - word);
+		store_msb_first(front, value.checked_shr(word as u32).unwrap_or(0));
+		store_msb_first(back, value);
+		return;
+	}
+	match BitSlice::<O, T>::linear_order() {
+		Some(false) => bits
+			.bitptr()
+			.into_bitslice_mut::<Msb0>()
+			.store_be::<usize>(value as usize),
+		Some(true) => {
+			let value = BitStore::reverse_bits((value as usize) << (word - len));
+			bits.bitptr().into_bitslice_mut::<Lsb0>().store_le::<usize>(value)
+		},
+		None => {
+			for (idx, mut bit) in bits.iter_mut().enumerate() {
+				*bit = (value >> (len - 1 - idx)) & 1 != 0;
+			}
+		},
+	}
+}
+
+/** The error produced when an integer does not fit in the bits it is given.
+
+This is returned by [`BitSlice::store_uint`] and its variants, and by
+[`BitVec::truncate_checked`] and [`BitVec::truncate_signed_checked`]. Each of
+these leaves its destination unchanged when it fails.
+
+[`BitSlice::store_uint`]: ../slice/struct.BitSlice.html#method.store_uint
+[`BitVec::truncate_checked`]: ../vec/struct.BitVec.html#method.truncate_checked
+[`BitVec::truncate_signed_checked`]: ../vec/struct.BitVec.html#method.truncate_signed_checked
+**/
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("Integer value does not fit in the requested width")
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Overflow {
+}
+
 #[allow(clippy::inconsistent_digit_grouping)]
 #[cfg(test)]
 mod tests {
@@ -1123,6 +1508,137 @@ mod tests {
 		}
 		*/
 	}
+
+	#[test]
+	#[cfg(target_pointer_width = "64")]
+	fn spanning_bytes() {
+		//  Every byte differs, so a load that takes its bytes out of order, or
+		//  from the wrong place, gives a different value.
+		let src = [0x01u8, 0x23, 0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF, 0xF0, 0x0F];
+		for start in 0 .. 8 {
+			for &len in &[9, 17, 25, 33, 41, 49, 57, 63, 64] {
+				let lsb = &src.bits::<Lsb0>()[start ..][.. len];
+				let expected = lsb
+					.iter()
+					.rev()
+					.fold(0u64, |acc, &bit| acc << 1 | bit as u64);
+				assert_eq!(lsb.load_le::<u64>(), expected);
+
+				let msb = &src.bits::<Msb0>()[start ..][.. len];
+				let expected = msb
+					.iter()
+					.fold(0u64, |acc, &bit| acc << 1 | bit as u64);
+				assert_eq!(msb.load_be::<u64>(), expected);
+
+				//  The mixed orderings round-trip through their stores.
+				let mut data = [0u8; 10];
+				data.bits_mut::<Lsb0>()[start ..][.. len].store_be(expected);
+				let bits = &data.bits::<Lsb0>()[start ..][.. len];
+				assert_eq!(bits.load_be::<u64>(), expected);
+				data.bits_mut::<Msb0>()[start ..][.. len].store_le(expected);
+				let bits = &data.bits::<Msb0>()[start ..][.. len];
+				assert_eq!(bits.load_le::<u64>(), expected);
+			}
+		}
+	}
+
+	/// Checks the covering-word loads of every field body in a patterned
+	/// buffer against the element-wise loads.
+	fn check_body<O, T>()
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let mut data = [T::FALSE; 12];
+		let bits = data.bits_mut::<O>();
+		for n in 0 .. bits.len() {
+			bits.set(n, n % 3 == 0 || n % 7 == 1);
+		}
+		let word = mem::size_of::<usize>() * 8;
+		let width = T::BITS as usize;
+		for &head in &[0, 1, width / 2, width - 1] {
+			for len in 1 ..= word {
+				let bits = &data.bits::<O>()[head ..][.. len];
+				let (h, body, t) = match bits.bitptr().domain().splat() {
+					Either::Left((h, Some(body), t)) => (h, body, t),
+					_ => continue,
+				};
+				let edges = (h.is_some(), t.is_some());
+				for &big in &[false, true] {
+					assert_eq!(
+						load_body::<T>(0x5A, body, edges, big),
+						load_elements::<T>(0x5A, body, big),
+						"head {}, len {}, big {}",
+						head,
+						len,
+						big,
+					);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn covering_word() {
+		check_body::<Lsb0, u8>();
+		check_body::<Msb0, u8>();
+		check_body::<Lsb0, u16>();
+		check_body::<Msb0, u16>();
+		check_body::<Lsb0, u32>();
+		check_body::<Msb0, u32>();
+		#[cfg(target_pointer_width = "64")]
+		{
+			check_body::<Lsb0, u64>();
+			check_body::<Msb0, u64>();
+		}
+	}
+
+	/// Swaps the bits of each pair, so that neither linear layout applies.
+	struct PairSwap;
+
+	impl BitOrder for PairSwap {
+		const TYPENAME: &'static str = "PairSwap";
+
+		fn at<T>(place: crate::indices::BitIdx<T>) -> crate::indices::BitPos<T>
+		where T: BitStore {
+			use crate::indices::Indexable;
+			(*place ^ 1).pos()
+		}
+	}
+
+	fn check_msb_first<O>()
+	where O: BitOrder {
+		//  The bits of the value are not symmetric, so a run stored or loaded
+		//  in the wrong direction changes.
+		let value = 0x0123_4567_89AB_CDEFu64;
+		for start in 0 .. 16 {
+			for &len in &[0, 1, 7, 16, 33, 64] {
+				let mut data = [0u16; 6];
+				let bits = &mut data.bits_mut::<O>()[start ..][.. len];
+				store_msb_first(bits, value);
+				let expected = (0 .. len)
+					.fold(0u64, |accum, idx| accum << 1 | bits[idx] as u64);
+				let mask = 1u64.checked_shl(len as u32).unwrap_or(0);
+				assert_eq!(expected, value & mask.wrapping_sub(1));
+				assert_eq!(load_msb_first(bits), expected);
+				//  No bit outside the run was written.
+				let ones = expected.count_ones() as usize;
+				assert_eq!(data.bits::<O>().count_ones(), ones);
+			}
+		}
+	}
+
+	#[test]
+	fn msb_first() {
+		//  The first bit of the run is the most significant bit of the value.
+		let mut byte = 0u8;
+		store_msb_first(&mut byte.bits_mut::<Lsb0>()[.. 4], 0b1011);
+		assert_eq!(byte, 0b1101);
+
+		check_msb_first::<Msb0>();
+		check_msb_first::<Lsb0>();
+		check_msb_first::<PairSwap>();
+	}
 }
 
 #[cfg(test)]
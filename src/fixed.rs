@@ -0,0 +1,396 @@
+/*! Binary fixed-point numbers
+
+A fixed-point number is an integer with an implied binary exponent: the integer
+`m` with exponent `e` has the value `m × 2^e`. Signal-processing code stores
+such numbers in bit containers of whatever width the hardware produces, and must
+repeatedly align exponents before adding values, and convert to and from
+floating point at the edges of the system.
+
+[`FixedBits`] pairs a `BitVec` holding a 2’s-complement integer with its
+exponent, and performs that alignment. As with the arithmetic operators on
+`BitVec`, the first bit of the vector is the most significant.
+
+[`FixedBits`]: struct.FixedBits.html
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::{
+	cmp,
+	ops::{
+		Add,
+		Neg,
+		Sub,
+	},
+};
+
+/** A 2’s-complement integer in a `BitVec`, scaled by a power of two.
+
+The value of a `FixedBits` is the signed integer held in its bits, most
+significant bit first, multiplied by two raised to its exponent. An empty
+vector holds zero.
+
+Addition and subtraction align their operands to the smaller exponent, and
+widen the result so that it cannot overflow. Neither operation loses precision.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within each storage element.
+- `T: BitStore`: The storage element type.
+
+# Examples
+
+```rust
+use bitvec::{fixed::FixedBits, prelude::*};
+
+//  1.25, in a Q3.2 format.
+let a = FixedBits::<Local, u8>::from_f64(1.25, -2, 6).unwrap();
+assert_eq!(a.as_bitslice(), bits![0, 0, 0, 1, 0, 1]);
+
+//  -0.375, in a Q1.3 format.
+let b = FixedBits::<Local, u8>::from_f64(-0.375, -3, 5).unwrap();
+
+let sum = a + b;
+assert_eq!(sum.exponent(), -3);
+assert_eq!(sum.to_f64(), 0.875);
+```
+**/
+#[derive(Clone, Debug)]
+pub struct FixedBits<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The 2’s-complement integer, most significant bit first.
+	bits: BitVec<O, T>,
+	/// The power of two by which the integer is scaled.
+	exp: isize,
+}
+
+impl<O, T> FixedBits<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Pairs a 2’s-complement integer with an exponent.
+	///
+	/// # Parameters
+	///
+	/// - `bits`: A signed integer, most significant bit first.
+	/// - `exp`: The power of two by which `bits` is scaled.
+	pub fn new(bits: BitVec<O, T>, exp: isize) -> Self {
+		Self { bits, exp }
+	}
+
+	/// Encodes a floating-point number, rounding to the nearest multiple of
+	/// `2^exp`.
+	///
+	/// Ties round away from zero.
+	///
+	/// # Parameters
+	///
+	/// - `value`: The number to encode.
+	/// - `exp`: The exponent of the encoding. Its magnitude is the number of
+	///   fractional bits when it is negative.
+	/// - `width`: The number of bits in the encoding, including the sign bit.
+	///   This may be at most 64.
+	///
+	/// # Returns
+	///
+	/// The encoded number, or `None` if `value` is not finite, `width` is zero
+	/// or greater than 64, or the rounded value does not fit in `width` bits.
+	pub fn from_f64(value: f64, exp: isize, width: usize) -> Option<Self> {
+		if !value.is_finite() || width == 0 || width > 64 {
+			return None;
+		}
+		//  `isize::MIN` has no negation, but `pow2` saturates long before it.
+		//  Zero stays zero even when the scale overflows to infinity.
+		let neg = exp.checked_neg().unwrap_or_else(isize::max_value);
+		let scaled = if value == 0.0 { 0.0 } else { value * pow2(neg) };
+		//  `i64` holds `-2^63 .. 2^63`. Values this large have no fractional
+		//  part to round.
+		let limit = (1u64 << 63) as f64;
+		if scaled >= limit || scaled < -limit {
+			return None;
+		}
+		let trunc = scaled as i64;
+		let frac = scaled - trunc as f64;
+		let int = if frac >= 0.5 {
+			trunc + 1
+		}
+		else if frac <= -0.5 {
+			trunc - 1
+		}
+		else {
+			trunc
+		};
+		if width < 64 {
+			let limit = 1i64 << (width - 1);
+			if int < -limit || int >= limit {
+				return None;
+			}
+		}
+		let bits = (0 .. width).rev().map(|n| (int >> n) & 1 != 0).collect();
+		Some(Self::new(bits, exp))
+	}
+
+	/// Encodes a floating-point number, rounding to the nearest multiple of
+	/// `2^exp`.
+	///
+	/// This is [`from_f64`], for `f32` values.
+	///
+	/// [`from_f64`]: #method.from_f64
+	pub fn from_f32(value: f32, exp: isize, width: usize) -> Option<Self> {
+		Self::from_f64(value as f64, exp, width)
+	}
+
+	/// Decodes the number into floating point.
+	///
+	/// Integers wider than the `f64` mantissa are rounded.
+	pub fn to_f64(&self) -> f64 {
+		let mut iter = self.bits.iter().copied();
+		let mut acc = match iter.next() {
+			Some(true) => -1.0,
+			_ => 0.0,
+		};
+		for bit in iter {
+			acc = acc * 2.0 + if bit { 1.0 } else { 0.0 };
+		}
+		acc * pow2(self.exp)
+	}
+
+	/// Decodes the number into floating point.
+	///
+	/// This is [`to_f64`], rounded to `f32`.
+	///
+	/// [`to_f64`]: #method.to_f64
+	pub fn to_f32(&self) -> f32 {
+		self.to_f64() as f32
+	}
+
+	/// Reads the power of two by which the integer is scaled.
+	pub fn exponent(&self) -> isize {
+		self.exp
+	}
+
+	/// Views the 2’s-complement integer, most significant bit first.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		&self.bits
+	}
+
+	/// Unwraps the number into its integer and exponent.
+	pub fn into_parts(self) -> (BitVec<O, T>, isize) {
+		(self.bits, self.exp)
+	}
+
+	/// Multiplies the number by `2^by`.
+	///
+	/// This only adjusts the exponent, and is exact.
+	pub fn scale(&mut self, by: isize) {
+		self.exp += by;
+	}
+
+	/// Re-encodes the number with a new exponent.
+	///
+	/// Lowering the exponent appends zero bits to the integer, and is exact.
+	/// Raising the exponent removes its least significant bits, which rounds
+	/// the value towards negative infinity.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `exp`: The new exponent.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::{fixed::FixedBits, prelude::*};
+	///
+	/// let mut num = FixedBits::<Local, u8>::from_f64(-1.75, -2, 4).unwrap();
+	/// num.align_to(-4);
+	/// assert_eq!(num.as_bitslice().len(), 6);
+	/// num.align_to(-1);
+	/// assert_eq!(num.to_f64(), -2.0);
+	/// ```
+	pub fn align_to(&mut self, exp: isize) {
+		let len = self.bits.len();
+		if exp < self.exp {
+			let grow = (self.exp - exp) as usize;
+			self.bits.resize(len + grow, false);
+		}
+		else if exp > self.exp {
+			let drop = (exp - self.exp) as usize;
+			let sign = self.bits.first().copied().unwrap_or(false);
+			self.bits.truncate(len.saturating_sub(drop));
+			//  Shifting out every bit leaves only the sign.
+			if self.bits.is_empty() && len > 0 {
+				self.bits.push(sign);
+			}
+		}
+		self.exp = exp;
+	}
+
+	/// Adds two numbers, after aligning them and widening them by one bit.
+	///
+	/// `invert` and `carry` are both set for subtraction, which adds the
+	/// 2’s-complement negation of `rhs`.
+	fn combine(mut self, mut rhs: Self, invert: bool, carry: bool) -> Self {
+		let exp = cmp::min(self.exp, rhs.exp);
+		self.align_to(exp);
+		rhs.align_to(exp);
+		let width = cmp::max(self.bits.len(), rhs.bits.len()) + 1;
+		self.bits.sign_extend(width);
+		rhs.bits.sign_extend(width);
+		let mut c = carry;
+		for n in (0 .. width).rev() {
+			let (y, z) = crate::rca1(self.bits[n], rhs.bits[n] ^ invert, c);
+			self.bits.set(n, y);
+			c = z;
+		}
+		self
+	}
+}
+
+/// Produces zero, as an empty integer with an exponent of zero.
+impl<O, T> Default for FixedBits<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn default() -> Self {
+		Self::new(BitVec::new(), 0)
+	}
+}
+
+/// Adds two fixed-point numbers.
+///
+/// The sum has the smaller of the two exponents, and is one bit wider than
+/// the wider of the aligned operands, so that it cannot overflow.
+impl<O, T> Add for FixedBits<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Output = Self;
+
+	fn add(self, rhs: Self) -> Self::Output {
+		self.combine(rhs, false, false)
+	}
+}
+
+/// Subtracts one fixed-point number from another.
+///
+/// The difference has the smaller of the two exponents, and is one bit wider
+/// than the wider of the aligned operands, so that it cannot overflow.
+impl<O, T> Sub for FixedBits<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Output = Self;
+
+	fn sub(self, rhs: Self) -> Self::Output {
+		self.combine(rhs, true, true)
+	}
+}
+
+/// Negates a fixed-point number.
+///
+/// The result is one bit wider than the operand, so that the most negative
+/// value can be negated.
+impl<O, T> Neg for FixedBits<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Output = Self;
+
+	fn neg(self) -> Self::Output {
+		let exp = self.exp;
+		Self::new(BitVec::new(), exp) - self
+	}
+}
+
+/// Computes `2^exp`.
+///
+/// Exponents beyond the range of `f64` saturate to zero or infinity.
+fn pow2(exp: isize) -> f64 {
+	//  `f64` underflows to zero below `2^-1074`, and overflows above
+	//  `2^1023`, so larger exponents need not be computed.
+	let steps = if exp < 0 {
+		exp.wrapping_neg() as usize
+	}
+	else {
+		exp as usize
+	};
+	let steps = cmp::min(steps, 1100);
+	let factor = if exp < 0 { 0.5 } else { 2.0 };
+	(0 .. steps).fold(1.0, |acc, _| acc * factor)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Msb0;
+
+	type Fixed = FixedBits<Msb0, u8>;
+
+	#[test]
+	fn round_trip() {
+		for &value in &[0.0, 1.0, -1.0, 0.75, -0.75, 3.5, -4.0, 1.0 / 16.0] {
+			let num = Fixed::from_f64(value, -4, 8).unwrap();
+			assert_eq!(num.to_f64(), value);
+			assert_eq!(num.as_bitslice().len(), 8);
+		}
+		assert!(Fixed::from_f64(8.0, -4, 8).is_none());
+		assert_eq!(Fixed::from_f64(-8.0, -4, 8).unwrap().to_f64(), -8.0);
+		assert!(Fixed::from_f64(f64::NAN, 0, 8).is_none());
+		assert_eq!(Fixed::from_f64(0.3, -2, 4).unwrap().to_f64(), 0.25);
+		assert_eq!(Fixed::from_f64(-0.375, -2, 4).unwrap().to_f64(), -0.5);
+		assert_eq!(Fixed::from_f32(96.0, 5, 3).unwrap().to_f32(), 96.0);
+
+		assert!(Fixed::from_f64(1.0, isize::min_value(), 8).is_none());
+		let zero = Fixed::from_f64(0.0, isize::min_value(), 8).unwrap();
+		assert_eq!(zero.to_f64(), 0.0);
+		assert_eq!(Fixed::default().to_f64(), 0.0);
+	}
+
+	#[test]
+	fn arithmetic() {
+		let a = Fixed::from_f64(-8.0, -4, 8).unwrap();
+		let b = Fixed::from_f64(7.9375, -4, 8).unwrap();
+		assert_eq!((a.clone() - b.clone()).to_f64(), -15.9375);
+		assert_eq!((b.clone() - a.clone()).to_f64(), 15.9375);
+		assert_eq!((a.clone() + a.clone()).to_f64(), -16.0);
+		assert_eq!((-a).to_f64(), 8.0);
+
+		let c = Fixed::from_f64(12.0, 2, 3).unwrap();
+		let sum = b + c;
+		assert_eq!(sum.exponent(), -4);
+		assert_eq!(sum.to_f64(), 19.9375);
+	}
+
+	#[test]
+	fn align() {
+		let mut num = Fixed::from_f64(-0.25, -2, 3).unwrap();
+		num.align_to(4);
+		assert_eq!(num.as_bitslice().len(), 1);
+		assert_eq!(num.to_f64(), -16.0);
+
+		let mut num = Fixed::from_f64(0.25, -2, 3).unwrap();
+		num.align_to(4);
+		assert_eq!(num.to_f64(), 0.0);
+		num.scale(-6);
+		assert_eq!(num.exponent(), -2);
+	}
+}
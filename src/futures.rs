@@ -0,0 +1,905 @@
+/*! Bit streams over asynchronous byte streams
+
+Asynchronous protocol stacks receive and send their data through the
+[`AsyncRead`] and [`AsyncWrite`] traits of the [`futures`] ecosystem, which move
+whole bytes. A protocol whose fields do not fall on byte boundaries must either
+buffer a complete message before viewing its bits, or track a bit offset into
+the bytes it has received so far.
+
+This module provides that tracking. [`AsyncBitReader`] wraps an `AsyncRead`,
+and returns the bits it reads as `BitVec`s or as decoded integers. Its
+[`chunks`], [`fields_be`], and [`fields_le`] methods turn it into a `Stream` of
+either. Whenever the reader holds fewer bits than a request needs, it asks its
+source for up to 4096 more bytes at once, so it may read well ahead of the bits
+that it has returned; those bytes stay in its [`buffered`] bits until they are
+requested.
+
+[`AsyncBitWriter`] wraps an `AsyncWrite`, and is a `Sink` of `BitSlice`s that
+need not hold a whole number of bytes: it writes each byte once it is complete,
+and holds the bits of the last, incomplete, byte until more bits arrive or the
+sink is closed.
+
+All bytes are numbered by the `BitOrder` type parameter of the adapter, which is
+usually `Msb0` for network protocols.
+
+[`AsyncBitReader`]: struct.AsyncBitReader.html
+[`AsyncBitWriter`]: struct.AsyncBitWriter.html
+[`AsyncRead`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncRead.html
+[`AsyncWrite`]: https://docs.rs/futures-io/0.3/futures_io/trait.AsyncWrite.html
+[`buffered`]: struct.AsyncBitReader.html#method.buffered
+[`chunks`]: struct.AsyncBitReader.html#method.chunks
+[`fields_be`]: struct.AsyncBitReader.html#method.fields_be
+[`fields_le`]: struct.AsyncBitReader.html#method.fields_le
+[`futures`]: https://docs.rs/futures/0.3/futures/
+!*/
+
+#![cfg(all(feature = "futures", not(target_env = "sgx")))]
+
+use crate::{
+	fields::BitField,
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::{
+		AsBits,
+		BitSlice,
+	},
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	marker::PhantomData,
+	pin::Pin,
+	task::{
+		Context,
+		Poll,
+	},
+};
+
+use ::futures_core::{
+	ready,
+	Stream,
+};
+//  `futures-io` re-exports the `std::io` types that its traits use. In this
+//  crate, `std` may instead be `sgx_tstd`, whose types are not the same.
+use ::futures_io::{
+	self as io,
+	AsyncRead,
+	AsyncWrite,
+};
+use ::futures_sink::Sink;
+
+/// The number of bytes that a reader requests from its source at a time.
+const READ_LEN: usize = 4096;
+
+/// The number of complete bytes that a writer holds before it applies
+/// backpressure to its sink.
+const WRITE_LEN: usize = 8192;
+
+/** Reads bits, and integers of any bit width, from an `AsyncRead`.
+
+# Type Parameters
+
+- `R: AsyncRead + Unpin`: The source of bytes.
+- `O: BitOrder`: The ordering used to index bits within each byte.
+
+# Examples
+
+This requires an executor, such as the one in the `futures` crate. Here, a
+hidden `poll` function stands in for one, and polls a task until it is ready.
+
+```rust
+use bitvec::{futures::AsyncBitReader, prelude::*};
+use core::pin::Pin;
+use futures_core::Stream;
+# use core::{
+#     ptr,
+#     task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+# };
+# fn poll<T>(mut task: impl FnMut(&mut Context) -> Poll<T>) -> T {
+#     fn clone(_: *const ()) -> RawWaker {
+#         RawWaker::new(ptr::null(), &VTABLE)
+#     }
+#     fn noop(_: *const ()) {}
+#     static VTABLE: RawWakerVTable =
+#         RawWakerVTable::new(clone, noop, noop, noop);
+#     let waker = unsafe { Waker::from_raw(clone(ptr::null())) };
+#     let mut cx = Context::from_waker(&waker);
+#     loop {
+#         if let Poll::Ready(out) = task(&mut cx) {
+#             return out;
+#         }
+#     }
+# }
+
+let data = [0b1011_0100u8, 0b1110_0000];
+let reader = AsyncBitReader::<_, Msb0>::new(&data[..]);
+let mut fields = reader.fields_be::<u8>(3);
+let mut values = Vec::new();
+while let Some(field) = poll(|cx| Pin::new(&mut fields).poll_next(cx)) {
+    values.push(field.unwrap());
+}
+assert_eq!(values, [0b101, 0b101, 0b001, 0b110, 0b000]);
+```
+**/
+pub struct AsyncBitReader<R, O = Local>
+where O: BitOrder
+{
+	/// The source of bytes.
+	reader: R,
+	/// Bytes received from `reader`, of which the first `head` bits have been
+	/// returned.
+	bytes: Vec<u8>,
+	/// The index of the first bit in `bytes` not yet returned.
+	head: usize,
+	/// Whether `reader` has reported the end of its stream.
+	eof: bool,
+	_order: PhantomData<O>,
+}
+
+impl<R, O> AsyncBitReader<R, O>
+where
+	R: AsyncRead + Unpin,
+	O: BitOrder,
+{
+	/// Wraps a byte source.
+	pub fn new(reader: R) -> Self {
+		Self {
+			reader,
+			bytes: Vec::new(),
+			head: 0,
+			eof: false,
+			_order: PhantomData,
+		}
+	}
+
+	/// Views the bits that have been received but not yet returned.
+	pub fn buffered(&self) -> &BitSlice<O, u8> {
+		&self.bytes.bits::<O>()[self.head ..]
+	}
+
+	/// Borrows the byte source.
+	pub fn get_ref(&self) -> &R {
+		&self.reader
+	}
+
+	/// Mutably borrows the byte source.
+	///
+	/// Bytes read directly from the source skip the bits still
+	/// [`buffered`] in `self`.
+	///
+	/// [`buffered`]: #method.buffered
+	pub fn get_mut(&mut self) -> &mut R {
+		&mut self.reader
+	}
+
+	/// Unwraps the byte source, discarding any buffered bits.
+	pub fn into_inner(self) -> R {
+		self.reader
+	}
+
+	/// Reads a number of bits.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `cx`: The context of the task reading the bits.
+	/// - `count`: The number of bits to read.
+	///
+	/// # Returns
+	///
+	/// The next `count` bits of the stream, or any error from the source.
+	///
+	/// # Errors
+	///
+	/// If the source ends with fewer than `count` bits remaining, this fails
+	/// with `io::ErrorKind::UnexpectedEof`, and the remaining bits stay
+	/// buffered.
+	pub fn poll_read_bits(
+		&mut self,
+		cx: &mut Context,
+		count: usize,
+	) -> Poll<io::Result<BitVec<O, u8>>>
+	{
+		ready!(self.poll_fill(cx, count))?;
+		self.split(count)
+			.map(BitVec::from_bitslice)
+			.ok_or_else(unexpected_eof)
+			.into()
+	}
+
+	/// Reads an integer, stored in big-endian byte order.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `cx`: The context of the task reading the integer.
+	/// - `width`: The number of bits in the integer.
+	///
+	/// # Returns
+	///
+	/// The next `width` bits of the stream, loaded as by
+	/// [`BitField::load_be`], or any error from the source.
+	///
+	/// # Errors
+	///
+	/// As [`poll_read_bits`].
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero, or wider than `M`.
+	///
+	/// [`BitField::load_be`]: ../fields/trait.BitField.html#tymethod.load_be
+	/// [`poll_read_bits`]: #method.poll_read_bits
+	pub fn poll_read_be<M>(
+		&mut self,
+		cx: &mut Context,
+		width: usize,
+	) -> Poll<io::Result<M>>
+	where
+		BitSlice<O, u8>: BitField,
+		M: BitStore,
+	{
+		self.poll_load(cx, width, BitSlice::load_be)
+	}
+
+	/// Reads an integer, stored in little-endian byte order.
+	///
+	/// This is [`poll_read_be`], loading the bits as by
+	/// [`BitField::load_le`].
+	///
+	/// [`BitField::load_le`]: ../fields/trait.BitField.html#tymethod.load_le
+	/// [`poll_read_be`]: #method.poll_read_be
+	pub fn poll_read_le<M>(
+		&mut self,
+		cx: &mut Context,
+		width: usize,
+	) -> Poll<io::Result<M>>
+	where
+		BitSlice<O, u8>: BitField,
+		M: BitStore,
+	{
+		self.poll_load(cx, width, BitSlice::load_le)
+	}
+
+	/// Converts the reader into a stream of fixed-size `BitVec`s.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	/// - `bits`: The number of bits in each item of the stream.
+	///
+	/// # Returns
+	///
+	/// A stream that yields `bits` bits at a time, and then, when the source
+	/// ends, whatever bits remain, if any.
+	///
+	/// # Panics
+	///
+	/// This panics if `bits` is zero.
+	pub fn chunks(self, bits: usize) -> Chunks<R, O> {
+		assert!(bits > 0, "Chunks must not be empty");
+		Chunks { reader: self, bits }
+	}
+
+	/// Converts the reader into a stream of integers, each stored in
+	/// big-endian byte order.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	/// - `width`: The number of bits in each integer.
+	///
+	/// # Returns
+	///
+	/// A stream that yields each integer as [`poll_read_be`] would. It ends
+	/// when the source does, and leaves any bits too few to fill a final
+	/// integer [`buffered`].
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero, or wider than `M`.
+	///
+	/// [`buffered`]: #method.buffered
+	/// [`poll_read_be`]: #method.poll_read_be
+	pub fn fields_be<M>(self, width: usize) -> Fields<R, O, M>
+	where
+		BitSlice<O, u8>: BitField,
+		M: BitStore,
+	{
+		Fields::new(self, width, BitSlice::load_be)
+	}
+
+	/// Converts the reader into a stream of integers, each stored in
+	/// little-endian byte order.
+	///
+	/// This is [`fields_be`], loading the bits as by [`poll_read_le`].
+	///
+	/// [`fields_be`]: #method.fields_be
+	/// [`poll_read_le`]: #method.poll_read_le
+	pub fn fields_le<M>(self, width: usize) -> Fields<R, O, M>
+	where
+		BitSlice<O, u8>: BitField,
+		M: BitStore,
+	{
+		Fields::new(self, width, BitSlice::load_le)
+	}
+
+	/// Reads from the source until `count` bits are buffered, or it ends.
+	fn poll_fill(
+		&mut self,
+		cx: &mut Context,
+		count: usize,
+	) -> Poll<io::Result<()>>
+	{
+		while self.buffered().len() < count && !self.eof {
+			//  Discard the bytes that have been completely returned.
+			self.bytes.drain(.. self.head / 8);
+			self.head %= 8;
+
+			let old = self.bytes.len();
+			self.bytes.resize(old + READ_LEN, 0);
+			let buf = &mut self.bytes[old ..];
+			let poll = Pin::new(&mut self.reader).poll_read(cx, buf);
+			let read = if let Poll::Ready(Ok(read)) = poll { read } else { 0 };
+			self.bytes.truncate(old + read);
+			match poll {
+				Poll::Pending => return Poll::Pending,
+				Poll::Ready(Ok(0)) => self.eof = true,
+				Poll::Ready(Ok(_)) => {},
+				Poll::Ready(Err(ref err))
+					if err.kind() == io::ErrorKind::Interrupted => {},
+				Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+			}
+		}
+		Poll::Ready(Ok(()))
+	}
+
+	/// Reads `width` bits and loads them into an integer.
+	fn poll_load<M>(
+		&mut self,
+		cx: &mut Context,
+		width: usize,
+		load: fn(&BitSlice<O, u8>) -> M,
+	) -> Poll<io::Result<M>>
+	where
+		M: BitStore,
+	{
+		assert!(
+			width > 0 && width <= M::BITS as usize,
+			"Cannot load {} bits into a {}",
+			width,
+			M::TYPENAME,
+		);
+		ready!(self.poll_fill(cx, width))?;
+		self.split(width).map(load).ok_or_else(unexpected_eof).into()
+	}
+
+	/// Returns the next `count` buffered bits, if that many are buffered.
+	fn split(&mut self, count: usize) -> Option<&BitSlice<O, u8>> {
+		if self.buffered().len() < count {
+			return None;
+		}
+		let head = self.head;
+		self.head += count;
+		Some(&self.bytes.bits::<O>()[head .. head + count])
+	}
+}
+
+/// The reader does not rely on being pinned. Its `BitOrder` parameter is only a
+/// marker, and does not prevent it from moving.
+impl<R, O> Unpin for AsyncBitReader<R, O>
+where
+	R: Unpin,
+	O: BitOrder,
+{
+}
+
+impl<R, O> Debug for AsyncBitReader<R, O>
+where
+	R: Debug,
+	O: BitOrder,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("AsyncBitReader")
+			.field("reader", &self.reader)
+			.field("buffered", &(self.bytes.len() * 8 - self.head))
+			.field("eof", &self.eof)
+			.finish()
+	}
+}
+
+/** A stream of fixed-size `BitVec`s read from an `AsyncRead`.
+
+This is produced by [`AsyncBitReader::chunks`].
+
+[`AsyncBitReader::chunks`]: struct.AsyncBitReader.html#method.chunks
+**/
+#[derive(Debug)]
+pub struct Chunks<R, O = Local>
+where O: BitOrder
+{
+	reader: AsyncBitReader<R, O>,
+	/// The number of bits in each chunk.
+	bits: usize,
+}
+
+impl<R, O> Chunks<R, O>
+where
+	R: AsyncRead + Unpin,
+	O: BitOrder,
+{
+	/// Unwraps the bit reader.
+	pub fn into_inner(self) -> AsyncBitReader<R, O> {
+		self.reader
+	}
+}
+
+impl<R, O> Stream for Chunks<R, O>
+where
+	R: AsyncRead + Unpin,
+	O: BitOrder,
+{
+	type Item = io::Result<BitVec<O, u8>>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context,
+	) -> Poll<Option<Self::Item>>
+	{
+		let this = self.get_mut();
+		let reader = &mut this.reader;
+		ready!(reader.poll_fill(cx, this.bits))?;
+		let len = reader.buffered().len();
+		if len == 0 {
+			return Poll::Ready(None);
+		}
+		let chunk = reader.split(cmp::min(this.bits, len));
+		Poll::Ready(chunk.map(BitVec::from_bitslice).map(Ok))
+	}
+}
+
+/** A stream of integers read from an `AsyncRead`.
+
+This is produced by [`AsyncBitReader::fields_be`] and
+[`AsyncBitReader::fields_le`].
+
+[`AsyncBitReader::fields_be`]: struct.AsyncBitReader.html#method.fields_be
+[`AsyncBitReader::fields_le`]: struct.AsyncBitReader.html#method.fields_le
+**/
+pub struct Fields<R, O, M>
+where O: BitOrder
+{
+	reader: AsyncBitReader<R, O>,
+	/// The number of bits in each integer.
+	width: usize,
+	/// Loads an integer from its bits.
+	load: fn(&BitSlice<O, u8>) -> M,
+}
+
+impl<R, O, M> Fields<R, O, M>
+where
+	R: AsyncRead + Unpin,
+	O: BitOrder,
+	M: BitStore,
+{
+	fn new(
+		reader: AsyncBitReader<R, O>,
+		width: usize,
+		load: fn(&BitSlice<O, u8>) -> M,
+	) -> Self
+	{
+		assert!(
+			width > 0 && width <= M::BITS as usize,
+			"Cannot load {} bits into a {}",
+			width,
+			M::TYPENAME,
+		);
+		Self {
+			reader,
+			width,
+			load,
+		}
+	}
+
+	/// Unwraps the bit reader.
+	pub fn into_inner(self) -> AsyncBitReader<R, O> {
+		self.reader
+	}
+}
+
+impl<R, O, M> Stream for Fields<R, O, M>
+where
+	R: AsyncRead + Unpin,
+	O: BitOrder,
+	M: BitStore,
+{
+	type Item = io::Result<M>;
+
+	fn poll_next(
+		self: Pin<&mut Self>,
+		cx: &mut Context,
+	) -> Poll<Option<Self::Item>>
+	{
+		let this = self.get_mut();
+		ready!(this.reader.poll_fill(cx, this.width))?;
+		Poll::Ready(this.reader.split(this.width).map(this.load).map(Ok))
+	}
+}
+
+impl<R, O, M> Debug for Fields<R, O, M>
+where
+	R: Debug,
+	O: BitOrder,
+	M: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("Fields")
+			.field("reader", &self.reader)
+			.field("width", &self.width)
+			.field("type", &M::TYPENAME)
+			.finish()
+	}
+}
+
+/** Writes bit slices of any length to an `AsyncWrite`.
+
+This is a `Sink` of `&BitSlice`s. Each slice is appended to the bits already
+sent, whatever their alignment, and bytes are written to the destination as
+they are completed. Flushing the sink writes every complete byte, but holds the
+bits of an incomplete byte until more bits complete it. Closing the sink fills
+an incomplete byte with zeros, and writes it.
+
+# Type Parameters
+
+- `W: AsyncWrite + Unpin`: The destination of bytes.
+- `O: BitOrder`: The ordering used to index bits within each byte.
+
+# Examples
+
+This requires an executor, such as the one in the `futures` crate. Here, a
+hidden `poll` function stands in for one, and polls a task until it is ready.
+
+```rust
+use bitvec::{futures::AsyncBitWriter, prelude::*};
+use core::pin::Pin;
+use futures_sink::Sink;
+# use core::{
+#     ptr,
+#     task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+# };
+# fn poll<T>(mut task: impl FnMut(&mut Context) -> Poll<T>) -> T {
+#     fn clone(_: *const ()) -> RawWaker {
+#         RawWaker::new(ptr::null(), &VTABLE)
+#     }
+#     fn noop(_: *const ()) {}
+#     static VTABLE: RawWakerVTable =
+#         RawWakerVTable::new(clone, noop, noop, noop);
+#     let waker = unsafe { Waker::from_raw(clone(ptr::null())) };
+#     let mut cx = Context::from_waker(&waker);
+#     loop {
+#         if let Poll::Ready(out) = task(&mut cx) {
+#             return out;
+#         }
+#     }
+# }
+
+let mut writer = AsyncBitWriter::<_, Msb0>::new(Vec::new());
+let mut sink = Pin::new(&mut writer);
+poll(|cx| Sink::<&BitSlice>::poll_ready(sink.as_mut(), cx)).unwrap();
+sink.as_mut().start_send(bits![1, 0, 1]).unwrap();
+poll(|cx| Sink::<&BitSlice>::poll_ready(sink.as_mut(), cx)).unwrap();
+sink.as_mut().start_send(bits![Lsb0, u16; 1; 10]).unwrap();
+//  The writer is a sink of every type of slice, so one must be named.
+poll(|cx| Sink::<&BitSlice>::poll_close(sink.as_mut(), cx)).unwrap();
+assert_eq!(writer.into_inner(), [0b1011_1111, 0b1111_1000]);
+```
+**/
+pub struct AsyncBitWriter<W, O = Local>
+where O: BitOrder
+{
+	/// The destination of bytes.
+	writer: W,
+	/// Bits sent to the sink, of which the first `written` bytes have been
+	/// written.
+	pending: BitVec<O, u8>,
+	/// The number of bytes in `pending` that have been written.
+	written: usize,
+}
+
+impl<W, O> AsyncBitWriter<W, O>
+where
+	W: AsyncWrite + Unpin,
+	O: BitOrder,
+{
+	/// Wraps a byte destination.
+	pub fn new(writer: W) -> Self {
+		Self {
+			writer,
+			pending: BitVec::new(),
+			written: 0,
+		}
+	}
+
+	/// Views the bits that have been sent but not yet written.
+	pub fn pending(&self) -> &BitSlice<O, u8> {
+		&self.pending[self.written * 8 ..]
+	}
+
+	/// Borrows the byte destination.
+	pub fn get_ref(&self) -> &W {
+		&self.writer
+	}
+
+	/// Mutably borrows the byte destination.
+	///
+	/// Bytes written directly to the destination come before the bits still
+	/// [`pending`] in `self`.
+	///
+	/// [`pending`]: #method.pending
+	pub fn get_mut(&mut self) -> &mut W {
+		&mut self.writer
+	}
+
+	/// Unwraps the byte destination, discarding any pending bits.
+	pub fn into_inner(self) -> W {
+		self.writer
+	}
+
+	/// Writes every complete pending byte to the destination.
+	fn poll_write_bytes(&mut self, cx: &mut Context) -> Poll<io::Result<()>> {
+		let whole = self.pending.len() / 8;
+		while self.written < whole {
+			let bytes = &self.pending.as_slice()[self.written .. whole];
+			match ready!(Pin::new(&mut self.writer).poll_write(cx, bytes)) {
+				Ok(0) => {
+					return Poll::Ready(Err(io::Error::new(
+						io::ErrorKind::WriteZero,
+						"failed to write pending bits",
+					)));
+				},
+				Ok(count) => self.written += count,
+				Err(ref err) if err.kind() == io::ErrorKind::Interrupted => {},
+				Err(err) => return Poll::Ready(Err(err)),
+			}
+		}
+		//  Move the bits of the incomplete byte, if any, to the front.
+		let tail = self.pending.len() - whole * 8;
+		if whole > 0 && tail > 0 {
+			let elts = self.pending.as_mut_slice();
+			elts[0] = elts[whole];
+		}
+		self.pending.truncate(tail);
+		self.written = 0;
+		Poll::Ready(Ok(()))
+	}
+}
+
+impl<'a, W, O, P, U> Sink<&'a BitSlice<P, U>> for AsyncBitWriter<W, O>
+where
+	W: AsyncWrite + Unpin,
+	O: BitOrder,
+	P: BitOrder,
+	U: BitStore,
+{
+	type Error = io::Error;
+
+	/// Writes pending bytes once enough of them accumulate.
+	fn poll_ready(
+		self: Pin<&mut Self>,
+		cx: &mut Context,
+	) -> Poll<io::Result<()>>
+	{
+		let this = self.get_mut();
+		if this.pending.len() / 8 - this.written < WRITE_LEN {
+			return Poll::Ready(Ok(()));
+		}
+		this.poll_write_bytes(cx)
+	}
+
+	fn start_send(
+		self: Pin<&mut Self>,
+		bits: &'a BitSlice<P, U>,
+	) -> io::Result<()>
+	{
+		self.get_mut().pending.extend_from_slice(bits);
+		Ok(())
+	}
+
+	/// Writes every complete pending byte, and flushes the destination.
+	fn poll_flush(
+		self: Pin<&mut Self>,
+		cx: &mut Context,
+	) -> Poll<io::Result<()>>
+	{
+		let this = self.get_mut();
+		ready!(this.poll_write_bytes(cx))?;
+		Pin::new(&mut this.writer).poll_flush(cx)
+	}
+
+	/// Fills the incomplete pending byte with zeros, writes every pending
+	/// byte, and closes the destination.
+	fn poll_close(
+		self: Pin<&mut Self>,
+		cx: &mut Context,
+	) -> Poll<io::Result<()>>
+	{
+		let this = self.get_mut();
+		let len = this.pending.len();
+		this.pending.resize(len + (8 - len % 8) % 8, false);
+		ready!(this.poll_write_bytes(cx))?;
+		Pin::new(&mut this.writer).poll_close(cx)
+	}
+}
+
+/// The writer does not rely on being pinned. Its `BitOrder` parameter is only a
+/// marker, and does not prevent it from moving.
+impl<W, O> Unpin for AsyncBitWriter<W, O>
+where
+	W: Unpin,
+	O: BitOrder,
+{
+}
+
+impl<W, O> Debug for AsyncBitWriter<W, O>
+where
+	W: Debug,
+	O: BitOrder,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("AsyncBitWriter")
+			.field("writer", &self.writer)
+			.field("pending", &(self.pending.len() - self.written * 8))
+			.finish()
+	}
+}
+
+/// Reports that a source ended in the middle of a read.
+fn unexpected_eof() -> io::Error {
+	io::Error::new(
+		io::ErrorKind::UnexpectedEof,
+		"the source ended before the requested bits",
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Msb0;
+	use core::{
+		cmp,
+		ptr,
+		task::{
+			RawWaker,
+			RawWakerVTable,
+			Waker,
+		},
+	};
+
+	/// Yields one byte per read, and is pending before each one.
+	struct Trickle<'a> {
+		bytes: &'a [u8],
+		/// Whether the previous poll was ready.
+		ready: bool,
+	}
+
+	impl AsyncRead for Trickle<'_> {
+		fn poll_read(
+			self: Pin<&mut Self>,
+			cx: &mut Context,
+			buf: &mut [u8],
+		) -> Poll<io::Result<usize>>
+		{
+			let this = self.get_mut();
+			this.ready = !this.ready;
+			if !this.ready {
+				cx.waker().wake_by_ref();
+				return Poll::Pending;
+			}
+			let len = cmp::min(cmp::min(this.bytes.len(), buf.len()), 1);
+			buf[.. len].copy_from_slice(&this.bytes[.. len]);
+			this.bytes = &this.bytes[len ..];
+			Poll::Ready(Ok(len))
+		}
+	}
+
+	/// Polls a task until it completes, counting the times it is pending.
+	fn poll<T>(mut task: impl FnMut(&mut Context) -> Poll<T>) -> (T, usize) {
+		fn noop_raw() -> RawWaker {
+			fn clone(_: *const ()) -> RawWaker {
+				noop_raw()
+			}
+			fn noop(_: *const ()) {
+			}
+			static VTABLE: RawWakerVTable =
+				RawWakerVTable::new(clone, noop, noop, noop);
+			RawWaker::new(ptr::null(), &VTABLE)
+		}
+		let waker = unsafe { Waker::from_raw(noop_raw()) };
+		let mut cx = Context::from_waker(&waker);
+		let mut pending = 0;
+		loop {
+			match task(&mut cx) {
+				Poll::Ready(out) => return (out, pending),
+				Poll::Pending => pending += 1,
+			}
+		}
+	}
+
+	#[test]
+	fn reader() {
+		let data = [0b1011_0100u8, 0b1110_0001, 0x5A];
+		let mut reader = AsyncBitReader::<_, Msb0>::new(Trickle {
+			bytes: &data,
+			ready: true,
+		});
+		let (bits, pending) = poll(|cx| reader.poll_read_bits(cx, 3));
+		assert_eq!(bits.unwrap(), data.bits::<Msb0>()[.. 3]);
+		assert_eq!(pending, 1);
+		let (field, pending) = poll(|cx| reader.poll_read_be::<u16>(cx, 9));
+		assert_eq!(field.unwrap(), 0b1_0100_1110);
+		assert_eq!(pending, 1);
+		let (field, _) = poll(|cx| reader.poll_read_le::<u8>(cx, 4));
+		assert_eq!(field.unwrap(), 0b0001);
+		assert_eq!(reader.buffered().len(), 0);
+
+		let (err, _) = poll(|cx| reader.poll_read_bits(cx, 9));
+		assert_eq!(err.unwrap_err().kind(), io::ErrorKind::UnexpectedEof);
+		assert_eq!(reader.buffered(), &data.bits::<Msb0>()[16 ..]);
+
+		let mut chunks = AsyncBitReader::<_, Msb0>::new(&data[..]).chunks(10);
+		let mut next = || poll(|cx| Pin::new(&mut chunks).poll_next(cx)).0;
+		assert_eq!(next().unwrap().unwrap(), data.bits::<Msb0>()[.. 10]);
+		assert_eq!(next().unwrap().unwrap(), data.bits::<Msb0>()[10 .. 20]);
+		assert_eq!(next().unwrap().unwrap(), data.bits::<Msb0>()[20 ..]);
+		assert!(next().is_none());
+
+		let mut fields = AsyncBitReader::<_, Msb0>::new(&data[..]).fields_be(5);
+		let mut values = [0u8; 4];
+		for value in &mut values {
+			let field = poll(|cx| Pin::new(&mut fields).poll_next(cx)).0;
+			*value = field.unwrap().unwrap();
+		}
+		assert_eq!(values, [0b10110, 0b10011, 0b10000, 0b10101]);
+		assert!(poll(|cx| Pin::new(&mut fields).poll_next(cx)).0.is_none());
+		assert_eq!(fields.into_inner().buffered(), bits![1, 0, 1, 0]);
+	}
+
+	#[test]
+	fn writer() {
+		type Bits = BitSlice<Msb0, u16>;
+
+		let mut writer = AsyncBitWriter::<_, Msb0>::new(Vec::new());
+		let data = [0xA5C3u16, 0x0FF0];
+		let bits = data.bits::<Msb0>();
+		for range in &[0 .. 3, 3 .. 20, 20 .. 23] {
+			let mut sink = Pin::new(&mut writer);
+			poll(|cx| Sink::<&Bits>::poll_ready(sink.as_mut(), cx))
+				.0
+				.unwrap();
+			sink.start_send(&bits[range.clone()]).unwrap();
+		}
+
+		poll(|cx| Sink::<&Bits>::poll_flush(Pin::new(&mut writer), cx))
+			.0
+			.unwrap();
+		assert_eq!(writer.get_ref(), &[0xA5, 0xC3]);
+		assert_eq!(writer.pending(), &bits[16 .. 23]);
+
+		Pin::new(&mut writer).start_send(bits![1, 1]).unwrap();
+		poll(|cx| Sink::<&Bits>::poll_close(Pin::new(&mut writer), cx))
+			.0
+			.unwrap();
+		assert_eq!(writer.into_inner(), [0xA5, 0xC3, 0x0F, 0b1000_0000]);
+	}
+}
@@ -0,0 +1,284 @@
+/*! Random generation of bit sequences for fuzzing and property tests.
+
+This module implements [`arbitrary::Arbitrary`], for coverage-guided fuzzers,
+and [`quickcheck::Arbitrary`], for property tests, on the `bitvec` types.
+
+Hand-written test inputs almost always begin at the first bit of an element and
+fill whole elements, which leaves the partial-element code paths untested. The
+generated values here instead have random lengths and random head offsets, so
+that they exercise every `BitDomain` shape: enclaves inside one element, partial
+head and tail elements, and spans of whole elements.
+
+[`arbitrary::Arbitrary`]: https://docs.rs/arbitrary/1/arbitrary/trait.Arbitrary.html
+[`quickcheck::Arbitrary`]: https://docs.rs/quickcheck/1/quickcheck/trait.Arbitrary.html
+!*/
+
+#![cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[cfg(feature = "alloc")]
+use crate::{
+	boxed::BitBox,
+	vec::BitVec,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::{
+	borrow::ToOwned,
+	vec::Vec,
+};
+
+/// Selects a subslice of `bits` from a head offset and length, each reduced
+/// modulo the available space.
+///
+/// The head offset is confined to the first element, so that the storage
+/// beyond the subslice is wasted as little as possible.
+fn subslice<O, T>(bits: &BitSlice<O, T>, head: usize, len: usize) -> &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let total = bits.len();
+	if total == 0 {
+		return bits;
+	}
+	let head = head % core::cmp::min(T::BITS as usize, total);
+	let len = len % (total - head + 1);
+	&bits[head ..][.. len]
+}
+
+#[cfg(feature = "arbitrary")]
+mod fuzzing {
+	use super::*;
+
+	use arbitrary::{
+		Arbitrary,
+		Result,
+		Unstructured,
+	};
+
+	/// Borrows a slice directly out of the fuzzer input, without copying.
+	///
+	/// Only byte storage can be borrowed, as fuzzer input has no alignment.
+	impl<'a, O> Arbitrary<'a> for &'a BitSlice<O, u8>
+	where O: BitOrder
+	{
+		fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+			let head = u.arbitrary::<u8>()? as usize;
+			let len = u.arbitrary::<usize>()?;
+			let bytes = u.arbitrary::<&'a [u8]>()?;
+			Ok(subslice(BitSlice::from_slice(bytes), head, len))
+		}
+
+		fn arbitrary_take_rest(mut u: Unstructured<'a>) -> Result<Self> {
+			let head = u.arbitrary::<u8>()? as usize;
+			let len = u.arbitrary::<usize>()?;
+			let bytes = u.take_rest();
+			Ok(subslice(BitSlice::from_slice(bytes), head, len))
+		}
+	}
+
+	#[cfg(feature = "alloc")]
+	impl<'a, O, T> Arbitrary<'a> for BitVec<O, T>
+	where
+		O: BitOrder,
+		T: BitStore + Arbitrary<'a>,
+	{
+		fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+			let head = u.arbitrary::<u8>()? as usize;
+			let len = u.arbitrary::<usize>()?;
+			let elts = u.arbitrary::<Vec<T>>()?;
+			Ok(subslice(BitSlice::from_slice(&elts), head, len).to_owned())
+		}
+
+		fn arbitrary_take_rest(mut u: Unstructured<'a>) -> Result<Self> {
+			let head = u.arbitrary::<u8>()? as usize;
+			let len = u.arbitrary::<usize>()?;
+			let elts = Vec::<T>::arbitrary_take_rest(u)?;
+			Ok(subslice(BitSlice::from_slice(&elts), head, len).to_owned())
+		}
+	}
+
+	#[cfg(feature = "alloc")]
+	impl<'a, O, T> Arbitrary<'a> for BitBox<O, T>
+	where
+		O: BitOrder,
+		T: BitStore + Arbitrary<'a>,
+	{
+		fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+			BitVec::arbitrary(u).map(BitVec::into_boxed_bitslice)
+		}
+
+		fn arbitrary_take_rest(u: Unstructured<'a>) -> Result<Self> {
+			BitVec::arbitrary_take_rest(u).map(BitVec::into_boxed_bitslice)
+		}
+	}
+}
+
+#[cfg(all(feature = "quickcheck", feature = "alloc"))]
+mod property {
+	use super::*;
+
+	use alloc::boxed::Box;
+
+	use quickcheck::{
+		Arbitrary,
+		Gen,
+	};
+
+	impl<O, T> Arbitrary for BitVec<O, T>
+	where
+		O: 'static + BitOrder,
+		T: BitStore + Arbitrary,
+	{
+		fn arbitrary(g: &mut Gen) -> Self {
+			let head = usize::arbitrary(g);
+			let len = usize::arbitrary(g);
+			let elts = Vec::<T>::arbitrary(g);
+			subslice(BitSlice::from_slice(&elts), head, len).to_owned()
+		}
+
+		/// Shrinks by removing bits, keeping the original head offset so that
+		/// a failure tied to misalignment is not shrunk away.
+		fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+			let this = self.clone();
+			let len = this.len();
+			//  Try removing all of the bits, then half, a quarter, and so on,
+			//  down to removing only the last bit.
+			Box::new(
+				core::iter::successors(Some(len), |&cut| Some(cut / 2))
+					.take_while(|&cut| cut > 0)
+					.map(move |cut| this[.. len - cut].to_owned()),
+			)
+		}
+	}
+
+	impl<O, T> Arbitrary for BitBox<O, T>
+	where
+		O: 'static + BitOrder,
+		T: BitStore + Arbitrary,
+	{
+		fn arbitrary(g: &mut Gen) -> Self {
+			BitVec::arbitrary(g).into_boxed_bitslice()
+		}
+
+		fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+			Box::new(
+				BitVec::from_bitslice(self)
+					.shrink()
+					.map(BitVec::into_boxed_bitslice),
+			)
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		pointer::BitPtr,
+	};
+
+	/// Checks that a handle starts in its first element, and that it fits in
+	/// `bits` bits of storage.
+	fn in_bounds<T>(bitptr: BitPtr<T>, bits: usize)
+	where T: BitStore {
+		let head = *bitptr.head() as usize;
+		assert!(head < T::BITS as usize);
+		assert!(head + bitptr.len() <= bits);
+	}
+
+	#[test]
+	#[cfg(feature = "arbitrary")]
+	fn arbitrary() {
+		use arbitrary::{
+			Arbitrary,
+			Unstructured,
+		};
+
+		let mut input = [0u8; 256];
+		for (n, byte) in input.iter_mut().enumerate() {
+			*byte = (n as u8).wrapping_mul(0x9D).rotate_left(n as u32);
+		}
+		let base = input.as_ptr() as usize;
+		for start in 0 .. 64 {
+			let src = &input[start ..];
+			//  The borrowed slice lies inside the input, in both modes.
+			let mut u = Unstructured::new(src);
+			let bits = <&BitSlice<Msb0, u8>>::arbitrary(&mut u).unwrap();
+			let rest = <&BitSlice<Lsb0, u8>>::arbitrary_take_rest(
+				Unstructured::new(src),
+			)
+			.unwrap();
+			for bitptr in [bits.bitptr(), rest.bitptr()].iter().copied() {
+				let at = bitptr.pointer().r() as usize - base;
+				in_bounds(bitptr, (input.len() - at) * 8);
+			}
+
+			//  The vector holds the bits that the input selects, from the
+			//  elements that it decodes.
+			#[cfg(feature = "alloc")]
+			{
+				let mut u = Unstructured::new(src);
+				let bv = BitVec::<Lsb0, u16>::arbitrary(&mut u).unwrap();
+				let mut u = Unstructured::new(src);
+				let head = u.arbitrary::<u8>().unwrap() as usize;
+				let len = u.arbitrary::<usize>().unwrap();
+				let elts = u.arbitrary::<Vec<u16>>().unwrap();
+				in_bounds(bv.bitptr(), elts.len() * 16);
+				let bits = BitSlice::<Lsb0, u16>::from_slice(&elts);
+				assert_eq!(bv, subslice(bits, head, len));
+				assert_eq!(*bv.bitptr().head() as usize, match elts.len() {
+					0 => 0,
+					n => head % core::cmp::min(16, n * 16),
+				});
+
+				let mut u = Unstructured::new(src);
+				let bb = BitBox::<Lsb0, u16>::arbitrary(&mut u).unwrap();
+				assert_eq!(bb.as_bitslice(), bv.as_bitslice());
+			}
+		}
+	}
+
+	#[test]
+	#[cfg(all(feature = "quickcheck", feature = "alloc"))]
+	fn quickcheck() {
+		use quickcheck::{
+			Arbitrary,
+			Gen,
+		};
+
+		//  The generated vectors of elements are no longer than the size.
+		let mut g = Gen::new(64);
+		for _ in 0 .. 200 {
+			let bv = BitVec::<Msb0, u8>::arbitrary(&mut g);
+			in_bounds(bv.bitptr(), 64 * 8);
+			//  Shrinking removes bits from the back, and keeps the head.
+			for small in bv.shrink() {
+				assert!(small.len() < bv.len());
+				assert_eq!(small, bv[.. small.len()]);
+				if !small.is_empty() {
+					assert_eq!(small.bitptr().head(), bv.bitptr().head());
+				}
+			}
+
+			let bb = BitBox::<Lsb0, u32>::arbitrary(&mut g);
+			in_bounds(bb.bitptr(), 64 * 32);
+			for small in bb.shrink() {
+				assert_eq!(small, bb[.. small.len()]);
+				if !small.is_empty() {
+					assert_eq!(small.bitptr().head(), bb.bitptr().head());
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,247 @@
+/*! Bit vectors that clear in constant time
+
+Searches and simulations that mark visited states in a bitmap often clear the
+whole map between runs. Clearing a `BitVec` writes every one of its elements,
+and when the map is large and each run touches few of its bits, that write
+dominates the cost of the run.
+
+[`GenerationalBitVec`] stamps each storage element with the generation in which
+it was last written. [`clear_all`] starts a new generation without touching the
+elements, and an element whose stamp is out of date reads as zero until it is
+next written. This costs one `u32` stamp for each storage element.
+
+[`GenerationalBitVec`]: struct.GenerationalBitVec.html
+[`clear_all`]: struct.GenerationalBitVec.html#method.clear_all
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	store::{
+		self,
+		BitStore,
+	},
+	vec::BitVec,
+};
+
+use alloc::vec::Vec;
+
+use core::fmt::{
+	self,
+	Debug,
+	Display,
+	Formatter,
+};
+
+/** A bit vector of fixed length whose bits can all be cleared in constant time.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within each storage element.
+- `T: BitStore`: The storage element type. Wider elements need fewer stamps,
+  but more bits are cleared when an out-of-date element is written.
+
+# Examples
+
+```rust
+use bitvec::{generational::GenerationalBitVec, prelude::*};
+
+let mut visited = GenerationalBitVec::<Msb0, u8>::new(20);
+visited.set(3, true);
+visited.set(17, true);
+assert!(visited.get(3));
+assert_eq!(visited.count_ones(), 2);
+
+visited.clear_all();
+assert!(!visited.get(3));
+assert!(!visited.set(17, true));
+assert_eq!(visited.to_bitvec().count_ones(), 1);
+```
+**/
+#[derive(Clone)]
+pub struct GenerationalBitVec<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The bits, beginning at the first bit of the first element. Elements
+	/// whose stamp is out of date may hold stale bits.
+	bits: BitVec<O, T>,
+	/// The generation in which each element of `bits` was last written.
+	stamps: Vec<u32>,
+	/// The current generation.
+	generation: u32,
+}
+
+impl<O, T> GenerationalBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Constructs a vector of `len` cleared bits.
+	pub fn new(len: usize) -> Self {
+		Self {
+			bits: BitVec::repeat(false, len),
+			stamps: alloc::vec![0; store::elts::<T>(len)],
+			generation: 0,
+		}
+	}
+
+	/// Counts the bits in the vector.
+	pub fn len(&self) -> usize {
+		self.bits.len()
+	}
+
+	/// Tests if the vector has no bits.
+	pub fn is_empty(&self) -> bool {
+		self.bits.is_empty()
+	}
+
+	/// Reads a bit.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	pub fn get(&self, index: usize) -> bool {
+		let bit = self.bits[index];
+		bit && self.is_current(index / T::BITS as usize)
+	}
+
+	/// Writes a bit.
+	///
+	/// If the bit’s element has not been written since the last
+	/// [`clear_all`], the element is cleared first.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The bit to write.
+	/// - `value`: The value to write into it.
+	///
+	/// # Returns
+	///
+	/// The value that the bit held before the write.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	///
+	/// [`clear_all`]: #method.clear_all
+	pub fn set(&mut self, index: usize, value: bool) -> bool {
+		let old = self.get(index);
+		let elt = index / T::BITS as usize;
+		if !self.is_current(elt) {
+			self.bits.as_mut_slice()[elt] = T::FALSE;
+			self.stamps[elt] = self.generation;
+		}
+		self.bits.set(index, value);
+		old
+	}
+
+	/// Clears every bit.
+	///
+	/// This takes constant time, except once in every `2^32` calls, when the
+	/// generation counter wraps and every element and stamp is cleared.
+	pub fn clear_all(&mut self) {
+		match self.generation.checked_add(1) {
+			Some(generation) => self.generation = generation,
+			None => {
+				self.bits.set_elements(T::FALSE);
+				for stamp in &mut self.stamps {
+					*stamp = 0;
+				}
+				self.generation = 0;
+			},
+		}
+	}
+
+	/// Counts the set bits.
+	///
+	/// Only elements written since the last [`clear_all`] are counted.
+	///
+	/// [`clear_all`]: #method.clear_all
+	pub fn count_ones(&self) -> usize {
+		self.bits
+			.as_slice()
+			.iter()
+			.zip(&self.stamps)
+			.filter(|&(_, &stamp)| stamp == self.generation)
+			.map(|(&elt, _)| T::count_ones(elt))
+			.sum()
+	}
+
+	/// Copies the current bits into a new `BitVec`.
+	pub fn to_bitvec(&self) -> BitVec<O, T> {
+		let mut out = self.bits.clone();
+		for (elt, &stamp) in out.as_mut_slice().iter_mut().zip(&self.stamps) {
+			if stamp != self.generation {
+				*elt = T::FALSE;
+			}
+		}
+		out
+	}
+
+	/// Tests if an element has been written since the last `clear_all`.
+	fn is_current(&self, elt: usize) -> bool {
+		self.stamps[elt] == self.generation
+	}
+}
+
+impl<O, T> Debug for GenerationalBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("GenerationalBitVec<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str(", ")?;
+		fmt.write_str(T::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.to_bitvec().as_bitslice(), fmt)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Lsb0;
+
+	#[test]
+	fn generations() {
+		let mut map = GenerationalBitVec::<Lsb0, u16>::new(100);
+		let mut model = BitVec::<Lsb0, u16>::repeat(false, 100);
+		let mut state = 0x2545_F491u32;
+		for round in 0 .. 2000 {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			if round % 97 == 0 {
+				map.clear_all();
+				model.set_all(false);
+				continue;
+			}
+			let index = state as usize % 100;
+			let value = state & 0x100 != 0;
+			assert_eq!(map.set(index, value), model[index]);
+			model.set(index, value);
+			assert_eq!(map.get(index), value);
+		}
+		assert_eq!(map.to_bitvec(), model);
+		assert_eq!(map.count_ones(), model.count_ones());
+
+		//  The generation counter wraps by clearing everything.
+		map.generation = !0;
+		for stamp in &mut map.stamps {
+			*stamp = !0;
+		}
+		map.clear_all();
+		assert_eq!(map.generation, 0);
+		assert_eq!(map.count_ones(), 0);
+		assert!(map.stamps.iter().all(|&stamp| stamp == 0));
+	}
+}
@@ -0,0 +1,284 @@
+/*! Hamming codes
+
+A Hamming code protects a block of data bits with parity bits placed at the
+power-of-two positions of the codeword, counting positions from one. The parity
+bit at position `2^i` covers every position whose number has bit `i` set, so
+that the positions of the set bits of a valid codeword `XOR` to zero, and a
+single flipped bit makes that sum, the syndrome, equal to its own position.
+
+This module adds the pieces of that scheme to `BitSlice`:
+
+- [`hamming_encode`] inserts the parity bits into a copy of the data.
+- [`hamming_syndrome`] computes the syndrome of a codeword.
+- [`hamming_correct`] repairs a single flipped bit in place.
+- [`hamming_extract`] removes the parity bits again.
+
+Positions are the indices of the slice, plus one, in the slice’s own order, so
+codewords need not begin or end on element boundaries. The extended form of the
+code, with an extra overall parity bit to detect double errors, can be built by
+appending the parity of the codeword, from [`BitSlice::count_ones`].
+
+[`BitSlice::count_ones`]: ../slice/struct.BitSlice.html#method.count_ones
+[`hamming_correct`]: ../slice/struct.BitSlice.html#method.hamming_correct
+[`hamming_encode`]: ../slice/struct.BitSlice.html#method.hamming_encode
+[`hamming_extract`]: ../slice/struct.BitSlice.html#method.hamming_extract
+[`hamming_syndrome`]: ../slice/struct.BitSlice.html#method.hamming_syndrome
+!*/
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[cfg(feature = "alloc")]
+use crate::vec::BitVec;
+
+use core::fmt::{
+	self,
+	Display,
+	Formatter,
+};
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Encodes the slice as the data bits of a Hamming codeword.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// A codeword with the bits of `self`, in order, at every position that is
+	/// not a power of two, and the parity bits at the powers of two. It has as
+	/// few parity bits as can cover its length, so 4 data bits produce the
+	/// classic 7-bit codeword, and 57 data bits produce a 63-bit codeword.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = bits![Msb0, u8; 1, 0, 1, 1];
+	/// let code = data.hamming_encode();
+	/// assert_eq!(code, bitvec![Msb0, u8; 0, 1, 1, 0, 0, 1, 1]);
+	/// assert_eq!(code.hamming_syndrome(), 0);
+	/// assert_eq!(code.hamming_extract(), data);
+	/// ```
+	#[cfg(feature = "alloc")]
+	pub fn hamming_encode(&self) -> BitVec<O, T> {
+		let data = self.len();
+		let mut parity = 0;
+		while (1usize << parity) < data + parity + 1 {
+			parity += 1;
+		}
+		let mut out = BitVec::<O, T>::repeat(false, data + parity);
+		let mut bits = self.iter().copied();
+		for pos in (1 ..= out.len()).filter(|&pos| !is_parity(pos)) {
+			let bit = bits.next().expect("Codeword length covers the data");
+			out.set(pos - 1, bit);
+		}
+		//  With every parity bit clear, the syndrome holds exactly the parity
+		//  bits that must be set to clear it.
+		let syndrome = out.hamming_syndrome();
+		for n in 0 .. parity {
+			out.set((1 << n) - 1, syndrome >> n & 1 == 1);
+		}
+		out
+	}
+
+	/// Computes the syndrome of a Hamming codeword.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The `XOR` of the positions, counting from one, of every set bit in
+	/// `self`. This is zero for a valid codeword, and the position of the
+	/// flipped bit for a codeword with one error.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut code = bitvec![Msb0, u8; 0, 1, 1, 0, 0, 1, 1];
+	/// code.set(4, true);
+	/// assert_eq!(code.hamming_syndrome(), 5);
+	/// ```
+	pub fn hamming_syndrome(&self) -> usize {
+		self.iter()
+			.enumerate()
+			.filter(|(_, bit)| **bit)
+			.fold(0, |acc, (idx, _)| acc ^ (idx + 1))
+	}
+
+	/// Repairs a single flipped bit in a Hamming codeword.
+	///
+	/// A codeword with more than one error may be reported as valid, or be
+	/// "repaired" into a different valid codeword. Use the extended code,
+	/// described in the [module documentation], to detect double errors.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	///
+	/// # Returns
+	///
+	/// - `Ok(None)` if the codeword is valid.
+	/// - `Ok(Some(idx))` if the bit at index `idx` was flipped back.
+	/// - `Err` if the syndrome names a position beyond the end of the
+	///   codeword, which no single error produces. `self` is not modified.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = bits![Msb0, u8; 1, 1, 0, 1, 0, 0, 1];
+	/// let mut code = data.hamming_encode();
+	/// let bit = !code[6];
+	/// code.set(6, bit);
+	/// assert_eq!(code.hamming_correct(), Ok(Some(6)));
+	/// assert_eq!(code.hamming_extract(), data);
+	/// ```
+	///
+	/// [module documentation]: ../hamming/index.html
+	pub fn hamming_correct(&mut self) -> Result<Option<usize>, HammingError> {
+		match self.hamming_syndrome() {
+			0 => Ok(None),
+			pos if pos <= self.len() => {
+				let idx = pos - 1;
+				let bit = !self[idx];
+				self.set(idx, bit);
+				Ok(Some(idx))
+			},
+			syndrome => Err(HammingError { syndrome }),
+		}
+	}
+
+	/// Removes the parity bits from a Hamming codeword.
+	///
+	/// This does not check or correct the codeword.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The bits of `self` at every position that is not a power of two, in
+	/// order.
+	#[cfg(feature = "alloc")]
+	pub fn hamming_extract(&self) -> BitVec<O, T> {
+		self.iter()
+			.enumerate()
+			.filter(|(idx, _)| !is_parity(idx + 1))
+			.map(|(_, bit)| *bit)
+			.collect()
+	}
+}
+
+/** The error produced when a Hamming codeword cannot be corrected.
+
+This is returned by [`BitSlice::hamming_correct`] when the codeword has more
+errors than it can correct, and the syndrome does not name one of its bits.
+
+[`BitSlice::hamming_correct`]: ../slice/struct.BitSlice.html#method.hamming_correct
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct HammingError {
+	/// The syndrome of the codeword.
+	syndrome: usize,
+}
+
+impl HammingError {
+	/// Gets the syndrome of the codeword.
+	pub fn syndrome(&self) -> usize {
+		self.syndrome
+	}
+}
+
+impl Display for HammingError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(fmt, "Uncorrectable Hamming syndrome {}", self.syndrome)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HammingError {
+}
+
+/// Tests if a position in a codeword, counting from one, holds a parity bit.
+fn is_parity(pos: usize) -> bool {
+	pos & (pos - 1) == 0
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+
+	#[test]
+	fn known_answers() {
+		//  Hamming(7, 4) codewords, as `p1 p2 d1 p3 d2 d3 d4`.
+		let table = [
+			(0b0000u8, 0b000_0000u8),
+			(0b1000, 0b111_0000),
+			(0b0100, 0b100_1100),
+			(0b0010, 0b010_1010),
+			(0b0001, 0b110_1001),
+			(0b1011, 0b011_0011),
+			(0b1111, 0b111_1111),
+		];
+		for &(data, code) in &table {
+			let data = &data.bits::<Msb0>()[4 ..];
+			let code = &code.bits::<Msb0>()[1 ..];
+			assert_eq!(data.hamming_encode(), code);
+			assert_eq!(code.hamming_extract(), data);
+		}
+		assert!(0u8.bits::<Msb0>()[.. 0].hamming_encode().is_empty());
+	}
+
+	#[test]
+	fn single_errors() {
+		//  Runs of set and cleared bits, across an element boundary.
+		let data = [!0u32, 0x0000_FFFF];
+		for &(from, to) in &[(0, 1), (3, 7), (5, 62), (0, 64), (9, 33)] {
+			let bits = &data.bits::<Msb0>()[from .. to];
+			let code = bits.hamming_encode();
+			assert_eq!(code.hamming_syndrome(), 0);
+			assert_eq!(code.hamming_extract(), bits);
+			for idx in 0 .. code.len() {
+				let mut bad = code.clone();
+				let bit = !bad[idx];
+				bad.set(idx, bit);
+				assert_eq!(bad.hamming_correct(), Ok(Some(idx)));
+				assert_eq!(bad, code);
+			}
+		}
+		//  Parity bits cover 1, 4, 11, 26, and 57 data bits exactly.
+		let bits = data.bits::<Msb0>();
+		for &(n, len) in &[(1, 3), (4, 7), (11, 15), (26, 31), (57, 63)] {
+			assert_eq!(bits[.. n].hamming_encode().len(), len);
+			//  One more data bit requires another parity bit.
+			assert_eq!(bits[.. n + 1].hamming_encode().len(), len + 2);
+		}
+	}
+
+	#[test]
+	fn uncorrectable() {
+		let mut code = bitvec![Msb0, u8; 0, 0, 0, 0, 0, 1];
+		code.set(0, true);
+		assert_eq!(code.hamming_correct().unwrap_err().syndrome(), 7);
+		assert!(code[0]);
+	}
+}
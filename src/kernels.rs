@@ -0,0 +1,132 @@
+/*! Domain-specialized inner loops
+
+The bulk operations on `BitSlice` split a slice into its domain: the partially
+owned elements at its edges, and the fully owned elements between them. The
+edges must be handled bit by bit, under the slice’s `BitOrder`, while the body
+can be handled a whole element at a time. The functions in this module are the
+loops that those operations run over each part, and the operations call them
+directly.
+
+This module is only public with the `bench-internals` feature, and is hidden
+from the documentation. It exists so that benchmarks, in this crate and
+downstream, can time these loops on their own `BitOrder` types, without the
+bounds checks and domain splitting of the public methods. It is not part of the
+stable API, and may change in any release.
+!*/
+
+use crate::{
+	access::BitAccess,
+	indices::Indexable,
+	order::BitOrder,
+	store::BitStore,
+};
+
+use core::{
+	ops::Range,
+	ptr,
+};
+
+/// Counts the set bits of one element that lie in a range of indices.
+///
+/// This is the loop that `BitSlice::count_ones` runs on the partial elements
+/// at the edges of a slice.
+///
+/// # Parameters
+///
+/// - `elt`: The element to inspect.
+/// - `bits`: The semantic indices, under `O`, to count. This must lie within
+///   `0 .. T::BITS`.
+///
+/// # Returns
+///
+/// The number of bits in `bits` that are set in `elt`.
+pub fn count_edge<O, T>(elt: T, bits: Range<u8>) -> usize
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	bits.filter(|n| elt.get::<O>(n.idx())).count()
+}
+
+/// Counts the set bits of whole elements.
+///
+/// This is the loop that `BitSlice::count_ones` runs on the elements that a
+/// slice fully owns.
+pub fn count_body<T>(body: &[T::Access]) -> usize
+where T: BitStore {
+	body.iter()
+		.map(BitAccess::load)
+		.map(T::count_ones)
+		.sum()
+}
+
+/// Copies whole elements from one region to another.
+///
+/// This is the loop that `BitSlice::copy_from_slice` runs on the elements
+/// that both slices fully own, when they begin at the same bit index. Each
+/// element is moved with one load and one store, which are atomic when the
+/// `atomic` feature is enabled.
+///
+/// # Parameters
+///
+/// - `dst`: The elements to overwrite.
+/// - `src`: The elements to copy. Only as many as `dst` has are copied.
+pub fn copy_body<T>(dst: &[T::Access], src: &[T::Access])
+where T: BitStore {
+	for (to, from) in dst.iter().zip(src) {
+		to.store(from.load());
+	}
+}
+
+/// Moves whole elements towards the front of a region, and zeroes the
+/// elements they vacate.
+///
+/// This is the memory move that `<<=` performs on a slice that fully owns its
+/// elements, for the part of the shift that is a multiple of the element
+/// width.
+///
+/// # Parameters
+///
+/// - `elts`: The region to shift.
+/// - `by`: The number of elements to shift by.
+///
+/// # Panics
+///
+/// This panics if `by` is greater than `elts.len()`.
+pub fn shift_elements_down<T>(elts: &mut [T], by: usize)
+where T: BitStore {
+	let len = elts.len();
+	assert!(by <= len, "Cannot shift {} elements by {}", len, by);
+	let rem = len - by;
+	let base = elts.as_mut_ptr();
+	unsafe {
+		ptr::copy(base.add(by), base, rem);
+		ptr::write_bytes(base.add(rem), 0, by);
+	}
+}
+
+/// Moves whole elements towards the back of a region, and zeroes the elements
+/// they vacate.
+///
+/// This is the memory move that `>>=` performs on a slice that fully owns its
+/// elements, for the part of the shift that is a multiple of the element
+/// width.
+///
+/// # Parameters
+///
+/// - `elts`: The region to shift.
+/// - `by`: The number of elements to shift by.
+///
+/// # Panics
+///
+/// This panics if `by` is greater than `elts.len()`.
+pub fn shift_elements_up<T>(elts: &mut [T], by: usize)
+where T: BitStore {
+	let len = elts.len();
+	assert!(by <= len, "Cannot shift {} elements by {}", len, by);
+	let base = elts.as_mut_ptr();
+	unsafe {
+		ptr::copy(base, base.add(by), len - by);
+		ptr::write_bytes(base, 0, by);
+	}
+}
@@ -0,0 +1,553 @@
+/*! Record layouts described at runtime
+
+[`BitRange`] and [`bit_range!`] describe the fields of a record at compile time.
+Tools that parse formats from a schema loaded at runtime, such as register maps
+or protocol dissectors, do not know their fields until then, so this module
+offers the same access through a value instead of a type.
+
+A [`Layout`] is a list of named bit ranges within a record of fixed width. It is
+checked once, when it is built: every field must lie within the record, and no
+two fields may overlap or share a name. [`BitSlice::view`] and
+[`BitSlice::view_mut`] then apply it to a record, and the returned views read
+and write fields by name. Since the fields are only known at runtime, a field
+that is missing, or that is wider than the integer used to access it, is
+reported as a [`LayoutError`] rather than a panic.
+
+[`BitRange`]: ../fields/trait.BitRange.html
+[`BitSlice::view`]: ../slice/struct.BitSlice.html#method.view
+[`BitSlice::view_mut`]: ../slice/struct.BitSlice.html#method.view_mut
+[`Layout`]: struct.Layout.html
+[`LayoutError`]: enum.LayoutError.html
+[`bit_range!`]: ../macro.bit_range.html
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	fields::BitField,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use alloc::{
+	string::String,
+	vec::Vec,
+};
+
+use core::{
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+	ops::Range,
+};
+
+/** The named fields of a record, checked to lie within it without overlapping.
+
+# Examples
+
+```rust
+use bitvec::{layout::{Layout, LayoutError}, prelude::*};
+
+let fields = vec![("version", 0 .. 4), ("flags", 4 .. 7), ("length", 7 .. 16)];
+let layout = Layout::new(16, fields).unwrap();
+
+let mut header = [0u8; 2];
+let mut view = header.bits_mut::<Msb0>().view_mut(&layout).unwrap();
+view.set_field("version", 4u8).unwrap();
+view.set_field("length", 300u16).unwrap();
+assert_eq!(view.set_field("checksum", 0u8), Err(LayoutError::Missing));
+assert_eq!(view.get_field::<u16>("length"), Ok(300));
+assert_eq!(
+  view.get_field::<u8>("length"),
+  Err(LayoutError::Narrow { field: 2, width: 9, bits: 8 }),
+);
+assert_eq!(header[0] >> 4, 4);
+```
+**/
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Layout {
+	/// The number of bits in the record.
+	width: usize,
+	/// The fields, in the order they were given.
+	fields: Vec<(String, Range<usize>)>,
+}
+
+impl Layout {
+	/// Builds a layout, and checks its fields.
+	///
+	/// # Parameters
+	///
+	/// - `width`: The number of bits in the record.
+	/// - `fields`: The name and bit range of each field within the record.
+	///
+	/// # Returns
+	///
+	/// The layout, or the first problem found with its fields. Fields are
+	/// numbered in the order they are given.
+	pub fn new<I, S>(width: usize, fields: I) -> Result<Self, LayoutError>
+	where
+		I: IntoIterator<Item = (S, Range<usize>)>,
+		S: Into<String>,
+	{
+		let fields = fields
+			.into_iter()
+			.map(|(name, range)| (name.into(), range))
+			.collect::<Vec<(String, Range<usize>)>>();
+		for (n, (name, range)) in fields.iter().enumerate() {
+			if range.start >= range.end {
+				return Err(LayoutError::Empty { field: n });
+			}
+			if range.end > width {
+				return Err(LayoutError::OutOfBounds {
+					field: n,
+					end: range.end,
+					width,
+				});
+			}
+			for (m, (other, span)) in fields[.. n].iter().enumerate() {
+				if other == name {
+					return Err(LayoutError::Duplicate { first: m, second: n });
+				}
+				if span.start < range.end && range.start < span.end {
+					return Err(LayoutError::Overlap { first: m, second: n });
+				}
+			}
+		}
+		Ok(Self { width, fields })
+	}
+
+	/// Gets the number of bits in the record.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	/// Looks up the bit range of a field.
+	pub fn field(&self, name: &str) -> Option<Range<usize>> {
+		self.fields
+			.iter()
+			.find(|(field, _)| field == name)
+			.map(|(_, range)| range.clone())
+	}
+
+	/// Iterates over the names and bit ranges of the fields, in the order they
+	/// were given.
+	pub fn fields(&self) -> impl Iterator<Item = (&str, Range<usize>)> + '_ {
+		self.fields
+			.iter()
+			.map(|(name, range)| (name.as_str(), range.clone()))
+	}
+
+	/// Looks up a field to be accessed through an integer of type `U`.
+	///
+	/// # Returns
+	///
+	/// The bit range of the field, or an error if the layout has no such
+	/// field, or if the field is wider than `U`.
+	fn field_as<U>(&self, name: &str) -> Result<Range<usize>, LayoutError>
+	where U: BitStore {
+		let (field, range) = self
+			.fields
+			.iter()
+			.enumerate()
+			.find(|(_, (field, _))| field == name)
+			.map(|(n, (_, range))| (n, range.clone()))
+			.ok_or(LayoutError::Missing)?;
+		let width = range.end - range.start;
+		if width > U::BITS as usize {
+			return Err(LayoutError::Narrow {
+				field,
+				width,
+				bits: U::BITS,
+			});
+		}
+		Ok(range)
+	}
+
+	/// Checks that a record is as wide as the layout.
+	fn check<O, T>(&self, record: &BitSlice<O, T>) -> Result<(), LayoutError>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		if record.len() == self.width {
+			Ok(())
+		}
+		else {
+			Err(LayoutError::Width {
+				expected: self.width,
+				found: record.len(),
+			})
+		}
+	}
+}
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Views the slice as a record with the fields of a layout.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `layout`: The fields of the record.
+	///
+	/// # Returns
+	///
+	/// A view that reads the fields of `self` by name, or an error if `self` is
+	/// not exactly as wide as `layout`.
+	pub fn view<'a>(
+		&'a self,
+		layout: &'a Layout,
+	) -> Result<RecordView<'a, O, T>, LayoutError>
+	{
+		layout.check(self)?;
+		Ok(RecordView {
+			bits: self,
+			layout,
+		})
+	}
+
+	/// Views the slice as a mutable record with the fields of a layout.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `layout`: The fields of the record.
+	///
+	/// # Returns
+	///
+	/// A view that reads and writes the fields of `self` by name, or an error
+	/// if `self` is not exactly as wide as `layout`.
+	pub fn view_mut<'a>(
+		&'a mut self,
+		layout: &'a Layout,
+	) -> Result<RecordViewMut<'a, O, T>, LayoutError>
+	{
+		layout.check(self)?;
+		Ok(RecordViewMut {
+			bits: self,
+			layout,
+		})
+	}
+}
+
+/** A record, read through the fields of a [`Layout`].
+
+This is produced by [`BitSlice::view`].
+
+[`BitSlice::view`]: ../slice/struct.BitSlice.html#method.view
+[`Layout`]: struct.Layout.html
+**/
+#[derive(Clone, Copy, Debug)]
+pub struct RecordView<'a, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The record, exactly as wide as the layout.
+	bits: &'a BitSlice<O, T>,
+	/// The fields of the record.
+	layout: &'a Layout,
+}
+
+impl<'a, O, T> RecordView<'a, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Gets the layout of the record.
+	pub fn layout(&self) -> &'a Layout {
+		self.layout
+	}
+
+	/// Selects the bits of a field, or `None` if the layout has no such field.
+	pub fn field(&self, name: &str) -> Option<&'a BitSlice<O, T>> {
+		self.layout.field(name).map(|range| &self.bits[range])
+	}
+
+	/// Loads the value of a field.
+	///
+	/// # Returns
+	///
+	/// The value of the field. This is a [`LayoutError::Missing`] error if the
+	/// layout has no such field, and a [`LayoutError::Narrow`] error if `U` is
+	/// narrower than the field.
+	///
+	/// [`LayoutError::Missing`]: enum.LayoutError.html#variant.Missing
+	/// [`LayoutError::Narrow`]: enum.LayoutError.html#variant.Narrow
+	pub fn get_field<U>(&self, name: &str) -> Result<U, LayoutError>
+	where
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		let range = self.layout.field_as::<U>(name)?;
+		Ok(self.bits[range].load())
+	}
+}
+
+/** A record, read and written through the fields of a [`Layout`].
+
+This is produced by [`BitSlice::view_mut`].
+
+[`BitSlice::view_mut`]: ../slice/struct.BitSlice.html#method.view_mut
+[`Layout`]: struct.Layout.html
+**/
+#[derive(Debug)]
+pub struct RecordViewMut<'a, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The record, exactly as wide as the layout.
+	bits: &'a mut BitSlice<O, T>,
+	/// The fields of the record.
+	layout: &'a Layout,
+}
+
+impl<'a, O, T> RecordViewMut<'a, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Gets the layout of the record.
+	pub fn layout(&self) -> &'a Layout {
+		self.layout
+	}
+
+	/// Reborrows the record for reading only.
+	pub fn as_view(&self) -> RecordView<'_, O, T> {
+		RecordView {
+			bits: self.bits,
+			layout: self.layout,
+		}
+	}
+
+	/// Selects the bits of a field, or `None` if the layout has no such field.
+	pub fn field(&self, name: &str) -> Option<&BitSlice<O, T>> {
+		self.layout.field(name).map(move |range| &self.bits[range])
+	}
+
+	/// Selects the bits of a field for writing, or `None` if the layout has no
+	/// such field.
+	pub fn field_mut(&mut self, name: &str) -> Option<&mut BitSlice<O, T>> {
+		let bits = &mut *self.bits;
+		self.layout.field(name).map(move |range| &mut bits[range])
+	}
+
+	/// Loads the value of a field.
+	///
+	/// # Returns
+	///
+	/// The value of the field. This is a [`LayoutError::Missing`] error if the
+	/// layout has no such field, and a [`LayoutError::Narrow`] error if `U` is
+	/// narrower than the field.
+	///
+	/// [`LayoutError::Missing`]: enum.LayoutError.html#variant.Missing
+	/// [`LayoutError::Narrow`]: enum.LayoutError.html#variant.Narrow
+	pub fn get_field<U>(&self, name: &str) -> Result<U, LayoutError>
+	where
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		let range = self.layout.field_as::<U>(name)?;
+		Ok(self.bits[range].load())
+	}
+
+	/// Stores a value into a field.
+	///
+	/// Only the low bits of `value`, as many as the field is wide, are written.
+	///
+	/// # Returns
+	///
+	/// A [`LayoutError::Missing`] error if the layout has no such field, and a
+	/// [`LayoutError::Narrow`] error if `U` is narrower than the field. Nothing
+	/// is written when this fails.
+	///
+	/// [`LayoutError::Missing`]: enum.LayoutError.html#variant.Missing
+	/// [`LayoutError::Narrow`]: enum.LayoutError.html#variant.Narrow
+	pub fn set_field<U>(
+		&mut self,
+		name: &str,
+		value: U,
+	) -> Result<(), LayoutError>
+	where
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		let range = self.layout.field_as::<U>(name)?;
+		self.bits[range].store(value);
+		Ok(())
+	}
+}
+
+/** The problems that a layout, or its use on a record, can have.
+
+Fields are numbered in the order they were given to [`Layout::new`].
+
+[`Layout::new`]: struct.Layout.html#method.new
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum LayoutError {
+	/// A field has no bits.
+	Empty {
+		/// The number of the field.
+		field: usize,
+	},
+	/// A field reaches past the end of the record.
+	OutOfBounds {
+		/// The number of the field.
+		field: usize,
+		/// The end of its bit range.
+		end: usize,
+		/// The number of bits in the record.
+		width: usize,
+	},
+	/// Two fields have the same name.
+	Duplicate {
+		/// The number of the earlier field.
+		first: usize,
+		/// The number of the later field.
+		second: usize,
+	},
+	/// Two fields share some bits.
+	Overlap {
+		/// The number of the earlier field.
+		first: usize,
+		/// The number of the later field.
+		second: usize,
+	},
+	/// A record is not as wide as the layout applied to it.
+	Width {
+		/// The number of bits in the layout.
+		expected: usize,
+		/// The number of bits in the record.
+		found: usize,
+	},
+	/// A record was accessed by the name of a field that its layout does not
+	/// have.
+	Missing,
+	/// A field is wider than the integer type used to load or store it.
+	Narrow {
+		/// The number of the field.
+		field: usize,
+		/// The number of bits in the field.
+		width: usize,
+		/// The number of bits in the integer type.
+		bits: u8,
+	},
+}
+
+impl Display for LayoutError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match *self {
+			LayoutError::Empty { field } => {
+				write!(fmt, "Field {} has no bits", field)
+			},
+			LayoutError::OutOfBounds { field, end, width } => write!(
+				fmt,
+				"Field {} ends at bit {}, past the record width {}",
+				field, end, width,
+			),
+			LayoutError::Duplicate { first, second } => write!(
+				fmt,
+				"Fields {} and {} have the same name",
+				first, second,
+			),
+			LayoutError::Overlap { first, second } => {
+				write!(fmt, "Fields {} and {} overlap", first, second)
+			},
+			LayoutError::Width { expected, found } => write!(
+				fmt,
+				"Layout of {} bits applied to a record of {} bits",
+				expected, found,
+			),
+			LayoutError::Missing => write!(fmt, "The layout has no such field"),
+			LayoutError::Narrow { field, width, bits } => write!(
+				fmt,
+				"Field {} has {} bits, and cannot be accessed as {} bits",
+				field, width, bits,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LayoutError {
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Lsb0,
+		slice::AsBits,
+	};
+	use alloc::vec;
+
+	#[test]
+	fn checks() {
+		let bad = |fields: &[(&str, Range<usize>)]| {
+			Layout::new(12, fields.iter().cloned()).unwrap_err()
+		};
+		assert_eq!(bad(&[("a", 0 .. 4), ("b", 4 .. 4)]), LayoutError::Empty {
+			field: 1,
+		});
+		assert_eq!(
+			bad(&[("a", 0 .. 13)]),
+			LayoutError::OutOfBounds {
+				field: 0,
+				end: 13,
+				width: 12,
+			}
+		);
+		assert_eq!(
+			bad(&[("a", 0 .. 4), ("b", 8 .. 12), ("a", 4 .. 8)]),
+			LayoutError::Duplicate { first: 0, second: 2 }
+		);
+		assert_eq!(
+			bad(&[("a", 4 .. 8), ("b", 0 .. 5)]),
+			LayoutError::Overlap { first: 0, second: 1 }
+		);
+
+		let layout = Layout::new(12, vec![("lo", 0 .. 5), ("hi", 5 .. 12)])
+			.unwrap();
+		assert_eq!(layout.field("hi"), Some(5 .. 12));
+		assert_eq!(layout.fields().map(|(name, _)| name).collect::<Vec<_>>(), [
+			"lo", "hi",
+		]);
+
+		let mut data = [0u16; 2];
+		let record = &mut data.bits_mut::<Lsb0>()[3 .. 15];
+		let mut view = record.view_mut(&layout).unwrap();
+		assert_eq!(view.set_field("lo", 0x1Fu8), Ok(()));
+		assert_eq!(view.set_field("hi", 0x55u8), Ok(()));
+		assert_eq!(view.as_view().get_field::<u8>("hi"), Ok(0x55));
+		assert_eq!(view.get_field::<u8>("mid"), Err(LayoutError::Missing));
+		assert_eq!(view.set_field("mid", 0u8), Err(LayoutError::Missing));
+		assert_eq!(data[0], (0x55 << 5 | 0x1F) << 3);
+
+		//  A field wider than the integer is refused, and left unwritten.
+		let wide = Layout::new(12, vec![("all", 0 .. 12)]).unwrap();
+		let record = &mut data.bits_mut::<Lsb0>()[3 .. 15];
+		let mut view = record.view_mut(&wide).unwrap();
+		let narrow = LayoutError::Narrow {
+			field: 0,
+			width: 12,
+			bits: 8,
+		};
+		assert_eq!(view.get_field::<u8>("all"), Err(narrow));
+		assert_eq!(view.set_field("all", 0u8), Err(narrow));
+		assert_eq!(view.get_field::<u16>("all"), Ok(0x55 << 5 | 0x1F));
+
+		let err = data.bits::<Lsb0>().view(&layout).unwrap_err();
+		assert_eq!(err, LayoutError::Width {
+			expected: 12,
+			found: 32,
+		});
+	}
+}
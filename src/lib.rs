@@ -55,14 +55,30 @@ extern crate serde_test;
 pub mod macros;
 
 mod access;
-mod domain;
+pub mod allocator;
+pub mod array;
+mod checksum;
+pub mod domain;
 pub mod fields;
+pub mod hamming;
 pub mod indices;
+pub mod masked;
+pub mod oblivious;
 pub mod order;
+pub mod pack;
 mod pointer;
 pub mod prelude;
+pub mod queue;
+pub mod scrambler;
 pub mod slice;
 pub mod store;
+pub mod varint;
+
+#[cfg(all(feature = "arrow", feature = "alloc"))]
+pub mod arrow;
+
+#[cfg(all(feature = "bloom", feature = "alloc"))]
+pub mod bloom;
 
 #[cfg(feature = "alloc")]
 pub mod boxed;
@@ -70,9 +86,88 @@ pub mod boxed;
 #[cfg(feature = "alloc")]
 pub mod vec;
 
+#[cfg(all(feature = "bytes", feature = "alloc"))]
+pub mod bytes;
+
+#[cfg(feature = "alloc")]
+pub mod canonical;
+
+#[cfg(feature = "alloc")]
+pub mod correlate;
+
+#[cfg(feature = "subtle")]
+pub mod ct;
+
+#[cfg(feature = "alloc")]
+pub mod delta;
+
+#[cfg(feature = "diagnostics")]
+pub mod diagnostics;
+
+#[cfg(feature = "alloc")]
+pub mod fixed;
+
+#[cfg(any(feature = "arbitrary", feature = "quickcheck"))]
+mod fuzz;
+
+#[cfg(all(feature = "futures", not(target_env = "sgx")))]
+pub mod futures;
+
+#[cfg(feature = "alloc")]
+pub mod generational;
+
+#[cfg(feature = "bench-internals")]
+#[doc(hidden)]
+pub mod kernels;
+
+#[cfg(not(feature = "bench-internals"))]
+mod kernels;
+
+#[cfg(feature = "alloc")]
+pub mod layout;
+
+#[cfg(feature = "alloc")]
+pub mod linecode;
+
+#[cfg(feature = "defmt")]
+mod logging;
+
+#[cfg(feature = "mmap")]
+pub mod mmap;
+
+#[cfg(feature = "nom")]
+pub mod nom;
+
+#[cfg(feature = "alloc")]
+pub mod persistent;
+
+#[cfg(feature = "alloc")]
+pub mod plane;
+
+#[cfg(feature = "rand")]
+mod random;
+
+#[cfg(feature = "alloc")]
+pub mod registers;
+
+#[cfg(feature = "alloc")]
+pub mod schema;
+
 #[cfg(feature = "serde")]
 mod serdes;
 
+#[cfg(feature = "alloc")]
+pub mod stuffing;
+
+#[cfg(feature = "alloc")]
+pub mod tracked;
+
+#[cfg(feature = "alloc")]
+pub mod trie;
+
+#[cfg(feature = "zeroize")]
+pub mod zeroize;
+
 /** Perform single-bit ripple-carry addition.
 
 This function performs carry-aware binary addition on single bits of each
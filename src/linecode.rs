@@ -0,0 +1,305 @@
+/*! Line codes
+
+Serial links and radio modems do not send data bits directly; they send a line
+code, which guarantees enough transitions for the receiver to recover the clock,
+or keeps the signal free of a DC component. This module adds encoders and
+decoders for two of the most common line codes to `BitSlice`:
+
+- Manchester code, in [`manchester_encode`] and [`manchester_decode`], sends
+  each bit as a pair of opposite levels, so that every bit has a transition in
+  its middle.
+- NRZI, in [`nrzi_encode`] and [`nrzi_decode`], sends each `1` as a change of
+  level and each `0` as no change.
+
+The encoded sequences are produced as `BitVec`s of the same type as the source,
+and are assembled one storage element at a time, rather than by pushing each bit
+onto the vector.
+
+[`manchester_decode`]: ../slice/struct.BitSlice.html#method.manchester_decode
+[`manchester_encode`]: ../slice/struct.BitSlice.html#method.manchester_encode
+[`nrzi_decode`]: ../slice/struct.BitSlice.html#method.nrzi_decode
+[`nrzi_encode`]: ../slice/struct.BitSlice.html#method.nrzi_encode
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	indices::Indexable,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::fmt::{
+	self,
+	Display,
+	Formatter,
+};
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Encodes the slice in Manchester code.
+	///
+	/// This uses the IEEE 802.3 convention: a `0` is sent as `1, 0`, and a `1`
+	/// as `0, 1`. The G. E. Thomas convention is the inverse of this, and can
+	/// be produced by inverting the output.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// A vector twice the length of `self`, holding its Manchester code.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = bits![Msb0, u8; 1, 0, 0];
+	/// let line = data.manchester_encode();
+	/// assert_eq!(line, bitvec![Msb0, u8; 0, 1, 1, 0, 1, 0]);
+	/// assert_eq!(line.manchester_decode().unwrap(), data);
+	/// ```
+	pub fn manchester_encode(&self) -> BitVec<O, T> {
+		let half = T::BITS as usize / 2;
+		let mut out = BitVec::<O, T>::repeat(false, self.len() * 2);
+		for (elt, chunk) in out.as_mut_slice().iter_mut().zip(self.chunks(half))
+		{
+			for (n, bit) in chunk.iter().copied().enumerate() {
+				elt.set::<O>((n as u8 * 2).idx(), !bit);
+				elt.set::<O>((n as u8 * 2 + 1).idx(), bit);
+			}
+		}
+		out
+	}
+
+	/// Decodes a Manchester-coded slice.
+	///
+	/// This is the inverse of [`manchester_encode`].
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The decoded bits, or an error if `self` has an odd length, or has a pair
+	/// of equal bits, which no data bit encodes to.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let line = bits![Msb0, u8; 0, 1, 1, 0, 1, 1];
+	/// assert_eq!(line.manchester_decode().unwrap_err().index(), 4);
+	/// ```
+	///
+	/// [`manchester_encode`]: #method.manchester_encode
+	pub fn manchester_decode(&self) -> Result<BitVec<O, T>, ManchesterError> {
+		let len = self.len();
+		if len & 1 != 0 {
+			return Err(ManchesterError { index: len - 1 });
+		}
+		let width = T::BITS as usize;
+		let mut out = BitVec::<O, T>::repeat(false, len / 2);
+		let chunks = self.chunks(width * 2);
+		for (idx, (elt, chunk)) in
+			out.as_mut_slice().iter_mut().zip(chunks).enumerate()
+		{
+			let mut bits = chunk.iter().copied();
+			let mut n = 0u8;
+			while let (Some(a), Some(b)) = (bits.next(), bits.next()) {
+				if a == b {
+					return Err(ManchesterError {
+						index: idx * width * 2 + n as usize * 2,
+					});
+				}
+				elt.set::<O>(n.idx(), b);
+				n += 1;
+			}
+		}
+		Ok(out)
+	}
+
+	/// Encodes the slice in NRZI (non-return-to-zero, inverted) code.
+	///
+	/// Each `1` in `self` is sent as a change of level, and each `0` as the
+	/// same level as the bit before it. USB uses the opposite convention, in
+	/// which `0` is the change of level, and can be produced by encoding the
+	/// inverse of the data.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `initial`: The level of the line before the first bit.
+	///
+	/// # Returns
+	///
+	/// A vector the same length as `self`, holding the level of the line
+	/// during each bit.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = bits![Msb0, u8; 1, 1, 0, 1];
+	/// let line = data.nrzi_encode(false);
+	/// assert_eq!(line, bitvec![Msb0, u8; 1, 0, 0, 1]);
+	/// assert_eq!(line.nrzi_decode(false), data);
+	/// ```
+	pub fn nrzi_encode(&self, initial: bool) -> BitVec<O, T> {
+		let mut level = initial;
+		self.assemble(|bit| {
+			level ^= bit;
+			level
+		})
+	}
+
+	/// Decodes an NRZI-coded slice.
+	///
+	/// This is the inverse of [`nrzi_encode`]. Every sequence of levels is a
+	/// valid NRZI code, so this cannot fail.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `initial`: The level of the line before the first bit.
+	///
+	/// # Returns
+	///
+	/// A vector the same length as `self`, holding `1` for each bit whose level
+	/// differs from the level before it.
+	///
+	/// [`nrzi_encode`]: #method.nrzi_encode
+	pub fn nrzi_decode(&self, initial: bool) -> BitVec<O, T> {
+		let mut level = initial;
+		self.assemble(|bit| {
+			let out = bit != level;
+			level = bit;
+			out
+		})
+	}
+
+	/// Maps each bit of the slice, in order, into a new vector of the same
+	/// length, writing the vector a whole element at a time.
+	fn assemble<F>(&self, mut func: F) -> BitVec<O, T>
+	where F: FnMut(bool) -> bool {
+		let width = T::BITS as usize;
+		let mut out = BitVec::<O, T>::repeat(false, self.len());
+		for (elt, chunk) in out.as_mut_slice().iter_mut().zip(self.chunks(width))
+		{
+			for (n, bit) in chunk.iter().copied().enumerate() {
+				elt.set::<O>((n as u8).idx(), func(bit));
+			}
+		}
+		out
+	}
+}
+
+/** The error produced when a bit sequence is not valid Manchester code.
+
+This is returned by [`BitSlice::manchester_decode`].
+
+[`BitSlice::manchester_decode`]: ../slice/struct.BitSlice.html#method.manchester_decode
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ManchesterError {
+	/// The index of the first bit of the invalid pair.
+	index: usize,
+}
+
+impl ManchesterError {
+	/// Gets the index of the first bit of the pair that could not be decoded.
+	/// When the input has an odd length, this is the index of its last bit.
+	pub fn index(&self) -> usize {
+		self.index
+	}
+}
+
+impl Display for ManchesterError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(fmt, "Invalid Manchester code at index {}", self.index)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ManchesterError {
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+
+	#[test]
+	fn known_answers() {
+		//  1010_0101 sends as 01 10 01 10 10 01 10 01.
+		let bits = 0xA5u8.bits::<Msb0>();
+		let line = bits.manchester_encode();
+		assert_eq!(line.as_slice(), &[0x66, 0x99]);
+		assert_eq!(line.manchester_decode().unwrap(), bits);
+
+		//  The level changes at each set bit, starting from `initial`.
+		assert_eq!(bits.nrzi_encode(false).as_slice(), &[0xC6]);
+		assert_eq!(bits.nrzi_encode(true).as_slice(), &[0x39]);
+		assert_eq!(0xC6u8.bits::<Msb0>().nrzi_decode(false), bits);
+
+		let empty = &bits[.. 0];
+		assert!(empty.manchester_encode().is_empty());
+		assert!(empty.manchester_decode().unwrap().is_empty());
+		assert!(empty.nrzi_encode(true).is_empty());
+		assert!(empty.nrzi_decode(true).is_empty());
+	}
+
+	#[test]
+	fn round_trip() {
+		//  Runs and alternations, in spans that start and end inside
+		//  elements.
+		let data = [0xFFFF_0000u32, 0xAAAA_AAAA, 0x0F0F_00FF];
+		for &(from, to) in &[(0, 96), (3, 90), (40, 41), (7, 7)] {
+			let bits = &data.bits::<Msb0>()[from .. to];
+			let line = bits.manchester_encode();
+			assert_eq!(line.len(), bits.len() * 2);
+			for (n, bit) in bits.iter().enumerate() {
+				assert_eq!((line[2 * n], line[2 * n + 1]), (!*bit, *bit));
+			}
+			assert_eq!(line.manchester_decode().unwrap(), bits);
+
+			for &initial in &[false, true] {
+				let line = bits.nrzi_encode(initial);
+				let mut level = initial;
+				for (bit, sent) in bits.iter().zip(line.iter()) {
+					level ^= *bit;
+					assert_eq!(*sent, level);
+				}
+				assert_eq!(line.nrzi_decode(initial), bits);
+			}
+		}
+
+		let bits = &data.bits::<Lsb0>()[5 .. 70];
+		assert_eq!(bits.manchester_encode().manchester_decode().unwrap(), bits);
+		assert_eq!(bits.nrzi_encode(true).nrzi_decode(true), bits);
+	}
+
+	#[test]
+	fn invalid_manchester() {
+		let line = [0b0110_1001u8, 0b1010_0101];
+		let bits = line.bits::<Msb0>();
+		assert!(bits.manchester_decode().is_ok());
+		assert_eq!(bits[1 ..].manchester_decode().unwrap_err().index(), 14);
+		assert_eq!(bits[1 .. 15].manchester_decode().unwrap_err().index(), 0);
+	}
+}
@@ -0,0 +1,166 @@
+/*! `defmt`-powered logging
+
+This module implements [`defmt::Format`] for the `bitvec` types, so that
+embedded and enclave targets which log through `defmt` can print bit buffers
+without linking the `core::fmt` machinery.
+
+The rendering matches the `{:b}` form of the `Binary` implementation: each
+storage element touched by the slice becomes one group of `0` and `1`
+characters, in semantic order, and the groups are written as a list. As in
+`{:b}`, and unlike the alternate `{:#b}`, the groups have no `0b` prefix.
+
+[`defmt::Format`]: https://docs.rs/defmt/0.3/defmt/trait.Format.html
+!*/
+
+#![cfg(feature = "defmt")]
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[cfg(feature = "alloc")]
+use crate::{
+	boxed::BitBox,
+	vec::BitVec,
+};
+
+use core::{
+	cmp,
+	str,
+};
+
+use defmt::{
+	Format,
+	Formatter,
+};
+
+/// Writes the contents of a `BitSlice` as a list of binary words, one for each
+/// storage element.
+///
+/// # Examples
+///
+/// A slice of `Msb0, u8` covering bits `3 .. 14` of the bytes `[0x0F, 0xF0]`
+/// logs as `[01111, 111100]`, which is also its `{:b}` rendering.
+impl<O, T> Format for BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn format(&self, fmt: Formatter) {
+		defmt::write!(fmt, "[");
+		let mut first = true;
+		words(self, |word| {
+			if !first {
+				defmt::write!(fmt, ", ");
+			}
+			first = false;
+			defmt::write!(fmt, "{=str}", word);
+		});
+		defmt::write!(fmt, "]");
+	}
+}
+
+/// Renders each storage element of a `BitSlice` as a word of `0` and `1`
+/// characters, in semantic order, and passes the words to `func` in turn.
+fn words<O, T, F>(bits: &BitSlice<O, T>, mut func: F)
+where
+	O: BitOrder,
+	T: BitStore,
+	F: FnMut(&str),
+{
+	let width = T::BITS as usize;
+	let mut text = [b'0'; 64];
+	//  The first group ends at the first element boundary; every group after
+	//  it is a full element, except perhaps the last.
+	let mut rest = bits;
+	let mut group = cmp::min(rest.len(), width - *bits.bitptr().head() as usize);
+	while !rest.is_empty() {
+		let (this, next) = rest.split_at(group);
+		for (slot, bit) in text.iter_mut().zip(this.iter()) {
+			*slot = b'0' + *bit as u8;
+		}
+		//  `text` only ever holds ASCII digits.
+		func(unsafe { str::from_utf8_unchecked(&text[.. group]) });
+		rest = next;
+		group = cmp::min(rest.len(), width);
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<O, T> Format for BitBox<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn format(&self, fmt: Formatter) {
+		self.as_bitslice().format(fmt)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<O, T> Format for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn format(&self, fmt: Formatter) {
+		self.as_bitslice().format(fmt)
+	}
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+
+	use alloc::{
+		format,
+		string::String,
+	};
+
+	/// Renders a slice as `Format` does, into a string.
+	fn render<O, T>(bits: &BitSlice<O, T>) -> String
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let mut out = String::from("[");
+		words(bits, |word| {
+			if out.len() > 1 {
+				out.push_str(", ");
+			}
+			out.push_str(word);
+		});
+		out.push(']');
+		out
+	}
+
+	#[test]
+	fn matches_binary() {
+		let bytes = [0x0Fu8, 0xF0, 0x5A];
+		let bits = bytes.bits::<Msb0>();
+		assert_eq!(render(&bits[3 .. 14]), "[01111, 111100]");
+		assert_eq!(render(&bits[3 .. 3]), "[]");
+		let ranges = [(0, 24), (3, 14), (8, 16), (9, 10), (5, 23)];
+		for &(start, end) in ranges.iter() {
+			let bits = &bits[start .. end];
+			assert_eq!(render(bits), format!("{:b}", bits));
+		}
+
+		let words = [0xA5C3u16, 0x0FF0, 0x1234];
+		let bits = words.bits::<Lsb0>();
+		for &(start, end) in [(0, 48), (1, 47), (16, 32), (15, 17)].iter() {
+			let bits = &bits[start .. end];
+			assert_eq!(render(bits), format!("{:b}", bits));
+			assert!(!render(bits).contains("0b"));
+		}
+	}
+}
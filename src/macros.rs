@@ -24,11 +24,24 @@ matched by the macro system.
 Like `vec!`, `bits!` supports bit lists `[0, 1, …]` and repetition markers
 `[1; n]`.
 
+The produced reference is `&'static BitSlice`: the bits are compacted into a
+`static` element array at compile time, and no allocation takes place. `bits!`
+is therefore available in `no_std` builds without the `alloc` feature, and its
+result can be returned from functions that name protocol constants.
+
+The deprecated ordering names `BigEndian` and `LittleEndian` are accepted as
+synonyms for `Msb0` and `Lsb0`, respectively.
+
 # Examples
 
 ```rust
 use bitvec::prelude::*;
 
+fn sync() -> &'static BitSlice<Msb0, u8> {
+  bits![Msb0, u8; 0, 1, 1, 1, 1, 1, 1, 0]
+}
+assert_eq!(sync().as_slice(), &[0x7E]);
+
 bits![Msb0, u8; 0, 1];
 bits![Lsb0, u8; 0, 1,];
 bits![Msb0; 0, 1];
@@ -44,6 +57,15 @@ bits![Local; 0, 1,];
 **/
 #[macro_export]
 macro_rules! bits {
+	//  Route the deprecated ordering names to their current equivalents, so
+	//  that the internal macros can recognize them.
+	(BigEndian $($rest:tt)*) => {
+		$crate::bits!(Msb0 $($rest)*)
+	};
+	(LittleEndian $($rest:tt)*) => {
+		$crate::bits!(Lsb0 $($rest)*)
+	};
+
 	//  Sequence syntax `[bit (, bit)*]` or `[(bit ,)*]`
 
 	//  Explicit order and store.
@@ -186,6 +208,66 @@ macro_rules! bitbox {
 	};
 }
 
+/** Declare a named field at a fixed position in a fixed-width bit record.
+
+This produces a zero-sized type implementing [`BitRange`]. The field position
+is checked against the record width while the program compiles: a field that is
+empty, or that extends past the end of its record, is a compilation error.
+
+The syntax is `visibility Name: WIDTH => START, LEN`, optionally preceded by
+attributes (including doc comments). All three numbers must be constant
+expressions.
+
+# Examples
+
+```rust
+use bitvec::{bit_range, fields::BitRange, prelude::*};
+
+bit_range!(pub Flag: 8 => 7, 1);
+
+let mut reg = 0u8;
+Flag::store(reg.bits_mut::<Msb0>(), 1u8);
+assert_eq!(reg, 1);
+```
+
+A field that does not fit in its record is rejected:
+
+```rust,compile_fail
+use bitvec::bit_range;
+
+bit_range!(Overflow: 8 => 6, 4);
+```
+
+[`BitRange`]: fields/trait.BitRange.html
+**/
+#[macro_export]
+macro_rules! bit_range {
+	(
+		$(#[$attr:meta])*
+		$vis:vis $name:ident: $width:expr => $start:expr, $len:expr
+	) => {
+		$(#[$attr])*
+		#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+		$vis struct $name;
+
+		impl $crate::fields::BitRange for $name {
+			const START: usize = $start;
+			const LEN: usize = $len;
+			const WIDTH: usize = $width;
+		}
+
+		impl $name {
+			//  An array whose length is `0` when the field is invalid cannot be
+			//  assigned to a binding whose type has length `1`. The types are
+			//  checked even though the constant is never used.
+			#[allow(dead_code)]
+			const __BIT_RANGE_VALID: [(); 1] = [(); (
+				($len) > 0 && ($start) + ($len) <= ($width)
+			) as usize];
+		}
+	};
+}
+
 #[cfg(test)]
 mod tests {
 	#[allow(unused_imports)]
@@ -230,6 +312,30 @@ mod tests {
 		}
 	}
 
+	#[test]
+	fn static_bits_macros() {
+		use crate::{
+			domain::Domain,
+			slice::BitSlice,
+		};
+
+		let msb: &'static BitSlice<Msb0, u8> = bits![BigEndian, u8; 1, 0, 1];
+		let lsb: &'static BitSlice<Lsb0, u16> = bits![LittleEndian, u16; 1, 0, 1];
+		match (msb.domain(), lsb.domain()) {
+			(
+				Domain::Region { tail: Some(m), .. },
+				Domain::Region { tail: Some(l), .. },
+			) => {
+				assert_eq!(m.load(), 0b1010_0000);
+				assert_eq!(l.load(), 0b101);
+			},
+			_ => unreachable!("three-bit slices have a partial tail"),
+		}
+
+		assert_eq!(bits![BigEndian; 1; 3], bits![Msb0; 1, 1, 1]);
+		assert_eq!(bits![LittleEndian, u8; 0; 9].len(), 9);
+	}
+
 	#[test]
 	#[cfg(feature = "alloc")]
 	fn compile_bitvec_macros() {
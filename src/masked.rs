@@ -0,0 +1,463 @@
+/*! Bit slices paired with validity masks
+
+Columnar formats such as Apache Arrow store a boolean column as two bitmaps of
+the same length: one holding the values, and one marking which of the values
+are present. A position whose validity bit is unset is null, and its value bit
+has no meaning.
+
+[`MaskedBits`] pairs the two slices, and answers queries about the column as a
+whole, so that every caller need not rebuild the combination of them. Its
+counts use the fused counting methods of `BitSlice`, and so work a whole memory
+element at a time when the slices are equally aligned.
+
+[`MaskedBits`]: struct.MaskedBits.html
+!*/
+
+use crate::{
+	fields,
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::{
+		self,
+		BitSlice,
+	},
+	store::BitStore,
+};
+
+use core::{
+	hash::{
+		Hash,
+		Hasher,
+	},
+	iter::FusedIterator,
+};
+
+/** A view of a data slice, together with a slice that marks which of its bits
+are valid.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within each storage element.
+- `T: BitStore`: The storage element type.
+
+# Examples
+
+```rust
+use bitvec::{masked::MaskedBits, prelude::*};
+
+let data = bits![Msb0, u8; 1, 0, 1, 1, 0, 1];
+let valid = bits![Msb0, u8; 1, 1, 0, 1, 0, 1];
+let column = MaskedBits::new(data, valid);
+assert_eq!(column.get(0), Some(true));
+assert_eq!(column.get(2), None);
+assert_eq!(column.count_valid(), 4);
+assert_eq!(column.count_valid_ones(), 3);
+assert_eq!(column.count_valid_zeros(), 1);
+
+let values = column.iter().collect::<Vec<_>>();
+assert_eq!(values, [
+    Some(true), Some(false), None, Some(true), None, Some(true),
+]);
+let present = column.iter_valid().collect::<Vec<_>>();
+assert_eq!(present, [(0, true), (1, false), (3, true), (5, true)]);
+```
+
+Two views are equal when they have the same validity bits, and the same data
+bits at their valid positions. The data bits at invalid positions are ignored
+by both equality and hashing.
+**/
+#[derive(Clone, Copy, Debug)]
+pub struct MaskedBits<'a, O = Local, T = usize>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The values.
+	data: &'a BitSlice<O, T>,
+	/// The validity of each value.
+	valid: &'a BitSlice<O, T>,
+}
+
+impl<'a, O, T> MaskedBits<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// Pairs a data slice with its validity slice.
+	///
+	/// # Panics
+	///
+	/// This panics if the slices have different lengths.
+	pub fn new(data: &'a BitSlice<O, T>, valid: &'a BitSlice<O, T>) -> Self {
+		assert_eq!(
+			data.len(),
+			valid.len(),
+			"Data and validity slices must have equal lengths",
+		);
+		Self { data, valid }
+	}
+
+	/// Views the data slice, including the bits at invalid positions.
+	pub fn data(&self) -> &'a BitSlice<O, T> {
+		self.data
+	}
+
+	/// Views the validity slice.
+	pub fn validity(&self) -> &'a BitSlice<O, T> {
+		self.valid
+	}
+
+	/// Counts the positions in the view, valid or not.
+	pub fn len(&self) -> usize {
+		self.data.len()
+	}
+
+	/// Tests if the view has no positions.
+	pub fn is_empty(&self) -> bool {
+		self.data.is_empty()
+	}
+
+	/// Reads the value at a position, if it is valid.
+	///
+	/// # Returns
+	///
+	/// The data bit at `index` if its validity bit is set, or `None` if it is
+	/// unset.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	pub fn get(&self, index: usize) -> Option<bool> {
+		if self.valid[index] {
+			Some(self.data[index])
+		}
+		else {
+			None
+		}
+	}
+
+	/// Counts the valid positions.
+	pub fn count_valid(&self) -> usize {
+		self.valid.count_ones()
+	}
+
+	/// Counts the invalid positions.
+	pub fn count_invalid(&self) -> usize {
+		self.valid.count_zeros()
+	}
+
+	/// Counts the valid positions whose value is set.
+	pub fn count_valid_ones(&self) -> usize {
+		self.data.and_count_ones(self.valid)
+	}
+
+	/// Counts the valid positions whose value is unset.
+	pub fn count_valid_zeros(&self) -> usize {
+		self.count_valid() - self.count_valid_ones()
+	}
+
+	/// Divides the view into two at an index.
+	///
+	/// # Panics
+	///
+	/// This panics if `mid` is greater than the length of the view.
+	pub fn split_at(&self, mid: usize) -> (Self, Self) {
+		let (dl, dr) = self.data.split_at(mid);
+		let (vl, vr) = self.valid.split_at(mid);
+		(Self::new(dl, vl), Self::new(dr, vr))
+	}
+
+	/// Iterates over every position, producing its value if it is valid, and
+	/// `None` if it is not.
+	pub fn iter(&self) -> Iter<'a, O, T> {
+		Iter {
+			data: self.data.iter(),
+			valid: self.valid.iter(),
+		}
+	}
+
+	/// Iterates over the valid positions only, producing the index and value
+	/// of each.
+	pub fn iter_valid(&self) -> IterValid<'a, O, T> {
+		IterValid {
+			data: self.data,
+			valid: self.valid,
+			index: 0,
+		}
+	}
+
+	/// Loads the view in 64-bit words, each pairing the data bits with their
+	/// invalid positions cleared and the validity bits.
+	fn words(&self) -> impl Iterator<Item = (u64, u64)> + 'a {
+		self.data.chunks(64).zip(self.valid.chunks(64)).map(|(data, valid)| {
+			let valid = fields::load_msb_first(valid);
+			(fields::load_msb_first(data) & valid, valid)
+		})
+	}
+}
+
+impl<'a, O, T> PartialEq for MaskedBits<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.len() == other.len() && self.words().eq(other.words())
+	}
+}
+
+impl<'a, O, T> Eq for MaskedBits<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+impl<'a, O, T> Hash for MaskedBits<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	fn hash<H>(&self, hasher: &mut H)
+	where H: Hasher {
+		self.len().hash(hasher);
+		for (data, valid) in self.words() {
+			data.hash(hasher);
+			valid.hash(hasher);
+		}
+	}
+}
+
+impl<'a, O, T> IntoIterator for MaskedBits<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type IntoIter = Iter<'a, O, T>;
+	type Item = Option<bool>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		self.iter()
+	}
+}
+
+/** An iterator over every position of a [`MaskedBits`] view.
+
+This is produced by [`MaskedBits::iter`].
+
+[`MaskedBits`]: struct.MaskedBits.html
+[`MaskedBits::iter`]: struct.MaskedBits.html#method.iter
+**/
+#[derive(Clone, Debug)]
+pub struct Iter<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The unvisited values.
+	data: slice::Iter<'a, O, T>,
+	/// The unvisited validity bits.
+	valid: slice::Iter<'a, O, T>,
+}
+
+impl<'a, O, T> Iterator for Iter<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = Option<bool>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let data = *self.data.next()?;
+		let valid = *self.valid.next()?;
+		Some(if valid { Some(data) } else { None })
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.data.size_hint()
+	}
+}
+
+impl<'a, O, T> DoubleEndedIterator for Iter<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	fn next_back(&mut self) -> Option<Self::Item> {
+		let data = *self.data.next_back()?;
+		let valid = *self.valid.next_back()?;
+		Some(if valid { Some(data) } else { None })
+	}
+}
+
+impl<'a, O, T> ExactSizeIterator for Iter<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+impl<'a, O, T> FusedIterator for Iter<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+/** An iterator over the valid positions of a [`MaskedBits`] view.
+
+This is produced by [`MaskedBits::iter_valid`]. Runs of invalid positions are
+skipped a memory element at a time where the validity slice allows it.
+
+[`MaskedBits`]: struct.MaskedBits.html
+[`MaskedBits::iter_valid`]: struct.MaskedBits.html#method.iter_valid
+**/
+#[derive(Clone, Debug)]
+pub struct IterValid<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The values.
+	data: &'a BitSlice<O, T>,
+	/// The validity of each value.
+	valid: &'a BitSlice<O, T>,
+	/// The index of the next position to examine.
+	index: usize,
+}
+
+impl<'a, O, T> Iterator for IterValid<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = (usize, bool);
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let width = T::BITS as usize;
+		while self.index < self.valid.len() {
+			let rest = &self.valid[self.index ..];
+			//  Stop the chunk at an element boundary, so that later chunks
+			//  cover whole elements.
+			let head = *rest.bitptr().head() as usize;
+			let chunk = &rest[.. core::cmp::min(rest.len(), width - head)];
+			if chunk.not_any() {
+				self.index += chunk.len();
+				continue;
+			}
+			let index = self.index + chunk.iter().take_while(|b| !**b).count();
+			self.index = index + 1;
+			return Some((index, self.data[index]));
+		}
+		None
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some(self.valid.len() - self.index))
+	}
+}
+
+impl<'a, O, T> FusedIterator for IterValid<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Lsb0,
+		slice::AsBits,
+	};
+
+	#[test]
+	fn queries() {
+		//  Data `0, 0, 1, 1` under validity `0, 1, 0, 1`.
+		let (data, valid) = (0b1100u8, 0b1010u8);
+		let view = MaskedBits::new(
+			&data.bits::<Lsb0>()[.. 4],
+			&valid.bits::<Lsb0>()[.. 4],
+		);
+		let expected = [None, Some(false), None, Some(true)];
+		assert!(view.iter().eq(expected.iter().copied()));
+		assert_eq!((view.count_valid_ones(), view.count_valid_zeros()), (1, 1));
+
+		//  The first element is entirely invalid, and the last only partly
+		//  valid.
+		let data = [0x0000_FFFFu32, 0xFF00_FF00, 0x0F0F_0F0F];
+		let valid = [0x0000_0000u32, 0x3333_CCCC, 0x0000_00FF];
+		let view = MaskedBits::new(
+			&data.bits::<Lsb0>()[5 .. 90],
+			&valid.bits::<Lsb0>()[5 .. 90],
+		);
+		let naive = (0 .. view.len()).map(|n| view.get(n));
+		assert!(view.iter().eq(naive.clone()));
+		assert!(view.iter().rev().eq(naive.clone().rev()));
+
+		let expected = naive
+			.enumerate()
+			.filter_map(|(n, bit)| bit.map(|bit| (n, bit)));
+		assert!(view.iter_valid().eq(expected.clone()));
+		assert_eq!(view.count_valid(), expected.clone().count());
+		assert_eq!(view.count_invalid(), view.len() - view.count_valid());
+		let ones = expected.filter(|(_, bit)| *bit).count();
+		assert_eq!(view.count_valid_ones(), ones);
+		assert_eq!(view.count_valid_zeros(), view.count_valid() - ones);
+
+		let (left, right) = view.split_at(40);
+		assert_eq!(left.count_valid() + right.count_valid(), view.count_valid());
+		assert_eq!(right.get(0), view.get(40));
+	}
+
+	#[test]
+	fn equality() {
+		//  Folds every byte written to it into one word.
+		#[derive(Default)]
+		struct Fold(u64);
+
+		impl Hasher for Fold {
+			fn finish(&self) -> u64 {
+				self.0
+			}
+
+			fn write(&mut self, bytes: &[u8]) {
+				for &byte in bytes {
+					self.0 = self.0.rotate_left(7) ^ byte as u64;
+				}
+			}
+		}
+
+		fn hash(view: &MaskedBits<Lsb0, u32>) -> u64 {
+			let mut hasher = Fold::default();
+			view.hash(&mut hasher);
+			hasher.finish()
+		}
+
+		//  The noise differs from the data in every bit.
+		let data = [0x0000_FFFFu32, 0xFF00_FF00, 0x0F0F_0F0F];
+		let noise = [!data[0], !data[1], !data[2]];
+		let valid = [0xFFFF_0000u32, 0x3333_CCCC, 0x0000_00FF];
+		//  Differ only in the data bits at invalid positions.
+		let mixed = [
+			(data[0] & valid[0]) | (noise[0] & !valid[0]),
+			(data[1] & valid[1]) | (noise[1] & !valid[1]),
+			(data[2] & valid[2]) | (noise[2] & !valid[2]),
+		];
+		let valid = &valid.bits::<Lsb0>()[5 .. 90];
+		let a = MaskedBits::new(&data.bits::<Lsb0>()[5 .. 90], valid);
+		let b = MaskedBits::new(&mixed.bits::<Lsb0>()[5 .. 90], valid);
+		assert_ne!(a.data(), b.data());
+		assert_eq!(a, b);
+		assert_eq!(hash(&a), hash(&b));
+
+		let c = MaskedBits::new(&noise.bits::<Lsb0>()[5 .. 90], valid);
+		assert_ne!(a, c);
+		assert_ne!(a, MaskedBits::new(&a.data()[.. 80], &valid[.. 80]));
+		let (left, _) = a.split_at(40);
+		assert_eq!(left, b.split_at(40).0);
+	}
+}
@@ -0,0 +1,212 @@
+/*! Bit-level views of memory-mapped files
+
+Large bitmap files, such as allocation maps, Bloom filters, and genome masks,
+are best analyzed through a memory map rather than read into a `BitVec`. The
+map only needs to be kept alive as long as the `BitSlice` over it, which
+ordinarily requires either a self-referential structure or `unsafe` pointer
+plumbing. This module provides [`MmapBitSlice`], which owns a read-only map and
+dereferences to the bits it covers.
+
+Creating the map remains `unsafe`, as the file may be modified by other
+processes while it is mapped. That is a property of memory maps, and not of the
+bit view.
+
+[`MmapBitSlice`]: struct.MmapBitSlice.html
+!*/
+
+#![cfg(feature = "mmap")]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	pointer::BitPtr,
+	slice::{
+		AsBits,
+		BitSlice,
+	},
+};
+
+use core::{
+	fmt::{
+		self,
+		Debug,
+		Formatter,
+	},
+	marker::PhantomData,
+	ops::Deref,
+};
+
+use memmap2::Mmap;
+
+/** A read-only memory map, viewed as a `BitSlice<O, u8>`.
+
+Files rarely hold a whole number of bytes of bits, so the view may be shortened
+to a length in bits, usually read from a header in the file or from a side-car
+metadata file.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering used to index bits within each byte of the file.
+
+# Examples
+
+```rust
+use bitvec::{mmap::MmapBitSlice, prelude::*};
+use memmap2::MmapOptions;
+
+//  A bitmap file would be mapped with `Mmap::map`. This builds the same
+//  contents in an anonymous map: the first eight bytes hold the bit count,
+//  in little-endian order, and the bitmap follows them.
+let mut map = MmapOptions::new().len(16).map_anon()?;
+map[.. 8].copy_from_slice(&100u64.to_le_bytes());
+map[8 .. 10].copy_from_slice(&[0xFF, 0x0F]);
+let map = map.make_read_only()?;
+
+let mut len = [0u8; 8];
+len.copy_from_slice(&map[.. 8]);
+let len = u64::from_le_bytes(len) as usize;
+
+let bits = MmapBitSlice::<Lsb0>::with_len(map, len);
+let body = &bits[64 ..];
+assert_eq!(body.len(), 36);
+assert_eq!(body.count_ones(), 12);
+# Ok::<(), std::io::Error>(())
+```
+**/
+pub struct MmapBitSlice<O = Local>
+where O: BitOrder
+{
+	/// The mapped file.
+	map: Mmap,
+	/// The number of live bits, counted from the start of the map.
+	len: usize,
+	_order: PhantomData<O>,
+}
+
+impl<O> MmapBitSlice<O>
+where O: BitOrder
+{
+	/// Views every bit of a memory map.
+	///
+	/// # Parameters
+	///
+	/// - `map`: A read-only memory map.
+	///
+	/// # Panics
+	///
+	/// This panics if the map has more bits than a `BitSlice` can address.
+	pub fn new(map: Mmap) -> Self {
+		let len = map.len().saturating_mul(8);
+		Self::with_len(map, len)
+	}
+
+	/// Views the first `len` bits of a memory map.
+	///
+	/// # Parameters
+	///
+	/// - `map`: A read-only memory map.
+	/// - `len`: The number of bits in the map that are meaningful.
+	///
+	/// # Panics
+	///
+	/// This panics if `len` is greater than the number of bits in the map, or
+	/// the number of bits a `BitSlice` can address.
+	pub fn with_len(map: Mmap, len: usize) -> Self {
+		let avail = map.len().saturating_mul(8);
+		assert!(len <= avail, "Index {} out of bounds: {}", len, avail);
+		assert!(
+			len <= BitPtr::<u8>::MAX_BITS,
+			"BitSlice cannot address {} bits; the maximum is {}",
+			len,
+			BitPtr::<u8>::MAX_BITS,
+		);
+		Self {
+			map,
+			len,
+			_order: PhantomData,
+		}
+	}
+
+	/// Views the bits of the map.
+	pub fn as_bitslice(&self) -> &BitSlice<O, u8> {
+		//  The whole map may have more bits than a `BitSlice` can address, so
+		//  only the bytes that hold live bits are viewed.
+		&self.map[.. (self.len + 7) / 8].bits::<O>()[.. self.len]
+	}
+
+	/// Unwraps the view into its memory map.
+	pub fn into_mmap(self) -> Mmap {
+		self.map
+	}
+}
+
+impl<O> Deref for MmapBitSlice<O>
+where O: BitOrder
+{
+	type Target = BitSlice<O, u8>;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<O> AsRef<BitSlice<O, u8>> for MmapBitSlice<O>
+where O: BitOrder
+{
+	fn as_ref(&self) -> &BitSlice<O, u8> {
+		self.as_bitslice()
+	}
+}
+
+impl<O> From<Mmap> for MmapBitSlice<O>
+where O: BitOrder
+{
+	fn from(map: Mmap) -> Self {
+		Self::new(map)
+	}
+}
+
+/// Prints the size of the view, but not its contents, which may be very large.
+impl<O> Debug for MmapBitSlice<O>
+where O: BitOrder
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("MmapBitSlice")
+			.field("order", &O::TYPENAME)
+			.field("bytes", &self.map.len())
+			.field("bits", &self.len)
+			.finish()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Msb0;
+	use memmap2::MmapOptions;
+
+	fn map(bytes: &[u8]) -> Mmap {
+		let mut map = MmapOptions::new().len(bytes.len()).map_anon().unwrap();
+		map.copy_from_slice(bytes);
+		map.make_read_only().unwrap()
+	}
+
+	#[test]
+	fn view() {
+		let bits = MmapBitSlice::<Msb0>::new(map(&[0x0F, 0xF0]));
+		assert_eq!(bits.len(), 16);
+		assert_eq!(bits.count_ones(), 8);
+
+		let bits = MmapBitSlice::<Msb0>::with_len(bits.into_mmap(), 10);
+		assert_eq!(bits.len(), 10);
+		assert_eq!(bits.count_ones(), 6);
+	}
+
+	#[test]
+	#[should_panic]
+	fn too_long() {
+		MmapBitSlice::<Msb0>::with_len(map(&[0]), 9);
+	}
+}
@@ -0,0 +1,323 @@
+/*! Parser-combinator input over `BitSlice`
+
+The [`nom`] parser combinators are generic over their input, which they reach
+through a family of traits for measuring, iterating, comparing, and splitting
+it. Its own bit-level parsers run over `(&[u8], usize)` tuples, which can only
+begin at a byte and only number bits from the most significant bit of each.
+
+This module implements those traits for `&BitSlice`, so that `tag`, `take`,
+`take_while`, `recognize`, and the combinators built on them run directly over
+bit slices of any ordering and storage type, and from any bit. The items of the
+input are `bool`s, and every position, length, and count of `Needed` bits is
+measured in bits.
+
+# Examples
+
+```rust
+use bitvec::prelude::*;
+use nom::{
+    bytes::complete::{tag, take, take_while},
+    IResult,
+};
+
+type Bits<'a> = &'a BitSlice<Msb0, u8>;
+
+fn frame(input: Bits) -> IResult<Bits, (Bits, Bits)> {
+    let (rest, _) = tag(bits![1, 1, 0])(input)?;
+    let (rest, kind) = take(4usize)(rest)?;
+    let (rest, _) = take_while(|bit: bool| !bit)(rest)?;
+    let (rest, mark) = take(2usize)(rest)?;
+    Ok((rest, (kind, mark)))
+}
+
+let data = [0b1101_0110u8, 0b0011_0000];
+let (rest, (kind, mark)) = frame(data.bits()).unwrap();
+assert_eq!(kind, bits![1, 0, 1, 1]);
+assert_eq!(mark, bits![1, 1]);
+assert_eq!(rest.len(), 4);
+```
+
+[`nom`]: https://docs.rs/nom/7/nom/
+!*/
+
+#![cfg(feature = "nom")]
+
+use crate::{
+	order::BitOrder,
+	slice::{
+		BitSlice,
+		Iter,
+	},
+	store::BitStore,
+};
+
+use core::{
+	cmp,
+	iter::{
+		Copied,
+		Enumerate,
+	},
+	ops::{
+		Range,
+		RangeFrom,
+		RangeFull,
+		RangeTo,
+	},
+};
+
+use ::nom::{
+	Compare,
+	CompareResult,
+	InputIter,
+	InputLength,
+	InputTake,
+	Needed,
+	Offset,
+	Slice,
+	UnspecializedInput,
+};
+
+impl<O, T> InputLength for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn input_len(&self) -> usize {
+		self.len()
+	}
+}
+
+impl<'a, O, T> InputIter for &'a BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Item = bool;
+	type Iter = Enumerate<Self::IterElem>;
+	type IterElem = Copied<Iter<'a, O, T>>;
+
+	fn iter_indices(&self) -> Self::Iter {
+		self.iter_elements().enumerate()
+	}
+
+	fn iter_elements(&self) -> Self::IterElem {
+		self.iter().copied()
+	}
+
+	fn position<P>(&self, predicate: P) -> Option<usize>
+	where P: Fn(bool) -> bool {
+		self.iter().position(|bit| predicate(*bit))
+	}
+
+	fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+		if count <= self.len() {
+			Ok(count)
+		}
+		else {
+			Err(Needed::new(count - self.len()))
+		}
+	}
+}
+
+impl<O, T> InputTake for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn take(&self, count: usize) -> Self {
+		&self[.. count]
+	}
+
+	/// Splits the input at `count`, and returns the remainder before the
+	/// taken prefix, as `nom` requires.
+	fn take_split(&self, count: usize) -> (Self, Self) {
+		let (prefix, suffix) = self.split_at(count);
+		(suffix, prefix)
+	}
+}
+
+/// Provides `InputTakeAtPosition`, and with it `take_while` and its relatives,
+/// through `nom`’s generic implementation over `InputIter` and `InputTake`.
+impl<O, T> UnspecializedInput for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+/// Compares the start of the input against a pattern, which may have any
+/// ordering or storage type.
+///
+/// The comparison is `Incomplete` when the input is a proper prefix of the
+/// pattern.
+impl<'b, O, T, P, U> Compare<&'b BitSlice<P, U>> for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+	P: BitOrder,
+	U: BitStore,
+{
+	fn compare(&self, pattern: &'b BitSlice<P, U>) -> CompareResult {
+		let len = cmp::min(self.len(), pattern.len());
+		if self[.. len] != pattern[.. len] {
+			CompareResult::Error
+		}
+		else if len < pattern.len() {
+			CompareResult::Incomplete
+		}
+		else {
+			CompareResult::Ok
+		}
+	}
+
+	/// Bits have no case, so this is the same as `compare`.
+	fn compare_no_case(&self, pattern: &'b BitSlice<P, U>) -> CompareResult {
+		self.compare(pattern)
+	}
+}
+
+/// Counts the bits from the start of one slice to the start of another, which
+/// must be a suffix of it.
+///
+/// `nom` only measures the remainder that a parser left of its own input, so
+/// this is the difference of their lengths. Empty suffixes do not point into
+/// the memory of the slice, so their addresses cannot be compared.
+impl<O, T> Offset for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn offset(&self, second: &Self) -> usize {
+		self.len() - second.len()
+	}
+}
+
+impl<O, T> Slice<Range<usize>> for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn slice(&self, range: Range<usize>) -> Self {
+		&self[range]
+	}
+}
+
+impl<O, T> Slice<RangeFrom<usize>> for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn slice(&self, range: RangeFrom<usize>) -> Self {
+		&self[range]
+	}
+}
+
+impl<O, T> Slice<RangeFull> for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn slice(&self, range: RangeFull) -> Self {
+		&self[range]
+	}
+}
+
+impl<O, T> Slice<RangeTo<usize>> for &BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn slice(&self, range: RangeTo<usize>) -> Self {
+		&self[range]
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+	use ::nom::{
+		bytes::{
+			complete,
+			streaming,
+		},
+		combinator::recognize,
+		error::{
+			Error,
+			ErrorKind,
+		},
+		sequence::pair,
+		Err,
+		IResult,
+	};
+
+	type Bits<'a> = &'a BitSlice<Lsb0, u16>;
+
+	fn ones(input: Bits) -> IResult<Bits, Bits> {
+		let one = &0b1u8.bits::<Msb0>()[7 ..];
+		recognize(pair(complete::tag(one), complete::take_while(|bit| bit)))(
+			input,
+		)
+	}
+
+	fn zeros(input: Bits) -> IResult<Bits, Bits> {
+		complete::take_while(|bit: bool| !bit)(input)
+	}
+
+	#[test]
+	fn parsers() {
+		let data = [0b0100_0000_0111_1000u16, 0];
+		let bits = &data.bits::<Lsb0>()[3 ..];
+
+		let (rest, run) = ones(bits).unwrap();
+		assert_eq!(run.len(), 4);
+		assert_eq!(bits.offset(&rest), 4);
+		assert_eq!(rest[.. 7].count_ones(), 0);
+		assert_eq!(
+			ones(rest),
+			Err(Err::Error(Error::new(rest, ErrorKind::Tag))),
+		);
+
+		let (rest, run) = zeros(rest).unwrap();
+		assert_eq!(run.len(), 7);
+		assert_eq!(&bits[11 ..], rest);
+
+		let short = &bits[.. 2];
+		let pattern = &0b111u8.bits::<Lsb0>()[.. 3];
+		assert_eq!(short.compare(pattern), CompareResult::Incomplete);
+		assert_eq!(rest.compare(pattern), CompareResult::Error);
+		assert_eq!(bits.compare(pattern), CompareResult::Ok);
+		assert_eq!(
+			streaming::take::<_, _, Error<_>>(3usize)(short),
+			Err(Err::Incomplete(Needed::new(1))),
+		);
+		assert_eq!(short.slice_index(3), Err(Needed::new(1)));
+		assert_eq!(short.iter_indices().nth(1), Some((1, true)));
+	}
+
+	#[test]
+	fn recognize_all() {
+		let data = [0xA5C3u16];
+		let bits = data.bits::<Lsb0>();
+
+		let (rest, all) =
+			recognize(complete::take::<_, _, Error<_>>(16usize))(bits).unwrap();
+		assert!(rest.is_empty());
+		assert_eq!(all, bits);
+		assert_eq!(bits.offset(&&bits[16 ..]), 16);
+
+		let tail = &bits[5 ..];
+		let (rest, all) = recognize(complete::take_while::<_, _, Error<_>>(
+			|_| true,
+		))(tail)
+		.unwrap();
+		assert!(rest.is_empty());
+		assert_eq!(all, tail);
+	}
+}
@@ -0,0 +1,309 @@
+/*! Bit access with data-independent memory traffic
+
+Indexing a `BitSlice` reads the one element that holds the requested bit. An
+observer who can see which memory a program touches, such as the host of an
+SGX enclave watching page faults or cache lines, learns the index from that
+read even when it cannot see the data.
+
+The methods in this module read, and for writes also store to, every element
+under the slice, in order, for each bit accessed. The element holding the bit
+is picked out with mask arithmetic, and neither the index nor the bit value
+decides any branch or address. The cost is linear in the length of the slice
+for each access.
+
+The length of the slice, its alignment within its storage, and the number of
+bits accessed are treated as public. An index out of bounds panics, which
+reveals only that it was out of bounds. The `Lsb0` and `Msb0` orderings compute
+bit positions without branching; other `BitOrder` implementations must do the
+same for these methods to keep their guarantee.
+!*/
+
+use crate::{
+	access::BitAccess,
+	indices::Indexable,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use core::mem;
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Reads a bit, touching every element under the slice.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `index`: The bit to read. Its value does not affect which memory is
+	///   read.
+	///
+	/// # Returns
+	///
+	/// The value of the bit at `index`.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0x00u8, 0x20, 0x00];
+	/// let bits = &data.bits::<Msb0>()[3 ..];
+	/// assert!(bits.oblivious_get(7));
+	/// assert!(!bits.oblivious_get(8));
+	/// ```
+	pub fn oblivious_get(&self, index: usize) -> bool {
+		let len = self.len();
+		assert!(index < len, "Index {} out of bounds: {}", index, len);
+		let (elt, pos) = self.locate(index);
+		let mut acc = T::FALSE;
+		for (idx, elem) in self.bitptr().as_access_slice().iter().enumerate() {
+			acc |= elem.load() & mask_of(T::from(same(idx, elt)));
+		}
+		(acc >> *O::at::<T>(pos.idx())) & T::from(1) != T::FALSE
+	}
+
+	/// Writes a bit, reading and storing to every element under the slice.
+	///
+	/// Each element is written through the same masks as
+	/// [`BitSlice::set`], and so bits outside the slice, and bits of the
+	/// slice other than the one at `index`, keep their values.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The bit to write. Its value does not affect which memory is
+	///   read or written.
+	/// - `value`: The new value of the bit.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0u16; 3];
+	/// data.bits_mut::<Lsb0>()[4 ..].oblivious_set(20, true);
+	/// assert_eq!(data, [0, 0x0100, 0]);
+	/// ```
+	///
+	/// [`BitSlice::set`]: #method.set
+	pub fn oblivious_set(&mut self, index: usize, value: bool) {
+		let len = self.len();
+		assert!(index < len, "Index {} out of bounds: {}", index, len);
+		let (elt, pos) = self.locate(index);
+		let place = *O::mask::<T>(pos.idx());
+		let bit = mask_of(T::from(value as u8));
+		for (idx, elem) in self.bitptr().as_access_slice().iter().enumerate() {
+			let hit = place & mask_of(T::from(same(idx, elt)));
+			elem.clear_bits(!hit);
+			elem.set_bits(bit & hit);
+		}
+	}
+
+	/// Reads a sequence of bits, at secret indices, into another slice.
+	///
+	/// Each bit is read with [`oblivious_get`], and stored into `out` in
+	/// order without branching on its value.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `indices`: The bits to read.
+	/// - `out`: The slice that receives the bit at `indices[n]` as its bit
+	///   `n`.
+	///
+	/// # Panics
+	///
+	/// This panics if `out` and `indices` are of different lengths, or if any
+	/// index is out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let table = bits![Msb0, u8; 0, 1, 1, 0, 1, 0, 0, 0, 1];
+	/// let mut out = 0u8;
+	/// table.oblivious_gather(&[8, 0, 4, 3], &mut out.bits_mut::<Msb0>()[.. 4]);
+	/// assert_eq!(out, 0b1010_0000);
+	/// ```
+	///
+	/// [`oblivious_get`]: #method.oblivious_get
+	pub fn oblivious_gather<P, U>(
+		&self,
+		indices: &[usize],
+		out: &mut BitSlice<P, U>,
+	) where
+		P: BitOrder,
+		U: BitStore,
+	{
+		assert_eq!(
+			indices.len(),
+			out.len(),
+			"Gathered bits require an output slice of the same length",
+		);
+		for (n, &index) in indices.iter().enumerate() {
+			let bit = self.oblivious_get(index);
+			out.store_bit(n, U::from(bit as u8));
+		}
+	}
+
+	/// Writes a sequence of bits into the slice, at secret indices.
+	///
+	/// Each bit is written with [`oblivious_set`], in order, so where an index
+	/// appears more than once, the last bit written to it is kept.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `indices`: The bits to write.
+	/// - `bits`: The slice whose bit `n` is written to `indices[n]`.
+	///
+	/// # Panics
+	///
+	/// This panics if `bits` and `indices` are of different lengths, or if any
+	/// index is out of bounds.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0u16;
+	/// let bits = bits![Msb0, u8; 1, 1, 0];
+	/// data.bits_mut::<Lsb0>().oblivious_scatter(&[2, 9, 2], bits);
+	/// assert_eq!(data, 0x0200);
+	/// ```
+	///
+	/// [`oblivious_set`]: #method.oblivious_set
+	pub fn oblivious_scatter<P, U>(
+		&mut self,
+		indices: &[usize],
+		bits: &BitSlice<P, U>,
+	) where
+		P: BitOrder,
+		U: BitStore,
+	{
+		assert_eq!(
+			indices.len(),
+			bits.len(),
+			"Scattered bits require an input slice of the same length",
+		);
+		for (n, &index) in indices.iter().enumerate() {
+			let bit = bits.load_bit(n) != U::FALSE;
+			self.oblivious_set(index, bit);
+		}
+	}
+
+	/// Reads one bit as the lowest bit of an element, without branching on
+	/// its value.
+	pub(crate) fn load_bit(&self, index: usize) -> T {
+		let bitptr = self.bitptr();
+		let (elt, bit) = bitptr.head().offset(index as isize);
+		let elem = unsafe { &*bitptr.pointer().a().offset(elt) };
+		(elem.load() >> *O::at::<T>(bit)) & T::from(1)
+	}
+
+	/// Writes the lowest bit of an element into one bit, without branching on
+	/// its value.
+	fn store_bit(&mut self, index: usize, bit: T) {
+		let bitptr = self.bitptr();
+		let (elt, place) = bitptr.head().offset(index as isize);
+		let elem = unsafe { &*bitptr.pointer().a().offset(elt) };
+		let place = *O::mask(place);
+		elem.clear_bits(!place);
+		elem.set_bits(place & mask_of(bit));
+	}
+
+	/// Finds the element, counted from the first under the slice, and the
+	/// index within it, of a bit, using only arithmetic on `index`.
+	fn locate(&self, index: usize) -> (usize, u8) {
+		let bit = *self.bitptr().head() as usize + index;
+		let width = T::BITS as usize;
+		(bit / width, (bit % width) as u8)
+	}
+}
+
+/// Widens the lowest bit of an element into an element that is all ones when
+/// it is set, and all zeros when it is not.
+pub(crate) fn mask_of<T>(bit: T) -> T
+where T: BitStore {
+	let mut mask = bit & T::from(1);
+	let mut shift = 1;
+	while shift < T::BITS {
+		mask |= mask << shift;
+		shift <<= 1;
+	}
+	mask
+}
+
+/// Produces `1` if two indices are equal, and `0` otherwise, without comparing
+/// them.
+fn same(a: usize, b: usize) -> u8 {
+	let diff = a ^ b;
+	let shift = mem::size_of::<usize>() * 8 - 1;
+	(((diff | diff.wrapping_neg()) >> shift) ^ 1) as u8
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+	use alloc::borrow::ToOwned;
+
+	#[test]
+	fn oblivious() {
+		//  Only the first bit and bit 71 are set, so a read that picks up any
+		//  other bit of the slice shows up.
+		let data = [0x0400_0000u32, 0, 0x0008_0000];
+		let msb0 = &data.bits::<Msb0>()[5 .. 83];
+		for idx in 0 .. msb0.len() {
+			assert_eq!(msb0.oblivious_get(idx), idx == 0 || idx == 71);
+		}
+		assert_eq!(same(3, 3), 1);
+		assert_eq!(same(0, !0), 0);
+
+		let mut out = [0xA5u8; 12];
+		let bits = &mut out.bits_mut::<Lsb0>()[3 .. 91];
+		let mut expected = bits.to_owned();
+		for &(idx, value) in &[(0, false), (5, true), (44, true), (87, false)] {
+			bits.oblivious_set(idx, value);
+			expected.set(idx, value);
+			assert_eq!(bits, &expected[..]);
+		}
+		assert_eq!(out[0] & 0b111, 0b101);
+		assert_eq!(out[11] & 0xF8, 0xA0);
+
+		let indices = [71, 2, 71, 0, 70];
+		let mut gathered = [0u16];
+		let bits = &mut gathered.bits_mut::<Msb0>()[1 .. 6];
+		msb0.oblivious_gather(&indices, bits);
+		for (n, &idx) in indices.iter().enumerate() {
+			assert_eq!(bits[n], msb0[idx]);
+		}
+		//  `1, 0, 1, 1, 0`, after one untouched bit.
+		assert_eq!(gathered, [0x5800]);
+
+		let mut scattered = [0u8; 4];
+		let bits = &mut scattered.bits_mut::<Msb0>()[2 .. 30];
+		bits.oblivious_scatter(&[27, 0, 9, 0], &0b1110u8.bits::<Msb0>()[4 ..]);
+		assert_eq!(scattered, [0x00, 0x10, 0x00, 0x04]);
+	}
+}
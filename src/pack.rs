@@ -0,0 +1,331 @@
+/*! Packing values into, and unpacking them out of, bit slices.
+
+The [`BitPack`] and [`BitUnpack`] traits describe types that have a fixed-width
+representation as a run of bits. They are implemented here for the integer
+primitives, `bool`, and arrays of packable types, and are intended as the
+building blocks from which packed record types are assembled, one field after
+another.
+
+Values are always written most significant bit first: index `0` of the
+destination slice receives the highest bit of the value. This holds for every
+`BitOrder`, so a packed layout reads the same in a `bitvec` debug print as it
+does in a protocol diagram.
+
+[`BitPack`]: trait.BitPack.html
+[`BitUnpack`]: trait.BitUnpack.html
+!*/
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use core::fmt::{
+	self,
+	Display,
+	Formatter,
+};
+
+/** A type with a fixed-width packed bit representation, which can be written
+into a `BitSlice`.
+
+# Examples
+
+```rust
+use bitvec::{pack::BitPack, prelude::*};
+
+let mut data = [0u8; 2];
+let bits = data.bits_mut::<Msb0>();
+0x5u8.pack_into(&mut bits[.. 8]);
+[true, false].pack_into(&mut bits[8 ..]);
+assert_eq!(data, [0x05, 0x80]);
+```
+**/
+pub trait BitPack {
+	/// The number of bits in the packed representation.
+	const BITS: usize;
+
+	/// Writes the packed representation into the front of a bit slice.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `bits`: The destination. Its first `Self::BITS` bits are overwritten;
+	///   any bits after them are untouched.
+	///
+	/// # Panics
+	///
+	/// This panics if `bits` is shorter than `Self::BITS`.
+	fn pack_into<O, T>(&self, bits: &mut BitSlice<O, T>)
+	where
+		O: BitOrder,
+		T: BitStore;
+}
+
+/** A type with a fixed-width packed bit representation, which can be read out
+of a `BitSlice`.
+
+# Examples
+
+```rust
+use bitvec::{pack::BitUnpack, prelude::*};
+
+let data = [0x12u8, 0x34];
+let bits = data.bits::<Msb0>();
+assert_eq!(u16::unpack_from(bits), Ok(0x1234));
+assert_eq!(<[u8; 2]>::unpack_from(bits), Ok([0x12, 0x34]));
+assert!(u32::unpack_from(bits).is_err());
+```
+**/
+pub trait BitUnpack: Sized {
+	/// The number of bits in the packed representation.
+	const BITS: usize;
+
+	/// Reads a value from the front of a bit slice.
+	///
+	/// # Parameters
+	///
+	/// - `bits`: The source. Only its first `Self::BITS` bits are read.
+	///
+	/// # Returns
+	///
+	/// The decoded value, or an error if `bits` is too short to hold one.
+	fn unpack_from<O, T>(bits: &BitSlice<O, T>) -> Result<Self, UnpackError>
+	where
+		O: BitOrder,
+		T: BitStore;
+}
+
+/** The reasons that a value can fail to unpack from a bit slice.
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum UnpackError {
+	/// The source slice was shorter than the packed representation.
+	TooShort {
+		/// The number of bits the representation requires.
+		needed: usize,
+		/// The number of bits the source slice held.
+		available: usize,
+	},
+}
+
+impl Display for UnpackError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			UnpackError::TooShort { needed, available } => write!(
+				fmt,
+				"Packed value needs {} bits, but only {} are available",
+				needed, available,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnpackError {
+}
+
+/// Checks that a source slice can hold a packed value of `needed` bits.
+fn check_len<O, T>(bits: &BitSlice<O, T>, needed: usize) -> Result<(), UnpackError>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let available = bits.len();
+	if available < needed {
+		Err(UnpackError::TooShort { needed, available })
+	}
+	else {
+		Ok(())
+	}
+}
+
+impl BitPack for bool {
+	const BITS: usize = 1;
+
+	fn pack_into<O, T>(&self, bits: &mut BitSlice<O, T>)
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		bits.set(0, *self);
+	}
+}
+
+impl BitUnpack for bool {
+	const BITS: usize = 1;
+
+	fn unpack_from<O, T>(bits: &BitSlice<O, T>) -> Result<Self, UnpackError>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		check_len(bits, 1)?;
+		Ok(bits[0])
+	}
+}
+
+/// Implements the packing traits on integers, by way of an unsigned type of
+/// the same width.
+macro_rules! pack_int {
+	($($t:ty => $u:ty),* $(,)?) => { $(
+		impl BitPack for $t {
+			const BITS: usize = <$u>::max_value().count_ones() as usize;
+
+			fn pack_into<O, T>(&self, bits: &mut BitSlice<O, T>)
+			where
+				O: BitOrder,
+				T: BitStore,
+			{
+				let width = <Self as BitPack>::BITS;
+				assert!(
+					bits.len() >= width,
+					"Packing {} requires {} bits: {}",
+					stringify!($t),
+					width,
+					bits.len(),
+				);
+				let value = *self as $u;
+				for n in 0 .. width {
+					unsafe {
+						bits.set_unchecked(n, value >> (width - 1 - n) & 1 != 0);
+					}
+				}
+			}
+		}
+
+		impl BitUnpack for $t {
+			const BITS: usize = <$u>::max_value().count_ones() as usize;
+
+			fn unpack_from<O, T>(bits: &BitSlice<O, T>) -> Result<Self, UnpackError>
+			where
+				O: BitOrder,
+				T: BitStore,
+			{
+				let width = <Self as BitUnpack>::BITS;
+				check_len(bits, width)?;
+				let value = bits[.. width]
+					.iter()
+					.fold(0 as $u, |accum, &bit| accum << 1 | bit as $u);
+				Ok(value as $t)
+			}
+		}
+	)* };
+}
+
+pack_int![
+	u8 => u8,
+	u16 => u16,
+	u32 => u32,
+	u64 => u64,
+	u128 => u128,
+	usize => usize,
+	i8 => u8,
+	i16 => u16,
+	i32 => u32,
+	i64 => u64,
+	i128 => u128,
+	isize => usize,
+];
+
+/// Implements the packing traits on arrays of packable types.
+macro_rules! pack_array {
+	($($n:expr),* $(,)?) => { $(
+		impl<P> BitPack for [P; $n]
+		where P: BitPack {
+			const BITS: usize = P::BITS * $n;
+
+			fn pack_into<O, T>(&self, bits: &mut BitSlice<O, T>)
+			where
+				O: BitOrder,
+				T: BitStore,
+			{
+				assert!(
+					bits.len() >= <Self as BitPack>::BITS,
+					"Packing an array requires {} bits: {}",
+					<Self as BitPack>::BITS,
+					bits.len(),
+				);
+				for (elt, slot) in self.iter().zip(bits.chunks_mut(P::BITS)) {
+					elt.pack_into(slot);
+				}
+			}
+		}
+
+		impl<P> BitUnpack for [P; $n]
+		where P: BitUnpack + Copy + Default {
+			const BITS: usize = P::BITS * $n;
+
+			fn unpack_from<O, T>(bits: &BitSlice<O, T>) -> Result<Self, UnpackError>
+			where
+				O: BitOrder,
+				T: BitStore,
+			{
+				check_len(bits, <Self as BitUnpack>::BITS)?;
+				let fill = P::default();
+				let mut out = [fill; $n];
+				for (n, elt) in out.iter_mut().enumerate() {
+					*elt = P::unpack_from(&bits[n * P::BITS ..])?;
+				}
+				Ok(out)
+			}
+		}
+	)* };
+}
+
+pack_array![
+	0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+	21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+];
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::prelude::*;
+
+	#[test]
+	fn round_trip() {
+		let mut data = [0u8; 32];
+		let bits = &mut data.bits_mut::<Lsb0>()[3 ..];
+
+		0x1234_5678u32.pack_into(bits);
+		assert_eq!(u32::unpack_from(bits), Ok(0x1234_5678));
+		assert_eq!(u16::unpack_from(bits), Ok(0x1234));
+
+		(-2i16).pack_into(bits);
+		assert_eq!(i16::unpack_from(bits), Ok(-2));
+		assert_eq!(u16::unpack_from(bits), Ok(0xFFFE));
+
+		let arr = [[1u8, 2], [3, 4], [5, 6]];
+		arr.pack_into(bits);
+		assert_eq!(<[[u8; 2]; 3]>::unpack_from(bits), Ok(arr));
+		assert_eq!(u8::unpack_from(&bits[16 ..]), Ok(3));
+
+		(!0u128).pack_into(bits);
+		assert!(bits[.. 128].all());
+		assert!(bits[128 ..].not_any());
+	}
+
+	#[test]
+	fn too_short() {
+		let data = 0u16;
+		let bits = &data.bits::<Msb0>()[.. 12];
+		assert_eq!(
+			u16::unpack_from(bits),
+			Err(UnpackError::TooShort {
+				needed: 16,
+				available: 12,
+			}),
+		);
+		assert_eq!(<[bool; 12]>::unpack_from(bits), Ok([false; 12]));
+		assert!(<[bool; 13]>::unpack_from(bits).is_err());
+	}
+
+	#[test]
+	#[should_panic]
+	fn pack_too_short() {
+		let mut data = 0u8;
+		0u16.pack_into(data.bits_mut::<Msb0>());
+	}
+}
@@ -0,0 +1,590 @@
+/*! Persistent bit vectors
+
+A persistent data structure keeps every version of itself: changing it produces
+a new version, and the old one remains valid and unchanged. Programs that keep
+the history of a large bitmap, or pass snapshots of it between stages of a
+pipeline, can do so without copying the whole map for each version.
+
+[`BitVector`] is a persistent bit vector. It stores its bits in fixed-size
+chunks, at the leaves of a shallow tree with a branching factor of 32, and
+shares unchanged subtrees between versions. Cloning it is `O(1)`. Writing one
+bit copies only the chunk and the tree nodes above it, so updates and appends
+cost `O(log n)`, with a logarithm base of 32: a vector of a million bits is two
+levels deep.
+
+For bulk work, such as the set operations, convert a version into a `BitVec`
+with [`to_bitvec`], or read it a chunk at a time with [`chunks`].
+
+[`BitVector`]: struct.BitVector.html
+[`chunks`]: struct.BitVector.html#method.chunks
+[`to_bitvec`]: struct.BitVector.html#method.to_bitvec
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::{
+	borrow::ToOwned,
+	sync::Arc,
+	vec::Vec,
+};
+
+use core::{
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
+	iter::FromIterator,
+	mem,
+	slice,
+};
+
+/// The number of bits in each chunk of the tree.
+const CHUNK: usize = 1024;
+
+/// The base-2 logarithm of the branching factor of the tree.
+const SHIFT: u32 = 5;
+
+/// The mask of a child index within one level of the tree.
+const MASK: usize = (1 << SHIFT) - 1;
+
+/** A persistent bit vector, which shares structure between its versions.
+
+Clones are cheap, and share all of their storage. Modifying a vector copies the
+parts of the tree that the modification touches, and that another version still
+shares; everything else stays shared.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within each storage element of a chunk.
+- `T: BitStore`: The storage element type of the chunks.
+
+# Examples
+
+```rust
+use bitvec::{persistent::BitVector, prelude::*};
+
+let v1: BitVector<Msb0, u8> = bits![Msb0, u8; 0; 5000].into();
+let v2 = v1.update(4000, true);
+let mut v3 = v2.clone();
+v3.push(true);
+
+assert_eq!(v1.count_ones(), 0);
+assert_eq!(v2.get(4000), Some(true));
+assert_eq!(v3.len(), 5001);
+assert_eq!(v3.to_bitvec().count_ones(), 2);
+```
+**/
+pub struct BitVector<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The number of live bits.
+	len: usize,
+	/// The level of the root. Its children are chunks when this is zero, and
+	/// each level above that multiplies its reach by the branching factor.
+	shift: u32,
+	/// The full chunks, in order, filling the tree from the left.
+	root: Arc<Node<O, T>>,
+	/// The bits after the last full chunk. This holds between one and `CHUNK`
+	/// bits, unless the vector is empty.
+	tail: Arc<BitVec<O, T>>,
+}
+
+/// A node of the tree.
+enum Node<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// An interior node, with up to 32 children.
+	Branch(Vec<Arc<Node<O, T>>>),
+	/// A full chunk.
+	Chunk(BitVec<O, T>),
+}
+
+impl<O, T> BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Constructs an empty vector.
+	pub fn new() -> Self {
+		Self {
+			len: 0,
+			shift: 0,
+			root: Arc::new(Node::Branch(Vec::new())),
+			tail: Arc::new(BitVec::new()),
+		}
+	}
+
+	/// Returns the number of bits in the vector.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Tests if the vector is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Reads a bit.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `index`: The index of the bit to read.
+	///
+	/// # Returns
+	///
+	/// The bit at `index`, or `None` if `index` is out of bounds.
+	pub fn get(&self, index: usize) -> Option<bool> {
+		if index >= self.len {
+			return None;
+		}
+		let offset = self.tail_offset();
+		if index >= offset {
+			return Some(self.tail[index - offset]);
+		}
+		let mut node = &*self.root;
+		let mut shift = self.shift;
+		let chunk = index / CHUNK;
+		loop {
+			match node {
+				Node::Branch(children) => {
+					node = &children[(chunk >> shift) & MASK];
+					shift = shift.saturating_sub(SHIFT);
+				},
+				Node::Chunk(bits) => return Some(bits[index % CHUNK]),
+			}
+		}
+	}
+
+	/// Writes a bit.
+	///
+	/// This copies the chunk holding the bit, and the path to it, if another
+	/// version shares them.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The index of the bit to write.
+	/// - `value`: The new value of the bit.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	pub fn set(&mut self, index: usize, value: bool) {
+		assert!(
+			index < self.len,
+			"Index {} out of bounds: {}",
+			index,
+			self.len,
+		);
+		let offset = self.tail_offset();
+		if index >= offset {
+			Arc::make_mut(&mut self.tail).set(index - offset, value);
+			return;
+		}
+		let chunk = index / CHUNK;
+		let mut node = &mut self.root;
+		let mut shift = self.shift;
+		loop {
+			match Arc::make_mut(node) {
+				Node::Branch(children) => {
+					node = &mut children[(chunk >> shift) & MASK];
+					shift = shift.saturating_sub(SHIFT);
+				},
+				Node::Chunk(bits) => {
+					bits.set(index % CHUNK, value);
+					return;
+				},
+			}
+		}
+	}
+
+	/// Produces a new version of the vector with one bit written.
+	///
+	/// This is [`set`] applied to a clone of `self`, which is unchanged.
+	///
+	/// [`set`]: #method.set
+	pub fn update(&self, index: usize, value: bool) -> Self {
+		let mut out = self.clone();
+		out.set(index, value);
+		out
+	}
+
+	/// Appends a bit to the back of the vector.
+	pub fn push(&mut self, value: bool) {
+		if self.tail.len() == CHUNK {
+			self.push_tail();
+		}
+		Arc::make_mut(&mut self.tail).push(value);
+		self.len += 1;
+	}
+
+	/// Removes the last bit of the vector, and returns it.
+	pub fn pop(&mut self) -> Option<bool> {
+		let out = Arc::make_mut(&mut self.tail).pop()?;
+		self.len -= 1;
+		//  An empty tail is refilled from the last chunk of the tree.
+		if self.tail.is_empty() && self.len > 0 {
+			self.tail = Arc::new(Self::pop_chunk(&mut self.root, self.shift));
+			let only = match &*self.root {
+				Node::Branch(children) if children.len() == 1 => {
+					Some(children[0].clone())
+				},
+				_ => None,
+			};
+			if let (Some(root), true) = (only, self.shift > 0) {
+				self.root = root;
+				self.shift -= SHIFT;
+			}
+		}
+		Some(out)
+	}
+
+	/// Iterates over the stored bits, one chunk at a time.
+	///
+	/// # Returns
+	///
+	/// An iterator of bit slices, which together hold the vector in order.
+	/// Every slice but the last holds the same number of bits.
+	pub fn chunks(&self) -> Chunks<'_, O, T> {
+		let mut stack = Vec::with_capacity(8);
+		stack.push(slice::from_ref(&self.root).iter());
+		Chunks {
+			stack,
+			tail: Some(self.tail.as_bitslice()),
+		}
+	}
+
+	/// Iterates over the bits of the vector.
+	pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+		self.chunks().flat_map(|chunk| chunk.iter().copied())
+	}
+
+	/// Counts the set bits in the vector.
+	pub fn count_ones(&self) -> usize {
+		self.chunks().map(BitSlice::count_ones).sum()
+	}
+
+	/// Copies the vector into a `BitVec`.
+	pub fn to_bitvec(&self) -> BitVec<O, T> {
+		let mut out = BitVec::with_capacity(self.len);
+		for chunk in self.chunks() {
+			out.extend_from_slice(chunk);
+		}
+		out
+	}
+
+	/// Counts the bits held in the tree, rather than the tail.
+	fn tail_offset(&self) -> usize {
+		self.len - self.tail.len()
+	}
+
+	/// Moves a full tail into the tree, after every chunk already in it, and
+	/// leaves the tail empty.
+	fn push_tail(&mut self) {
+		let chunk = self.tail_offset() / CHUNK;
+		let tail = mem::replace(&mut self.tail, Arc::new(BitVec::new()));
+		let bits = Arc::try_unwrap(tail).unwrap_or_else(|tail| (*tail).clone());
+		let leaf = Arc::new(Node::Chunk(bits));
+		//  A full root gains a sibling, and both move under a new root.
+		if chunk == 1 << (self.shift + SHIFT) {
+			let path = Self::new_path(self.shift, leaf);
+			let children = alloc::vec![self.root.clone(), path];
+			self.root = Arc::new(Node::Branch(children));
+			self.shift += SHIFT;
+			return;
+		}
+		let mut node = &mut self.root;
+		let mut shift = self.shift;
+		loop {
+			let children = Self::branch(node);
+			let idx = (chunk >> shift) & MASK;
+			if shift == 0 {
+				children.push(leaf);
+				return;
+			}
+			if idx == children.len() {
+				children.push(Self::new_path(shift - SHIFT, leaf));
+				return;
+			}
+			node = &mut children[idx];
+			shift -= SHIFT;
+		}
+	}
+
+	/// Removes the last chunk from a subtree, and any nodes left empty.
+	fn pop_chunk(node: &mut Arc<Node<O, T>>, shift: u32) -> BitVec<O, T> {
+		let children = Self::branch(node);
+		if shift == 0 {
+			let leaf = children.pop().expect("Tree branches are never empty");
+			return match Arc::try_unwrap(leaf) {
+				Ok(Node::Chunk(bits)) => bits,
+				Ok(Node::Branch(_)) => unreachable!("Chunks are leaves"),
+				Err(shared) => match &*shared {
+					Node::Chunk(bits) => bits.clone(),
+					Node::Branch(_) => unreachable!("Chunks are leaves"),
+				},
+			};
+		}
+		let last = children.last_mut().expect("Tree branches are never empty");
+		let out = Self::pop_chunk(last, shift - SHIFT);
+		if let Node::Branch(grand) = &**last {
+			if grand.is_empty() {
+				children.pop();
+			}
+		}
+		out
+	}
+
+	/// Builds a chain of single-child branches from level `shift` down to a
+	/// chunk.
+	fn new_path(shift: u32, leaf: Arc<Node<O, T>>) -> Arc<Node<O, T>> {
+		let mut out = leaf;
+		let mut level = 0;
+		loop {
+			out = Arc::new(Node::Branch(alloc::vec![out]));
+			if level == shift {
+				return out;
+			}
+			level += SHIFT;
+		}
+	}
+
+	/// Gets unique access to the children of a branch, copying it if shared.
+	fn branch(node: &mut Arc<Node<O, T>>) -> &mut Vec<Arc<Node<O, T>>> {
+		match Arc::make_mut(node) {
+			Node::Branch(children) => children,
+			Node::Chunk(_) => unreachable!("Chunks are leaves"),
+		}
+	}
+}
+
+/** An iterator over the chunks of a [`BitVector`].
+
+This is produced by [`BitVector::chunks`].
+
+[`BitVector`]: struct.BitVector.html
+[`BitVector::chunks`]: struct.BitVector.html#method.chunks
+**/
+pub struct Chunks<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The unvisited children of each branch on the path to the next chunk.
+	stack: Vec<slice::Iter<'a, Arc<Node<O, T>>>>,
+	/// The tail, which follows every chunk in the tree.
+	tail: Option<&'a BitSlice<O, T>>,
+}
+
+impl<'a, O, T> Iterator for Chunks<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = &'a BitSlice<O, T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		loop {
+			let iter = match self.stack.last_mut() {
+				Some(iter) => iter,
+				None => return self.tail.take().filter(|tail| !tail.is_empty()),
+			};
+			match iter.next().map(|node| &**node) {
+				Some(Node::Branch(children)) => self.stack.push(children.iter()),
+				Some(Node::Chunk(bits)) => return Some(bits.as_bitslice()),
+				None => {
+					self.stack.pop();
+				},
+			}
+		}
+	}
+}
+
+impl<O, T> Clone for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn clone(&self) -> Self {
+		Self {
+			len: self.len,
+			shift: self.shift,
+			root: self.root.clone(),
+			tail: self.tail.clone(),
+		}
+	}
+}
+
+impl<O, T> Clone for Node<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn clone(&self) -> Self {
+		match self {
+			Node::Branch(children) => Node::Branch(children.clone()),
+			Node::Chunk(bits) => Node::Chunk(bits.clone()),
+		}
+	}
+}
+
+impl<O, T> Debug for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("BitVector<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str(", ")?;
+		fmt.write_str(T::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.to_bitvec().as_bitslice(), fmt)
+	}
+}
+
+impl<O, T> Default for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<O, T> Eq for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+impl<O, T> Extend<bool> for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn extend<I>(&mut self, src: I)
+	where I: IntoIterator<Item = bool> {
+		for bit in src {
+			self.push(bit);
+		}
+	}
+}
+
+impl<O, T> From<&BitSlice<O, T>> for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from(src: &BitSlice<O, T>) -> Self {
+		let mut out = Self::new();
+		for chunk in src.chunks(CHUNK) {
+			if out.tail.len() == CHUNK {
+				out.push_tail();
+			}
+			out.tail = Arc::new(chunk.to_owned());
+			out.len += chunk.len();
+		}
+		out
+	}
+}
+
+impl<O, T> From<&BitVector<O, T>> for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from(src: &BitVector<O, T>) -> Self {
+		src.to_bitvec()
+	}
+}
+
+impl<O, T> FromIterator<bool> for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from_iter<I>(src: I) -> Self
+	where I: IntoIterator<Item = bool> {
+		let mut out = Self::new();
+		out.extend(src);
+		out
+	}
+}
+
+impl<O, T> PartialEq for BitVector<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.len == other.len && self.iter().eq(other.iter())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::Msb0;
+
+	#[test]
+	fn versions() {
+		//  Enough bits for a tree three levels deep.
+		let len = CHUNK * 32 * 2 + 700;
+		let mut seed = 0x9E37_79B9u32;
+		let mut bits = || {
+			seed ^= seed << 13;
+			seed ^= seed >> 17;
+			seed ^= seed << 5;
+			seed & 1 == 1
+		};
+		let reference: BitVec<Msb0, u16> = (0 .. len).map(|_| bits()).collect();
+
+		let pushed = reference.iter().copied().collect::<BitVector<_, _>>();
+		let built = BitVector::from(reference.as_bitslice());
+		assert_eq!(pushed.shift, SHIFT);
+		assert_eq!(pushed, built);
+		assert_eq!(pushed.to_bitvec(), reference);
+		assert_eq!(pushed.count_ones(), reference.count_ones());
+
+		//  Updates leave the old version intact.
+		let mut changed = pushed.clone();
+		for &idx in &[0, 1023, 1024, 40_000, len - 1] {
+			changed.set(idx, !reference[idx]);
+			assert_eq!(pushed.get(idx), Some(reference[idx]));
+			assert_eq!(changed.get(idx), Some(!reference[idx]));
+		}
+		assert_eq!(pushed.get(len), None);
+
+		//  Popping back down through each level of the tree.
+		let mut popped = pushed.clone();
+		for idx in (0 .. len).rev() {
+			assert_eq!(popped.pop(), Some(reference[idx]));
+			if idx % 9999 == 0 {
+				assert_eq!(popped.to_bitvec(), reference[.. idx]);
+			}
+		}
+		assert_eq!(popped.pop(), None);
+		assert!(popped.chunks().next().is_none());
+		assert_eq!(pushed.to_bitvec(), reference);
+	}
+}
@@ -0,0 +1,607 @@
+/*! Bit-plane slicing of element buffers
+
+A bit plane of a buffer is the sequence of the bits at one significance in each
+of its elements: plane 0 holds the lowest bit of every element, and plane 7 of
+a byte buffer holds the highest. Image and texture codecs compress each plane of
+a picture separately, since the high planes vary slowly and compress well, and
+text processing can read the case of ASCII letters from plane 5 of a string.
+
+Gathering a plane one element at a time, and writing each bit into a `BitVec`
+as it is found, costs a bounds-checked bit write for every element.
+[`extract_bit_plane`] instead assembles each storage element of its output in a
+register, and writes it once. [`deposit_bit_plane`] is its inverse, and writes a
+plane back into a buffer without disturbing its other planes.
+
+Bit-sliced indices, which answer range queries over a column by scanning its
+planes, need every plane of the column. [`bit_planes`] produces all of them in
+one pass, by transposing square blocks of bits a word at a time, and
+[`from_bit_planes`] reverses it. [`compare_planes`] and [`compare_geq_planes`]
+then compare every value of such a column against a constant, producing the
+bitmap of matching rows, a plane element at a time.
+
+Planes are numbered by numeric significance, so plane `n` of an element `x` is
+`(x >> n) & 1`, whatever `BitOrder` the output uses.
+
+[`bit_planes`]: fn.bit_planes.html
+[`compare_geq_planes`]: fn.compare_geq_planes.html
+[`compare_planes`]: fn.compare_planes.html
+[`deposit_bit_plane`]: fn.deposit_bit_plane.html
+[`extract_bit_plane`]: fn.extract_bit_plane.html
+[`from_bit_planes`]: fn.from_bit_planes.html
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	indices::Indexable,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::{
+	borrow::Cow,
+	vec::Vec,
+};
+
+use core::cmp::{
+	self,
+	Ordering,
+};
+
+/// Gathers one bit plane of a buffer into a bit vector.
+///
+/// # Parameters
+///
+/// - `data`: The buffer whose plane is taken. Strings can be sliced by way of
+///   `str::as_bytes`.
+/// - `plane`: The significance of the bit to take from each element.
+///
+/// # Returns
+///
+/// A bit vector whose bit `i` is bit `plane` of `data[i]`.
+///
+/// # Panics
+///
+/// This panics if `plane` is not less than the bit width of `U`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{plane, prelude::*};
+///
+/// //  Plane 5 of an ASCII letter is set when it is lowercase.
+/// let text = "BitVec";
+/// let lower = plane::extract_bit_plane::<Msb0, u8, _>(text.as_bytes(), 5);
+/// assert_eq!(lower, bits![0, 1, 1, 0, 1, 1]);
+/// ```
+pub fn extract_bit_plane<O, T, U>(data: &[U], plane: u8) -> BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+	U: BitStore,
+{
+	check_plane::<U>(plane);
+	let one = U::from(1);
+	let elts = data
+		.chunks(T::BITS as usize)
+		.map(|chunk| {
+			chunk.iter().enumerate().fold(T::FALSE, |elt, (idx, &val)| {
+				if val >> plane & one == U::FALSE {
+					elt
+				}
+				else {
+					elt | *O::mask::<T>((idx as u8).idx())
+				}
+			})
+		})
+		.collect::<Vec<_>>();
+	let mut out = BitVec::from_vec(elts);
+	out.truncate(data.len());
+	out
+}
+
+/// Scatters a bit vector into one bit plane of a buffer.
+///
+/// # Parameters
+///
+/// - `data`: The buffer whose plane is overwritten. Its other planes are not
+///   modified.
+/// - `plane`: The significance of the bit to write in each element.
+/// - `bits`: The bits to write. Bit `i` is written into `data[i]`.
+///
+/// # Panics
+///
+/// This panics if `plane` is not less than the bit width of `U`, or if `bits`
+/// and `data` have different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{plane, prelude::*};
+///
+/// let mut text = *b"BitVec";
+/// let lower = bits![0; 6];
+/// plane::deposit_bit_plane(&mut text, 5, lower);
+/// assert_eq!(&text, b"BITVEC");
+/// ```
+pub fn deposit_bit_plane<O, T, U>(
+	data: &mut [U],
+	plane: u8,
+	bits: &BitSlice<O, T>,
+) where
+	O: BitOrder,
+	T: BitStore,
+	U: BitStore,
+{
+	check_plane::<U>(plane);
+	assert_eq!(
+		data.len(),
+		bits.len(),
+		"Bit planes require equal lengths",
+	);
+	let mask = U::from(1) << plane;
+	for (val, &bit) in data.iter_mut().zip(bits) {
+		if bit {
+			*val |= mask;
+		}
+		else {
+			*val &= !mask;
+		}
+	}
+}
+
+/// Gathers every bit plane of a buffer, in one pass over it.
+///
+/// Each run of `U::BITS` elements is a square matrix of bits, whose columns
+/// are the storage elements of the planes. When `O` is `Lsb0` or `Msb0`, the
+/// matrix is transposed in registers, in `log2(U::BITS)` rounds of masked
+/// swaps, rather than by moving each bit on its own. Other orderings gather
+/// each plane in turn, as [`extract_bit_plane`] does.
+///
+/// # Returns
+///
+/// A vector of `U::BITS` bit vectors, whose element `n` is plane `n` of
+/// `src`.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{plane, prelude::*};
+///
+/// let src = [3u16, 5, 0x8000];
+/// let planes = plane::bit_planes::<Msb0, _>(&src);
+/// assert_eq!(planes.len(), 16);
+/// assert_eq!(planes[0], bits![1, 1, 0]);
+/// assert_eq!(planes[1], bits![1, 0, 0]);
+/// assert_eq!(planes[2], bits![0, 1, 0]);
+/// assert_eq!(planes[15], bits![0, 0, 1]);
+/// assert_eq!(plane::from_bit_planes(&planes), src);
+/// ```
+///
+/// [`extract_bit_plane`]: fn.extract_bit_plane.html
+pub fn bit_planes<O, U>(src: &[U]) -> Vec<BitVec<O, U>>
+where
+	O: BitOrder,
+	U: BitStore,
+{
+	let lsb0 = match BitSlice::<O, U>::linear_order() {
+		Some(lsb0) => lsb0,
+		None => {
+			return (0 .. U::BITS).map(|n| extract_bit_plane(src, n)).collect();
+		},
+	};
+	let width = U::BITS as usize;
+	let blocks = src.len() / width + cmp::min(src.len() % width, 1);
+	let mut planes = (0 .. width)
+		.map(|_| Vec::with_capacity(blocks))
+		.collect::<Vec<Vec<U>>>();
+	let mut block = alloc::vec![U::FALSE; width];
+	for chunk in src.chunks(width) {
+		for row in block.iter_mut() {
+			*row = U::FALSE;
+		}
+		for (idx, &val) in chunk.iter().enumerate() {
+			block[row_of(lsb0, width, idx)] = val;
+		}
+		transpose(&mut block);
+		for (n, plane) in planes.iter_mut().enumerate() {
+			plane.push(block[width - 1 - n]);
+		}
+	}
+	planes
+		.into_iter()
+		.map(|elts| {
+			let mut plane = BitVec::from_vec(elts);
+			plane.truncate(src.len());
+			plane
+		})
+		.collect()
+}
+
+/// Reassembles a buffer from all of its bit planes.
+///
+/// This is the inverse of [`bit_planes`], and uses the same transposition.
+///
+/// # Parameters
+///
+/// - `planes`: The bit planes of the buffer, from the least significant.
+///
+/// # Returns
+///
+/// A buffer whose element `i` has bit `n` equal to bit `i` of `planes[n]`.
+///
+/// # Panics
+///
+/// This panics if there are not exactly `U::BITS` planes, or if the planes
+/// have different lengths.
+///
+/// [`bit_planes`]: fn.bit_planes.html
+pub fn from_bit_planes<O, U>(planes: &[BitVec<O, U>]) -> Vec<U>
+where
+	O: BitOrder,
+	U: BitStore,
+{
+	let width = U::BITS as usize;
+	let len = check_planes(planes);
+	let lsb0 = match BitSlice::<O, U>::linear_order() {
+		Some(lsb0) => lsb0,
+		None => {
+			let mut out = alloc::vec![U::FALSE; len];
+			for (n, plane) in planes.iter().enumerate() {
+				deposit_bit_plane(&mut out, n as u8, plane);
+			}
+			return out;
+		},
+	};
+	let words = plane_words(planes);
+	let mut out = Vec::with_capacity(len);
+	let mut block = alloc::vec![U::FALSE; width];
+	for start in (0 .. len).step_by(width) {
+		let elt = start / width;
+		for (n, words) in words.iter().enumerate() {
+			block[width - 1 - n] = words[elt];
+		}
+		transpose(&mut block);
+		let count = cmp::min(width, len - start);
+		out.extend((0 .. count).map(|idx| block[row_of(lsb0, width, idx)]));
+	}
+	out
+}
+
+/// Selects the elements of a bit-sliced column that are at least a constant.
+///
+/// This is `compare_planes(planes, value, Ordering::Less)`, inverted.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{plane, prelude::*};
+///
+/// let column = [12u8, 200, 7, 99, 100, 0];
+/// let planes = plane::bit_planes::<Lsb0, _>(&column);
+/// let hits = plane::compare_geq_planes(&planes, 99);
+/// assert_eq!(hits, bits![0, 1, 0, 1, 1, 0]);
+/// ```
+pub fn compare_geq_planes<O, U>(
+	planes: &[BitVec<O, U>],
+	value: U,
+) -> BitVec<O, U>
+where
+	O: BitOrder,
+	U: BitStore,
+{
+	compare_with(planes, value, |lt, _, _| !lt)
+}
+
+/// Compares each element of a bit-sliced column against a constant.
+///
+/// The column is held as the bit planes that [`bit_planes`] produces. Each
+/// element of the planes holds one bit of many column values, so the
+/// comparison proceeds a whole element at a time, from the most significant
+/// plane down, tracking which values are already known to be less or greater
+/// than `value`. It stops early for each element once every value in it has
+/// been decided.
+///
+/// # Parameters
+///
+/// - `planes`: The bit planes of the column, from the least significant.
+/// - `value`: The constant to compare against. Values are compared as
+///   unsigned integers.
+/// - `order`: The outcome to select.
+///
+/// # Returns
+///
+/// A bit vector whose bit `i` is set when value `i` of the column compares
+/// to `value` as `order`.
+///
+/// # Panics
+///
+/// This panics if there are not exactly `U::BITS` planes, or if the planes
+/// have different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{plane, prelude::*};
+/// use core::cmp::Ordering;
+///
+/// let column = [5u16, 9, 5, 0, 300];
+/// let planes = plane::bit_planes::<Msb0, _>(&column);
+/// let less = plane::compare_planes(&planes, 5, Ordering::Less);
+/// let equal = plane::compare_planes(&planes, 5, Ordering::Equal);
+/// let greater = plane::compare_planes(&planes, 5, Ordering::Greater);
+/// assert_eq!(less, bits![0, 0, 0, 1, 0]);
+/// assert_eq!(equal, bits![1, 0, 1, 0, 0]);
+/// assert_eq!(greater, bits![0, 1, 0, 0, 1]);
+/// ```
+///
+/// [`bit_planes`]: fn.bit_planes.html
+pub fn compare_planes<O, U>(
+	planes: &[BitVec<O, U>],
+	value: U,
+	order: Ordering,
+) -> BitVec<O, U>
+where
+	O: BitOrder,
+	U: BitStore,
+{
+	match order {
+		Ordering::Less => compare_with(planes, value, |lt, _, _| lt),
+		Ordering::Equal => compare_with(planes, value, |_, eq, _| eq),
+		Ordering::Greater => compare_with(planes, value, |_, _, gt| gt),
+	}
+}
+
+/// Checks that a plane index is within the elements of a buffer.
+fn check_plane<U>(plane: u8)
+where U: BitStore {
+	assert!(
+		plane < U::BITS,
+		"Bit plane {} out of bounds: {}",
+		plane,
+		U::BITS,
+	);
+}
+
+/// Checks that a set of bit planes describes a buffer of `U`, and returns its
+/// length.
+fn check_planes<O, U>(planes: &[BitVec<O, U>]) -> usize
+where
+	O: BitOrder,
+	U: BitStore,
+{
+	assert_eq!(
+		planes.len(),
+		U::BITS as usize,
+		"A {} buffer has {} bit planes",
+		U::TYPENAME,
+		U::BITS,
+	);
+	let len = planes[0].len();
+	assert!(
+		planes.iter().all(|plane| plane.len() == len),
+		"Bit planes require equal lengths",
+	);
+	len
+}
+
+/// Views the storage elements of each bit plane.
+///
+/// The plane kernels read whole elements, so each plane must begin at the
+/// front of its first element. Planes that do not are copied and aligned.
+fn plane_words<O, U>(planes: &[BitVec<O, U>]) -> Vec<Cow<'_, [U]>>
+where
+	O: BitOrder,
+	U: BitStore,
+{
+	planes
+		.iter()
+		.map(|plane| {
+			if *plane.bitptr().head() == 0 {
+				Cow::Borrowed(plane.as_slice())
+			}
+			else {
+				let mut plane = plane.clone();
+				plane.force_align();
+				Cow::Owned(plane.into_vec())
+			}
+		})
+		.collect()
+}
+
+/// Runs the bit-sliced comparison of a column against a constant.
+///
+/// For each element of the planes, this computes the masks of the values
+/// that are less than, equal to, and greater than `value`, and collects the
+/// element that `select` makes of them.
+fn compare_with<O, U, F>(
+	planes: &[BitVec<O, U>],
+	value: U,
+	select: F,
+) -> BitVec<O, U>
+where
+	O: BitOrder,
+	U: BitStore,
+	F: Fn(U, U, U) -> U,
+{
+	let len = check_planes(planes);
+	let words = plane_words(planes);
+	let elts = (0 .. words[0].len())
+		.map(|elt| {
+			let (mut lt, mut eq, mut gt) = (U::FALSE, U::TRUE, U::FALSE);
+			for (n, words) in words.iter().enumerate().rev() {
+				let bits = words[elt];
+				if value >> n as u8 & U::from(1) == U::FALSE {
+					gt |= eq & bits;
+					eq &= !bits;
+				}
+				else {
+					lt |= eq & !bits;
+					eq &= bits;
+				}
+				if eq == U::FALSE {
+					break;
+				}
+			}
+			select(lt, eq, gt)
+		})
+		.collect::<Vec<_>>();
+	let mut out = BitVec::from_vec(elts);
+	out.truncate(len);
+	out
+}
+
+/// Finds the row of a transposition block that holds the element at `idx`.
+///
+/// The transposition places the first row of the block in the most
+/// significant bit of each plane element, which is bit 0 of an `Msb0`
+/// element. `Lsb0` planes begin at the least significant bit, and so fill the
+/// block from its last row.
+fn row_of(lsb0: bool, width: usize, idx: usize) -> usize {
+	if lsb0 { width - 1 - idx } else { idx }
+}
+
+/// Transposes a square matrix of bits in place.
+///
+/// Row `r` of the matrix is `block[r]`, and column `c` is bit `U::MASK - c`
+/// of each row. The matrix is divided into quadrants, and the off-diagonal
+/// quadrants of each are swapped at every scale from half the width down to
+/// single bits.
+fn transpose<U>(block: &mut [U])
+where U: BitStore {
+	let width = block.len();
+	let mut step = width / 2;
+	let mut mask = !U::FALSE >> step as u8;
+	while step != 0 {
+		let mut row = 0;
+		while row < width {
+			let swap = xor(block[row], block[row + step] >> step as u8) & mask;
+			block[row] = xor(block[row], swap);
+			block[row + step] = xor(block[row + step], swap << step as u8);
+			row = (row + step + 1) & !step;
+		}
+		step /= 2;
+		mask = xor(mask, mask << step as u8);
+	}
+}
+
+/// Computes the exclusive-or of two elements.
+fn xor<U>(a: U, b: U) -> U
+where U: BitStore {
+	(a | b) & !(a & b)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::{
+		Lsb0,
+		Msb0,
+	};
+
+	#[test]
+	fn round_trip() {
+		let mut state = 0x2545_F491u32;
+		let mut data = [0u16; 75];
+		for elt in data.iter_mut() {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			*elt = state as u16;
+		}
+		for plane in 0 .. 16 {
+			let bits = extract_bit_plane::<Msb0, u32, _>(&data, plane);
+			assert_eq!(bits.len(), data.len());
+			for (bit, &val) in bits.iter().zip(&data) {
+				assert_eq!(*bit, val >> plane & 1 == 1);
+			}
+
+			let mut copy = data;
+			let flipped = !extract_bit_plane::<Lsb0, u8, _>(&data, plane);
+			deposit_bit_plane(&mut copy, plane, &flipped);
+			for (&new, &old) in copy.iter().zip(&data) {
+				assert_eq!(new, old ^ 1 << plane);
+			}
+		}
+		assert!(extract_bit_plane::<Lsb0, u8, u8>(&[], 3).is_empty());
+	}
+
+	#[test]
+	#[cfg(target_pointer_width = "64")]
+	fn decomposition() {
+		let mut state = 0x9E37_79B9_7F4A_7C15u64;
+		let mut data = [0u64; 150];
+		for elt in data.iter_mut() {
+			state ^= state << 13;
+			state ^= state >> 7;
+			state ^= state << 17;
+			*elt = state;
+		}
+		for &len in &[0, 1, 64, 150] {
+			let src = &data[.. len];
+			let planes = bit_planes::<Lsb0, u64>(src);
+			for (n, plane) in planes.iter().enumerate() {
+				let one = extract_bit_plane::<Lsb0, u64, _>(src, n as u8);
+				assert_eq!(*plane, one);
+			}
+			assert_eq!(from_bit_planes(&planes), src);
+
+			let narrow = src.iter().map(|&val| val as u8).collect::<Vec<_>>();
+			let planes = bit_planes::<Msb0, u8>(&narrow);
+			for (n, plane) in planes.iter().enumerate() {
+				let one = extract_bit_plane::<Msb0, u8, _>(&narrow, n as u8);
+				assert_eq!(*plane, one);
+			}
+			assert_eq!(from_bit_planes(&planes), narrow);
+		}
+
+		//  Planes that do not begin at the front of an element.
+		let src = data[.. 40].iter().map(|&val| val as u16).collect::<Vec<_>>();
+		let planes = bit_planes::<Lsb0, u16>(&src)
+			.iter()
+			.map(|plane| {
+				let mut wide = BitVec::<Lsb0, u16>::repeat(false, 5);
+				wide.extend(plane.iter().copied());
+				BitVec::from_bitslice(&wide[5 ..])
+			})
+			.collect::<Vec<_>>();
+		assert_ne!(*planes[0].bitptr().head(), 0);
+		assert_eq!(from_bit_planes(&planes), src);
+	}
+
+	#[test]
+	fn comparison() {
+		let mut state = 0x2545_F491u32;
+		let column = (0 .. 300)
+			.map(|_| {
+				state ^= state << 13;
+				state ^= state >> 17;
+				state ^= state << 5;
+				//  Narrow the values so that the constants have equal matches.
+				(state % 1000) as u16
+			})
+			.collect::<Vec<_>>();
+		let planes = bit_planes::<Lsb0, u16>(&column);
+		for &value in &[0, 1, 499, column[17], 999, !0] {
+			for &order in &[Ordering::Less, Ordering::Equal, Ordering::Greater] {
+				let hits = compare_planes(&planes, value, order);
+				assert_eq!(hits.len(), column.len());
+				for (hit, val) in hits.iter().zip(&column) {
+					assert_eq!(*hit, val.cmp(&value) == order);
+				}
+			}
+			let geq = compare_geq_planes(&planes, value);
+			for (hit, val) in geq.iter().zip(&column) {
+				assert_eq!(*hit, *val >= value);
+			}
+		}
+	}
+
+	#[test]
+	#[should_panic(expected = "Bit plane 8 out of bounds")]
+	fn plane_bounds() {
+		extract_bit_plane::<Lsb0, u8, u8>(&[0], 8);
+	}
+}
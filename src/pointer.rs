@@ -35,6 +35,14 @@ use crate::indices::BitTail;
 /// Width in bits of a pointer on the target machine.
 const PTR_BITS: usize = size_of::<*const u8>() * 8;
 
+/// Whether the `_unchecked` accessors audit their arguments. The
+/// `debug-unchecked` feature audits them in every build, and the
+/// `unchecked-release` feature only in builds with debug assertions.
+pub(crate) const AUDIT: bool = cfg!(any(
+	feature = "debug-unchecked",
+	all(feature = "unchecked-release", debug_assertions),
+));
+
 /** Union to permit reinterpreting a pointer-shaped value as a read pointer,
 write pointer, or bare numeric address.
 
@@ -338,11 +346,22 @@ where T: BitStore
 	pub fn empty() -> Self {
 		Self {
 			_ty: PhantomData,
-			ptr: NonNull::dangling(),
+			ptr: Self::dangling(),
 			len: 0,
 		}
 	}
 
+	/// Produces a dangling pointer that is well aligned for `T`.
+	///
+	/// `NonNull::<u8>::dangling()` has its low bits set, and so decodes to a
+	/// null data pointer once the head bits are masked away. Element slices
+	/// of empty handles are built from the decoded pointer, and must not be
+	/// null even when they have no elements.
+	#[inline]
+	fn dangling() -> NonNull<u8> {
+		NonNull::<T>::dangling().cast::<u8>()
+	}
+
 	/// Produces an uninhabited slice from a bare pointer.
 	///
 	/// # Parameters
@@ -758,7 +777,7 @@ where T: BitStore
 		let src = unsafe { &*(bs as *const BitSlice<O, T> as *const [()]) };
 		let ptr = Pointer::from(src.as_ptr() as *const u8);
 		let (ptr, len) = match (ptr.w(), src.len()) {
-			(_, 0) => (NonNull::dangling(), 0),
+			(_, 0) => (Self::dangling(), 0),
 			(p, _) if p.is_null() => unreachable!("Rust forbids null refs"),
 			(p, l) => (unsafe { NonNull::new_unchecked(p) }, l),
 		};
@@ -820,6 +839,147 @@ where T: BitStore
 	where O: BitOrder {
 		unsafe { &*ptr }.bitptr()
 	}
+
+	/// Audits an index passed to an unchecked accessor.
+	///
+	/// When `AUDIT` is set, this halts the program
+	/// with a description of the handle if `index` is not less than
+	/// `self.len()`. Otherwise, it compiles to nothing.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `index`: The index the accessor is about to use.
+	/// - `op`: The name of the accessor, for the diagnostic.
+	#[inline(always)]
+	pub(crate) fn audit_index(&self, index: usize, op: &str) {
+		self.audit_handle(op);
+		if AUDIT && index >= self.len() {
+			self.audit_failed(
+				op,
+				format_args!("index {} is out of bounds: {}", index, self.len()),
+			);
+		}
+	}
+
+	/// Audits a range passed to an unchecked accessor.
+	///
+	/// When `AUDIT` is set, this halts the program
+	/// with a description of the handle unless `start <= end <= self.len()`.
+	/// Otherwise, it compiles to nothing.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `start`: The start of the range the accessor is about to use.
+	/// - `end`: The end of the range the accessor is about to use.
+	/// - `op`: The name of the accessor, for the diagnostic.
+	#[inline(always)]
+	pub(crate) fn audit_range(&self, start: usize, end: usize, op: &str) {
+		self.audit_handle(op);
+		if AUDIT && (start > end || end > self.len()) {
+			self.audit_failed(
+				op,
+				format_args!(
+					"range {} .. {} is out of bounds: {}",
+					start,
+					end,
+					self.len(),
+				),
+			);
+		}
+	}
+
+	/// Audits the handle itself before an unchecked accessor uses it.
+	///
+	/// When `AUDIT` is set, this halts the program
+	/// if a handle with live bits has a null data pointer, or describes
+	/// elements that run past the end of the address space. Otherwise, it
+	/// compiles to nothing.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `op`: The name of the accessor, for the diagnostic.
+	#[inline(always)]
+	pub(crate) fn audit_handle(&self, op: &str) {
+		if !AUDIT || self.len() == 0 {
+			return;
+		}
+		let base = self.pointer().u();
+		if base == 0 {
+			self.audit_failed(op, format_args!("the data pointer is null"));
+		}
+		let wraps = self
+			.elements()
+			.checked_mul(size_of::<T>())
+			.and_then(|size| base.checked_add(size))
+			.is_none();
+		if wraps {
+			self.audit_failed(
+				op,
+				format_args!("the elements wrap the address space"),
+			);
+		}
+	}
+
+	/// Audits the location an unchecked accessor is about to touch.
+	///
+	/// The bits of the edge elements that lie outside the handle may belong
+	/// to other handles, such as the other half of a `split_at_mut`, which
+	/// are free to write them concurrently. An access to one of those bits
+	/// breaks the aliasing rules even when it stays inside an element that
+	/// the handle can see, so this checks the resolved bit position rather
+	/// than the index.
+	///
+	/// When `AUDIT` is set, this halts the program
+	/// unless bit `bit` of the element `elt` places after the handle’s first
+	/// element is one of the handle’s live bits. Otherwise, it compiles to
+	/// nothing.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `elt`: The element offset, from the start of the handle.
+	/// - `bit`: The bit index inside that element.
+	/// - `op`: The name of the accessor, for the diagnostic.
+	#[inline(always)]
+	pub(crate) fn audit_bit(&self, elt: isize, bit: BitIdx<T>, op: &str) {
+		if !AUDIT {
+			return;
+		}
+		if elt < 0 || elt as usize >= self.elements() {
+			self.audit_failed(
+				op,
+				format_args!(
+					"element {} is outside the {} elements of the handle",
+					elt,
+					self.elements(),
+				),
+			);
+		}
+		let pos = elt as usize * T::BITS as usize + *bit as usize;
+		let head = *self.head() as usize;
+		if pos < head || pos >= head + self.len() {
+			self.audit_failed(
+				op,
+				format_args!(
+					"bit {} of element {} is not live in the handle",
+					*bit, elt,
+				),
+			);
+		}
+	}
+
+	/// Reports a failed audit and halts.
+	///
+	/// This panics, rather than aborting directly, so that it works in
+	/// `no_std` builds; builds that set `panic = "abort"` abort here.
+	#[cold]
+	#[inline(never)]
+	pub(crate) fn audit_failed(&self, op: &str, what: fmt::Arguments) -> ! {
+		panic!("Unchecked access violation in `{}`: {} in {:?}", op, what, self)
+	}
 }
 
 /** Gets write access to all elements in the underlying storage, including the
@@ -967,6 +1127,11 @@ mod tests {
 		assert_eq!(bp.len(), 0);
 		assert_eq!(*bp.head(), 2);
 		assert_eq!(*bp.tail(), 2);
+
+		let bp = BitPtr::<u32>::empty();
+		assert!(!bp.pointer().r().is_null());
+		assert_eq!(*bp.head(), 0);
+		assert!(bp.as_access_slice().is_empty());
 	}
 
 	#[cfg(not(miri))]
@@ -979,4 +1144,36 @@ mod tests {
 			BitPtr::<u32>::MAX_BITS + 1,
 		);
 	}
+
+	#[test]
+	fn audit_live_bits() {
+		let data = [0u8; 2];
+		let bp = BitPtr::<u8>::new(&data as *const u8, 3u8.idx(), 6);
+		bp.audit_bit(0, 3u8.idx(), "audit_live_bits");
+		bp.audit_bit(1, 0u8.idx(), "audit_live_bits");
+	}
+
+	#[test]
+	#[cfg(any(
+		feature = "debug-unchecked",
+		all(feature = "unchecked-release", debug_assertions),
+	))]
+	#[should_panic(expected = "bit 2 of element 0 is not live")]
+	fn audit_dead_head_bit() {
+		let data = [0u8; 2];
+		let bp = BitPtr::<u8>::new(&data as *const u8, 3u8.idx(), 6);
+		bp.audit_bit(0, 2u8.idx(), "audit_dead_head_bit");
+	}
+
+	#[test]
+	#[cfg(any(
+		feature = "debug-unchecked",
+		all(feature = "unchecked-release", debug_assertions),
+	))]
+	#[should_panic(expected = "bit 1 of element 1 is not live")]
+	fn audit_dead_tail_bit() {
+		let data = [0u8; 2];
+		let bp = BitPtr::<u8>::new(&data as *const u8, 3u8.idx(), 6);
+		bp.audit_bit(1, 1u8.idx(), "audit_dead_tail_bit");
+	}
 }
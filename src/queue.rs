@@ -0,0 +1,317 @@
+/*! A register-width bit queue
+
+Encoders produce bits in fields of varying widths, and moving each field into a
+bit container one bit at a time is slow. The usual remedy is to stage fields in
+a machine register, and move them out only once it has filled.
+
+[`BitQueue`] is that register: a `u64` and a count of the bits it holds. Fields
+are pushed in at the back and popped from the front as integers, and the whole
+queue can be flushed into a `BitVec`. As with the integers in the [`pack`]
+module, the most significant bit of each field is the first one queued.
+
+[`BitQueue`]: struct.BitQueue.html
+[`pack`]: ../pack/index.html
+!*/
+
+use crate::{
+	fields,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[cfg(feature = "alloc")]
+use crate::vec::BitVec;
+
+/** A first-in, first-out queue of up to 64 bits, held in one register.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, queue::BitQueue};
+
+let mut queue = BitQueue::new();
+queue.push_bits(0b101, 3);
+queue.push_bits(0x3C, 8);
+assert_eq!(queue.len(), 11);
+assert_eq!(queue.pop_bits(4), 0b1010);
+
+let mut bv = BitVec::<Msb0, u8>::new();
+queue.flush_to(&mut bv);
+assert_eq!(bv, bits![0, 1, 1, 1, 1, 0, 0]);
+assert!(queue.is_empty());
+```
+**/
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct BitQueue {
+	/// The queued bits, in the low `len` bits of the register. The oldest bit
+	/// is the most significant of them, and the unused bits are zero.
+	reg: u64,
+	/// The number of queued bits.
+	len: u8,
+}
+
+impl BitQueue {
+	/// The number of bits that the queue can hold.
+	pub const CAPACITY: u8 = 64;
+
+	/// Constructs an empty queue.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Counts the queued bits.
+	pub fn len(&self) -> u8 {
+		self.len
+	}
+
+	/// Tests if the queue is empty.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Counts the bits that can be pushed before the queue is full.
+	pub fn remaining(&self) -> u8 {
+		Self::CAPACITY - self.len
+	}
+
+	/// Appends the low bits of an integer to the back of the queue.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `value`: The integer whose low `count` bits are queued, most
+	///   significant first. Its higher bits are ignored.
+	/// - `count`: The number of bits to queue.
+	///
+	/// # Panics
+	///
+	/// This panics if `count` is more than [`remaining`].
+	///
+	/// [`remaining`]: #method.remaining
+	pub fn push_bits(&mut self, value: u64, count: u8) {
+		assert!(
+			count <= self.remaining(),
+			"Cannot push {} bits into a queue with room for {}",
+			count,
+			self.remaining(),
+		);
+		self.reg = shl(self.reg, count) | (value & mask(count));
+		self.len += count;
+	}
+
+	/// Appends one bit to the back of the queue.
+	///
+	/// # Panics
+	///
+	/// This panics if the queue is full.
+	pub fn push_bit(&mut self, value: bool) {
+		self.push_bits(value as u64, 1);
+	}
+
+	/// Appends the bits of a slice to the back of the queue, in order.
+	///
+	/// Slices in the `Msb0` or `Lsb0` layout are loaded a whole element at a
+	/// time.
+	///
+	/// # Panics
+	///
+	/// This panics if the slice is longer than [`remaining`].
+	///
+	/// [`remaining`]: #method.remaining
+	pub fn push_bitslice<O, T>(&mut self, bits: &BitSlice<O, T>)
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		assert!(
+			bits.len() <= self.remaining() as usize,
+			"Cannot push {} bits into a queue with room for {}",
+			bits.len(),
+			self.remaining(),
+		);
+		self.push_bits(fields::load_msb_first(bits), bits.len() as u8);
+	}
+
+	/// Removes bits from the front of the queue.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `count`: The number of bits to remove.
+	///
+	/// # Returns
+	///
+	/// The removed bits, as the low `count` bits of an integer. The first bit
+	/// removed is the most significant.
+	///
+	/// # Panics
+	///
+	/// This panics if `count` is more than [`len`].
+	///
+	/// [`len`]: #method.len
+	pub fn pop_bits(&mut self, count: u8) -> u64 {
+		let out = self.peek_bits(count);
+		self.len -= count;
+		self.reg &= mask(self.len);
+		out
+	}
+
+	/// Reads bits from the front of the queue without removing them.
+	///
+	/// # Panics
+	///
+	/// This panics if `count` is more than [`len`].
+	///
+	/// [`len`]: #method.len
+	pub fn peek_bits(&self, count: u8) -> u64 {
+		assert!(
+			count <= self.len,
+			"Cannot take {} bits from a queue of {}",
+			count,
+			self.len,
+		);
+		shr(self.reg, self.len - count) & mask(count)
+	}
+
+	/// Removes one bit from the front of the queue.
+	pub fn pop_bit(&mut self) -> Option<bool> {
+		if self.is_empty() {
+			None
+		}
+		else {
+			Some(self.pop_bits(1) != 0)
+		}
+	}
+
+	/// Removes every bit from the queue.
+	pub fn clear(&mut self) {
+		*self = Self::new();
+	}
+
+	/// Moves the queued bits into the front of a slice.
+	///
+	/// The bits are written with one store of the register, which touches each
+	/// element of `dst` once when it is in the `Msb0` or `Lsb0` layout.
+	///
+	/// # Returns
+	///
+	/// The number of bits moved, which is the lesser of the lengths of the
+	/// queue and of `dst`. Any bits that did not fit stay in the queue.
+	pub fn flush_into<O, T>(&mut self, dst: &mut BitSlice<O, T>) -> usize
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let count = core::cmp::min(self.len as usize, dst.len());
+		let value = self.pop_bits(count as u8);
+		fields::store_msb_first(&mut dst[.. count], value);
+		count
+	}
+
+	/// Moves the queued bits onto the back of a bit vector.
+	#[cfg(feature = "alloc")]
+	pub fn flush_to<O, T>(&mut self, dst: &mut BitVec<O, T>)
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let len = dst.len();
+		dst.resize(len + self.len as usize, false);
+		fields::store_msb_first(&mut dst[len ..], self.reg);
+		self.clear();
+	}
+}
+
+/// Produces a mask of the low `count` bits of a register.
+fn mask(count: u8) -> u64 {
+	shl(1, count).wrapping_sub(1)
+}
+
+/// Shifts a register up, clearing it when the shift is its full width.
+fn shl(reg: u64, count: u8) -> u64 {
+	reg.checked_shl(count as u32).unwrap_or(0)
+}
+
+/// Shifts a register down, clearing it when the shift is its full width.
+fn shr(reg: u64, count: u8) -> u64 {
+	reg.checked_shr(count as u32).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Lsb0,
+		slice::AsBits,
+	};
+
+	#[test]
+	fn fields() {
+		let mut queue = BitQueue::new();
+		queue.push_bits(!0, 64);
+		assert_eq!(queue.remaining(), 0);
+		assert_eq!(queue.pop_bits(64), !0);
+
+		queue.push_bits(0x1234_5678, 28);
+		queue.push_bits(0xFFFF_FFFF_FFFF_FFF5, 4);
+		queue.push_bit(true);
+		assert_eq!(queue.peek_bits(8), 0x23);
+		assert_eq!(queue.pop_bits(12), 0x234);
+		assert_eq!(queue.pop_bits(16), 0x5678);
+		assert_eq!(queue.pop_bit(), Some(false));
+		assert_eq!(queue.len(), 4);
+
+		let mut data = 0u8;
+		queue.push_bitslice(&0x0Fu8.bits::<Lsb0>()[.. 6]);
+		assert_eq!(queue.flush_into(data.bits_mut::<Lsb0>()), 8);
+		assert_eq!(queue.pop_bits(2), 0b00);
+		assert_eq!(queue.pop_bit(), None);
+		//  The queue held 1, 0, 1, 1, then 1, 1, 1, 1, 0, 0.
+		assert_eq!(data, 0b1111_1101);
+	}
+
+	#[test]
+	#[cfg(feature = "alloc")]
+	fn spanning_elements() {
+		use crate::{
+			order::Msb0,
+			vec::BitVec,
+		};
+
+		//  The `Lsb0` nibbles count up from zero, so a bit that moves through
+		//  the queue out of order changes the result.
+		let src = [0x3210u16, 0x7654, 0xBA98, 0xFEDC];
+		let bits = &src.bits::<Lsb0>()[5 ..][.. 40];
+		let mut queue = BitQueue::new();
+		queue.push_bits(0b10, 2);
+		queue.push_bitslice(bits);
+		let expected =
+			bits.iter().fold(0b10u64, |acc, &bit| acc << 1 | bit as u64);
+		assert_eq!(queue.peek_bits(42), expected);
+
+		let mut dst = [0u8; 3];
+		let dst = &mut dst.bits_mut::<Msb0>()[3 ..];
+		assert_eq!(queue.flush_into(dst), 21);
+		assert!(dst.iter().zip((0 .. 21).rev()).all(|(&bit, n)| {
+			bit == ((expected >> (n + 21)) & 1 != 0)
+		}));
+
+		let mut bv = BitVec::<Lsb0, u32>::repeat(true, 30);
+		queue.flush_to(&mut bv);
+		assert!(queue.is_empty());
+		assert_eq!(bv.len(), 51);
+		assert!(bv[.. 30].all());
+		assert!(bv[30 ..].iter().zip((0 .. 21).rev()).all(|(&bit, n)| {
+			bit == ((expected >> n) & 1 != 0)
+		}));
+	}
+
+	#[test]
+	#[should_panic(expected = "Cannot push 2 bits")]
+	fn overflow() {
+		let mut queue = BitQueue::new();
+		queue.push_bits(0, 63);
+		queue.push_bits(0, 2);
+	}
+}
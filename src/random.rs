@@ -0,0 +1,219 @@
+/*! Random generation of bit sequences
+
+This module integrates `BitSlice` with the [`rand`] crate. It implements
+[`rand::Fill`], so that `rng.fill(bits)` randomizes a slice, and adds
+[`BitSlice::choose_set_bit`] for sampling the index of a random high bit.
+
+Filling draws random bytes for each whole storage element of the slice and
+writes them in one store, rather than drawing and writing one bit at a time.
+Only the partial elements at either edge of the slice are written bit by bit, so
+that the bits outside the slice are left unchanged.
+
+[`BitSlice::choose_set_bit`]: ../slice/struct.BitSlice.html#method.choose_set_bit
+[`rand`]: https://docs.rs/rand/0.8/rand/
+[`rand::Fill`]: https://docs.rs/rand/0.8/rand/trait.Fill.html
+!*/
+
+#![cfg(feature = "rand")]
+
+use crate::{
+	access::BitAccess,
+	indices::Indexable,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use core::{
+	cmp,
+	iter,
+	mem,
+};
+
+use either::Either;
+
+use ::rand::{
+	Error,
+	Fill,
+	Rng,
+};
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Chooses the index of a high bit, uniformly at random.
+	///
+	/// This counts the high bits of the slice, draws one random number below
+	/// that count, and then seeks to the high bit of that rank. Whole storage
+	/// elements are skipped by their population count, so this runs in time
+	/// proportional to the number of elements, not the number of bits.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `rng`: The source of randomness.
+	///
+	/// # Returns
+	///
+	/// The index of a high bit in `self`, or `None` if `self` has no high bits.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	/// use rand::rngs::mock::StepRng;
+	///
+	/// let mut rng = StepRng::new(0, 0x9E37_79B9_7F4A_7C15);
+	/// let bits = bits![0, 1, 0, 0, 1, 1, 0];
+	/// let idx = bits.choose_set_bit(&mut rng).unwrap();
+	/// assert!(bits[idx]);
+	/// assert!(bits![0; 7].choose_set_bit(&mut rng).is_none());
+	/// ```
+	pub fn choose_set_bit<R>(&self, rng: &mut R) -> Option<usize>
+	where R: Rng + ?Sized {
+		let count = self.count_ones();
+		if count == 0 {
+			return None;
+		}
+		self.select_one(rng.gen_range(0 .. count))
+	}
+
+	/// Finds the index of the high bit with a given rank.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `rank`: The number of high bits that precede the bit to find.
+	///
+	/// # Returns
+	///
+	/// The index of the high bit that has `rank` high bits before it, or `None`
+	/// if `self` has no more than `rank` high bits.
+	fn select_one(&self, mut rank: usize) -> Option<usize> {
+		let width = T::BITS as usize;
+		//  After the first element boundary, every chunk is one whole element.
+		let first = cmp::min(self.len(), width - *self.bitptr().head() as usize);
+		let (head, rest) = self.split_at(first);
+		let mut offset = 0;
+		for chunk in iter::once(head).chain(rest.chunks(width)) {
+			let ones = chunk.count_ones();
+			if rank < ones {
+				return chunk
+					.iter()
+					.enumerate()
+					.filter(|(_, bit)| **bit)
+					.nth(rank)
+					.map(|(idx, _)| offset + idx);
+			}
+			rank -= ones;
+			offset += chunk.len();
+		}
+		None
+	}
+}
+
+/// Randomizes every bit in the slice.
+///
+/// Bits outside the slice, in the edge elements it partially covers, are not
+/// modified.
+impl<O, T> Fill for BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn try_fill<R>(&mut self, rng: &mut R) -> Result<(), Error>
+	where R: Rng + ?Sized {
+		match self.bitptr().domain().splat() {
+			Either::Right((h, e, t)) => {
+				let rand = random_elt::<T, R>(rng)?;
+				for n in *h .. *t {
+					e.set::<O>(n.idx(), rand.get::<O>(n.idx()));
+				}
+			},
+			Either::Left((h, b, t)) => {
+				if let Some((h, head)) = h {
+					let rand = random_elt::<T, R>(rng)?;
+					for n in *h .. T::BITS {
+						head.set::<O>(n.idx(), rand.get::<O>(n.idx()));
+					}
+				}
+				if let Some(body) = b {
+					for elt in body {
+						elt.store(random_elt::<T, R>(rng)?);
+					}
+				}
+				if let Some((tail, t)) = t {
+					let rand = random_elt::<T, R>(rng)?;
+					for n in 0 .. *t {
+						tail.set::<O>(n.idx(), rand.get::<O>(n.idx()));
+					}
+				}
+			},
+		}
+		Ok(())
+	}
+}
+
+/// Draws a storage element with every bit random.
+fn random_elt<T, R>(rng: &mut R) -> Result<T, Error>
+where
+	T: BitStore,
+	R: Rng + ?Sized,
+{
+	let mut bytes = [0u8; 8];
+	let bytes = &mut bytes[.. mem::size_of::<T>()];
+	rng.try_fill_bytes(bytes)?;
+	//  Starting from the first byte, rather than from zero, keeps `u8` from
+	//  ever being shifted by its full width.
+	Ok(bytes[1 ..]
+		.iter()
+		.fold(T::from(bytes[0]), |acc, &byte| (acc << 8) | T::from(byte)))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+	use ::rand::rngs::mock::StepRng;
+
+	#[test]
+	fn fill_keeps_outside_bits() {
+		let mut rng = StepRng::new(!0, 0);
+		let mut data = [0u16; 3];
+		rng.fill(&mut data.bits_mut::<Msb0>()[5 .. 40]);
+		assert_eq!(data, [0x07FF, 0xFFFF, 0xFF00]);
+
+		let mut data = [!0u8; 2];
+		let mut rng = StepRng::new(0, 0);
+		rng.fill(&mut data.bits_mut::<Msb0>()[2 .. 5]);
+		assert_eq!(data, [0xC7, 0xFF]);
+	}
+
+	#[test]
+	fn choose_set_bit() {
+		let mut data = [0u32; 4];
+		let bits = &mut data.bits_mut::<Msb0>()[7 ..];
+		for &idx in &[0, 30, 31, 64, 120] {
+			bits.set(idx, true);
+		}
+		let bits = &*bits;
+		for n in 0 .. 5 {
+			assert_eq!(
+				bits.select_one(n),
+				Some([0, 30, 31, 64, 120][n]),
+			);
+		}
+		assert!(bits.select_one(5).is_none());
+
+		let mut rng = StepRng::new(0, 0x9E37_79B9_7F4A_7C15);
+		for _ in 0 .. 32 {
+			let idx = bits.choose_set_bit(&mut rng).unwrap();
+			assert!(bits[idx]);
+		}
+	}
+}
@@ -0,0 +1,271 @@
+/*! Arrays of narrow integer registers
+
+Cardinality sketches such as HyperLogLog, and counting sketches such as
+count-min, keep thousands of small counters that are only a few bits wide.
+Storing each in a byte wastes most of the memory the sketch is meant to save, so
+they are packed end to end, and many of them straddle element boundaries.
+
+[`PackedRegisters`] holds such an array in a `BitVec`. Each register is read and
+written through [`BitField`], which moves whole elements at a time rather than
+individual bits.
+
+[`BitField`]: ../fields/trait.BitField.html
+[`PackedRegisters`]: struct.PackedRegisters.html
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	fields::BitField,
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::ops::Range;
+
+/** A fixed-length array of unsigned integers of one bit width, packed end to
+end in a `BitVec`.
+
+Register `n` occupies bits `n * width .. (n + 1) * width` of the vector, and is
+accessed with [`BitField::load_be`] and [`BitField::store_be`].
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within each storage element. This must be
+  `Lsb0` or `Msb0`, as only they implement `BitField`.
+- `T: BitStore`: The storage element type.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, registers::PackedRegisters};
+
+//  The 64 six-bit registers of a small HyperLogLog sketch.
+let mut hll = PackedRegisters::<Msb0, u8>::new(64, 6);
+assert!(hll.update_max(3, 17));
+assert!(!hll.update_max(3, 9));
+assert_eq!(hll.get(3), 17);
+assert_eq!(hll.as_bitslice().len(), 384);
+```
+
+[`BitField::load_be`]: ../fields/trait.BitField.html#tymethod.load_be
+[`BitField::store_be`]: ../fields/trait.BitField.html#tymethod.store_be
+**/
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct PackedRegisters<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The registers, end to end.
+	bits: BitVec<O, T>,
+	/// The number of bits in each register.
+	width: usize,
+}
+
+impl<O, T> PackedRegisters<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+	BitSlice<O, T>: BitField,
+{
+	/// Creates an array of registers, all zero.
+	///
+	/// # Parameters
+	///
+	/// - `count`: The number of registers.
+	/// - `width`: The number of bits in each register.
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero or wider than `usize`, or if the array
+	/// would have more bits than a `BitVec` can hold.
+	pub fn new(count: usize, width: usize) -> Self {
+		let len = count.checked_mul(width).expect("capacity overflow");
+		Self::from_bitvec(BitVec::repeat(false, len), width)
+	}
+
+	/// Interprets a bit vector as an array of registers.
+	///
+	/// # Parameters
+	///
+	/// - `bits`: The registers, end to end, as produced by [`into_bitvec`].
+	/// - `width`: The number of bits in each register.
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero or wider than `usize`, or if the length
+	/// of `bits` is not a multiple of `width`.
+	///
+	/// [`into_bitvec`]: #method.into_bitvec
+	pub fn from_bitvec(bits: BitVec<O, T>, width: usize) -> Self {
+		let max = 0usize.count_zeros() as usize;
+		assert!(
+			width > 0 && width <= max,
+			"Register width {} must be in 1 ..= {}",
+			width,
+			max,
+		);
+		assert_eq!(
+			bits.len() % width,
+			0,
+			"{} bits cannot be divided into {}-bit registers",
+			bits.len(),
+			width,
+		);
+		Self { bits, width }
+	}
+
+	/// Unwraps the array into its bits.
+	pub fn into_bitvec(self) -> BitVec<O, T> {
+		self.bits
+	}
+
+	/// Views the registers, end to end.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		&self.bits
+	}
+
+	/// Counts the registers.
+	pub fn len(&self) -> usize {
+		self.bits.len() / self.width
+	}
+
+	/// Tests if there are no registers.
+	pub fn is_empty(&self) -> bool {
+		self.bits.is_empty()
+	}
+
+	/// Counts the bits in each register.
+	pub fn width(&self) -> usize {
+		self.width
+	}
+
+	/// Reads a register.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `index`: The register to read.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is not less than `self.len()`.
+	pub fn get(&self, index: usize) -> usize {
+		self.bits[self.span(index)].load_be()
+	}
+
+	/// Writes a register.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The register to write.
+	/// - `value`: The new value of the register.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is not less than `self.len()`, or if `value` has
+	/// more significant bits than a register can hold.
+	pub fn set(&mut self, index: usize, value: usize) {
+		assert!(
+			self.fits(value),
+			"{} does not fit in a {}-bit register",
+			value,
+			self.width,
+		);
+		let span = self.span(index);
+		self.bits[span].store_be(value);
+	}
+
+	/// Raises a register to a value, if it is currently lower.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The register to update.
+	/// - `value`: The value the register must be no less than.
+	///
+	/// # Returns
+	///
+	/// `true` if the register was raised.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is not less than `self.len()`, or if `value` has
+	/// more significant bits than a register can hold.
+	pub fn update_max(&mut self, index: usize, value: usize) -> bool {
+		let raise = value > self.get(index);
+		if raise {
+			self.set(index, value);
+		}
+		raise
+	}
+
+	/// Computes the bits of the vector that hold a register.
+	fn span(&self, index: usize) -> Range<usize> {
+		let len = self.len();
+		assert!(index < len, "Index {} out of bounds: {}", index, len);
+		let start = index * self.width;
+		start .. start + self.width
+	}
+
+	/// Tests if a value has no more significant bits than a register.
+	fn fits(&self, value: usize) -> bool {
+		self.width == 0usize.count_zeros() as usize || value >> self.width == 0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::{
+		Lsb0,
+		Msb0,
+	};
+
+	#[test]
+	fn cross_element() {
+		let mut regs = PackedRegisters::<Msb0, u8>::new(10, 5);
+		for n in 0 .. 10 {
+			regs.set(n, 31 - n);
+		}
+		for n in 0 .. 10 {
+			assert_eq!(regs.get(n), 31 - n);
+		}
+		//  Register 1 holds 30, in bits 5 .. 10.
+		assert_eq!(&regs.as_bitslice()[5 .. 10], bits![1, 1, 1, 1, 0]);
+
+		let mut regs = PackedRegisters::<Lsb0, u16>::new(5, 13);
+		regs.set(2, 0x1ABC);
+		regs.set(3, 0x0123);
+		assert_eq!(regs.get(2), 0x1ABC);
+		assert_eq!(regs.get(3), 0x0123);
+		assert_eq!(regs.get(1), 0);
+		assert_eq!(regs.get(4), 0);
+	}
+
+	#[test]
+	fn update_max() {
+		let mut regs = PackedRegisters::<Msb0, u32>::new(7, 6);
+		assert!(regs.update_max(6, 40));
+		assert!(!regs.update_max(6, 40));
+		assert!(regs.update_max(6, 63));
+		assert_eq!(regs.get(6), 63);
+		assert_eq!(regs.len(), 7);
+
+		let regs = PackedRegisters::from_bitvec(regs.into_bitvec(), 6);
+		assert_eq!(regs.get(6), 63);
+	}
+
+	#[test]
+	#[should_panic]
+	fn too_wide_value() {
+		PackedRegisters::<Msb0, u8>::new(4, 3).set(0, 8);
+	}
+}
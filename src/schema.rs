@@ -0,0 +1,585 @@
+/*! Bitstreams described by a runtime schema
+
+Reverse-engineering tools and protocol testers often learn the format of a
+bitstream at runtime, from a description rather than from a type, and each of
+them ends up writing the same loop to walk the description and pull fields out
+of the bits. This module is that loop.
+
+A schema is a tree of [`Field`]s: flags, unsigned and signed integers of any
+width up to 64 bits in either byte order, arrays with a repeat count, and groups
+of fields in sequence. [`Field::decode`] reads a bit slice into a matching tree
+of [`Value`]s, and [`Field::encode`] writes such a tree back out.
+
+Integers follow the convention of the [`pack`] module: a big-endian integer is
+written most significant bit first, whatever the `BitOrder` of the slice. A
+little-endian integer is split into bytes, least significant first, and each
+byte is written most significant bit first; the last byte of an integer whose
+width is not a multiple of eight is short, and holds its highest bits.
+
+[`Field`]: enum.Field.html
+[`Field::decode`]: enum.Field.html#method.decode
+[`Field::encode`]: enum.Field.html#method.encode
+[`Value`]: enum.Value.html
+[`pack`]: ../pack/index.html
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::{
+	boxed::Box,
+	vec::Vec,
+};
+
+use core::fmt::{
+	self,
+	Display,
+	Formatter,
+};
+
+/// The widest integer field that a schema can describe.
+const MAX_WIDTH: usize = 64;
+
+/// The byte order of a multi-byte integer field.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Endian {
+	/// The most significant byte is first.
+	Big,
+	/// The least significant byte is first.
+	Little,
+}
+
+/** One node of a schema.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, schema::*};
+
+//  A 3-bit version, a flag, then two 6-bit signed samples.
+let version = Field::Uint { width: 3, endian: Endian::Big };
+let sample = Field::Int { width: 6, endian: Endian::Big };
+let samples = Field::Array { count: 2, item: Box::new(sample) };
+let schema = Field::Group(vec![version, Field::Flag, samples]);
+assert_eq!(schema.width(), 16);
+
+let data = [0b101_1_1111u8, 0b10_000011];
+let value = schema.decode(data.bits::<Msb0>()).unwrap();
+let samples = Value::List(vec![Value::Int(-2), Value::Int(3)]);
+let fields = vec![Value::Uint(5), Value::Flag(true), samples];
+assert_eq!(value, Value::List(fields));
+assert_eq!(schema.encode::<Msb0, u8>(&value).unwrap(), data.bits::<Msb0>());
+```
+**/
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Field {
+	/// A single bit, decoded as a `Value::Flag`.
+	Flag,
+	/// An unsigned integer, decoded as a `Value::Uint`.
+	Uint {
+		/// The number of bits in the integer, no more than 64.
+		width: usize,
+		/// The byte order of the integer.
+		endian: Endian,
+	},
+	/// A two’s-complement signed integer, decoded as a `Value::Int`.
+	Int {
+		/// The number of bits in the integer, no more than 64.
+		width: usize,
+		/// The byte order of the integer.
+		endian: Endian,
+	},
+	/// A field repeated end to end, decoded as a `Value::List`.
+	Array {
+		/// The number of repetitions.
+		count: usize,
+		/// The repeated field. Unless `count` is zero, this must occupy at least
+		/// one bit.
+		item: Box<Field>,
+	},
+	/// A sequence of fields, decoded as a `Value::List`.
+	Group(Vec<Field>),
+}
+
+impl Field {
+	/// Counts the bits that the field occupies.
+	///
+	/// This saturates at `usize::max_value()` rather than overflowing.
+	pub fn width(&self) -> usize {
+		match self {
+			Field::Flag => 1,
+			Field::Uint { width, .. } | Field::Int { width, .. } => *width,
+			Field::Array { count, item } => item.width().saturating_mul(*count),
+			Field::Group(fields) => fields
+				.iter()
+				.fold(0, |acc: usize, field| acc.saturating_add(field.width())),
+		}
+	}
+
+	/// Decodes the field from the front of a bit slice.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `bits`: The source. Only its first [`self.width()`] bits are read.
+	///
+	/// # Returns
+	///
+	/// The decoded value, or an error if `bits` is too short, an integer field
+	/// is wider than 64 bits, or an array repeats a field of no bits.
+	///
+	/// [`self.width()`]: #method.width
+	pub fn decode<O, T>(
+		&self,
+		bits: &BitSlice<O, T>,
+	) -> Result<Value, SchemaError>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let needed = self.width();
+		if bits.len() < needed {
+			return Err(SchemaError::TooShort {
+				needed,
+				available: bits.len(),
+			});
+		}
+		self.read(bits, &mut 0)
+	}
+
+	/// Encodes a value into a new bit vector.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `value`: A value with the shape of `self`, as [`decode`] produces.
+	///
+	/// # Returns
+	///
+	/// A vector of [`self.width()`] bits, or an error if `value` does not have
+	/// the shape of `self`, or does not fit in it.
+	///
+	/// [`decode`]: #method.decode
+	/// [`self.width()`]: #method.width
+	pub fn encode<O, T>(
+		&self,
+		value: &Value,
+	) -> Result<BitVec<O, T>, SchemaError>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let mut out = BitVec::with_capacity(self.width());
+		self.encode_into(value, &mut out)?;
+		Ok(out)
+	}
+
+	/// Encodes a value onto the end of a bit vector.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `value`: A value with the shape of `self`, as [`decode`] produces.
+	/// - `out`: The destination, to which [`self.width()`] bits are appended.
+	///
+	/// # Returns
+	///
+	/// An error if `value` does not have the shape of `self`, or does not fit
+	/// in it. `out` may have been partly written.
+	///
+	/// [`decode`]: #method.decode
+	/// [`self.width()`]: #method.width
+	pub fn encode_into<O, T>(
+		&self,
+		value: &Value,
+		out: &mut BitVec<O, T>,
+	) -> Result<(), SchemaError>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let offset = out.len();
+		match (self, value) {
+			(Field::Flag, Value::Flag(bit)) => out.push(*bit),
+			(Field::Uint { width, endian }, Value::Uint(val)) => {
+				let width = check_width(*width, offset)?;
+				if width < MAX_WIDTH && val >> width != 0 {
+					return Err(SchemaError::Overflow { offset });
+				}
+				write_uint(out, width, *endian, *val);
+			},
+			(Field::Int { width, endian }, Value::Int(val)) => {
+				let width = check_width(*width, offset)?;
+				let fits = match width {
+					0 => *val == 0,
+					MAX_WIDTH => true,
+					w => (-1 << (w - 1) ..= !(-1 << (w - 1))).contains(val),
+				};
+				if !fits {
+					return Err(SchemaError::Overflow { offset });
+				}
+				write_uint(out, width, *endian, *val as u64);
+			},
+			(Field::Array { count, item }, Value::List(vals))
+				if vals.len() == *count =>
+			{
+				check_item(*count, item, offset)?;
+				for val in vals {
+					item.encode_into(val, out)?;
+				}
+			},
+			(Field::Group(fields), Value::List(vals))
+				if vals.len() == fields.len() =>
+			{
+				for (field, val) in fields.iter().zip(vals) {
+					field.encode_into(val, out)?;
+				}
+			},
+			_ => return Err(SchemaError::Mismatch { offset }),
+		}
+		Ok(())
+	}
+
+	/// Decodes the field at `*pos`, and moves `*pos` past it.
+	///
+	/// The caller has checked that `bits` holds the whole field.
+	fn read<O, T>(
+		&self,
+		bits: &BitSlice<O, T>,
+		pos: &mut usize,
+	) -> Result<Value, SchemaError>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let offset = *pos;
+		Ok(match self {
+			Field::Flag => {
+				*pos += 1;
+				Value::Flag(bits[offset])
+			},
+			Field::Uint { width, endian } => {
+				let width = check_width(*width, offset)?;
+				*pos += width;
+				Value::Uint(read_uint(&bits[offset .. *pos], *endian))
+			},
+			Field::Int { width, endian } => {
+				let width = check_width(*width, offset)?;
+				*pos += width;
+				let mut val = read_uint(&bits[offset .. *pos], *endian);
+				if width > 0 && width < MAX_WIDTH && val >> (width - 1) != 0 {
+					val |= !0 << width;
+				}
+				Value::Int(val as i64)
+			},
+			Field::Array { count, item } => {
+				check_item(*count, item, offset)?;
+				Value::List(
+					(0 .. *count)
+						.map(|_| item.read(bits, pos))
+						.collect::<Result<_, _>>()?,
+				)
+			},
+			Field::Group(fields) => Value::List(
+				fields
+					.iter()
+					.map(|field| field.read(bits, pos))
+					.collect::<Result<_, _>>()?,
+			),
+		})
+	}
+}
+
+/// A decoded field.
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Value {
+	/// The value of a `Field::Flag`.
+	Flag(bool),
+	/// The value of a `Field::Uint`.
+	Uint(u64),
+	/// The value of a `Field::Int`.
+	Int(i64),
+	/// The values of a `Field::Array` or `Field::Group`, in order.
+	List(Vec<Value>),
+}
+
+/** The reasons that a schema can fail to decode or encode a value.
+
+Offsets count bits from the start of the slice being decoded, or of the vector
+being encoded into, to the start of the field at fault.
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SchemaError {
+	/// The source slice was shorter than the schema.
+	TooShort {
+		/// The number of bits the schema requires.
+		needed: usize,
+		/// The number of bits the source slice held.
+		available: usize,
+	},
+	/// An integer field was wider than 64 bits.
+	TooWide {
+		/// The offset of the field.
+		offset: usize,
+		/// The width of the field.
+		width: usize,
+	},
+	/// A value did not have the shape of its field.
+	Mismatch {
+		/// The offset of the field.
+		offset: usize,
+	},
+	/// An integer value did not fit in the width of its field.
+	Overflow {
+		/// The offset of the field.
+		offset: usize,
+	},
+	/// An array repeated a field that occupies no bits.
+	EmptyItem {
+		/// The offset of the array.
+		offset: usize,
+	},
+}
+
+impl Display for SchemaError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match *self {
+			SchemaError::TooShort { needed, available } => write!(
+				fmt,
+				"Schema needs {} bits, but only {} are available",
+				needed, available,
+			),
+			SchemaError::TooWide { offset, width } => write!(
+				fmt,
+				"Integer field at bit {} is {} bits wide, more than {}",
+				offset, width, MAX_WIDTH,
+			),
+			SchemaError::Mismatch { offset } => write!(
+				fmt,
+				"Value for the field at bit {} does not match its shape",
+				offset,
+			),
+			SchemaError::Overflow { offset } => write!(
+				fmt,
+				"Value for the field at bit {} does not fit its width",
+				offset,
+			),
+			SchemaError::EmptyItem { offset } => write!(
+				fmt,
+				"Array at bit {} repeats a field of no bits",
+				offset,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for SchemaError {
+}
+
+/// Checks that an array’s item occupies bits, so that its count is bounded by
+/// the length of the slice it is decoded from.
+fn check_item(
+	count: usize,
+	item: &Field,
+	offset: usize,
+) -> Result<(), SchemaError>
+{
+	if count > 0 && item.width() == 0 {
+		Err(SchemaError::EmptyItem { offset })
+	}
+	else {
+		Ok(())
+	}
+}
+
+/// Checks that an integer field fits in a `u64`.
+fn check_width(width: usize, offset: usize) -> Result<usize, SchemaError> {
+	if width <= MAX_WIDTH {
+		Ok(width)
+	}
+	else {
+		Err(SchemaError::TooWide { offset, width })
+	}
+}
+
+/// Reads an integer that fills `bits`.
+fn read_uint<O, T>(bits: &BitSlice<O, T>, endian: Endian) -> u64
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let fold = |bits: &BitSlice<O, T>| {
+		bits.iter().fold(0u64, |acc, &bit| acc << 1 | bit as u64)
+	};
+	match endian {
+		Endian::Big => fold(bits),
+		Endian::Little => bits
+			.chunks(8)
+			.enumerate()
+			.fold(0, |acc, (n, byte)| acc | fold(byte) << (8 * n)),
+	}
+}
+
+/// Appends the low `width` bits of an integer.
+fn write_uint<O, T>(
+	out: &mut BitVec<O, T>,
+	width: usize,
+	endian: Endian,
+	val: u64,
+) where
+	O: BitOrder,
+	T: BitStore,
+{
+	let mut put = |val: u64, width: usize| {
+		for n in (0 .. width).rev() {
+			out.push(val >> n & 1 != 0);
+		}
+	};
+	match endian {
+		Endian::Big => put(val, width),
+		Endian::Little => {
+			for start in (0 .. width).step_by(8) {
+				put(val >> start, (width - start).min(8));
+			}
+		},
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+	use alloc::vec;
+
+	#[test]
+	fn round_trip() {
+		let schema = Field::Group(vec![
+			Field::Uint {
+				width: 12,
+				endian: Endian::Little,
+			},
+			Field::Int {
+				width: 64,
+				endian: Endian::Big,
+			},
+			Field::Array {
+				count: 3,
+				item: Box::new(Field::Group(vec![Field::Flag, Field::Int {
+					width: 10,
+					endian: Endian::Little,
+				}])),
+			},
+		]);
+		assert_eq!(schema.width(), 12 + 64 + 33);
+		let value = Value::List(vec![
+			Value::Uint(0xABC),
+			Value::Int(-0x1234_5678_9ABC),
+			Value::List(vec![
+				Value::List(vec![Value::Flag(true), Value::Int(-512)]),
+				Value::List(vec![Value::Flag(false), Value::Int(511)]),
+				Value::List(vec![Value::Flag(true), Value::Int(-1)]),
+			]),
+		]);
+		let bits = schema.encode::<Lsb0, u16>(&value).unwrap();
+		assert_eq!(bits.len(), schema.width());
+		//  The low byte of a little-endian field comes first.
+		assert_eq!(bits[.. 12], 0xBCAu16.bits::<Msb0>()[4 ..]);
+		assert_eq!(schema.decode(&bits), Ok(value.clone()));
+
+		let err = schema.decode(&bits[1 ..]).unwrap_err();
+		assert_eq!(err, SchemaError::TooShort {
+			needed: 109,
+			available: 108,
+		});
+
+		let mut bad = value;
+		if let Value::List(fields) = &mut bad {
+			fields[2] = Value::List(vec![Value::List(vec![
+				Value::Flag(true),
+				Value::Int(512),
+			])]);
+		}
+		let err = schema.encode::<Msb0, u8>(&bad).unwrap_err();
+		assert_eq!(err, SchemaError::Mismatch { offset: 76 });
+		let field = Field::Array {
+			count: 1,
+			item: Box::new(Field::Group(vec![Field::Flag, Field::Int {
+				width: 10,
+				endian: Endian::Big,
+			}])),
+		};
+		if let Value::List(fields) = bad {
+			let err = field.encode::<Msb0, u8>(&fields[2]).unwrap_err();
+			assert_eq!(err, SchemaError::Overflow { offset: 1 });
+		}
+
+		let wide = Field::Uint {
+			width: 65,
+			endian: Endian::Big,
+		};
+		assert_eq!(
+			wide.decode(&bits),
+			Err(SchemaError::TooWide {
+				offset: 0,
+				width: 65,
+			})
+		);
+	}
+
+	#[test]
+	fn empty_items() {
+		let bits = [0xA5u8].bits::<Msb0>();
+		let schema = Field::Group(vec![Field::Flag, Field::Array {
+			count: usize::max_value(),
+			item: Box::new(Field::Group(vec![Field::Uint {
+				width: 0,
+				endian: Endian::Big,
+			}])),
+		}]);
+		assert_eq!(schema.width(), 1);
+		assert_eq!(
+			schema.decode(bits),
+			Err(SchemaError::EmptyItem { offset: 1 })
+		);
+
+		let schema = Field::Array {
+			count: 2,
+			item: Box::new(Field::Array {
+				count: 3,
+				item: Box::new(Field::Group(vec![])),
+			}),
+		};
+		assert_eq!(
+			schema.decode(bits),
+			Err(SchemaError::EmptyItem { offset: 0 })
+		);
+		let empty = Value::List(vec![]);
+		let value = Value::List(vec![empty.clone(), empty.clone(), empty]);
+		assert_eq!(
+			schema.encode::<Msb0, u8>(&Value::List(vec![value.clone(), value])),
+			Err(SchemaError::EmptyItem { offset: 0 })
+		);
+
+		//  An array of no items may repeat any field.
+		let schema = Field::Array {
+			count: 0,
+			item: Box::new(Field::Group(vec![])),
+		};
+		assert_eq!(schema.decode(bits), Ok(Value::List(vec![])));
+		let bv = schema.encode::<Msb0, u8>(&Value::List(vec![])).unwrap();
+		assert!(bv.is_empty());
+	}
+}
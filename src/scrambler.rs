@@ -0,0 +1,280 @@
+/*! Polynomial scramblers
+
+Telecommunication links scramble their data before sending it, so that long runs
+of identical bits, or other regular patterns, do not reach the line. The
+scrambler is a shift register whose feedback is described by a polynomial
+`x^k + … + 1`; the exponents of its terms, other than the constant, are the
+register stages tapped for the feedback.
+
+[`Scrambler`] supports both common forms of this:
+
+- An additive, or synchronous, scrambler runs its register independently of the
+  data, and adds (`XOR`s) its output into the data. Scrambling and descrambling
+  are the same operation, and the receiver must start in the same state as the
+  sender.
+- A multiplicative, or self-synchronizing, scrambler shifts the scrambled stream
+  itself through its register. The receiver recovers from any starting state
+  after `k` bits.
+
+The scrambler keeps its register between calls, so a stream can be processed in
+pieces of any length, and need not be split on element boundaries.
+
+[`Scrambler`]: struct.Scrambler.html
+!*/
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[cfg(feature = "alloc")]
+use crate::vec::BitVec;
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::ToOwned;
+
+/// The forms of scrambler that [`Scrambler`] can run.
+///
+/// [`Scrambler`]: struct.Scrambler.html
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ScramblerMode {
+	/// The register runs freely, and its output is added into the data.
+	Additive,
+	/// The register holds the most recent bits of the scrambled stream.
+	Multiplicative,
+}
+
+/** A scrambler and descrambler for a polynomial of degree at most 64.
+
+# Examples
+
+The IEEE 802.11 data scrambler, `x^7 + x^4 + 1`, seeded with all ones, turns a
+stream of zeros into its scrambling sequence:
+
+```rust
+use bitvec::{prelude::*, scrambler::*};
+
+let mut scr = Scrambler::new(&[7, 4], 0x7F, ScramblerMode::Additive);
+let mut data = [0u8; 2];
+scr.scramble(data.bits_mut::<Msb0>());
+assert_eq!(data, [0b0000_1110, 0b1111_0010]);
+
+//  The receiver runs an identical scrambler over the received data.
+let mut rx = Scrambler::new(&[7, 4], 0x7F, ScramblerMode::Additive);
+rx.descramble(data.bits_mut::<Msb0>());
+assert_eq!(data, [0, 0]);
+```
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Scrambler {
+	/// The tapped stages. Bit `n` taps the bit that entered the register
+	/// `n + 1` steps ago.
+	taps: u64,
+	/// The register. Bit `0` is the most recent bit to enter it.
+	state: u64,
+	/// The stages that hold live bits of the register.
+	live: u64,
+	/// The form of the scrambler.
+	mode: ScramblerMode,
+}
+
+impl Scrambler {
+	/// Creates a scrambler.
+	///
+	/// # Parameters
+	///
+	/// - `taps`: The exponents of the non-constant terms of the polynomial, in
+	///   any order. The largest of them is the degree of the polynomial, and
+	///   the length of the register.
+	/// - `seed`: The starting contents of the register. Bit `n` is the bit that
+	///   entered the register `n + 1` steps ago. Bits above the degree of the
+	///   polynomial are discarded.
+	/// - `mode`: The form of the scrambler.
+	///
+	/// # Panics
+	///
+	/// This panics if `taps` is empty, or has an exponent that is zero or
+	/// greater than 64.
+	pub fn new(taps: &[u8], seed: u64, mode: ScramblerMode) -> Self {
+		assert!(!taps.is_empty(), "A scrambler requires at least one tap");
+		let mut mask = 0u64;
+		for &tap in taps {
+			assert!(
+				(1 ..= 64).contains(&tap),
+				"Scrambler tap x^{} must be in x^1 ..= x^64",
+				tap,
+			);
+			mask |= 1 << (tap - 1);
+		}
+		let degree = 64 - mask.leading_zeros();
+		let live = !0u64 >> (64 - degree);
+		Self {
+			taps: mask,
+			state: seed & live,
+			live,
+			mode,
+		}
+	}
+
+	/// Gets the current contents of the register, in the layout described by
+	/// [`new`].
+	///
+	/// [`new`]: #method.new
+	pub fn state(&self) -> u64 {
+		self.state
+	}
+
+	/// Replaces the contents of the register, in the layout described by
+	/// [`new`].
+	///
+	/// [`new`]: #method.new
+	pub fn set_state(&mut self, state: u64) {
+		self.state = state & self.live;
+	}
+
+	/// Gets the form of the scrambler.
+	pub fn mode(&self) -> ScramblerMode {
+		self.mode
+	}
+
+	/// Scrambles a slice in place, continuing from the current state.
+	pub fn scramble<O, T>(&mut self, bits: &mut BitSlice<O, T>)
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		for mut bit in bits.iter_mut() {
+			*bit = self.step(*bit, false);
+		}
+	}
+
+	/// Descrambles a slice in place, continuing from the current state.
+	pub fn descramble<O, T>(&mut self, bits: &mut BitSlice<O, T>)
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		for mut bit in bits.iter_mut() {
+			*bit = self.step(*bit, true);
+		}
+	}
+
+	/// Scrambles a copy of a slice, continuing from the current state.
+	#[cfg(feature = "alloc")]
+	pub fn scrambled<O, T>(&mut self, bits: &BitSlice<O, T>) -> BitVec<O, T>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let mut out = bits.to_owned();
+		self.scramble(&mut out);
+		out
+	}
+
+	/// Descrambles a copy of a slice, continuing from the current state.
+	#[cfg(feature = "alloc")]
+	pub fn descrambled<O, T>(&mut self, bits: &BitSlice<O, T>) -> BitVec<O, T>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let mut out = bits.to_owned();
+		self.descramble(&mut out);
+		out
+	}
+
+	/// Runs the register for one bit of the stream.
+	fn step(&mut self, bit: bool, descramble: bool) -> bool {
+		let feedback = (self.state & self.taps).count_ones() & 1 == 1;
+		let out = bit ^ feedback;
+		let shifted = match self.mode {
+			ScramblerMode::Additive => feedback,
+			//  The register always holds the scrambled side of the stream:
+			//  the output when scrambling, and the input when descrambling.
+			ScramblerMode::Multiplicative => {
+				if descramble {
+					bit
+				}
+				else {
+					out
+				}
+			},
+		};
+		self.state = (self.state << 1 | shifted as u64) & self.live;
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+
+	#[test]
+	fn known_answers() {
+		//  The 127-bit IEEE 802.11 sequence, and the first bit of its repeat.
+		let mut scr = Scrambler::new(&[7, 4], 0x7F, ScramblerMode::Additive);
+		let mut data = [0u8; 16];
+		scr.scramble(data.bits_mut::<Msb0>());
+		let sequence = [
+			0x0E, 0xF2, 0xC9, 0x02, 0x26, 0x2E, 0xB6, 0x0C, 0xD4, 0xE7, 0xB4,
+			0x2A, 0xFA, 0x51, 0xB8, 0xFE,
+		];
+		assert_eq!(data, sequence);
+
+		//  A multiplicative scrambler turns an impulse into the power series
+		//  of `1 / (x^7 + x^4 + 1)`.
+		let mode = ScramblerMode::Multiplicative;
+		let mut data = [0x80u8, 0, 0, 0];
+		Scrambler::new(&[7, 4], 0, mode).scramble(data.bits_mut::<Msb0>());
+		assert_eq!(data, [0x89, 0x8B, 0xAD, 0x83]);
+		Scrambler::new(&[7, 4], 0, mode).descramble(data.bits_mut::<Msb0>());
+		assert_eq!(data, [0x80, 0, 0, 0]);
+	}
+
+	#[test]
+	fn streaming() {
+		//  Long runs are what a scrambler exists to break up.
+		let data = [0u32, !0, 0xFFFF_0000];
+		for &mode in &[ScramblerMode::Additive, ScramblerMode::Multiplicative] {
+			let scr = Scrambler::new(&[23, 18], 0x1234, mode);
+
+			let mut whole = data;
+			let mut one = scr;
+			one.scramble(whole.bits_mut::<Msb0>());
+			assert_ne!(whole, data);
+
+			//  Pieces that split elements produce the same stream.
+			let mut parts = data;
+			let mut tx = scr;
+			let bits = parts.bits_mut::<Msb0>();
+			tx.scramble(&mut bits[.. 5]);
+			tx.scramble(&mut bits[5 .. 61]);
+			tx.scramble(&mut bits[61 ..]);
+			assert_eq!(parts, whole);
+
+			let mut rx = scr;
+			rx.descramble(parts.bits_mut::<Msb0>());
+			assert_eq!(parts, data);
+		}
+	}
+
+	#[test]
+	fn self_synchronizing() {
+		let data = [!0u32, 0];
+		let mode = ScramblerMode::Multiplicative;
+		let mut tx = Scrambler::new(&[23, 18], 0, mode);
+		let mut line = data;
+		tx.scramble(line.bits_mut::<Msb0>());
+
+		//  A receiver in the wrong state recovers after the register fills.
+		let mut rx = Scrambler::new(&[23, 18], !0, mode);
+		rx.descramble(line.bits_mut::<Msb0>());
+		assert_ne!(line[0], data[0]);
+		assert_eq!(&line.bits::<Msb0>()[23 ..], &data.bits::<Msb0>()[23 ..]);
+	}
+}
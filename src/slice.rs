@@ -9,21 +9,52 @@ Rust slices, and must never be interchanged except through the provided APIs.
 !*/
 
 use crate::{
-	access::BitAccess,
+	access::{
+		self,
+		BitAccess,
+	},
 	domain::*,
+	fields::{
+		self,
+		BitField,
+		Overflow,
+		Signed,
+	},
 	indices::Indexable,
+	kernels,
 	order::{
 		BitOrder,
 		Local,
 	},
-	pointer::BitPtr,
+	pointer::{
+		self,
+		BitPtr,
+	},
 	store::BitStore,
 };
 
-use core::marker::PhantomData;
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+	marker::PhantomData,
+	mem,
+	ops::Range,
+	ptr,
+	sync::atomic::{
+		self,
+		Ordering,
+	},
+};
 
 use either::Either;
 
+#[cfg(feature = "diagnostics")]
+use crate::diagnostics::BitPtrParts;
+
 /** A compact slice of bits, whose order and storage types can be customized.
 
 `BitSlice` is a specialized slice type, which can only ever be held by
@@ -79,6 +110,13 @@ assert_eq!(base[1], 4);
   (64-bit systems only). This is the actual type in memory that the slice will
   use to store data.
 
+# Length Limit
+
+The handle stores the index of the first live bit in the low three bits of its
+length field, so a `BitSlice` can hold at most `usize::max_value() >> 3` bits,
+and can be built over at most `(usize::max_value() >> 6) + 1` elements. Only
+32-bit targets have enough address space to reach these limits.
+
 # Safety
 
 The `&BitSlice` reference handle has the same *size* as standard Rust slice
@@ -218,7 +256,8 @@ where
 	/// # Panics
 	///
 	/// The source slice must not exceed the maximum number of elements that a
-	/// `BitSlice` can contain. This value is documented in [`BitPtr`].
+	/// `BitSlice` can contain. This value is documented in the [length limit]
+	/// of the type.
 	///
 	/// # Examples
 	///
@@ -235,7 +274,7 @@ where
 	/// assert!(bits[23]);
 	/// ```
 	///
-	/// [`BitPtr`]: ../pointer/struct.BitPtr.html
+	/// [length limit]: struct.BitSlice.html#length-limit
 	pub fn from_slice(slice: &[T]) -> &Self {
 		let len = slice.len();
 		assert!(
@@ -264,7 +303,8 @@ where
 	/// # Panics
 	///
 	/// The source slice must not exceed the maximum number of elements that a
-	/// `BitSlice` can contain. This value is documented in [`BitPtr`].
+	/// `BitSlice` can contain. This value is documented in the [length limit]
+	/// of the type.
 	///
 	/// # Examples
 	///
@@ -280,12 +320,204 @@ where
 	/// assert_eq!(bits.as_ref(), &[0, 2, 3]);
 	/// ```
 	///
-	/// [`BitPtr`]: ../pointer/struct.BitPtr.html
+	/// [length limit]: struct.BitSlice.html#length-limit
 	#[inline]
 	pub fn from_slice_mut(slice: &mut [T]) -> &mut Self {
 		Self::from_slice(slice).bitptr().into_bitslice_mut()
 	}
 
+	/// Views a region of raw memory as a `BitSlice`.
+	///
+	/// This is intended for memory that the program does not own in the Rust
+	/// sense, such as buffers shared with untrusted code, DMA regions, or
+	/// sealed blobs handed across an enclave boundary. It builds the slice
+	/// handle directly from the pointer, without first producing an `&[T]`
+	/// over the region, and performs no validation of its arguments. See
+	/// [`try_from_raw_ptr`] for a variant that checks everything it can.
+	///
+	/// # Parameters
+	///
+	/// - `ptr`: The address of the first element containing live bits.
+	/// - `head_bit`: The index, within `*ptr`, of the first live bit.
+	/// - `bits`: The number of live bits in the region.
+	///
+	/// # Returns
+	///
+	/// A `BitSlice` over `bits` bits, beginning at bit `head_bit` of `*ptr`.
+	///
+	/// # Safety
+	///
+	/// The caller must ensure that:
+	///
+	/// - `ptr` is non-null and aligned to `T`.
+	/// - `head_bit` is less than `T::BITS`.
+	/// - `bits` is within the [length limit] of `BitSlice`.
+	/// - Every element from `ptr` up to the one holding the last live bit is
+	///   readable for the whole lifetime `'a`, and the region does not wrap
+	///   around the end of the address space.
+	/// - No other agent writes to the region during `'a`, except through
+	///   atomic operations when the `atomic` feature is enabled. Memory that
+	///   the host may change at any time must be copied out before it is
+	///   viewed, rather than viewed in place.
+	///
+	/// The lifetime `'a` is unbounded; bind it to a value that actually
+	/// governs the region.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0x0Fu8, 0xF0];
+	/// let bits = unsafe {
+	///     BitSlice::<Msb0, u8>::from_raw_ptr(data.as_ptr(), 4, 8)
+	/// };
+	/// assert!(bits.all());
+	/// ```
+	///
+	/// [length limit]: struct.BitSlice.html#length-limit
+	/// [`try_from_raw_ptr`]: #method.try_from_raw_ptr
+	pub unsafe fn from_raw_ptr<'a>(
+		ptr: *const T,
+		head_bit: u8,
+		bits: usize,
+	) -> &'a Self {
+		debug_assert!(
+			Self::raw_ptr_is_valid(ptr, head_bit, bits),
+			"Invalid BitSlice region: {:p}, head {}, {} bits",
+			ptr,
+			head_bit,
+			bits,
+		);
+		BitPtr::new_unchecked(ptr, head_bit.idx(), bits).into_bitslice()
+	}
+
+	/// Views a region of raw memory as a mutable `BitSlice`.
+	///
+	/// This is the mutable counterpart to [`from_raw_ptr`].
+	///
+	/// # Safety
+	///
+	/// All of the requirements of [`from_raw_ptr`] apply, except that the
+	/// region must be writable, and the caller must additionally ensure that
+	/// no other handle reads or writes any bit of the region during `'a`.
+	/// Bits of the edge elements outside the region may be used by others,
+	/// as they may with any `&mut BitSlice`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0u8; 2];
+	/// let bits = unsafe {
+	///     BitSlice::<Msb0, u8>::from_raw_ptr_mut(data.as_mut_ptr(), 4, 8)
+	/// };
+	/// bits.set_all(true);
+	/// assert_eq!(data, [0x0F, 0xF0]);
+	/// ```
+	///
+	/// [`from_raw_ptr`]: #method.from_raw_ptr
+	pub unsafe fn from_raw_ptr_mut<'a>(
+		ptr: *mut T,
+		head_bit: u8,
+		bits: usize,
+	) -> &'a mut Self {
+		Self::from_raw_ptr(ptr, head_bit, bits)
+			.bitptr()
+			.into_bitslice_mut()
+	}
+
+	/// Views a region of raw memory as a `BitSlice`, after validating every
+	/// property of the arguments that can be checked.
+	///
+	/// # Parameters
+	///
+	/// - `ptr`: The address of the first element containing live bits.
+	/// - `head_bit`: The index, within `*ptr`, of the first live bit.
+	/// - `bits`: The number of live bits in the region.
+	///
+	/// # Returns
+	///
+	/// `None` if `ptr` is null or misaligned, if `head_bit` is not less than
+	/// `T::BITS`, if `bits` exceeds the [length limit], or if the region
+	/// would wrap around the end of the address space. Otherwise, the same
+	/// slice as [`from_raw_ptr`].
+	///
+	/// # Safety
+	///
+	/// The checks cannot establish that the region is allocated, readable, or
+	/// free of concurrent writes. Those requirements of [`from_raw_ptr`]
+	/// remain with the caller.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	/// use core::ptr;
+	///
+	/// let data = [0u16; 2];
+	/// let ptr = data.as_ptr();
+	/// unsafe {
+	///     assert!(BitSlice::<Lsb0, u16>::try_from_raw_ptr(ptr, 15, 17).is_some());
+	///     assert!(BitSlice::<Lsb0, u16>::try_from_raw_ptr(ptr, 16, 1).is_none());
+	///     assert!(BitSlice::<Lsb0, u16>::try_from_raw_ptr(ptr::null(), 0, 1).is_none());
+	///
+	///     let odd = (ptr as *const u8).add(1) as *const u16;
+	///     assert!(BitSlice::<Lsb0, u16>::try_from_raw_ptr(odd, 0, 1).is_none());
+	/// }
+	/// ```
+	///
+	/// [length limit]: struct.BitSlice.html#length-limit
+	/// [`from_raw_ptr`]: #method.from_raw_ptr
+	pub unsafe fn try_from_raw_ptr<'a>(
+		ptr: *const T,
+		head_bit: u8,
+		bits: usize,
+	) -> Option<&'a Self> {
+		if Self::raw_ptr_is_valid(ptr, head_bit, bits) {
+			Some(Self::from_raw_ptr(ptr, head_bit, bits))
+		}
+		else {
+			None
+		}
+	}
+
+	/// Views a region of raw memory as a mutable `BitSlice`, after validating
+	/// every property of the arguments that can be checked.
+	///
+	/// This is the mutable counterpart to [`try_from_raw_ptr`].
+	///
+	/// # Safety
+	///
+	/// See [`from_raw_ptr_mut`].
+	///
+	/// [`from_raw_ptr_mut`]: #method.from_raw_ptr_mut
+	/// [`try_from_raw_ptr`]: #method.try_from_raw_ptr
+	pub unsafe fn try_from_raw_ptr_mut<'a>(
+		ptr: *mut T,
+		head_bit: u8,
+		bits: usize,
+	) -> Option<&'a mut Self> {
+		Self::try_from_raw_ptr(ptr, head_bit, bits)
+			.map(|bits| bits.bitptr().into_bitslice_mut())
+	}
+
+	/// Tests the checkable requirements of `from_raw_ptr`.
+	fn raw_ptr_is_valid(ptr: *const T, head_bit: u8, bits: usize) -> bool {
+		if ptr.is_null()
+			|| (ptr as usize & (mem::align_of::<T>() - 1)) != 0
+			|| head_bit >= T::BITS
+			|| bits > BitPtr::<T>::MAX_BITS
+		{
+			return false;
+		}
+		let elts = head_bit.idx::<T>().span(bits).0;
+		elts.checked_mul(mem::size_of::<T>())
+			.and_then(|bytes| (ptr as usize).checked_add(bytes))
+			.is_some()
+	}
+
 	/// Sets the bit value at the given position.
 	///
 	/// # Parameters
@@ -344,9 +576,6 @@ where
 	///
 	/// # Examples
 	///
-	/// This example uses a bit slice of length 2, and demonstrates
-	/// out-of-bounds access to the last bit in the element.
-	///
 	/// ```rust
 	/// use bitvec::prelude::*;
 	///
@@ -354,17 +583,186 @@ where
 	/// let bits = &mut src.bits_mut::<Msb0>()[2 .. 4];
 	/// assert_eq!(bits.len(), 2);
 	/// unsafe {
-	///     bits.set_unchecked(5, true);
+	///     bits.set_unchecked(1, true);
 	/// }
-	/// assert_eq!(src, 1);
+	/// assert_eq!(src, 0b0001_0000);
 	/// ```
 	///
 	/// [`set`]: #method.set
 	pub unsafe fn set_unchecked(&mut self, index: usize, value: bool) {
-		let bitptr = self.bitptr();
-		let (elt, bit) = bitptr.head().offset(index as isize);
-		let data_ptr = bitptr.pointer().a();
-		(*data_ptr.offset(elt)).set::<O>(bit, value);
+		let (elem, bit) =
+			access::unchecked_element(self.bitptr(), index, "set_unchecked");
+		elem.set::<O>(bit, value);
+	}
+
+	/// Sets the bit at the given position, and returns its previous value.
+	///
+	/// The bit is read and written in one operation on its storage element,
+	/// which is atomic when the `atomic` feature is enabled, so this never
+	/// races with writes to other bits that share the element.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The bit index to set. It must be in the domain `0 ..
+	///   self.len()`.
+	/// - `value`: The value to be set, `true` for `1` and `false` for `0`.
+	///
+	/// # Returns
+	///
+	/// The value of the bit before it was set.
+	///
+	/// # Panics
+	///
+	/// This method panics if `index` is outside the slice domain.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut store = 0u8;
+	/// let bits = store.bits_mut::<Msb0>();
+	/// assert!(!bits.replace(3, true));
+	/// assert!(bits.replace(3, true));
+	/// assert_eq!(store, 0x10);
+	/// ```
+	pub fn replace(&mut self, index: usize, value: bool) -> bool {
+		let len = self.len();
+		assert!(index < len, "Index out of range: {} >= {}", index, len);
+		unsafe { self.replace_unchecked(index, value) }
+	}
+
+	/// Version of [`replace`] that does not perform boundary checks.
+	///
+	/// # Safety
+	///
+	/// `index` must be less than `self.len()`, otherwise the memory access is
+	/// unsound and may induce undefined behavior.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut store = 0u8;
+	/// let bits = &mut store.bits_mut::<Msb0>()[4 ..];
+	/// assert!(!unsafe { bits.replace_unchecked(0, true) });
+	/// assert_eq!(store, 0x08);
+	/// ```
+	///
+	/// [`replace`]: #method.replace
+	pub unsafe fn replace_unchecked(&mut self, index: usize, value: bool) -> bool {
+		let (elem, bit) =
+			access::unchecked_element(self.bitptr(), index, "replace_unchecked");
+		elem.replace_bit::<O>(bit, value)
+	}
+
+	/// Inverts the bit at the given position, and returns its previous value.
+	///
+	/// As with [`replace`], the bit is read and written in one operation on
+	/// its storage element.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The bit index to invert. It must be in the domain `0 ..
+	///   self.len()`.
+	///
+	/// # Returns
+	///
+	/// The value of the bit before it was inverted.
+	///
+	/// # Panics
+	///
+	/// This method panics if `index` is outside the slice domain.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut store = 0u8;
+	/// let bits = store.bits_mut::<Lsb0>();
+	/// assert!(!bits.toggle(0));
+	/// assert!(bits.toggle(0));
+	/// assert_eq!(store, 0);
+	/// ```
+	///
+	/// [`replace`]: #method.replace
+	pub fn toggle(&mut self, index: usize) -> bool {
+		let len = self.len();
+		assert!(index < len, "Index out of range: {} >= {}", index, len);
+		unsafe { self.toggle_unchecked(index) }
+	}
+
+	/// Version of [`toggle`] that does not perform boundary checks.
+	///
+	/// # Safety
+	///
+	/// `index` must be less than `self.len()`, otherwise the memory access is
+	/// unsound and may induce undefined behavior.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut store = 0u8;
+	/// let bits = &mut store.bits_mut::<Lsb0>()[4 ..];
+	/// assert!(!unsafe { bits.toggle_unchecked(3) });
+	/// assert_eq!(store, 0x80);
+	/// ```
+	///
+	/// [`toggle`]: #method.toggle
+	pub unsafe fn toggle_unchecked(&mut self, index: usize) -> bool {
+		let (elem, bit) =
+			access::unchecked_element(self.bitptr(), index, "toggle_unchecked");
+		elem.toggle_bit::<O>(bit)
+	}
+
+	/// Sets the bit at the given position, if its current value satisfies a
+	/// predicate.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The bit index to set. It must be in the domain `0 ..
+	///   self.len()`.
+	/// - `value`: The value to be set, `true` for `1` and `false` for `0`.
+	/// - `predicate`: A test of the current value of the bit. `value` is only
+	///   written if this returns `true`.
+	///
+	/// # Returns
+	///
+	/// The value of the bit before the call.
+	///
+	/// # Panics
+	///
+	/// This method panics if `index` is outside the slice domain.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut store = 0u8;
+	/// let bits = store.bits_mut::<Msb0>();
+	/// assert!(!bits.set_if(7, true, |old| !old));
+	/// assert!(!bits.set_if(6, true, |old| old));
+	/// assert_eq!(store, 1);
+	/// ```
+	pub fn set_if<F>(&mut self, index: usize, value: bool, predicate: F) -> bool
+	where F: FnOnce(bool) -> bool {
+		let len = self.len();
+		assert!(index < len, "Index out of range: {} >= {}", index, len);
+		//  No other handle can write this bit, so it cannot change between the
+		//  read and the write.
+		let old = unsafe { *self.get_unchecked(index) };
+		if predicate(old) {
+			unsafe { self.set_unchecked(index, value) };
+		}
+		old
 	}
 
 	/// Produces a write reference to a region of the slice.
@@ -468,6 +866,7 @@ where
 	/// induce safety violations. The caller must ensure that `mid` is within
 	/// the boundaries of `self` before calling.
 	pub unsafe fn split_at_unchecked(&self, mid: usize) -> (&Self, &Self) {
+		self.bitptr().audit_range(mid, mid, "split_at_unchecked");
 		match mid {
 			0 => (BitSlice::empty(), self),
 			n if n == self.len() => (self, BitSlice::empty()),
@@ -510,6 +909,152 @@ where
 		self.set_unchecked(b, bit_a);
 	}
 
+	/// Version of [`copy_within`](#method.copy_within) that does not perform
+	/// boundary checks.
+	///
+	/// # Safety
+	///
+	/// `src` must be a non-decreasing range whose end is no greater than
+	/// `self.len()`, and `dest + src.len()` must be no greater than
+	/// `self.len()`, otherwise the memory access is unsound and may induce
+	/// undefined behavior.
+	pub unsafe fn copy_within_unchecked(
+		&mut self,
+		src: Range<usize>,
+		dest: usize,
+	) {
+		let Range { start: from, end: upto } = src;
+		let bp = self.bitptr();
+		bp.audit_range(from, upto, "copy_within_unchecked");
+		let count = upto - from;
+		if pointer::AUDIT {
+			match dest.checked_add(count) {
+				Some(end) => bp.audit_range(dest, end, "copy_within_unchecked"),
+				None => bp.audit_failed(
+					"copy_within_unchecked",
+					format_args!("destination {} + {} overflows", dest, count),
+				),
+			}
+		}
+		if count == 0 || from == dest {
+			return;
+		}
+
+		let bits = T::BITS as usize;
+		let dist = cmp::max(from, dest) - cmp::min(from, dest);
+		let order = Self::linear_order();
+		//  The bits of an element in any other ordering cannot be moved by a
+		//  shift, so they can only be moved together by whole elements.
+		if order.is_none() && dist % bits != 0 {
+			if from > dest {
+				for n in 0 .. count {
+					self.copy_unchecked(from + n, dest + n);
+				}
+			}
+			else {
+				for n in (0 .. count).rev() {
+					self.copy_unchecked(from + n, dest + n);
+				}
+			}
+			return;
+		}
+		let lsb = order == Some(true);
+
+		//  The first live bit of the source and destination regions, counted
+		//  from the start of the first element of the slice.
+		let head = *self.bitptr().head() as usize;
+		let (s_base, d_base) = (head + from, head + dest);
+		//  The destination is split at its element boundaries into a partial
+		//  element at each edge, which is masked, and whole elements between
+		//  them, which are stored outright.
+		let d_head = d_base % bits;
+		let front = if d_head == 0 {
+			0
+		}
+		else {
+			cmp::min(bits - d_head, count)
+		};
+		let elts = (count - front) / bits;
+		let tail = front + elts * bits;
+		let d_elt = (d_base + front) / bits;
+		let body = self.bitptr().as_access_slice();
+
+		//  Shifts the bits of an element toward lower or higher indices.
+		let down = |elt: T, by: usize| {
+			if lsb { elt >> by as u8 } else { elt << by as u8 }
+		};
+		let up = |elt: T, by: usize| {
+			if lsb { elt << by as u8 } else { elt >> by as u8 }
+		};
+		/* Loads the `len` bits that begin at index `idx`, counted from the
+		start of the first element, into the lowest indices of an element. The
+		bits above them are unspecified. The bits straddle at most two source
+		elements, and the second is only read when it holds some of them.
+		*/
+		let load = |idx: usize, len: usize| {
+			let (elt, off) = (idx / bits, idx % bits);
+			let lo = down(body[elt].load(), off);
+			if off + len > bits {
+				lo | up(body[elt + 1].load(), bits - off)
+			}
+			else {
+				lo
+			}
+		};
+		//  Copies `len` bits at offset `n` of the region, where the
+		//  destination bits share one element, but do not fill it.
+		let part = |n: usize, len: usize| {
+			let idx = d_base + n;
+			let off = idx % bits;
+			let mask = up(down(T::TRUE, bits - len), off);
+			let val = up(load(s_base + n, len), off);
+			let elt = &body[idx / bits];
+			elt.clear_bits(!(mask & !val));
+			elt.set_bits(mask & val);
+		};
+		let copy_edge = |this: &mut Self, n: usize, len: usize| {
+			if len == 0 {
+				return;
+			}
+			if order.is_some() {
+				part(n, len);
+			}
+			else if from > dest {
+				for n in n .. n + len {
+					this.copy_unchecked(from + n, dest + n);
+				}
+			}
+			else {
+				for n in (n .. n + len).rev() {
+					this.copy_unchecked(from + n, dest + n);
+				}
+			}
+		};
+		let whole = |n: usize| {
+			body[d_elt + n].store(load(s_base + front + n * bits, bits));
+		};
+
+		/* Copy front to back when moving toward the start of the slice, and
+		back to front when moving toward the end, so that no bit of `src` is
+		overwritten before it has been read. Each element of the destination
+		is written only after all the source bits it receives have been read.
+		*/
+		if from > dest {
+			copy_edge(self, 0, front);
+			for n in 0 .. elts {
+				whole(n);
+			}
+			copy_edge(self, tail, count - tail);
+		}
+		else {
+			copy_edge(self, tail, count - tail);
+			for n in (0 .. elts).rev() {
+				whole(n);
+			}
+			copy_edge(self, 0, front);
+		}
+	}
+
 	/// Tests if *all* bits in the slice domain are set (logical `∧`).
 	///
 	/// # Truth Table
@@ -751,27 +1296,19 @@ where
 	pub fn count_ones(&self) -> usize {
 		match self.bitptr().domain().splat() {
 			Either::Right((h, e, t)) => {
-				let elt = e.load();
-				(*h .. *t).filter(|n| elt.get::<O>(n.idx())).count()
+				kernels::count_edge::<O, T>(e.load(), *h .. *t)
 			},
 			Either::Left((h, b, t)) => {
 				let mut out = 0usize;
 				if let Some((h, head)) = h {
 					let elt = head.load();
-					out += (*h .. T::BITS)
-						.filter(|n| elt.get::<O>(n.idx()))
-						.count();
+					out += kernels::count_edge::<O, T>(elt, *h .. T::BITS);
 				}
 				if let Some(body) = b {
-					out += body
-						.iter()
-						.map(BitAccess::load)
-						.map(T::count_ones)
-						.sum::<usize>();
+					out += kernels::count_body::<T>(body);
 				}
 				if let Some((tail, t)) = t {
-					let elt = tail.load();
-					out += (0 .. *t).filter(|n| elt.get::<O>(n.idx())).count();
+					out += kernels::count_edge::<O, T>(tail.load(), 0 .. *t);
 				}
 				out
 			},
@@ -800,46 +1337,771 @@ where
 		self.len() - self.count_ones()
 	}
 
-	/// Set all bits in the slice to a value.
+	/// Counts the bits that are set high in both `self` and `other`.
+	///
+	/// This is `(self & other).count_ones()`, computed in one pass without
+	/// building the intersection. When the two slices begin at the same bit
+	/// index in their first elements, the elements under them are combined
+	/// whole; otherwise, they are combined bit by bit.
 	///
 	/// # Parameters
 	///
-	/// - `&mut self`
-	/// - `value`: The bit value to which all bits in the slice will be set.
+	/// - `&self`
+	/// - `other`: A slice of the same length as `self`.
+	///
+	/// # Panics
+	///
+	/// This panics if the slices have different lengths.
 	///
 	/// # Examples
 	///
 	/// ```rust
 	/// use bitvec::prelude::*;
 	///
-	/// let mut src = 0u8;
-	/// let bits = src.bits_mut::<Msb0>();
-	/// bits[2 .. 6].set_all(true);
-	/// assert_eq!(bits.as_ref(), &[0b0011_1100]);
-	/// bits[3 .. 5].set_all(false);
-	/// assert_eq!(bits.as_ref(), &[0b0010_0100]);
-	/// bits[.. 1].set_all(true);
-	/// assert_eq!(bits.as_ref(), &[0b1010_0100]);
+	/// let a = [0xF0u8, 0xFF, 0x0F];
+	/// let b = [0x3Cu8, 0x0F, 0xFF];
+	/// let (a, b) = (&a.bits::<Msb0>()[2 .. 22], &b.bits::<Msb0>()[2 .. 22]);
+	/// assert_eq!(a.and_count_ones(b), 8);
 	/// ```
-	pub fn set_all(&mut self, value: bool) {
-		match self.bitptr().domain().splat() {
-			Either::Right((h, e, t)) => {
-				for n in *h .. *t {
-					e.set::<O>(n.idx(), value);
-				}
-			},
-			Either::Left((h, b, t)) => {
-				if let Some((h, head)) = h {
-					for n in *h .. T::BITS {
-						head.set::<O>(n.idx(), value);
-					}
-				}
-				if let Some(body) = b {
-					for elt in body {
-						elt.store(if value { T::TRUE } else { T::FALSE });
-					}
-				}
-				if let Some((tail, t)) = t {
+	pub fn and_count_ones(&self, other: &Self) -> usize {
+		self.fused_count(other, |a, b| a & b, |a, b| a & b)
+	}
+
+	/// Counts the bits that are set high in either `self` or `other`.
+	///
+	/// This is `(self | other).count_ones()`, computed in one pass without
+	/// building the union. See [`and_count_ones`] for details.
+	///
+	/// # Panics
+	///
+	/// This panics if the slices have different lengths.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = [0xF0u8, 0xFF, 0x0F];
+	/// let b = [0x3Cu8, 0x0F, 0xFF];
+	/// let (a, b) = (&a.bits::<Msb0>()[2 .. 22], &b.bits::<Msb0>()[2 .. 22]);
+	/// assert_eq!(a.or_count_ones(b), 18);
+	/// ```
+	///
+	/// [`and_count_ones`]: #method.and_count_ones
+	pub fn or_count_ones(&self, other: &Self) -> usize {
+		self.fused_count(other, |a, b| a | b, |a, b| a | b)
+	}
+
+	/// Counts the bits that differ between `self` and `other`.
+	///
+	/// This is `(self ^ other).count_ones()`, the Hamming distance between
+	/// the slices, computed in one pass. See [`and_count_ones`] for details.
+	///
+	/// # Panics
+	///
+	/// This panics if the slices have different lengths.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = [0xF0u8, 0xFF, 0x0F];
+	/// let b = [0x3Cu8, 0x0F, 0xFF];
+	/// let (a, b) = (&a.bits::<Msb0>()[2 .. 22], &b.bits::<Msb0>()[2 .. 22]);
+	/// assert_eq!(a.xor_count_ones(b), 10);
+	/// ```
+	///
+	/// [`and_count_ones`]: #method.and_count_ones
+	pub fn xor_count_ones(&self, other: &Self) -> usize {
+		self.fused_count(other, |a, b| (a | b) & !(a & b), |a, b| a ^ b)
+	}
+
+	/// Computes the fraction of the slice’s bits that are set.
+	///
+	/// # Returns
+	///
+	/// The number of set bits divided by the length, in `0.0 ..= 1.0`. An
+	/// empty slice has a density of `0.0`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// assert_eq!(0b1011_0000u8.bits::<Msb0>().density(), 0.375);
+	/// ```
+	pub fn density(&self) -> f64 {
+		match self.len() {
+			0 => 0.0,
+			len => self.count_ones() as f64 / len as f64,
+		}
+	}
+
+	/// Counts the set bits in each window of `width` bits, as [`windows`]
+	/// would produce them.
+	///
+	/// The first window is counted once, and each later window adjusts that
+	/// count by the bit that leaves it and the bit that enters it, so sliding
+	/// the window costs two bit reads however wide it is.
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![1, 1, 0, 1, 0, 0, 1];
+	/// let counts = bits.popcount_windows(3).collect::<Vec<_>>();
+	/// assert_eq!(counts, [2, 2, 1, 1, 1]);
+	/// ```
+	///
+	/// [`windows`]: #method.windows
+	pub fn popcount_windows(&self, width: usize) -> PopcountWindows<'_, O, T> {
+		assert_ne!(width, 0, "Window width cannot be zero");
+		let ones = self.get(.. width).map_or(0, BitSlice::count_ones);
+		PopcountWindows {
+			inner: self,
+			width,
+			ones,
+		}
+	}
+
+	/// Computes the density of each window of `width` bits, as [`windows`]
+	/// would produce them.
+	///
+	/// This is [`popcount_windows`], with each count divided by `width`.
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![1, 1, 0, 1, 0, 0];
+	/// let densities = bits.density_windows(4).collect::<Vec<_>>();
+	/// assert_eq!(densities, [0.75, 0.5, 0.25]);
+	/// ```
+	///
+	/// [`popcount_windows`]: #method.popcount_windows
+	/// [`windows`]: #method.windows
+	pub fn density_windows(&self, width: usize) -> DensityWindows<'_, O, T> {
+		DensityWindows {
+			inner: self.popcount_windows(width),
+		}
+	}
+
+	/// Counts the places where a bit differs from the bit before it.
+	///
+	/// This is the number of `01` and `10` boundaries in the slice, which is
+	/// one less than the number of runs in a non-empty slice. In `Lsb0` and
+	/// `Msb0` orders, each element is compared against itself shifted by one
+	/// bit, and only the boundaries between elements are tested bit by bit.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![0, 0, 1, 1, 1, 0, 1, 0, 0];
+	/// assert_eq!(bits.count_transitions(), 4);
+	/// ```
+	pub fn count_transitions(&self) -> usize {
+		let len = self.len();
+		let lsb = match Self::linear_order() {
+			Some(lsb) => lsb,
+			None => {
+				return (1 .. len).filter(|&n| self[n - 1] != self[n]).count();
+			},
+		};
+		//  Compares each live bit with the live bit before it in the element.
+		let inner = self.fold_elements(0, |count, elt, mask| {
+			let (prev, pairs) = if lsb {
+				(elt << 1, mask & (mask << 1))
+			}
+			else {
+				(elt >> 1, mask & (mask >> 1))
+			};
+			let diff = (elt | prev) & !(elt & prev);
+			count + (diff & pairs).count_ones()
+		});
+		let width = T::BITS as usize;
+		let head = *self.bitptr().head() as usize;
+		let edges = (width - head .. len)
+			.step_by(width)
+			.filter(|&n| self[n - 1] != self[n])
+			.count();
+		inner + edges
+	}
+
+	/// Finds the longest run of bits equal to `value`.
+	///
+	/// Elements that the run covers entirely are tested whole, so long runs are
+	/// measured an element at a time.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `value`: The bit value of which to find a run.
+	///
+	/// # Returns
+	///
+	/// The length of the longest run, and the index at which it starts. When
+	/// several runs are equally long, the first is returned. When no bit is
+	/// equal to `value`, this is `(0, 0)`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![0, 1, 1, 0, 1, 1, 1, 0, 0, 1, 1, 1];
+	/// assert_eq!(bits.longest_run(true), (3, 4));
+	/// assert_eq!(bits.longest_run(false), (2, 7));
+	/// assert_eq!(bits[1 .. 3].longest_run(false), (0, 0));
+	/// ```
+	pub fn longest_run(&self, value: bool) -> (usize, usize) {
+		let width = T::BITS as usize;
+		let len = self.len();
+		let head = *self.bitptr().head() as usize;
+		//  Split at the element boundaries, so that every chunk is the live
+		//  part of one element.
+		let (first, rest) = self.split_at(cmp::min(len, width - head));
+		let (mut best, mut start) = (0, 0);
+		let (mut run, mut idx) = (0, 0);
+		for chunk in core::iter::once(first).chain(rest.chunks(width)) {
+			let uniform = if value { chunk.all() } else { chunk.not_any() };
+			if uniform {
+				run += chunk.len();
+				idx += chunk.len();
+			}
+			else {
+				for &bit in chunk {
+					idx += 1;
+					if bit == value {
+						run += 1;
+						continue;
+					}
+					if run > best {
+						best = run;
+						start = idx - 1 - run;
+					}
+					run = 0;
+				}
+			}
+		}
+		if run > best {
+			best = run;
+			start = idx - run;
+		}
+		(best, start)
+	}
+
+	/// Counts the high bits of a boolean combination of two slices.
+	///
+	/// `elts` combines two memory elements, and `bits` combines two bits. The
+	/// combination must map two low bits to a low bit, so that the dead bits
+	/// of partial elements, which are masked away, never count.
+	fn fused_count<E, B>(&self, other: &Self, elts: E, bits: B) -> usize
+	where
+		E: Fn(T, T) -> T,
+		B: Fn(bool, bool) -> bool,
+	{
+		assert_eq!(
+			self.len(),
+			other.len(),
+			"Fused counts require equal lengths",
+		);
+		if self.is_empty() {
+			return 0;
+		}
+		if self.bitptr().head() != other.bitptr().head() {
+			return self
+				.iter()
+				.zip(other.iter())
+				.filter(|(a, b)| bits(**a, **b))
+				.count();
+		}
+		//  With equal heads and lengths, both domains have the same shape.
+		let edge = |a: Option<PartialElement<O, T>>,
+		            b: Option<PartialElement<O, T>>| {
+			match (a, b) {
+				(Some(a), Some(b)) => elts(a.load(), b.load()).count_ones(),
+				_ => 0,
+			}
+		};
+		match (self.domain(), other.domain()) {
+			(Domain::Enclave(a), Domain::Enclave(b)) => edge(Some(a), Some(b)),
+			(
+				Domain::Region {
+					head: ah,
+					body: ab,
+					tail: at,
+				},
+				Domain::Region {
+					head: bh,
+					body: bb,
+					tail: bt,
+				},
+			) => {
+				edge(ah, bh)
+					+ ab.iter()
+						.zip(bb)
+						.map(|(a, b)| elts(*a, *b).count_ones())
+						.sum::<usize>() + edge(at, bt)
+			},
+			_ => unreachable!("Slices with equal heads and lengths"),
+		}
+	}
+
+	/// `AND`s any number of slices into `self`, in one pass.
+	///
+	/// This is equivalent to `&=`-ing each operand into `self` in turn, but
+	/// rather than walking all of `self` once per operand, it walks `self` in
+	/// blocks, and combines every operand into a block before moving on to the
+	/// next. Each element of `self` is loaded and stored while it is still in
+	/// cache, no matter how many operands there are.
+	///
+	/// Operands that begin at the same bit index in their first element as
+	/// `self` are combined a whole element at a time; others, bit by bit.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `others`: The operands. These are traversed once to check their
+	///   lengths, and then once per block, so the iterator must be `Clone`.
+	///
+	/// # Panics
+	///
+	/// This panics if any operand has a different length than `self`. This
+	/// check happens before `self` is modified.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = [0b1111_0110u8];
+	/// let b = [0b0111_1110u8];
+	/// let c = [0b1110_0111u8];
+	/// let mut dst = [!0u8];
+	/// let operands = [a.bits::<Msb0>(), b.bits::<Msb0>(), c.bits::<Msb0>()];
+	/// dst.bits_mut::<Msb0>().and_many(operands.iter().copied());
+	/// assert_eq!(dst, [0b0110_0110]);
+	/// ```
+	pub fn and_many<'a, I>(&mut self, others: I)
+	where
+		I: IntoIterator<Item = &'a Self>,
+		I::IntoIter: Clone,
+		O: 'a,
+		T: 'a,
+	{
+		self.combine_many(others, |a, b| a & b, |a, b| a & b)
+	}
+
+	/// `OR`s any number of slices into `self`, in one pass.
+	///
+	/// See [`and_many`] for how the pass is performed.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `others`: The operands. The iterator must be `Clone`.
+	///
+	/// # Panics
+	///
+	/// This panics if any operand has a different length than `self`. This
+	/// check happens before `self` is modified.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = [0b1000_0000u8, 0];
+	/// let b = [0b0000_0001u8, 0];
+	/// let mut dst = [0u8; 2];
+	/// let operands = [&a.bits::<Msb0>()[3 ..], &b.bits::<Msb0>()[3 ..]];
+	/// dst.bits_mut::<Msb0>()[3 ..].or_many(operands.iter().copied());
+	/// assert_eq!(dst, [0b0000_0001, 0]);
+	/// ```
+	///
+	/// [`and_many`]: #method.and_many
+	pub fn or_many<'a, I>(&mut self, others: I)
+	where
+		I: IntoIterator<Item = &'a Self>,
+		I::IntoIter: Clone,
+		O: 'a,
+		T: 'a,
+	{
+		self.combine_many(others, |a, b| a | b, |a, b| a | b)
+	}
+
+	/// Combines every operand into `self`, one block of `self` at a time.
+	///
+	/// `elts` and `bits` are the same operation on elements and on bits. The
+	/// element operation must leave the bits outside an edge element’s mask
+	/// cleared, which `AND` and `OR` of two masked loads do.
+	fn combine_many<'a, I, E, B>(&mut self, others: I, elts: E, bits: B)
+	where
+		I: IntoIterator<Item = &'a Self>,
+		I::IntoIter: Clone,
+		O: 'a,
+		T: 'a,
+		E: Fn(T, T) -> T,
+		B: Fn(bool, bool) -> bool,
+	{
+		/// The number of elements of `self` in each block.
+		const BLOCK: usize = 64;
+
+		let others = others.into_iter();
+		let len = self.len();
+		for other in others.clone() {
+			assert_eq!(
+				other.len(),
+				len,
+				"Bulk operations require equal lengths",
+			);
+		}
+		let block = BLOCK * T::BITS as usize;
+		//  The first block ends on an element boundary of `self`, so that no
+		//  element is split between two blocks.
+		let mut start = 0;
+		let mut end = cmp::min(len, block - *self.bitptr().head() as usize);
+		while start < len {
+			let dst = &mut self[start .. end];
+			for other in others.clone() {
+				dst.combine(&other[start .. end], &elts, &bits);
+			}
+			start = end;
+			end = cmp::min(len, end + block);
+		}
+	}
+
+	/// Combines one operand of the same length into `self`.
+	fn combine<E, B>(&mut self, other: &Self, elts: &E, bits: &B)
+	where
+		E: Fn(T, T) -> T,
+		B: Fn(bool, bool) -> bool,
+	{
+		if self.bitptr().head() != other.bitptr().head() {
+			for (idx, bit) in other.iter().copied().enumerate() {
+				let val = bits(unsafe { *self.get_unchecked(idx) }, bit);
+				unsafe { self.set_unchecked(idx, val) };
+			}
+			return;
+		}
+		self.zip_words_mut(other, other, |d, a, _| elts(d, a));
+	}
+
+	/// Applies a function to each memory element under `self`, together with
+	/// the elements under two other slices at the same position, and stores
+	/// the result in `self`.
+	///
+	/// This is the general form of the element-wise bulk operations: it lets
+	/// custom kernels, such as a bitwise majority vote, run a whole element at
+	/// a time while the slice handles the partial elements at its edges.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `a`: A slice with the same length as `self`, that begins at the same
+	///   bit index in its first element.
+	/// - `b`: Another such slice.
+	/// - `func`: A function which receives the current element of `self` and
+	///   the corresponding elements of `a` and `b`, in that order, and returns
+	///   the new element of `self`.
+	///
+	/// In the partial elements at either edge, the bits that are not part of
+	/// the slices are cleared before `func` sees them, and the bits of its
+	/// result outside `self` are discarded. `func` should therefore compute
+	/// each bit of its result only from the same bit of its arguments.
+	///
+	/// # Panics
+	///
+	/// This panics if `a` or `b` has a different length than `self`, or begins
+	/// at a different bit index.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// //  A bitwise majority vote of three bitmaps, written into the first.
+	/// let mut x = [0b1100_1010u8, 0x0F];
+	/// let y = [0b1010_0110u8, 0x33];
+	/// let z = [0b0110_1100u8, 0x55];
+	/// x.bits_mut::<Msb0>()[2 .. 14].zip_words_mut(
+	///     &y.bits::<Msb0>()[2 .. 14],
+	///     &z.bits::<Msb0>()[2 .. 14],
+	///     |x, y, z| x & y | x & z | y & z,
+	/// );
+	/// assert_eq!(x, [0b1110_1110, 0b0001_0111]);
+	/// ```
+	pub fn zip_words_mut<F>(&mut self, a: &Self, b: &Self, mut func: F)
+	where F: FnMut(T, T, T) -> T {
+		for other in &[a, b] {
+			assert_eq!(
+				(self.len(), self.bitptr().head()),
+				(other.len(), other.bitptr().head()),
+				"Element-wise operations require equally aligned slices",
+			);
+		}
+		if self.is_empty() {
+			return;
+		}
+		match (self.domain_mut(), a.domain(), b.domain()) {
+			(
+				DomainMut::Enclave(mut d),
+				Domain::Enclave(a),
+				Domain::Enclave(b),
+			) => {
+				d.store(func(d.load(), a.load(), b.load()));
+			},
+			(
+				DomainMut::Region {
+					head: dh,
+					body: db,
+					tail: dt,
+				},
+				Domain::Region {
+					head: ah,
+					body: ab,
+					tail: at,
+				},
+				Domain::Region {
+					head: bh,
+					body: bb,
+					tail: bt,
+				},
+			) => {
+				if let (Some(mut d), Some(a), Some(b)) = (dh, ah, bh) {
+					d.store(func(d.load(), a.load(), b.load()));
+				}
+				for (d, (a, b)) in db.iter_mut().zip(ab.iter().zip(bb)) {
+					*d = func(*d, *a, *b);
+				}
+				if let (Some(mut d), Some(a), Some(b)) = (dt, at, bt) {
+					d.store(func(d.load(), a.load(), b.load()));
+				}
+			},
+			_ => unreachable!("Slices with equal heads and lengths"),
+		}
+	}
+
+	/// Copies the bits of `src` into `self` wherever `mask` is set, and leaves
+	/// the bits of `self` unchanged wherever it is unset.
+	///
+	/// This computes `self = (self & !mask) | (src & mask)` in one pass over
+	/// the three slices, without building the intermediate results. When all
+	/// three begin at the same bit index in their first element, the pass
+	/// works a whole memory element at a time; otherwise it works bit by bit.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `src`: The bits to copy in. It must have the same length as `self`.
+	/// - `mask`: The selection of bits to copy. It must have the same length
+	///   as `self`.
+	///
+	/// # Panics
+	///
+	/// This panics if `src` or `mask` has a different length than `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0b1111_0000u8, 0x0F];
+	/// let src = [0b1010_1010u8, 0xAA];
+	/// let mask = [0b0011_1100u8, 0xFF];
+	/// data.bits_mut::<Msb0>()[.. 12].assign_masked(
+	///     &src.bits::<Msb0>()[.. 12],
+	///     &mask.bits::<Msb0>()[.. 12],
+	/// );
+	/// assert_eq!(data, [0b1110_1000, 0xAF]);
+	/// ```
+	pub fn assign_masked(&mut self, src: &Self, mask: &Self) {
+		let len = self.len();
+		assert!(
+			src.len() == len && mask.len() == len,
+			"Masked assignment requires equal lengths",
+		);
+		let head = self.bitptr().head();
+		if src.bitptr().head() == head && mask.bitptr().head() == head {
+			self.zip_words_mut(src, mask, |d, s, m| (d & !m) | (s & m));
+			return;
+		}
+		for (idx, (&s, &m)) in src.iter().zip(mask).enumerate() {
+			if m {
+				unsafe { self.set_unchecked(idx, s) };
+			}
+		}
+	}
+
+	/// Applies a function to each memory element under the slice, and stores
+	/// its result back under the mask of the element’s live bits.
+	///
+	/// This lets custom transforms that work across the bits of an element,
+	/// such as smearing the highest set bit downward, run a whole element at a
+	/// time. The function sees the mask of the bits that are part of the
+	/// slice, and only those bits of its result are stored, so the bits of the
+	/// edge elements that lie outside the slice are never modified.
+	///
+	/// The elements are visited in the order of the slice: the partial head
+	/// element, then the whole elements, then the partial tail element. A
+	/// function that carries state from one element to the next, such as a
+	/// prefix scan, sees them in memory order.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `func`: A function which receives an element, with the bits outside
+	///   the slice cleared, and the mask of the bits inside it. It returns the
+	///   new value of the element.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// //  Smears each element’s highest live bit down to the bottom.
+	/// let mut data = [0b0100_0100u8, 0b0100_0000];
+	/// data.bits_mut::<Msb0>()[2 .. 12].map_elements(|elt, mask| {
+	///     let elt = elt | elt >> 1;
+	///     let elt = elt | elt >> 2;
+	///     (elt | elt >> 4) & mask
+	/// });
+	/// assert_eq!(data, [0b0100_0111, 0b0111_0000]);
+	/// ```
+	pub fn map_elements<F>(&mut self, mut func: F)
+	where F: FnMut(T, T) -> T {
+		if self.is_empty() {
+			return;
+		}
+		match self.domain_mut() {
+			DomainMut::Enclave(mut elt) => {
+				elt.store(func(elt.load(), elt.mask()));
+			},
+			DomainMut::Region { head, body, tail } => {
+				if let Some(mut elt) = head {
+					elt.store(func(elt.load(), elt.mask()));
+				}
+				for elt in body {
+					*elt = func(*elt, T::TRUE);
+				}
+				if let Some(mut elt) = tail {
+					elt.store(func(elt.load(), elt.mask()));
+				}
+			},
+		}
+	}
+
+	/// Folds each memory element under the slice into an accumulator, together
+	/// with the mask of the element’s live bits.
+	///
+	/// This is the read-only counterpart of [`map_elements`]. It lets custom
+	/// statistics, such as counting the transitions between runs, be gathered
+	/// a whole element at a time while the slice handles the partial elements
+	/// at its edges.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `init`: The initial value of the accumulator.
+	/// - `func`: A function which receives the accumulator, an element with
+	///   the bits outside the slice cleared, and the mask of the bits inside
+	///   it. It returns the next accumulator.
+	///
+	/// # Returns
+	///
+	/// The final accumulator. The elements are visited in the order of the
+	/// slice, so an empty slice returns `init`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0b1011_0110u8, 0b0011_1100];
+	/// let bits = &data.bits::<Lsb0>()[3 .. 14];
+	/// let (ones, live) = bits.fold_elements((0, 0), |(ones, live), elt, mask| {
+	///     (ones + elt.count_ones(), live + mask.count_ones())
+	/// });
+	/// assert_eq!((ones as usize, live as usize), (bits.count_ones(), 11));
+	/// ```
+	///
+	/// [`map_elements`]: #method.map_elements
+	pub fn fold_elements<B, F>(&self, init: B, mut func: F) -> B
+	where F: FnMut(B, T, T) -> B {
+		if self.is_empty() {
+			return init;
+		}
+		match self.domain() {
+			Domain::Enclave(elt) => func(init, elt.load(), elt.mask()),
+			Domain::Region { head, body, tail } => {
+				let mut accum = init;
+				if let Some(elt) = head {
+					accum = func(accum, elt.load(), elt.mask());
+				}
+				for &elt in body {
+					accum = func(accum, elt, T::TRUE);
+				}
+				if let Some(elt) = tail {
+					accum = func(accum, elt.load(), elt.mask());
+				}
+				accum
+			},
+		}
+	}
+
+	/// Set all bits in the slice to a value.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `value`: The bit value to which all bits in the slice will be set.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut src = 0u8;
+	/// let bits = src.bits_mut::<Msb0>();
+	/// bits[2 .. 6].set_all(true);
+	/// assert_eq!(bits.as_ref(), &[0b0011_1100]);
+	/// bits[3 .. 5].set_all(false);
+	/// assert_eq!(bits.as_ref(), &[0b0010_0100]);
+	/// bits[.. 1].set_all(true);
+	/// assert_eq!(bits.as_ref(), &[0b1010_0100]);
+	/// ```
+	pub fn set_all(&mut self, value: bool) {
+		match self.bitptr().domain().splat() {
+			Either::Right((h, e, t)) => {
+				for n in *h .. *t {
+					e.set::<O>(n.idx(), value);
+				}
+			},
+			Either::Left((h, b, t)) => {
+				if let Some((h, head)) = h {
+					for n in *h .. T::BITS {
+						head.set::<O>(n.idx(), value);
+					}
+				}
+				if let Some(body) = b {
+					for elt in body {
+						elt.store(if value { T::TRUE } else { T::FALSE });
+					}
+				}
+				if let Some((tail, t)) = t {
 					for n in 0 .. *t {
 						tail.set::<O>(n.idx(), value);
 					}
@@ -848,186 +2110,2165 @@ where
 		}
 	}
 
-	/// Provides mutable traversal of the collection.
+	/// Provides mutable traversal of the collection.
+	///
+	/// It is impossible to implement `IndexMut` on `BitSlice`, because bits do
+	/// not have addresses, so there can be no `&mut u1`. This method allows the
+	/// client to receive an enumerated bit, and provide a new bit to set at
+	/// each index.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `func`: A function which receives a `(usize, bool)` pair of index and
+	///   value, and returns a bool. It receives the bit at each position, and
+	///   the return value is written back at that position.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut src = 0u8;
+	/// let bits = src.bits_mut::<Msb0>();
+	/// bits.for_each(|idx, _bit| idx % 3 == 0);
+	/// assert_eq!(src, 0b1001_0010);
+	/// ```
+	pub fn for_each<F>(&mut self, func: F)
+	where F: Fn(usize, bool) -> bool {
+		for idx in 0 .. self.len() {
+			unsafe {
+				let tmp = *self.get_unchecked(idx);
+				let new = func(idx, tmp);
+				self.set_unchecked(idx, new);
+			}
+		}
+	}
+
+	/// Provides mutable traversal of the collection, stopping at the first
+	/// error.
+	///
+	/// This is [`for_each`], except that the function may fail. The traversal
+	/// stops at the first failure, so a validation pass over a large slice
+	/// does no more work than it must.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `func`: A function which receives a `(usize, bool)` pair of index and
+	///   value. It returns either the bit to write back at that position, or
+	///   an error.
+	///
+	/// # Returns
+	///
+	/// The first error that `func` returns. The bits before the position that
+	/// produced it have been written, and it and the bits after it have not.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut src = 0b0001_0110u8;
+	/// let bits = src.bits_mut::<Msb0>();
+	/// let res = bits.try_for_each(|idx, bit| match (idx, bit) {
+	///     (5, true) => Err(idx),
+	///     _ => Ok(!bit),
+	/// });
+	/// assert_eq!(res, Err(5));
+	/// assert_eq!(src, 0b1110_1110);
+	/// ```
+	///
+	/// [`for_each`]: #method.for_each
+	pub fn try_for_each<F, E>(&mut self, mut func: F) -> Result<(), E>
+	where F: FnMut(usize, bool) -> Result<bool, E> {
+		for idx in 0 .. self.len() {
+			unsafe {
+				let tmp = *self.get_unchecked(idx);
+				let new = func(idx, tmp)?;
+				self.set_unchecked(idx, new);
+			}
+		}
+		Ok(())
+	}
+
+	/// Folds every bit of the slice into an accumulator, stopping at the
+	/// first error.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `init`: The initial value of the accumulator.
+	/// - `func`: A function which receives the accumulator, and the index and
+	///   value of a bit, and returns either the next accumulator or an error.
+	///
+	/// # Returns
+	///
+	/// The final accumulator, or the first error that `func` returns.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// //  Counts the set bits, rejecting any run of more than three.
+	/// let check = |run: usize, idx: usize, bit: bool| match (run, bit) {
+	///     (3, true) => Err(idx),
+	///     (_, true) => Ok(run + 1),
+	///     (_, false) => Ok(0),
+	/// };
+	/// let bits = 0b0111_0110u8.bits::<Msb0>();
+	/// assert_eq!(bits.try_fold(0, check), Ok(0));
+	/// let bits = 0b0111_1010u8.bits::<Msb0>();
+	/// assert_eq!(bits.try_fold(0, check), Err(4));
+	/// ```
+	pub fn try_fold<B, F, E>(&self, init: B, mut func: F) -> Result<B, E>
+	where F: FnMut(B, usize, bool) -> Result<B, E> {
+		let mut accum = init;
+		for (idx, bit) in self.iter().enumerate() {
+			accum = func(accum, idx, *bit)?;
+		}
+		Ok(accum)
+	}
+
+	/// Performs “reverse” addition (left to right instead of right to left).
+	///
+	/// This addition interprets the slice, and the other addend, as having its
+	/// least significant bits first in the order and its most significant bits
+	/// last. This is most likely to be numerically useful under a
+	/// `Lsb0` `BitOrder` type.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`: The addition uses `self` as one addend, and writes the
+	///   sum back into `self`.
+	/// - `addend: impl IntoIterator<Item=bool>`: A stream of bits. When this is
+	///   another `BitSlice`, iteration proceeds from left to right.
+	///
+	/// # Return
+	///
+	/// The final carry bit is returned
+	///
+	/// # Effects
+	///
+	/// Starting from index `0` and proceeding upwards until either `self` or
+	/// `addend` expires, the carry-propagated addition of `self[i]` and
+	/// `addend[i]` is written to `self[i]`.
+	///
+	/// ```text
+	///   101111
+	/// + 0010__ (the two missing bits are logically zero)
+	/// --------
+	///   100000 1 (the carry-out is returned)
+	/// ```
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut a = 0b0000_1010u8;
+	/// let     b = 0b0000_1100u8;
+	/// //      s =      1 0110
+	/// let ab = &mut a.bits_mut::<Lsb0>()[.. 4];
+	/// let bb = &b.bits::<Lsb0>()[.. 4];
+	/// let c = ab.add_assign_reverse(bb.iter().copied());
+	/// assert!(c);
+	/// assert_eq!(a, 0b0000_0110u8);
+	/// ```
+	///
+	/// # Performance Notes
+	///
+	/// When using `Lsb0` `BitOrder` types, this can be accelerated by
+	/// delegating the addition to the underlying types. This is a software
+	/// implementation of the [ripple-carry adder], which has `O(n)` runtime in
+	/// the number of bits. The CPU is much faster, as it has access to
+	/// element-wise or vectorized addition operations.
+	///
+	/// If your use case sincerely needs binary-integer arithmetic operations on
+	/// bit sets, please file an issue.
+	///
+	/// [ripple-carry adder]: https://en.wikipedia.org/wiki/Ripple-carry_adder
+	pub fn add_assign_reverse<I>(&mut self, addend: I) -> bool
+	where I: IntoIterator<Item = bool> {
+		//  See AddAssign::add_assign for algorithm details
+		let mut c = false;
+		let len = self.len();
+		let zero = core::iter::repeat(false);
+		for (i, b) in addend.into_iter().chain(zero).enumerate().take(len) {
+			//  The iterator is clamped to the upper bound of `self`.
+			c = unsafe {
+				let a = *self.get_unchecked(i);
+				let (y, z) = crate::rca1(a, b, c);
+				//  Write the sum into `self`
+				self.set_unchecked(i, y);
+				//  Propagate the carry
+				z
+			};
+		}
+		c
+	}
+
+	/// Performs unsigned subtraction in place, reporting the final borrow.
+	///
+	/// This interprets the slice and the subtrahend in the same manner as the
+	/// `AddAssign` implementation: the most significant bit is first, and the
+	/// subtrahend is right-aligned against `self` and zero-extended at the
+	/// left. The subtrahend is only read, never modified.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`: The minuend. The difference is written back into `self`.
+	/// - `subtrahend: impl IntoIterator<Item=bool>`: A stream of bits. It must
+	///   be finite and double-ended, since subtraction operates in reverse. If
+	///   it is longer than `self`, it is truncated to its last `self.len()`
+	///   bits, and its excess front bits are never read.
+	///
+	/// # Returns
+	///
+	/// The borrow out of the most significant bit of `self`. This is `true`
+	/// when the subtrahend, after any truncation, was greater than the
+	/// minuend, and the difference in `self` has wrapped around. The excess
+	/// bits of a longer subtrahend do not produce a borrow, even when they are
+	/// set, so this does not report whether the whole subtrahend was greater.
+	///
+	/// # Effects
+	///
+	/// ```text
+	///   0101
+	/// -   11
+	/// ------
+	///   0010 (no borrow)
+	/// ```
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut a = 0b0101_0000u8;
+	/// let bits = &mut a.bits_mut::<Msb0>()[.. 4];
+	/// assert!(!bits.sub_assign_borrow(bits![1, 1].iter().copied()));
+	/// assert_eq!(a, 0b0010_0000);
+	///
+	/// let bits = &mut a.bits_mut::<Msb0>()[.. 4];
+	/// assert!(bits.sub_assign_borrow(bits![1, 1].iter().copied()));
+	/// assert_eq!(a, 0b1111_0000);
+	///
+	/// //  Only the last four bits, `0001`, are subtracted.
+	/// let long = bits![1, 1, 0, 0, 0, 1];
+	/// let bits = &mut a.bits_mut::<Msb0>()[.. 4];
+	/// assert!(!bits.sub_assign_borrow(long.iter().copied()));
+	/// assert_eq!(a, 0b1110_0000);
+	/// ```
+	pub fn sub_assign_borrow<I>(&mut self, subtrahend: I) -> bool
+	where
+		I: IntoIterator<Item = bool>,
+		I::IntoIter: DoubleEndedIterator,
+	{
+		use core::iter::repeat;
+
+		/* `a - b` is computed as `a + !b + 1`: the subtrahend is inverted as it
+		is read, rather than in place, and the `+ 1` is supplied as the initial
+		carry-in. The zero-extension of the subtrahend therefore appears as
+		ones after inversion. A carry out of the final bit means no borrow was
+		taken.
+		*/
+		let mut c = true;
+		let sub_iter = subtrahend.into_iter().rev().chain(repeat(false));
+		for (i, b) in (0 .. self.len()).rev().zip(sub_iter) {
+			//  Bounds checks are performed in the loop header.
+			let a = unsafe { *self.get_unchecked(i) };
+			let (y, z) = crate::rca1(a, !b, c);
+			unsafe {
+				self.set_unchecked(i, y);
+			}
+			c = z;
+		}
+		!c
+	}
+
+	/// Adds one to the slice, treated as an unsigned integer, in place.
+	///
+	/// As with the arithmetic methods, the most significant bit is first, so
+	/// this steps the slice to its successor in lexicographic order among
+	/// sequences of the same length.
+	///
+	/// The run of `1` bits at the back of the slice is measured an element at
+	/// a time, then cleared, and the `0` bit before it is set.
+	///
+	/// # Returns
+	///
+	/// The carry out of the most significant bit. When this is `true`, every
+	/// bit was `1`, and the slice has wrapped around to all `0`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0b1011_1111u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// assert!(!bits[.. 6].increment());
+	/// assert_eq!(data, 0b1100_0011);
+	/// assert!(data.bits_mut::<Msb0>()[6 ..].increment());
+	/// assert_eq!(data, 0b1100_0000);
+	/// ```
+	pub fn increment(&mut self) -> bool {
+		self.step(true)
+	}
+
+	/// Subtracts one from the slice, treated as an unsigned integer, in
+	/// place.
+	///
+	/// This is the inverse of [`increment`], and steps the slice to its
+	/// predecessor in lexicographic order among sequences of the same length.
+	///
+	/// # Returns
+	///
+	/// The borrow out of the most significant bit. When this is `true`, every
+	/// bit was `0`, and the slice has wrapped around to all `1`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0b1100_0000u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// assert!(!bits[.. 6].decrement());
+	/// assert_eq!(data, 0b1011_1100);
+	/// assert!(data.bits_mut::<Msb0>()[6 ..].decrement());
+	/// assert_eq!(data, 0b1011_1111);
+	/// ```
+	///
+	/// [`increment`]: #method.increment
+	pub fn decrement(&mut self) -> bool {
+		self.step(false)
+	}
+
+	/// Implements `increment`, when `up` is set, and `decrement`, when it is
+	/// not.
+	///
+	/// Both flip the run of `up` bits at the back of the slice, and the one bit
+	/// before the run, if there is one.
+	fn step(&mut self, up: bool) -> bool {
+		let len = self.len();
+		let run = self.trailing_run(up);
+		let (head, tail) = self.split_at_mut(len - run);
+		tail.set_all(!up);
+		match head.last_mut() {
+			Some(mut bit) => {
+				*bit = up;
+				false
+			},
+			None => true,
+		}
+	}
+
+	/// Counts the bits at the back of the slice that are equal to `value`.
+	///
+	/// The slice is walked backwards an element at a time, and only the last
+	/// element that is not entirely `value` is searched bit by bit.
+	fn trailing_run(&self, value: bool) -> usize {
+		let width = T::BITS as usize;
+		let len = self.len();
+		//  The number of bits after the last element boundary in the slice.
+		let tail = (*self.bitptr().head() as usize + len) & (width - 1);
+		let tail = if tail == 0 { width } else { tail };
+		let (body, last) = self.split_at(len - cmp::min(len, tail));
+		let mut run = 0;
+		for chunk in core::iter::once(last).chain(body.rchunks(width)) {
+			let uniform = if value { chunk.all() } else { chunk.not_any() };
+			if !uniform {
+				return run
+					+ chunk.iter().rev().take_while(|bit| **bit == value).count();
+			}
+			run += chunk.len();
+		}
+		run
+	}
+
+	/// Shifts the slice one bit toward the front, in place, through a carry.
+	///
+	/// This is `<<= 1`, except that the bit shifted out of the front is
+	/// returned, and the bit shifted in at the back is `carry_in` rather than
+	/// `0`. Chaining the carry out of one slice into the next shifts them as
+	/// one long number, which is the core step of multi-precision arithmetic
+	/// and of bitwise CRC computation.
+	///
+	/// In `Lsb0` and `Msb0` orders, the elements that the slice fully owns are
+	/// shifted a whole element at a time, with the carry passed between them.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `carry_in`: The bit to shift in at the back.
+	///
+	/// # Returns
+	///
+	/// The bit shifted out of the front. An empty slice returns `carry_in`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0b1000_0001u8, 0b0100_0000];
+	/// let bits = data.bits_mut::<Msb0>();
+	/// let carry = bits[8 ..].shl_through_carry(true);
+	/// assert!(!carry);
+	/// assert!(bits[.. 8].shl_through_carry(carry));
+	/// assert_eq!(data, [0b0000_0010, 0b1000_0001]);
+	/// ```
+	pub fn shl_through_carry(&mut self, carry_in: bool) -> bool {
+		let ascending = match Self::linear_order() {
+			Some(ascending) => ascending,
+			None => return self.shl_carry_bits(carry_in),
+		};
+		let (head, body, tail) = self.split_whole_mut();
+		let carry = tail.shl_carry_bits(carry_in);
+		let carry = body.as_mut_slice().iter_mut().rev().fold(carry, |c, elt| {
+			let e = *elt;
+			let (out, shifted) = if ascending {
+				(e & T::from(1), e >> 1 | T::from(c as u8) << T::MASK)
+			}
+			else {
+				(e >> T::MASK, e << 1 | T::from(c as u8))
+			};
+			*elt = shifted;
+			out != T::FALSE
+		});
+		head.shl_carry_bits(carry)
+	}
+
+	/// Shifts the slice one bit toward the back, in place, through a carry.
+	///
+	/// This is `>>= 1`, except that the bit shifted out of the back is
+	/// returned, and the bit shifted in at the front is `carry_in` rather than
+	/// `0`. It is the inverse of [`shl_through_carry`].
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `carry_in`: The bit to shift in at the front.
+	///
+	/// # Returns
+	///
+	/// The bit shifted out of the back. An empty slice returns `carry_in`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0b0000_0010u8, 0b1000_0001];
+	/// let bits = data.bits_mut::<Msb0>();
+	/// let carry = bits[.. 8].shr_through_carry(true);
+	/// assert!(!carry);
+	/// assert!(bits[8 ..].shr_through_carry(carry));
+	/// assert_eq!(data, [0b1000_0001, 0b0100_0000]);
+	/// ```
+	///
+	/// [`shl_through_carry`]: #method.shl_through_carry
+	pub fn shr_through_carry(&mut self, carry_in: bool) -> bool {
+		let ascending = match Self::linear_order() {
+			Some(ascending) => ascending,
+			None => return self.shr_carry_bits(carry_in),
+		};
+		let (head, body, tail) = self.split_whole_mut();
+		let carry = head.shr_carry_bits(carry_in);
+		let carry = body.as_mut_slice().iter_mut().fold(carry, |c, elt| {
+			let e = *elt;
+			let (out, shifted) = if ascending {
+				(e >> T::MASK, e << 1 | T::from(c as u8))
+			}
+			else {
+				(e & T::from(1), e >> 1 | T::from(c as u8) << T::MASK)
+			};
+			*elt = shifted;
+			out != T::FALSE
+		});
+		tail.shr_carry_bits(carry)
+	}
+
+	/// Implements `shl_through_carry` one bit at a time.
+	fn shl_carry_bits(&mut self, carry_in: bool) -> bool {
+		let len = self.len();
+		if len == 0 {
+			return carry_in;
+		}
+		unsafe {
+			let out = *self.get_unchecked(0);
+			for n in 1 .. len {
+				self.copy_unchecked(n, n - 1);
+			}
+			self.set_unchecked(len - 1, carry_in);
+			out
+		}
+	}
+
+	/// Implements `shr_through_carry` one bit at a time.
+	fn shr_carry_bits(&mut self, carry_in: bool) -> bool {
+		let len = self.len();
+		if len == 0 {
+			return carry_in;
+		}
+		unsafe {
+			let out = *self.get_unchecked(len - 1);
+			for n in (1 .. len).rev() {
+				self.copy_unchecked(n - 1, n);
+			}
+			self.set_unchecked(0, carry_in);
+			out
+		}
+	}
+
+	/// Splits the slice into the partial element at its front, the elements
+	/// it fully owns, and the partial element at its back. Either partial
+	/// part may be empty.
+	fn split_whole_mut(&mut self) -> (&mut Self, &mut Self, &mut Self) {
+		let width = T::BITS as usize;
+		let len = self.len();
+		let head = *self.bitptr().head() as usize;
+		let front = cmp::min(len, (width - head) & (width - 1));
+		let body = (len - front) & !(width - 1);
+		let (front, rest) = self.split_at_mut(front);
+		let (body, back) = rest.split_at_mut(body);
+		(front, body, back)
+	}
+
+	/// Tests whether the ordering numbers the bits of a `T` in a straight
+	/// line, so that shifting the element shifts the indices.
+	///
+	/// # Returns
+	///
+	/// - `Some(true)` if index `n` is at position `n`, as in `Lsb0`.
+	/// - `Some(false)` if index `n` is at position `T::MASK - n`, as in
+	///   `Msb0`.
+	/// - `None` for any other ordering.
+	pub(crate) fn linear_order() -> Option<bool> {
+		let at = |n: u8| *O::at::<T>(n.idx());
+		if (0 .. T::BITS).all(|n| at(n) == n) {
+			Some(true)
+		}
+		else if (0 .. T::BITS).all(|n| at(n) == T::MASK - n) {
+			Some(false)
+		}
+		else {
+			None
+		}
+	}
+
+	/// Converts an unsigned binary number into its reflected Gray code, in
+	/// place.
+	///
+	/// As with the arithmetic methods, the most significant bit is first. Each
+	/// bit of the Gray code is the exclusive-or of the corresponding binary
+	/// bit and the binary bit before it; the first bit is unchanged.
+	///
+	/// In `Lsb0` and `Msb0` orders, each element is encoded with one shift and
+	/// one exclusive-or, and the last bit of the element before it is carried
+	/// in.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0b1011_0000u8;
+	/// let bits = &mut data.bits_mut::<Msb0>()[.. 4];
+	/// bits.to_gray();
+	/// assert_eq!(data, 0b1110_0000);
+	/// ```
+	pub fn to_gray(&mut self) {
+		let lsb = match Self::linear_order() {
+			Some(lsb) => lsb,
+			None => {
+				let mut prev = false;
+				for i in 0 .. self.len() {
+					//  Bounds checks are performed in the loop header.
+					unsafe {
+						let bit = *self.get_unchecked(i);
+						self.set_unchecked(i, bit ^ prev);
+						prev = bit;
+					}
+				}
+				return;
+			},
+		};
+		let mut carry = false;
+		//  Encodes an element whose dead bits are clear, folding in the last
+		//  bit of the element before it, and updates the carry.
+		let mut gray = |elt: T, mask: T| {
+			//  Each bit is combined with the bit before it, which is the next
+			//  lower index.
+			let (prev, first, last) = if lsb {
+				(elt << 1u8, mask & !(mask << 1u8), mask & !(mask >> 1u8))
+			}
+			else {
+				(elt >> 1u8, mask & !(mask >> 1u8), mask & !(mask << 1u8))
+			};
+			let prev = if carry { prev | first } else { prev };
+			carry = elt & last != T::FALSE;
+			(elt | prev) & !(elt & prev)
+		};
+		if self.is_empty() {
+			return;
+		}
+		match self.domain_mut() {
+			DomainMut::Enclave(mut elt) => {
+				elt.store(gray(elt.load(), elt.mask()));
+			},
+			DomainMut::Region { head, body, tail } => {
+				if let Some(mut elt) = head {
+					elt.store(gray(elt.load(), elt.mask()));
+				}
+				for elt in body {
+					*elt = gray(*elt, T::TRUE);
+				}
+				if let Some(mut elt) = tail {
+					elt.store(gray(elt.load(), elt.mask()));
+				}
+			},
+		}
+	}
+
+	/// Converts a reflected Gray code into the unsigned binary number it
+	/// encodes, in place.
+	///
+	/// This is the inverse of [`to_gray`]. Each binary bit is the running
+	/// exclusive-or of every Gray code bit up to and including it.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0b1110_0000u8;
+	/// let bits = &mut data.bits_mut::<Msb0>()[.. 4];
+	/// bits.from_gray();
+	/// assert_eq!(data, 0b1011_0000);
+	/// ```
+	///
+	/// [`to_gray`]: #method.to_gray
+	#[allow(clippy::wrong_self_convention)]
+	pub fn from_gray(&mut self) {
+		self.prefix_xor();
+	}
+
+	/// Replaces each bit with the exclusive-or of itself and every bit before
+	/// it, in place.
+	///
+	/// This is the running parity of the slice. When the set bits of the
+	/// input mark the boundaries between runs, the output has the runs
+	/// alternately cleared and set, which decodes a run-boundary encoding.
+	///
+	/// In `Lsb0` and `Msb0` orders, each element is scanned with
+	/// `log2(T::BITS)` shifts, and the parity of the elements before it is
+	/// carried in.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0b0100_1000u8, 0b0001_0000];
+	/// data.bits_mut::<Msb0>()[1 ..].prefix_xor();
+	/// assert_eq!(data, [0b0111_0000, 0b0001_1111]);
+	/// ```
+	pub fn prefix_xor(&mut self) {
+		self.prefix_scan(Scan::Xor, true);
+	}
+
+	/// Replaces each bit with the or of itself and every bit before it, in
+	/// place.
+	///
+	/// Every bit from the first set bit onward is set, and every bit before it
+	/// is cleared. This propagates a mask forward from its first live bit.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0b0000_0000u8, 0b0010_0100];
+	/// data.bits_mut::<Msb0>()[.. 12].prefix_or();
+	/// assert_eq!(data, [0, 0b0011_0100]);
+	/// ```
+	pub fn prefix_or(&mut self) {
+		self.prefix_scan(Scan::Or, true);
+	}
+
+	/// Fills the intervals between pairs of boundary markers, in place.
+	///
+	/// The set bits of the slice are taken as markers, in alternating pairs:
+	/// the first opens an interval, the second closes it, the third opens the
+	/// next, and so on. Every bit from an opening marker through its closing
+	/// marker is set, and every bit outside the intervals is cleared. If the
+	/// last interval is not closed, it runs to the end of the slice.
+	///
+	/// This is [`prefix_xor`], with the closing markers kept set.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bits = bitvec![Msb0, u8; 0, 1, 0, 0, 1, 0, 1, 1, 0, 1, 0, 0];
+	/// bits.fill_between_markers();
+	/// assert_eq!(bits, bits![0, 1, 1, 1, 1, 0, 1, 1, 0, 1, 1, 1]);
+	/// ```
+	///
+	/// [`prefix_xor`]: #method.prefix_xor
+	pub fn fill_between_markers(&mut self) {
+		self.prefix_scan(Scan::Fill, true);
+	}
+
+	/// Sets every bit that follows a set bit, toward the back of the slice.
+	///
+	/// Each set bit is smeared rightward, as the slice is printed, up to the
+	/// end of the slice. This is the same as [`prefix_or`]. To stop each smear
+	/// at a marker bit instead, use [`smear_right_until`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0b0010_0100u8;
+	/// data.bits_mut::<Msb0>()[.. 6].smear_right();
+	/// assert_eq!(data, 0b0011_1100);
+	/// ```
+	///
+	/// [`prefix_or`]: #method.prefix_or
+	/// [`smear_right_until`]: #method.smear_right_until
+	pub fn smear_right(&mut self) {
+		self.prefix_scan(Scan::Or, true);
+	}
+
+	/// Sets every bit that precedes a set bit, toward the front of the slice.
+	///
+	/// Each set bit is smeared leftward, as the slice is printed, up to the
+	/// start of the slice, so every bit up to the last set bit is set. This is
+	/// the mirror image of [`smear_right`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0b0010_0100u8, 0b1000_0000];
+	/// data.bits_mut::<Msb0>()[2 .. 16].smear_left();
+	/// assert_eq!(data, [0b0011_1111, 0b1000_0000]);
+	/// ```
+	///
+	/// [`smear_right`]: #method.smear_right
+	pub fn smear_left(&mut self) {
+		self.prefix_scan(Scan::Or, false);
+	}
+
+	/// Sets the bits that follow each set bit, toward the back of the slice,
+	/// up to the next marker bit.
+	///
+	/// Each set bit is smeared rightward, as the slice is printed, and the
+	/// smear stops before the next position whose bit in `markers` is set, or
+	/// at the end of the slice. A marker position keeps its own bit, and if
+	/// that bit is set, it starts a new smear.
+	///
+	/// In `Lsb0` and `Msb0` orders, when both slices begin at the same bit
+	/// index, each element is smeared with `log2(T::BITS)` shifts, and the
+	/// smear is carried from one element into the next. Otherwise, this works
+	/// bit by bit.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `markers`: The positions that stop a smear. It must have the same
+	///   length as `self`.
+	///
+	/// # Panics
+	///
+	/// This panics if `markers` has a different length than `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bits = bitvec![Msb0, u8; 0, 1, 0, 0, 0, 0, 1, 0, 0, 0];
+	/// let markers = bits![Msb0, u8; 0, 0, 0, 1, 0, 1, 0, 0, 1, 0];
+	/// bits.smear_right_until(markers);
+	/// assert_eq!(bits, bits![0, 1, 1, 0, 0, 0, 1, 1, 0, 0]);
+	/// ```
+	pub fn smear_right_until(&mut self, markers: &Self) {
+		self.smear_until(markers, true);
+	}
+
+	/// Sets the bits that precede each set bit, toward the front of the slice,
+	/// up to the previous marker bit.
+	///
+	/// This is the mirror image of [`smear_right_until`]: each set bit is
+	/// smeared leftward, as the slice is printed, and the smear stops after
+	/// the previous position whose bit in `markers` is set, or at the start of
+	/// the slice.
+	///
+	/// # Panics
+	///
+	/// This panics if `markers` has a different length than `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bits = bitvec![Msb0, u8; 0, 0, 0, 1, 0, 0, 0, 0, 1, 0];
+	/// let markers = bits![Msb0, u8; 0, 1, 0, 0, 0, 1, 0, 0, 0, 0];
+	/// bits.smear_left_until(markers);
+	/// assert_eq!(bits, bits![0, 0, 1, 1, 0, 0, 1, 1, 1, 0]);
+	/// ```
+	///
+	/// [`smear_right_until`]: #method.smear_right_until
+	pub fn smear_left_until(&mut self, markers: &Self) {
+		self.smear_until(markers, false);
+	}
+
+	/// Implements the prefix scans and the smears.
+	///
+	/// The scan runs from the front of the slice to the back when `forward` is
+	/// set, and from the back to the front when it is not.
+	fn prefix_scan(&mut self, op: Scan, forward: bool) {
+		let lsb = match Self::linear_order() {
+			Some(lsb) => lsb,
+			None => {
+				let len = self.len();
+				let mut acc = false;
+				for n in 0 .. len {
+					let i = if forward { n } else { len - 1 - n };
+					//  Bounds checks are performed in the loop header.
+					unsafe {
+						let bit = *self.get_unchecked(i);
+						acc = if op == Scan::Or { acc | bit } else { acc ^ bit };
+						self.set_unchecked(i, acc || op == Scan::Fill && bit);
+					}
+				}
+				return;
+			},
+		};
+		let combine = |a: T, b: T| {
+			if op == Scan::Or {
+				a | b
+			}
+			else {
+				(a | b) & !(a & b)
+			}
+		};
+		let mut carry = false;
+		//  Scans an element whose dead bits are clear, folding in the carry
+		//  from the elements before it, and updates the carry.
+		let mut scan = |elt: T, mask: T| {
+			let mut acc = elt;
+			let mut shift = 1;
+			while shift < T::BITS {
+				//  Scanning forward moves bits toward higher indices.
+				let moved = if lsb == forward {
+					acc << shift
+				}
+				else {
+					acc >> shift
+				};
+				acc = combine(acc, moved);
+				shift <<= 1;
+			}
+			if carry {
+				acc = combine(acc, mask);
+			}
+			carry = match op {
+				Scan::Or => carry || elt != T::FALSE,
+				Scan::Xor | Scan::Fill => {
+					carry ^ (T::count_ones(elt) & 1 == 1)
+				},
+			};
+			if op == Scan::Fill { acc | elt } else { acc }
+		};
+		if self.is_empty() {
+			return;
+		}
+		match self.domain_mut() {
+			DomainMut::Enclave(mut elt) => {
+				elt.store(scan(elt.load(), elt.mask()));
+			},
+			DomainMut::Region {
+				mut head,
+				body,
+				mut tail,
+			} => {
+				let (first, last) = if forward {
+					(&mut head, &mut tail)
+				}
+				else {
+					(&mut tail, &mut head)
+				};
+				if let Some(elt) = first {
+					elt.store(scan(elt.load(), elt.mask()));
+				}
+				if forward {
+					for elt in body.iter_mut() {
+						*elt = scan(*elt, T::TRUE);
+					}
+				}
+				else {
+					for elt in body.iter_mut().rev() {
+						*elt = scan(*elt, T::TRUE);
+					}
+				}
+				if let Some(elt) = last {
+					elt.store(scan(elt.load(), elt.mask()));
+				}
+			},
+		}
+	}
+
+	/// Implements the smears that stop at marker bits.
+	///
+	/// The smear runs from the front of the slice to the back when `forward`
+	/// is set, and from the back to the front when it is not.
+	fn smear_until(&mut self, markers: &Self, forward: bool) {
+		let len = self.len();
+		assert_eq!(len, markers.len(), "Smear markers must have equal length");
+		let lsb = match Self::linear_order() {
+			Some(lsb) if self.bitptr().head() == markers.bitptr().head() => lsb,
+			_ => {
+				let mut carry = false;
+				for n in 0 .. len {
+					let i = if forward { n } else { len - 1 - n };
+					//  Bounds checks are performed in the loop header.
+					unsafe {
+						let bit = *self.get_unchecked(i)
+							|| carry && !*markers.get_unchecked(i);
+						self.set_unchecked(i, bit);
+						carry = bit;
+					}
+				}
+				return;
+			},
+		};
+		//  Smearing forward moves bits toward higher indices.
+		let up = lsb == forward;
+		let shift = |elt: T, by: u8| if up { elt << by } else { elt >> by };
+		//  The positions at which a smear enters and leaves an element.
+		let entry = !shift(T::TRUE, 1);
+		let exit = !if up { T::TRUE >> 1u8 } else { T::TRUE << 1u8 };
+		let mut carry = false;
+		//  Smears an element whose dead bits are clear, starting with the
+		//  carry from the elements before it, and updates the carry.
+		let mut smear = |elt: T, marks: T| {
+			let mut open = !marks;
+			let mut acc = if carry { elt | (entry & open) } else { elt };
+			let mut by = 1;
+			while by < T::BITS {
+				acc = acc | (open & shift(acc, by));
+				open = open & shift(open, by);
+				by <<= 1;
+			}
+			carry = acc & exit != T::FALSE;
+			acc
+		};
+		if self.is_empty() {
+			return;
+		}
+		match (self.domain_mut(), markers.domain()) {
+			(DomainMut::Enclave(mut elt), Domain::Enclave(marks)) => {
+				elt.store(smear(elt.load(), marks.load()));
+			},
+			(
+				DomainMut::Region {
+					mut head,
+					body,
+					mut tail,
+				},
+				Domain::Region {
+					head: mh,
+					body: mb,
+					tail: mt,
+				},
+			) => {
+				let (first, last, mf, ml) = if forward {
+					(&mut head, &mut tail, mh, mt)
+				}
+				else {
+					(&mut tail, &mut head, mt, mh)
+				};
+				if let (Some(elt), Some(marks)) = (first, mf) {
+					elt.store(smear(elt.load(), marks.load()));
+				}
+				if forward {
+					for (elt, marks) in body.iter_mut().zip(mb) {
+						*elt = smear(*elt, *marks);
+					}
+				}
+				else {
+					for (elt, marks) in body.iter_mut().zip(mb).rev() {
+						*elt = smear(*elt, *marks);
+					}
+				}
+				if let (Some(elt), Some(marks)) = (last, ml) {
+					elt.store(smear(elt.load(), marks.load()));
+				}
+			},
+			_ => unreachable!("Slices with equal heads and lengths"),
+		}
+	}
+
+	/// Iterates over the slice in bit-reversed index order.
+	///
+	/// This is a view, and does not move any bits. Position `n` of the
+	/// iteration yields the bit at the index whose `log2(len)`-digit binary
+	/// representation is `n` reversed. For the reversal of each memory
+	/// element, rather than of indices, use [`reverse_bits_per_element`].
+	///
+	/// # Panics
+	///
+	/// This panics if the length of `self` is not zero or a power of two.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = 0b1100_0000u8;
+	/// let bits = data.bits::<Msb0>();
+	/// let order: BitVec = bits.iter_bit_reversed().copied().collect();
+	/// //  Indices 0 and 1 are produced at positions 0 and 4.
+	/// assert_eq!(order, bitvec![1, 0, 0, 0, 1, 0, 0, 0]);
+	/// ```
+	///
+	/// [`reverse_bits_per_element`]: #method.reverse_bits_per_element
+	pub fn iter_bit_reversed(&self) -> BitReversed<'_, O, T> {
+		let len = self.len();
+		assert!(
+			len & len.wrapping_sub(1) == 0,
+			"Bit-reversed iteration requires a power-of-two length, not {}",
+			len,
+		);
+		BitReversed {
+			inner: self,
+			front: 0,
+			back: len,
+		}
+	}
+
+	/// Splits the slice into consecutive chunks with a bounded number of set
+	/// bits in each.
+	///
+	/// Each chunk is as long as it can be without holding more than `weight`
+	/// set bits, so every chunk but the last holds exactly `weight` set bits,
+	/// and every chunk but the first begins with a set bit. This divides sparse
+	/// work, where each set bit is one task, evenly between workers.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `weight`: The most set bits that any chunk may hold.
+	///
+	/// # Returns
+	///
+	/// An iterator over subslices of `self`, which together cover it in order.
+	///
+	/// # Panics
+	///
+	/// This panics if `weight` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bits = bits![Msb0, u8; 0, 1, 1, 0, 1, 0, 0, 1, 1, 1, 0];
+	/// let lens = bits
+	///     .chunks_by_weight(2)
+	///     .map(|chunk| chunk.len())
+	///     .collect::<Vec<_>>();
+	/// assert_eq!(lens, [4, 4, 3]);
+	/// ```
+	pub fn chunks_by_weight(&self, weight: usize) -> ChunksByWeight<'_, O, T> {
+		assert_ne!(weight, 0, "Chunk weight cannot be zero");
+		ChunksByWeight {
+			inner: self,
+			weight,
+		}
+	}
+
+	/// Produces write guards for the bits at the positions that a mask sets.
+	///
+	/// This gathers a scattered subset of the slice for update. Only the
+	/// selected bits are written back when their guards drop; the others are
+	/// never touched. The mask is searched 64 bits at a time, so a sparse mask
+	/// costs one step per 64 cleared bits rather than one per bit.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `mask`: A slice of the same length as `self`, whose set bits select
+	///   the positions to produce.
+	///
+	/// # Returns
+	///
+	/// An iterator over guards for the selected bits, in order.
+	///
+	/// # Panics
+	///
+	/// This panics if `mask` is not the same length as `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0b1100_1100u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// for mut bit in bits.select_mut(0b0110_0110u8.bits::<Msb0>()) {
+	///     *bit = !*bit;
+	/// }
+	/// assert_eq!(data, 0b1010_1010);
+	/// ```
+	pub fn select_mut<'a, P, U>(
+		&'a mut self,
+		mask: &'a BitSlice<P, U>,
+	) -> SelectMut<'a, O, T, P, U>
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		assert_eq!(
+			self.len(),
+			mask.len(),
+			"Selection requires a mask of equal length",
+		);
+		SelectMut { inner: self, mask }
+	}
+
+	/// Splits the slice into mutable chunks that never share a memory element.
+	///
+	/// `chunks_mut` splits at exact bit indices, so two adjacent chunks will
+	/// usually both refer to the element that holds the split point. When the
+	/// `atomic` feature is enabled, `BitSlice` routes every write through
+	/// atomic read/modify/write instructions and this is harmless; without it,
+	/// handing such chunks to separate threads is a data race on the shared
+	/// element.
+	///
+	/// This iterator instead moves each split point forward to the next element
+	/// boundary in memory. Each chunk is at least `width` bits long, and no more
+	/// than `width + T::BITS - 1`, except for the last, which is whatever
+	/// remains. The chunks are safe to modify concurrently in any build
+	/// configuration.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `width`: The minimum number of bits in each chunk.
+	///
+	/// # Returns
+	///
+	/// An iterator over element-disjoint mutable subslices of `self`.
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0u8; 4];
+	/// let bits = &mut data.bits_mut::<Msb0>()[2 ..];
+	/// let lens = bits
+	///     .chunks_mut_disjoint(10)
+	///     .map(|chunk| chunk.len())
+	///     .collect::<Vec<_>>();
+	/// //  The first chunk is 2 .. 16; the next begins at element 2.
+	/// assert_eq!(lens, [14, 16]);
+	/// ```
+	pub fn chunks_mut_disjoint(
+		&mut self,
+		width: usize,
+	) -> ChunksMutDisjoint<'_, O, T> {
+		assert_ne!(width, 0, "Chunk width cannot be zero");
+		ChunksMutDisjoint { inner: self, width }
+	}
+
+	/// Divides one mutable slice into two at the element boundary nearest to
+	/// an index.
+	///
+	/// [`split_at_mut`] divides at exactly `mid`, and unless `mid` falls on an
+	/// element boundary, both halves refer to the element holding it. This
+	/// moves the split point to whichever element boundary in memory is
+	/// closest to `mid` (rounding up when `mid` is exactly between two), so
+	/// that the two halves never share an element. The halves can then be
+	/// modified concurrently without atomic instructions, and do not contend
+	/// for the same element when the `atomic` feature is enabled.
+	///
+	/// If the nearest boundary lies outside the slice, the split is made at
+	/// the nearer end of the slice instead, and one half is empty.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `mid`: The index near which to split.
+	///
+	/// # Returns
+	///
+	/// - `[.. split]`
+	/// - `[split ..]`
+	/// - `split`: The index at which the slice was actually divided.
+	///
+	/// # Panics
+	///
+	/// This panics if `mid` is greater than `self.len()`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0u8; 3];
+	/// let bits = &mut data.bits_mut::<Msb0>()[4 ..];
+	///
+	/// //  Index 5 is bit 9 in memory, nearest the boundary at bit 8.
+	/// let (left, right, split) = bits.split_at_mut_aligned(5);
+	/// assert_eq!(split, 4);
+	/// left.set_all(true);
+	/// right.set_all(false);
+	/// assert_eq!(data, [0x0F, 0, 0]);
+	/// ```
+	///
+	/// [`split_at_mut`]: #method.split_at_mut
+	pub fn split_at_mut_aligned(
+		&mut self,
+		mid: usize,
+	) -> (&mut Self, &mut Self, usize) {
+		let len = self.len();
+		assert!(mid <= len, "Index {} out of bounds: {}", mid, len);
+		let head = *self.bitptr().head() as usize;
+		let width = T::BITS as usize;
+
+		//  Work in bit positions measured from the start of the first element.
+		let pos = head + mid;
+		let over = pos % width;
+		let target = if over < width - over {
+			pos - over
+		}
+		else {
+			pos - over + width
+		};
+		let split = cmp::min(cmp::max(target, head), head + len) - head;
+		let (left, right) = unsafe { self.split_at_mut_unchecked(split) };
+		(left, right, split)
+	}
+
+	/// Tests whether two bit slices refer to any of the same bits in memory.
+	///
+	/// This is exact to the bit: two slices that each refer to a different
+	/// part of the same memory element do not overlap, though they do share
+	/// that element (see [`shares_elements`]). Empty slices never overlap
+	/// anything.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `other`: Another bit slice with the same type parameters. Slices with
+	///   different `BitOrder` parameters map indices to different bits within
+	///   an element, so bit-level overlap is only meaningful within one
+	///   ordering.
+	///
+	/// # Returns
+	///
+	/// Whether any bit is visible through both `self` and `other`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0u8; 2];
+	/// let bits = data.bits::<Msb0>();
+	/// assert!(bits[.. 6].overlaps(&bits[5 ..]));
+	/// assert!(!bits[.. 5].overlaps(&bits[5 ..]));
+	/// assert!(bits[.. 5].shares_elements(&bits[5 ..]));
+	/// ```
+	///
+	/// [`shares_elements`]: #method.shares_elements
+	pub fn overlaps(&self, other: &Self) -> bool {
+		/* Each slice is a half-open span from `(address, head)` to the
+		`(address, index)` pair just past its last bit. Since both slices use
+		the same element type, these pairs order correctly when compared
+		lexicographically.
+		*/
+		fn span<O, T>(bits: &BitSlice<O, T>) -> ((usize, usize), (usize, usize))
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			let bp = bits.bitptr();
+			let (addr, head) = (bp.pointer().u(), *bp.head() as usize);
+			let end = head + bp.len();
+			let width = T::BITS as usize;
+			let size = mem::size_of::<T>();
+			(
+				(addr, head),
+				(addr + end / width * size, end % width),
+			)
+		}
+
+		if self.is_empty() || other.is_empty() {
+			return false;
+		}
+		let (a_start, a_end) = span(self);
+		let (b_start, b_end) = span(other);
+		a_start < b_end && b_start < a_end
+	}
+
+	/// Tests whether two bit slices refer to any of the same memory elements.
+	///
+	/// This is coarser than [`overlaps`]: it is true whenever any byte of
+	/// memory is touched by both slices, including when the slices each hold a
+	/// different part of one shared edge element. Without the `atomic`
+	/// feature, concurrent writes through two slices that share an element are
+	/// a data race even when the slices do not overlap.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `other`: Another bit slice of any type parameters.
+	///
+	/// # Returns
+	///
+	/// Whether the memory regions under `self` and `other` intersect. Empty
+	/// slices do not touch memory, and share elements with nothing.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0u8; 3];
+	/// let bits = data.bits::<Lsb0>();
+	/// assert!(bits[.. 12].shares_elements(&bits[12 ..]));
+	/// assert!(!bits[.. 16].shares_elements(&bits[16 ..]));
+	/// ```
+	///
+	/// [`overlaps`]: #method.overlaps
+	pub fn shares_elements<P, U>(&self, other: &BitSlice<P, U>) -> bool
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		if self.is_empty() || other.is_empty() {
+			return false;
+		}
+		let (a, b) = (self.bitptr(), other.bitptr());
+		let a_start = a.pointer().u();
+		let a_end = a_start + a.elements() * mem::size_of::<T>();
+		let b_start = b.pointer().u();
+		let b_end = b_start + b.elements() * mem::size_of::<U>();
+		a_start < b_end && b_start < a_end
+	}
+
+	/// Views the memory elements underneath the slice, split into the wholly
+	/// owned body and the partially owned edges.
+	///
+	/// This is the entry point for writing element-wise kernels outside the
+	/// crate. See [`Domain`] for details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::{domain::Domain, prelude::*};
+	///
+	/// let data = [1u8, 2, 3, 4];
+	/// let bits = &data.bits::<Lsb0>()[4 .. 28];
+	/// if let Domain::Region { head, body, tail } = bits.domain() {
+	///     assert_eq!(head.unwrap().load(), 0);
+	///     assert_eq!(body, &[2, 3]);
+	///     assert_eq!(tail.unwrap().load(), 4);
+	/// }
+	/// ```
+	///
+	/// [`Domain`]: ../domain/enum.Domain.html
+	pub fn domain(&self) -> Domain<'_, O, T> {
+		Domain::new(self)
+	}
+
+	/// Views the memory elements underneath the slice for modification, split
+	/// into the wholly owned body and the partially owned edges.
+	///
+	/// See [`DomainMut`] for details.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::{domain::DomainMut, prelude::*};
+	///
+	/// let mut data = 0u8;
+	/// let bits = &mut data.bits_mut::<Msb0>()[2 .. 6];
+	/// if let DomainMut::Enclave(mut elem) = bits.domain_mut() {
+	///     assert_eq!(elem.mask(), 0b0011_1100);
+	///     elem.store(!0);
+	/// }
+	/// assert_eq!(data, 0b0011_1100);
+	/// ```
+	///
+	/// [`DomainMut`]: ../domain/enum.DomainMut.html
+	pub fn domain_mut(&mut self) -> DomainMut<'_, O, T> {
+		DomainMut::new(self)
+	}
+
+	/// Iterates over every memory element underneath the slice, paired with
+	/// the mask of its bits that are part of the slice.
+	///
+	/// The edge elements are included, and are yielded with partial masks.
+	/// See [`RawElements`] for details.
+	///
+	/// # Safety
+	///
+	/// The elements are yielded through their shared-mutability access type,
+	/// which permits writing to them. Callers must not write to any bit that
+	/// is outside the mask paired with its element, as other slices may own
+	/// it, and must not write to any bit at all unless they hold the slice
+	/// by `&mut` reference.
+	///
+	/// # Examples
+	///
+	/// This computes the population count of the `AND` of two slices in one
+	/// pass over their memory, without writing to either.
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	/// use radium::Radium;
+	/// use std::sync::atomic::Ordering;
+	///
+	/// let a = [0xF0u8, 0xFF, 0x0F];
+	/// let b = [0x3Cu8, 0x0F, 0xFF];
+	/// let (a, b) = (&a.bits::<Msb0>()[2 .. 22], &b.bits::<Msb0>()[2 .. 22]);
+	///
+	/// let count = unsafe { a.raw_elements().zip(b.raw_elements()) }
+	///     .map(|((x, m), (y, _))| {
+	///         (x.load(Ordering::Relaxed) & y.load(Ordering::Relaxed) & m)
+	///             .count_ones()
+	///     })
+	///     .sum::<u32>();
+	/// assert_eq!(count, 8);
+	/// ```
+	///
+	/// [`RawElements`]: ../domain/struct.RawElements.html
+	#[cfg(feature = "unsafe-word-ops")]
+	pub unsafe fn raw_elements(&self) -> RawElements<'_, O, T> {
+		RawElements::new(self)
+	}
+
+	/// Accesses the backing storage of the `BitSlice` as a slice of its
+	/// elements.
+	///
+	/// This will not include partially-owned edge elements, as they may be
+	/// contended by other slice handles.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// A slice of all the elements that the `BitSlice` uses for storage.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let src = [1u8, 66];
+	/// let bits = src.bits::<Msb0>();
+	///
+	/// let accum = bits
+	///     .as_slice()
+	///     .iter()
+	///     .map(|elt| elt.count_ones())
+	///     .sum::<u32>();
+	/// assert_eq!(accum, 3);
+	/// ```
+	pub fn as_slice(&self) -> &[T] {
+		unsafe {
+			BitAccess::as_slice_mut(match self.bitptr().domain() {
+				BitDomain::Empty | BitDomain::Minor(..) => &[],
+				BitDomain::PartialHead(_, _, body)
+				| BitDomain::PartialTail(body, ..)
+				| BitDomain::Major(_, _, body, ..)
+				| BitDomain::Spanning(body) => body,
+			})
+		}
+	}
+
+	/// Accesses the underlying store.
+	///
+	/// This will not include partially-owned edge elements, as they may be
+	/// contended by other slice handles.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut src = [1u8, 64];
+	/// let bits = src.bits_mut::<Msb0>();
+	/// for elt in bits.as_mut_slice() {
+	///     *elt |= 2;
+	/// }
+	/// assert_eq!(&[3, 66], bits.as_slice());
+	/// ```
+	pub fn as_mut_slice(&mut self) -> &mut [T] {
+		unsafe {
+			BitAccess::as_slice_mut(match self.bitptr().domain() {
+				BitDomain::Empty | BitDomain::Minor(..) => &[],
+				BitDomain::PartialHead(_, _, body)
+				| BitDomain::PartialTail(body, ..)
+				| BitDomain::Major(_, _, body, ..)
+				| BitDomain::Spanning(body) => body,
+			})
+		}
+	}
+
+	/// Views the same memory under a different `BitOrder`, without copying.
+	///
+	/// A slice that covers whole elements, from the first bit of its first
+	/// element to the last bit of its last, covers the same memory under
+	/// every ordering, so only the meaning of each index changes. A slice
+	/// with partial edge elements would cover different bits of them under
+	/// another ordering, and might reach bits that other handles own, so it
+	/// cannot be recast.
+	///
+	/// # Type Parameters
+	///
+	/// - `P`: The ordering under which to view the memory.
+	///
+	/// # Returns
+	///
+	/// The slice under the new ordering, or `None` if it does not cover whole
+	/// elements. An empty slice always succeeds.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = [0b1000_0001u8, 0b0000_0010];
+	/// let bits = data.bits::<Msb0>();
+	/// let lsb0 = bits.recast_order::<Lsb0>().unwrap();
+	/// assert_eq!(lsb0.as_slice(), bits.as_slice());
+	/// assert!(bits[14] && lsb0[9]);
+	///
+	/// assert!(bits[.. 12].recast_order::<Lsb0>().is_none());
+	/// assert!(bits[8 ..].recast_order::<Lsb0>().is_some());
+	/// ```
+	pub fn recast_order<P>(&self) -> Option<&BitSlice<P, T>>
+	where P: BitOrder {
+		match self.bitptr().domain() {
+			BitDomain::Empty => Some(BitSlice::empty()),
+			BitDomain::Spanning(_) => Some(self.bitptr().into_bitslice()),
+			_ => None,
+		}
+	}
+
+	/// Views the same memory under a different `BitOrder`, without copying,
+	/// and with write access.
+	///
+	/// See [`recast_order`] for the requirements.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0u16; 2];
+	/// let bits = &mut data.bits_mut::<Lsb0>()[16 ..];
+	/// bits.recast_order_mut::<Msb0>().unwrap().set(0, true);
+	/// assert_eq!(data, [0, 0x8000]);
+	/// ```
+	///
+	/// [`recast_order`]: #method.recast_order
+	pub fn recast_order_mut<P>(&mut self) -> Option<&mut BitSlice<P, T>>
+	where P: BitOrder {
+		match self.bitptr().domain() {
+			BitDomain::Empty => Some(BitSlice::empty_mut()),
+			BitDomain::Spanning(_) => Some(self.bitptr().into_bitslice_mut()),
+			_ => None,
+		}
+	}
+
+	/// Reads a single bit with a volatile load of its element.
+	///
+	/// Ordinary bit access may be merged, cached, or removed by the compiler
+	/// when it can prove the result unused. When the slice covers memory-mapped
+	/// device registers, each access to an element must instead reach the
+	/// device exactly once, in program order. The `*_volatile` methods provide
+	/// this: each reads or writes every element they touch exactly once, with
+	/// `core::ptr::read_volatile` and `core::ptr::write_volatile`.
+	///
+	/// Volatile accesses are not atomic, even when the `atomic` feature is
+	/// enabled, and so these methods are `unsafe`.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `index`: The bit to read.
+	///
+	/// # Returns
+	///
+	/// The value of the bit at `index`.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	///
+	/// # Safety
+	///
+	/// No other thread may write to the element holding the bit during the
+	/// call, including through a slice that shares that element with this
+	/// one, such as the other half of a [`split_at_mut`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let status = 0b0100_0000u8;
+	/// assert!(unsafe { status.bits::<Msb0>().get_volatile(1) });
+	/// ```
+	///
+	/// [`split_at_mut`]: #method.split_at_mut
+	pub unsafe fn get_volatile(&self, index: usize) -> bool {
+		let len = self.len();
+		assert!(index < len, "Index {} out of bounds: {}", index, len);
+		let bp = self.bitptr();
+		let (elt, bit) = bp.head().offset(index as isize);
+		let elem = &*bp.pointer().a().offset(elt);
+		BitStore::get::<O>(&elem.load_volatile(), bit)
+	}
+
+	/// Writes a single bit with a volatile read/modify/write of its element.
+	///
+	/// The other bits of the element are written back with the values just
+	/// read. This is not atomic, even when the `atomic` feature is enabled.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The bit to write.
+	/// - `value`: The new value of the bit.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	///
+	/// # Safety
+	///
+	/// No other thread may read or write the element holding the bit during
+	/// the call, including through a slice that shares that element with this
+	/// one, such as the other half of a [`split_at_mut`]. A concurrent write
+	/// to a neighboring bit would race with this one, and be lost.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut control = 0u8;
+	/// unsafe {
+	///     control.bits_mut::<Lsb0>().set_volatile(7, true);
+	/// }
+	/// assert_eq!(control, 0x80);
+	/// ```
+	///
+	/// [`split_at_mut`]: #method.split_at_mut
+	pub unsafe fn set_volatile(&mut self, index: usize, value: bool) {
+		let len = self.len();
+		assert!(index < len, "Index {} out of bounds: {}", index, len);
+		let bp = self.bitptr();
+		let (elt, bit) = bp.head().offset(index as isize);
+		let elem = &*bp.pointer().a().offset(elt);
+		let mut tmp = elem.load_volatile();
+		BitStore::set::<O>(&mut tmp, bit, value);
+		elem.store_volatile(tmp);
+	}
+
+	/// Loads the slice as an integer, with one volatile load of each element.
+	///
+	/// This has the same behavior as [`BitField::load`], except that the
+	/// backing elements are each read exactly once, with a volatile load,
+	/// before any bits are extracted.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The same value as `self.load::<U>()`.
+	///
+	/// # Panics
+	///
+	/// This panics if `self` is empty, or wider than a `U` element.
+	///
+	/// # Safety
+	///
+	/// No other thread may write to any element under the slice during the
+	/// call, including the edge elements that it shares with other slices.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let reg = 0x5Au8;
+	/// let val = unsafe { reg.bits::<Lsb0>()[4 ..].load_volatile::<u8>() };
+	/// assert_eq!(val, 0x5);
+	/// ```
+	///
+	/// [`BitField::load`]: ../fields/trait.BitField.html#method.load
+	pub unsafe fn load_volatile<U>(&self) -> U
+	where
+		U: BitStore,
+		Self: BitField,
+	{
+		let len = self.len();
+		assert!(
+			len > 0 && len <= U::BITS as usize,
+			"Cannot load {} bits from a {}-bit region",
+			U::BITS,
+			len,
+		);
+		let bp = self.bitptr();
+		//  The widest `U` is 64 bits, which spans at most nine bytes.
+		let mut buf = [T::FALSE; 9];
+		for (slot, elem) in buf.iter_mut().zip(bp.as_access_slice()) {
+			*slot = elem.load_volatile();
+		}
+		BitPtr::new_unchecked(buf.as_ptr(), bp.head(), len)
+			.into_bitslice::<O>()
+			.load()
+	}
+
+	/// Stores an integer into the slice, with one volatile load and one
+	/// volatile store of each element.
+	///
+	/// This has the same behavior as [`BitField::store`], except that each
+	/// backing element is read once with a volatile load, modified locally,
+	/// and then written once with a volatile store. Bits of the edge elements
+	/// outside the slice are written back with the values just read. This is
+	/// not atomic, even when the `atomic` feature is enabled.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `value`: A value whose `self.len()` least significant bits will be
+	///   stored into `self`.
+	///
+	/// # Panics
+	///
+	/// This panics if `self` is empty, or wider than a `U` element.
+	///
+	/// # Safety
+	///
+	/// No other thread may read or write any element under the slice during
+	/// the call, including the edge elements that it shares with other slices.
+	/// A concurrent write to a bit outside the slice in an edge element would
+	/// race with the write-back of that element, and be lost.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut reg = 0u8;
+	/// unsafe {
+	///     reg.bits_mut::<Msb0>()[2 .. 6].store_volatile(0xFu8);
+	/// }
+	/// assert_eq!(reg, 0b0011_1100);
+	/// ```
+	///
+	/// [`BitField::store`]: ../fields/trait.BitField.html#method.store
+	pub unsafe fn store_volatile<U>(&mut self, value: U)
+	where
+		U: BitStore,
+		Self: BitField,
+	{
+		let len = self.len();
+		assert!(
+			len > 0 && len <= U::BITS as usize,
+			"Cannot store {} bits into a {}-bit region",
+			U::BITS,
+			len,
+		);
+		let bp = self.bitptr();
+		let elems = bp.as_access_slice();
+		let mut buf = [T::FALSE; 9];
+		for (slot, elem) in buf.iter_mut().zip(elems) {
+			*slot = elem.load_volatile();
+		}
+		BitPtr::new_unchecked(buf.as_mut_ptr(), bp.head(), len)
+			.into_bitslice_mut::<O>()
+			.store(value);
+		for (slot, elem) in buf.iter().zip(elems) {
+			elem.store_volatile(*slot);
+		}
+	}
+
+	/// Clears every bit of the slice with volatile stores, so that the
+	/// clearing is not removed even when the memory is never read again.
+	///
+	/// This is meant for scrubbing secret bits before their memory is released
+	/// or reused. The elements wholly inside the slice are overwritten with
+	/// zero by volatile stores. The edge elements, which the slice may share
+	/// with other handles, have only the bits of the slice cleared. When the
+	/// `atomic` feature is enabled, this is the same atomic read-modify-write
+	/// as [`BitSlice::set`], so that concurrent writes to their other bits are
+	/// not lost. Otherwise, the edges are read and written back with volatile
+	/// accesses, which cannot race because the slice is not `Sync`.
+	///
+	/// A compiler fence follows the stores, so that later accesses to the
+	/// memory are not reordered before them.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut key = [0xA5u8, 0x3C, 0xFF];
+	/// key.bits_mut::<Msb0>()[4 .. 20].secure_clear();
+	/// assert_eq!(key, [0xA0, 0x00, 0x0F]);
+	/// ```
+	///
+	/// [`BitSlice::set`]: #method.set
+	pub fn secure_clear(&mut self) {
+		let elems = self.bitptr().as_access_slice();
+		let clear = |elem: &T::Access, mask: T| {
+			#[cfg(feature = "atomic")]
+			elem.clear_bits(!mask);
+			//  `Cell` is not `Sync`, so no other thread can write the element
+			//  between the volatile load and store.
+			#[cfg(not(feature = "atomic"))]
+			unsafe {
+				elem.store_volatile(elem.load_volatile() & !mask);
+			}
+		};
+		match self.domain_mut() {
+			DomainMut::Enclave(elem) => clear(&elems[0], elem.mask()),
+			DomainMut::Region { head, body, tail } => {
+				if let Some(elem) = head {
+					clear(&elems[0], elem.mask());
+				}
+				for elt in body {
+					unsafe {
+						ptr::write_volatile(elt, T::FALSE);
+					}
+				}
+				if let Some(elem) = tail {
+					clear(&elems[elems.len() - 1], elem.mask());
+				}
+			},
+		}
+		atomic::compiler_fence(Ordering::SeqCst);
+	}
+
+	/// Interprets the whole slice as an unsigned integer.
 	///
-	/// It is impossible to implement `IndexMut` on `BitSlice`, because bits do
-	/// not have addresses, so there can be no `&mut u1`. This method allows the
-	/// client to receive an enumerated bit, and provide a new bit to set at
-	/// each index.
+	/// The bits are loaded as by [`BitField::load`], so the slice’s
+	/// `BitOrder` determines which of its bits is the least significant. Unlike
+	/// `load`, this rejects slices that do not fit in `U` rather than
+	/// panicking, and reads the empty slice as zero.
 	///
 	/// # Parameters
 	///
-	/// - `&mut self`
-	/// - `func`: A function which receives a `(usize, bool)` pair of index and
-	///   value, and returns a bool. It receives the bit at each position, and
-	///   the return value is written back at that position.
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The value of `self` zero-extended into a `U`, or `None` if `self` is
+	/// wider than `U`.
 	///
 	/// # Examples
 	///
 	/// ```rust
 	/// use bitvec::prelude::*;
 	///
-	/// let mut src = 0u8;
-	/// let bits = src.bits_mut::<Msb0>();
-	/// bits.for_each(|idx, _bit| idx % 3 == 0);
-	/// assert_eq!(src, 0b1001_0010);
+	/// let data = 0b1011_0000u8;
+	/// let bits = data.bits::<Msb0>();
+	/// assert_eq!(bits[.. 4].as_uint::<u8>(), Some(0b1011));
+	/// assert_eq!(bits[.. 0].as_uint::<u8>(), Some(0));
+	/// assert_eq!(bitvec![Msb0, u8; 1; 9].as_uint::<u8>(), None);
 	/// ```
-	pub fn for_each<F>(&mut self, func: F)
-	where F: Fn(usize, bool) -> bool {
-		for idx in 0 .. self.len() {
-			unsafe {
-				let tmp = *self.get_unchecked(idx);
-				let new = func(idx, tmp);
-				self.set_unchecked(idx, new);
+	///
+	/// [`BitField::load`]: ../fields/trait.BitField.html#method.load
+	pub fn as_uint<U>(&self) -> Option<U>
+	where
+		U: BitStore,
+		Self: BitField,
+	{
+		match self.len() {
+			0 => Some(U::FALSE),
+			n if n <= U::BITS as usize => Some(self.load()),
+			_ => None,
+		}
+	}
+
+	/// Interprets the whole slice as a two’s-complement signed integer.
+	///
+	/// This loads the slice as [`as_uint`] does, then sign-extends it: the
+	/// most significant bit of the slice is the sign bit, and is copied into
+	/// every higher bit of the result.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The value of `self` sign-extended into an `I`, or `None` if `self` is
+	/// wider than `I`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = 0b1011_0000u8;
+	/// let bits = data.bits::<Msb0>();
+	/// assert_eq!(bits[.. 4].as_int::<i8>(), Some(-5));
+	/// assert_eq!(bits[1 .. 4].as_int::<i16>(), Some(3));
+	/// assert_eq!(bits.as_int::<i8>(), Some(0b1011_0000u8 as i8));
+	/// ```
+	///
+	/// [`as_uint`]: #method.as_uint
+	pub fn as_int<I>(&self) -> Option<I>
+	where
+		I: Signed,
+		Self: BitField,
+	{
+		let len = self.len();
+		let mut value = self.as_uint::<I::Unsigned>()?;
+		let width = <I::Unsigned as BitStore>::BITS as usize;
+		if len > 0 && len < width {
+			let sign = len as u8 - 1;
+			let one = I::Unsigned::from(1u8);
+			if (value >> sign) & one != I::Unsigned::FALSE {
+				value |= I::Unsigned::TRUE << len as u8;
 			}
 		}
+		Some(I::from_unsigned(value))
 	}
 
-	/// Performs “reverse” addition (left to right instead of right to left).
+	/// Stores an unsigned integer into the whole slice, if it fits.
 	///
-	/// This addition interprets the slice, and the other addend, as having its
-	/// least significant bits first in the order and its most significant bits
-	/// last. This is most likely to be numerically useful under a
-	/// `Lsb0` `BitOrder` type.
+	/// The value is written as by [`BitField::store`], so the slice’s
+	/// `BitOrder` determines which of its bits is the least significant, and
+	/// the local CPU byte order determines the order of elements. Unlike
+	/// `store`, this rejects values that do not fit in the slice rather than
+	/// truncating them, and rejects slices that are wider than `U` rather than
+	/// panicking. Use [`store_uint_le`] or [`store_uint_be`] to name the
+	/// element order explicitly.
 	///
 	/// # Parameters
 	///
-	/// - `&mut self`: The addition uses `self` as one addend, and writes the
-	///   sum back into `self`.
-	/// - `addend: impl IntoIterator<Item=bool>`: A stream of bits. When this is
-	///   another `BitSlice`, iteration proceeds from left to right.
+	/// - `&mut self`
+	/// - `value`: The integer to store. Every bit of it above `self.len()`
+	///   must be clear.
 	///
-	/// # Return
+	/// # Returns
 	///
-	/// The final carry bit is returned
+	/// `Err(Overflow)` if `value` does not fit in `self.len()` bits, or if
+	/// `self` is wider than `U`. In that case, `self` is unchanged.
 	///
-	/// # Effects
+	/// # Examples
 	///
-	/// Starting from index `0` and proceeding upwards until either `self` or
-	/// `addend` expires, the carry-propagated addition of `self[i]` and
-	/// `addend[i]` is written to `self[i]`.
+	/// ```rust
+	/// use bitvec::prelude::*;
 	///
-	/// ```text
-	///   101111
-	/// + 0010__ (the two missing bits are logically zero)
-	/// --------
-	///   100000 1 (the carry-out is returned)
+	/// let mut data = 0u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// assert!(bits[2 .. 5].store_uint(0b101u8).is_ok());
+	/// assert_eq!(data, 0b0010_1000);
+	///
+	/// let bits = data.bits_mut::<Msb0>();
+	/// assert!(bits[2 .. 5].store_uint(0b1000u8).is_err());
+	/// assert_eq!(data, 0b0010_1000);
 	/// ```
 	///
+	/// [`BitField::store`]: ../fields/trait.BitField.html#method.store
+	/// [`store_uint_be`]: #method.store_uint_be
+	/// [`store_uint_le`]: #method.store_uint_le
+	pub fn store_uint<U>(&mut self, value: U) -> Result<(), Overflow>
+	where
+		U: BitStore,
+		Self: BitField,
+	{
+		self.store_uint_with(value, BitField::store)
+	}
+
+	/// Stores an unsigned integer into the whole slice, if it fits, with the
+	/// least significant element first.
+	///
+	/// This is [`store_uint`], using [`BitField::store_le`].
+	///
 	/// # Examples
 	///
 	/// ```rust
 	/// use bitvec::prelude::*;
 	///
-	/// let mut a = 0b0000_1010u8;
-	/// let     b = 0b0000_1100u8;
-	/// //      s =      1 0110
-	/// let ab = &mut a.bits_mut::<Lsb0>()[.. 4];
-	/// let bb = &b.bits::<Lsb0>()[.. 4];
-	/// let c = ab.add_assign_reverse(bb.iter().copied());
-	/// assert!(c);
-	/// assert_eq!(a, 0b0000_0110u8);
+	/// let mut data = [0u8; 2];
+	/// let bits = data.bits_mut::<Msb0>();
+	/// assert!(bits.store_uint_le(0x1234u16).is_ok());
+	/// assert_eq!(data, [0x34, 0x12]);
 	/// ```
 	///
-	/// # Performance Notes
+	/// [`BitField::store_le`]: ../fields/trait.BitField.html#tymethod.store_le
+	/// [`store_uint`]: #method.store_uint
+	pub fn store_uint_le<U>(&mut self, value: U) -> Result<(), Overflow>
+	where
+		U: BitStore,
+		Self: BitField,
+	{
+		self.store_uint_with(value, BitField::store_le)
+	}
+
+	/// Stores an unsigned integer into the whole slice, if it fits, with the
+	/// most significant element first.
 	///
-	/// When using `Lsb0` `BitOrder` types, this can be accelerated by
-	/// delegating the addition to the underlying types. This is a software
-	/// implementation of the [ripple-carry adder], which has `O(n)` runtime in
-	/// the number of bits. The CPU is much faster, as it has access to
-	/// element-wise or vectorized addition operations.
+	/// This is [`store_uint`], using [`BitField::store_be`].
 	///
-	/// If your use case sincerely needs binary-integer arithmetic operations on
-	/// bit sets, please file an issue.
+	/// # Examples
 	///
-	/// [ripple-carry adder]: https://en.wikipedia.org/wiki/Ripple-carry_adder
-	pub fn add_assign_reverse<I>(&mut self, addend: I) -> bool
-	where I: IntoIterator<Item = bool> {
-		//  See AddAssign::add_assign for algorithm details
-		let mut c = false;
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = [0u8; 2];
+	/// let bits = data.bits_mut::<Msb0>();
+	/// assert!(bits.store_uint_be(0x1234u16).is_ok());
+	/// assert_eq!(data, [0x12, 0x34]);
+	/// ```
+	///
+	/// [`BitField::store_be`]: ../fields/trait.BitField.html#tymethod.store_be
+	/// [`store_uint`]: #method.store_uint
+	pub fn store_uint_be<U>(&mut self, value: U) -> Result<(), Overflow>
+	where
+		U: BitStore,
+		Self: BitField,
+	{
+		self.store_uint_with(value, BitField::store_be)
+	}
+
+	/// Implements the `store_uint` methods, with `store` writing the value.
+	///
+	/// `BitField` stores panic on empty slices, so those only check that the
+	/// value is zero.
+	fn store_uint_with<U, F>(
+		&mut self,
+		value: U,
+		store: F,
+	) -> Result<(), Overflow>
+	where
+		U: BitStore,
+		F: FnOnce(&mut Self, U),
+	{
 		let len = self.len();
-		let zero = core::iter::repeat(false);
-		for (i, b) in addend.into_iter().chain(zero).enumerate().take(len) {
-			//  The iterator is clamped to the upper bound of `self`.
-			c = unsafe {
-				let a = *self.get_unchecked(i);
-				let (y, z) = crate::rca1(a, b, c);
-				//  Write the sum into `self`
-				self.set_unchecked(i, y);
-				//  Propagate the carry
-				z
-			};
+		let bits = U::BITS as usize;
+		if len > bits || (len < bits && value >> len as u8 != U::FALSE) {
+			return Err(Overflow);
 		}
-		c
+		if len > 0 {
+			store(self, value);
+		}
+		Ok(())
 	}
 
-	/// Accesses the backing storage of the `BitSlice` as a slice of its
-	/// elements.
-	///
-	/// This will not include partially-owned edge elements, as they may be
-	/// contended by other slice handles.
+	/// Reverses the byte order of each storage element that the slice fully
+	/// owns.
 	///
-	/// # Parameters
+	/// `BitOrder` only describes how bits are numbered within one element; the
+	/// order of bytes within a multi-byte element is that of the local CPU.
+	/// Buffers read from, or destined for, a wire format with a different byte
+	/// order can be converted in place with this method before or after bit
+	/// access. For multi-element fields whose *elements* are stored
+	/// most-significant first, use [`BitField::load_be`] and
+	/// [`BitField::store_be`] instead.
 	///
-	/// - `&self`
+	/// Partially-owned edge elements are left untouched, as they may be
+	/// contended by other slice handles, and swapping their bytes would move
+	/// bits across the slice boundary.
 	///
-	/// # Returns
+	/// # Parameters
 	///
-	/// A slice of all the elements that the `BitSlice` uses for storage.
+	/// - `&mut self`
 	///
 	/// # Examples
 	///
 	/// ```rust
 	/// use bitvec::prelude::*;
 	///
-	/// let src = [1u8, 66];
-	/// let bits = src.bits::<Msb0>();
+	/// let mut data = [0x1234u16, 0xABCD];
+	/// data.bits_mut::<Msb0>().swap_element_bytes();
+	/// assert_eq!(data, [0x3412, 0xCDAB]);
 	///
-	/// let accum = bits
-	///     .as_slice()
-	///     .iter()
-	///     .map(|elt| elt.count_ones())
-	///     .sum::<u32>();
-	/// assert_eq!(accum, 3);
+	/// //  The partial head element is not modified.
+	/// data.bits_mut::<Msb0>()[4 ..].swap_element_bytes();
+	/// assert_eq!(data, [0x3412, 0xABCD]);
 	/// ```
-	pub fn as_slice(&self) -> &[T] {
-		unsafe {
-			BitAccess::as_slice_mut(match self.bitptr().domain() {
-				BitDomain::Empty | BitDomain::Minor(..) => &[],
-				BitDomain::PartialHead(_, _, body)
-				| BitDomain::PartialTail(body, ..)
-				| BitDomain::Major(_, _, body, ..)
-				| BitDomain::Spanning(body) => body,
-			})
+	///
+	/// [`BitField::load_be`]: ../fields/trait.BitField.html#tymethod.load_be
+	/// [`BitField::store_be`]: ../fields/trait.BitField.html#tymethod.store_be
+	pub fn swap_element_bytes(&mut self) {
+		for elt in self.as_mut_slice() {
+			*elt = elt.swap_bytes();
 		}
 	}
 
-	/// Accesses the underlying store.
+	/// Reverses the order of the bits in each storage element that the slice
+	/// fully owns.
 	///
-	/// This will not include partially-owned edge elements, as they may be
-	/// contended by other slice handles.
+	/// This converts memory between `Lsb0` and `Msb0` layouts: bit `n` of
+	/// each element under one ordering becomes bit `n` under the other. It is
+	/// not a permutation of the slice’s indices unless the slice is aligned to
+	/// element boundaries. For a bit-reversed view of the indices, use
+	/// [`iter_bit_reversed`].
+	///
+	/// Partially-owned edge elements are left untouched, as they may be
+	/// contended by other slice handles, and reversing them would move bits
+	/// across the slice boundary.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
 	///
 	/// # Examples
 	///
 	/// ```rust
 	/// use bitvec::prelude::*;
 	///
-	/// let mut src = [1u8, 64];
-	/// let bits = src.bits_mut::<Msb0>();
-	/// for elt in bits.as_mut_slice() {
-	///     *elt |= 2;
-	/// }
-	/// assert_eq!(&[3, 66], bits.as_slice());
+	/// let mut data = [0b0000_0001u8, 0b0000_0011];
+	/// data.bits_mut::<Msb0>().reverse_bits_per_element();
+	/// assert_eq!(data, [0b1000_0000, 0b1100_0000]);
+	///
+	/// //  The partial head element is not modified.
+	/// data.bits_mut::<Msb0>()[4 ..].reverse_bits_per_element();
+	/// assert_eq!(data, [0b1000_0000, 0b0000_0011]);
 	/// ```
-	pub fn as_mut_slice(&mut self) -> &mut [T] {
-		unsafe {
-			BitAccess::as_slice_mut(match self.bitptr().domain() {
-				BitDomain::Empty | BitDomain::Minor(..) => &[],
-				BitDomain::PartialHead(_, _, body)
-				| BitDomain::PartialTail(body, ..)
-				| BitDomain::Major(_, _, body, ..)
-				| BitDomain::Spanning(body) => body,
-			})
+	///
+	/// [`iter_bit_reversed`]: #method.iter_bit_reversed
+	pub fn reverse_bits_per_element(&mut self) {
+		for elt in self.as_mut_slice() {
+			*elt = elt.reverse_bits();
 		}
 	}
 
@@ -1049,6 +4290,35 @@ where
 		self.bitptr().as_access_slice()
 	}
 
+	/// Decodes the slice handle into its components, for diagnostics.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// The base address, head bit index, bit length, element count, and
+	/// domain shape that the handle encodes.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::{diagnostics::DomainKind, prelude::*};
+	///
+	/// let data = [0u16; 3];
+	/// let parts = data.bits::<Lsb0>()[20 .. 40].bitptr_parts();
+	/// assert_eq!(parts.address, &data[1] as *const u16);
+	/// assert_eq!(parts.head, 4);
+	/// assert_eq!(parts.bits, 20);
+	/// assert_eq!(parts.elements, 2);
+	/// assert_eq!(parts.domain, DomainKind::Major);
+	/// ```
+	#[cfg(feature = "diagnostics")]
+	pub fn bitptr_parts(&self) -> BitPtrParts<T> {
+		BitPtrParts::new(self.bitptr())
+	}
+
 	/// Accesses the underlying pointer structure.
 	///
 	/// # Parameters
@@ -1205,7 +4475,80 @@ impl_bits_for![
 	21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
 ];
 
+/// The running operations that `BitSlice::prefix_scan` can perform.
+#[derive(Clone, Copy, Eq, PartialEq)]
+enum Scan {
+	/// Each bit becomes the or of itself and the bits before it.
+	Or,
+	/// Each bit becomes the exclusive-or of itself and the bits before it.
+	Xor,
+	/// As `Xor`, but each bit that was set stays set.
+	Fill,
+}
+
+/** The error produced when a `BitSlice` is too long to convert into a value of
+fixed width.
+
+This is returned by the `TryFrom<&BitSlice>` implementations for the unsigned
+integers and for byte arrays.
+
+# Examples
+
+```rust
+use bitvec::prelude::*;
+use core::convert::TryFrom;
+
+let data = [0x1234u16];
+let bits = data.bits::<Lsb0>();
+assert_eq!(u16::try_from(bits), Ok(0x1234));
+assert_eq!(u8::try_from(&bits[4 .. 12]), Ok(0x23));
+
+let err = u8::try_from(bits).unwrap_err();
+assert_eq!((err.source_len(), err.max()), (16, 8));
+```
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct TryFromBitSliceError {
+	/// The length of the source slice.
+	len: usize,
+	/// The most bits the destination can hold.
+	max: usize,
+}
+
+impl TryFromBitSliceError {
+	pub(crate) fn new(len: usize, max: usize) -> Self {
+		Self { len, max }
+	}
+
+	/// Gets the length of the slice that could not be converted.
+	pub fn source_len(&self) -> usize {
+		self.len
+	}
+
+	/// Gets the most bits the destination type can hold.
+	pub fn max(&self) -> usize {
+		self.max
+	}
+}
+
+impl Display for TryFromBitSliceError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(
+			fmt,
+			"A slice of {} bits does not fit in {} bits",
+			self.len,
+			self.max,
+		)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TryFromBitSliceError {
+}
+
 mod api;
+#[cfg(feature = "alloc")]
+mod find;
 pub(crate) mod iter;
 mod ops;
 mod proxy;
@@ -1219,5 +4562,8 @@ pub use self::{
 	proxy::*,
 };
 
+#[cfg(feature = "alloc")]
+pub use self::find::*;
+
 #[cfg(test)]
 mod tests;
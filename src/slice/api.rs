@@ -11,8 +11,12 @@ be documented in a section called `API Differences`.
 !*/
 
 use crate::{
-	access::BitAccess,
+	access::{
+		self,
+		BitAccess,
+	},
 	indices::BitIdx,
+	kernels,
 	order::BitOrder,
 	pointer::BitPtr,
 	slice::{
@@ -48,7 +52,9 @@ use core::{
 	cmp,
 	marker::PhantomData,
 	ops::{
+		Bound,
 		Range,
+		RangeBounds,
 		RangeFrom,
 		RangeFull,
 		RangeInclusive,
@@ -60,7 +66,13 @@ use core::{
 };
 
 #[cfg(feature = "alloc")]
-use crate::vec::BitVec;
+use crate::vec::{
+	BitCow,
+	BitVec,
+};
+
+#[cfg(feature = "alloc")]
+use alloc::borrow::Cow;
 
 /** Forms a `BitSlice` from a pointer, starting position, and length.
 
@@ -503,7 +515,9 @@ where
 	/// - If given a position, returns a reference to the bit at that position
 	///   or `None` if out of bounds.
 	/// - If given a range, returns the subslice corresponding to that range, or
-	///   `None` if out of bounds.
+	///   `None` if out of bounds. All of the `core::ops` range types are
+	///   accepted, as is a `(Bound<usize>, Bound<usize>)` pair. This never
+	///   panics, including for inclusive ranges ending at `usize::MAX`.
 	///
 	/// # Examples
 	///
@@ -765,6 +779,11 @@ where
 
 	/// Returns an iterator that allows modifying each bit.
 	///
+	/// Each bit is produced as a [`BitMut`] proxy, which writes back into the
+	/// slice when it drops. The iterator runs from either end and knows its
+	/// length, and as an ordinary loop it can stop early or carry state
+	/// between bits.
+	///
 	/// # Examples
 	///
 	/// ```rust
@@ -776,6 +795,26 @@ where
 	/// }
 	/// assert_eq!(data, 3);
 	/// ```
+	///
+	/// Clearing the trailing set bits, stopping at the first unset bit:
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let mut data = 0b0110_0111u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// let mut iter = bits.iter_mut();
+	/// assert_eq!(iter.len(), 8);
+	/// while let Some(mut bit) = iter.next_back() {
+	///     if !*bit {
+	///         break;
+	///     }
+	///     *bit = false;
+	/// }
+	/// assert_eq!(iter.as_bitslice(), bits![0, 1, 1, 0]);
+	/// assert_eq!(data, 0b0110_0000);
+	/// ```
+	///
+	/// [`BitMut`]: struct.BitMut.html
 	#[inline]
 	pub fn iter_mut(&mut self) -> IterMut<O, T> {
 		self.into_iter()
@@ -1706,6 +1745,124 @@ where
 		len >= slen && suffix == unsafe { self.get_unchecked(len - slen ..) }
 	}
 
+	/// Returns the slice with `prefix` removed from its front, if `prefix` is a
+	/// prefix of the slice.
+	///
+	/// # Original
+	///
+	/// [`slice::strip_prefix`](https://doc.rust-lang.org/std/primitive.slice.html#method.strip_prefix)
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let bits = bits![1, 0, 1, 1];
+	/// assert_eq!(bits.strip_prefix(bits![1, 0]), Some(bits![1, 1]));
+	/// assert!(bits.strip_prefix(bits![0]).is_none());
+	/// ```
+	pub fn strip_prefix<P, U>(&self, prefix: &BitSlice<P, U>) -> Option<&Self>
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		if self.starts_with(prefix) {
+			Some(unsafe { self.get_unchecked(prefix.len() ..) })
+		}
+		else {
+			None
+		}
+	}
+
+	/// Returns the slice with `suffix` removed from its back, if `suffix` is a
+	/// suffix of the slice.
+	///
+	/// # Original
+	///
+	/// [`slice::strip_suffix`](https://doc.rust-lang.org/std/primitive.slice.html#method.strip_suffix)
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let bits = bits![1, 0, 1, 1];
+	/// assert_eq!(bits.strip_suffix(bits![1, 1]), Some(bits![1, 0]));
+	/// assert!(bits.strip_suffix(bits![0]).is_none());
+	/// ```
+	pub fn strip_suffix<P, U>(&self, suffix: &BitSlice<P, U>) -> Option<&Self>
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		if self.ends_with(suffix) {
+			Some(unsafe { self.get_unchecked(.. self.len() - suffix.len()) })
+		}
+		else {
+			None
+		}
+	}
+
+	/// Counts the bits at the front of two slices that are equal.
+	///
+	/// When the two slices begin at the same bit index in their first
+	/// elements, every element they both fully own is compared whole, and only
+	/// the first unequal element, and the partial elements at the edges, are
+	/// searched bit by bit. Otherwise, the slices are compared bit by bit.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `other`: Another slice.
+	///
+	/// # Returns
+	///
+	/// The length of the longest slice that is a prefix of both `self` and
+	/// `other`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let a = [0x12u8, 0x34, 0x56];
+	/// let b = [0x12u8, 0x34, 0x57];
+	/// let (a, b) = (a.bits::<Msb0>(), b.bits::<Msb0>());
+	/// assert_eq!(a.common_prefix_len(b), 23);
+	/// assert_eq!(a[.. 20].common_prefix_len(b), 20);
+	/// assert_eq!(a[1 ..].common_prefix_len(b), 2);
+	/// ```
+	pub fn common_prefix_len(&self, other: &Self) -> usize {
+		fn by_bits<O, T>(a: &BitSlice<O, T>, b: &BitSlice<O, T>) -> usize
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			a.iter().zip(b.iter()).take_while(|(x, y)| x == y).count()
+		}
+
+		let len = cmp::min(self.len(), other.len());
+		let (a, b) = (&self[.. len], &other[.. len]);
+		let head = *a.bitptr().head() as usize;
+		if head != *b.bitptr().head() as usize {
+			return by_bits(a, b);
+		}
+		//  The slices cross element boundaries at the same indices, so after
+		//  the first boundary their fully-owned elements line up.
+		let first = cmp::min(len, T::BITS as usize - head);
+		let (a_head, a) = a.split_at(first);
+		let (b_head, b) = b.split_at(first);
+		let same = by_bits(a_head, b_head);
+		if same < first {
+			return same;
+		}
+		let elts = a
+			.as_slice()
+			.iter()
+			.zip(b.as_slice())
+			.take_while(|(x, y)| x == y)
+			.count();
+		let skip = elts * T::BITS as usize;
+		first + skip + by_bits(&a[skip ..], &b[skip ..])
+	}
+
 	/// Rotates the slice in-place such that the first `by` bits of the slice
 	/// move to the end while the last `self.len() - by` bits move to the
 	/// front. After calling `rotate_left`, the bit previously at index `by`
@@ -1967,7 +2124,101 @@ where
 	/// assert_eq!(data, 0x33);
 	/// ```
 	pub fn copy_from_slice(&mut self, src: &Self) {
-		self.clone_from_slice(src)
+		let len = self.len();
+		assert_eq!(len, src.len(), "Copying from slice requires equal lengths");
+		let head = *self.bitptr().head() as usize;
+		if head != *src.bitptr().head() as usize {
+			return self.clone_from_slice(src);
+		}
+
+		/* When both slices begin at the same index within their first element,
+		their domains have identical shapes, and every fully-spanned element in
+		the destination lines up with one in the source. Only the partial edge
+		elements need to be copied bit by bit; the body is copied as whole
+		elements.
+		*/
+		let bits = T::BITS as usize;
+		let edge = if head == 0 { 0 } else { cmp::min(bits - head, len) };
+		let elts = (len - edge) / bits;
+		let tail = edge + elts * bits;
+		let skip = (head != 0) as usize;
+		unsafe {
+			self.get_unchecked_mut(.. edge)
+				.clone_from_slice(src.get_unchecked(.. edge));
+			if elts > 0 {
+				kernels::copy_body::<T>(
+					&self.as_total_slice()[skip ..][.. elts],
+					&src.as_total_slice()[skip ..][.. elts],
+				);
+			}
+			self.get_unchecked_mut(tail ..)
+				.clone_from_slice(src.get_unchecked(tail ..));
+		}
+	}
+
+	/// Copies bits from one part of the slice to another part of itself.
+	///
+	/// `src` is the range within `self` to copy from. `dest` is the starting
+	/// index of the range within `self` to copy to, which will have the same
+	/// length as `src`. The two ranges may overlap. The ends of the two ranges
+	/// must be less than or equal to `self.len()`.
+	///
+	/// Every element fully spanned by the destination range is written as a
+	/// whole element, in the same direction that `ptr::copy` would use, and
+	/// only the partial elements at the range edges are masked. In `Lsb0` and
+	/// `Msb0` orders, a distance that is not a multiple of the storage element
+	/// width is bridged by shifting the two source elements that each
+	/// destination element draws from; other orderings copy such distances
+	/// bit by bit.
+	///
+	/// # Original
+	///
+	/// [`slice::copy_within`](https://doc.rust-lang.org/std/primitive.slice.html#method.copy_within)
+	///
+	/// # Panics
+	///
+	/// This function will panic if either range exceeds the end of the slice,
+	/// or if the end of `src` is before the start.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let mut data = 0b1100_1010u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// bits.copy_within(4 .., 2);
+	/// assert_eq!(data, 0b1110_1010);
+	/// ```
+	pub fn copy_within<R>(&mut self, src: R, dest: usize)
+	where R: RangeBounds<usize> {
+		use core::ops::Bound::*;
+		let len = self.len();
+		let from = match src.start_bound() {
+			Included(&n) => n,
+			Excluded(&n) => {
+				n.checked_add(1).expect("range start overflows usize")
+			},
+			Unbounded => 0,
+		};
+		let upto = match src.end_bound() {
+			Included(&n) => {
+				n.checked_add(1).expect("range end overflows usize")
+			},
+			Excluded(&n) => n,
+			Unbounded => len,
+		};
+		assert!(from <= upto, "Range start {} is after end {}", from, upto);
+		assert!(upto <= len, "Range end {} out of bounds: {}", upto, len);
+		let count = upto - from;
+		assert!(
+			dest <= len - count,
+			"Destination {} out of bounds: {}",
+			dest,
+			len - count,
+		);
+		unsafe {
+			self.copy_within_unchecked(from .. upto, dest);
+		}
 	}
 
 	/// Swaps all bits in `self` with those in `other`.
@@ -2145,6 +2396,77 @@ where
 	pub fn to_vec(&self) -> BitVec<O, T> {
 		BitVec::from_bitslice(self)
 	}
+
+	/// Borrows `self` as a clone-on-write sequence.
+	///
+	/// This is `Cow::Borrowed(self)`, for use where a [`BitCow`] is expected,
+	/// such as when building the keys of a map.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let bits = bits![0, 1, 1];
+	/// let mut cow = bits.to_cow();
+	/// cow.to_mut().push(true);
+	/// assert_eq!(cow.count_ones(), 3);
+	/// assert_eq!(bits.count_ones(), 2);
+	/// ```
+	///
+	/// [`BitCow`]: ../vec/type.BitCow.html
+	#[cfg(feature = "alloc")]
+	#[inline]
+	pub fn to_cow(&self) -> BitCow<'_, O, T> {
+		Cow::Borrowed(self)
+	}
+
+	/// Creates a vector by repeating a slice `n` times.
+	///
+	/// The output is built by copying the slice once, then repeatedly copying
+	/// the entire filled region onto its own end. This takes `log2(n)` copy
+	/// passes, and once the filled region is a whole number of elements long,
+	/// each pass moves whole elements rather than individual bits.
+	///
+	/// # Original
+	///
+	/// [`slice::repeat`](https://doc.rust-lang.org/std/primitive.slice.html#method.repeat)
+	///
+	/// # Panics
+	///
+	/// This function will panic if the capacity would overflow.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let bits = bits![Msb0, u8; 1, 0, 0];
+	/// assert_eq!(
+	///     bits.repeat(3),
+	///     bitvec![Msb0, u8; 1, 0, 0, 1, 0, 0, 1, 0, 0],
+	/// );
+	/// ```
+	#[cfg(feature = "alloc")]
+	pub fn repeat(&self, n: usize) -> BitVec<O, T> {
+		let len = self.len();
+		let total = len.checked_mul(n).expect("capacity overflow");
+		let mut out = BitVec::with_capacity(total);
+		if total == 0 {
+			return out;
+		}
+		unsafe {
+			out.set_len(total);
+		}
+		out[.. len].copy_from_slice(self);
+
+		let mut filled = len;
+		while filled < total {
+			let step = cmp::min(filled, total - filled);
+			let (done, rest) = out.split_at_mut(filled);
+			rest[.. step].copy_from_slice(&done[.. step]);
+			filled += step;
+		}
+		out
+	}
 }
 
 /** Replacement for [`slice::SliceIndex`].
@@ -2155,6 +2477,94 @@ must be duplicated here.
 
 There is no tracking issue for `feature(slice_index_methods)`.
 
+Unlike `SliceIndex`, this trait is not sealed. Downstream crates may implement
+it on their own index types, which can then be passed to [`get`], [`get_mut`],
+and [`at`], and can drive the `[]` operator through `Index` and `IndexMut`
+implementations that forward to [`index`] and [`index_mut`]. This keeps the
+translation from an index type to a bit range, and its bounds checking, in one
+place.
+
+# Examples
+
+A record whose fields are named by an enum, rather than by bare ranges:
+
+```rust
+use bitvec::{prelude::*, slice::BitSliceIndex};
+use std::ops::{Index, IndexMut, Range};
+
+#[derive(Clone, Copy)]
+enum Field {
+    Version,
+    Length,
+}
+
+impl Field {
+    fn span(self) -> Range<usize> {
+        match self {
+            Field::Version => 0 .. 4,
+            Field::Length => 4 .. 16,
+        }
+    }
+}
+
+impl<'a, O, T> BitSliceIndex<'a, O, T> for Field
+where O: 'a + BitOrder, T: 'a + BitStore {
+    type Immut = &'a BitSlice<O, T>;
+    type Mut = &'a mut BitSlice<O, T>;
+
+    fn get(self, slice: Self::Immut) -> Option<Self::Immut> {
+        self.span().get(slice)
+    }
+
+    fn get_mut(self, slice: Self::Mut) -> Option<Self::Mut> {
+        self.span().get_mut(slice)
+    }
+
+    unsafe fn get_unchecked(self, slice: Self::Immut) -> Self::Immut {
+        self.span().get_unchecked(slice)
+    }
+
+    unsafe fn get_unchecked_mut(self, slice: Self::Mut) -> Self::Mut {
+        self.span().get_unchecked_mut(slice)
+    }
+
+    fn index(self, slice: Self::Immut) -> Self::Immut {
+        self.span().index(slice)
+    }
+
+    fn index_mut(self, slice: Self::Mut) -> Self::Mut {
+        self.span().index_mut(slice)
+    }
+}
+
+impl<O: BitOrder, T: BitStore> Index<Field> for BitSlice<O, T> {
+    type Output = Self;
+
+    fn index(&self, field: Field) -> &Self {
+        field.index(self)
+    }
+}
+
+impl<O: BitOrder, T: BitStore> IndexMut<Field> for BitSlice<O, T> {
+    fn index_mut(&mut self, field: Field) -> &mut Self {
+        field.index_mut(self)
+    }
+}
+
+let mut header = [0u8; 2];
+let bits = header.bits_mut::<Msb0>();
+bits[Field::Version].set_all(true);
+assert_eq!(bits[Field::Length].len(), 12);
+assert!(bits.get(Field::Length).unwrap().not_any());
+assert!(bits[.. 8].get(Field::Length).is_none());
+assert_eq!(header, [0xF0, 0x00]);
+```
+
+[`at`]: struct.BitSlice.html#method.at
+[`get`]: struct.BitSlice.html#method.get
+[`get_mut`]: struct.BitSlice.html#method.get_mut
+[`index`]: #tymethod.index
+[`index_mut`]: #tymethod.index_mut
 [`slice::SliceIndex`]: https://doc.rust-lang.org/stable/core/slice/trait.SliceIndex.html
 **/
 pub trait BitSliceIndex<'a, O, T>
@@ -2300,11 +2710,10 @@ where
 	}
 
 	unsafe fn get_unchecked(self, slice: &'a BitSlice<O, T>) -> Self::Immut {
-		let bitptr = slice.bitptr();
-		let (elt, bit) = bitptr.head().offset(self as isize);
-		let data_ptr = bitptr.pointer().a();
+		let (elem, bit) =
+			access::unchecked_element(slice.bitptr(), self, "get_unchecked");
 
-		if (&*data_ptr.offset(elt)).get::<O>(bit) {
+		if elem.get::<O>(bit) {
 			&true
 		}
 		else {
@@ -2318,27 +2727,29 @@ where
 		slice: &'a mut BitSlice<O, T>,
 	) -> Self::Mut
 	{
-		let bp = slice.bitptr();
-		let (offset, head) = bp.head().offset(self as isize);
-		let ptr = bp.pointer().a().offset(offset);
+		let (elem, head) = access::unchecked_element(
+			slice.bitptr(),
+			self,
+			"get_unchecked_mut",
+		);
 		BitMut {
 			_parent: PhantomData,
-			data: NonNull::new_unchecked(ptr as *mut T::Access),
+			data: NonNull::from(elem),
 			head,
-			bit: (*ptr).get::<O>(head),
+			bit: elem.get::<O>(head),
 		}
 	}
 
 	fn index(self, slice: &'a BitSlice<O, T>) -> Self::Immut {
-		self.get(slice).unwrap_or_else(|| {
-			panic!("Index {} out of bounds: {}", self, slice.len())
-		})
+		let len = slice.len();
+		assert!(self < len, "Index {} out of bounds: {}", self, len);
+		unsafe { self.get_unchecked(slice) }
 	}
 
 	fn index_mut(self, slice: &'a mut BitSlice<O, T>) -> Self::Mut {
 		let len = slice.len();
-		self.get_mut(slice)
-			.unwrap_or_else(|| panic!("Index {} out of bounds: {}", self, len))
+		assert!(self < len, "Index {} out of bounds: {}", self, len);
+		unsafe { self.get_unchecked_mut(slice) }
 	}
 }
 
@@ -2349,10 +2760,9 @@ where
 /// Only `get` and `get_unchecked` are interesting; the other four methods can
 /// all be implemented in terms of these two.
 ///
-/// This macro can be invoked either with definition bodies for `get` and
-/// `get_unchecked`, or with a range transform. The latter is useful for
-/// converting inclusive ranges to exclusive; the former allows different range
-/// shape to perform only the work that they actually need.
+/// Each invocation supplies definition bodies for `get` and `get_unchecked`,
+/// which allows different range shapes to perform only the work that they
+/// actually need.
 macro_rules! range_impl {
 	( $( $r:ty => get $get:expr, unchecked $unchecked:expr; )* ) => { $(
 		impl<'a, O, T> BitSliceIndex<'a, O, T> for $r
@@ -2395,44 +2805,6 @@ macro_rules! range_impl {
 			}
 		}
 	)* };
-
-	( $( $r:ty => map $func:expr; )* ) => { $(
-		impl<'a, O, T> BitSliceIndex<'a, O, T> for $r
-		where O: 'a + BitOrder, T: 'a + BitStore {
-			type Immut = &'a BitSlice<O, T>;
-			type Mut = &'a mut BitSlice<O, T>;
-
-			#[inline]
-			fn get(self, slice: Self::Immut) -> Option<Self::Immut> {
-				$func(self).get(slice)
-			}
-
-			#[inline]
-			fn get_mut(self, slice: Self::Mut) -> Option<Self::Mut> {
-				$func(self).get_mut(slice)
-			}
-
-			#[inline]
-			unsafe fn get_unchecked(self, slice: Self::Immut) -> Self::Immut {
-				$func(self).get_unchecked(slice)
-			}
-
-			#[inline]
-			unsafe fn get_unchecked_mut(self, slice: Self::Mut) -> Self::Mut {
-				$func(self).get_unchecked_mut(slice)
-			}
-
-			#[inline]
-			fn index(self, slice: Self::Immut) -> Self::Immut {
-				$func(self).index(slice)
-			}
-
-			#[inline]
-			fn index_mut(self, slice: Self::Mut) -> Self::Mut {
-				$func(self).index_mut(slice)
-			}
-		}
-	)* };
 }
 
 range_impl! {
@@ -2446,7 +2818,9 @@ range_impl! {
 		Some(unsafe { (start .. end).get_unchecked(slice) })
 	},
 	unchecked |Range { start, end }, slice: Self::Immut| {
-		let (data, head, _) = slice.bitptr().raw_parts();
+		let bp = slice.bitptr();
+		bp.audit_range(start, end, "get_unchecked");
+		let (data, head, _) = bp.raw_parts();
 
 		let (skip, new_head) = head.offset(start as isize);
 
@@ -2467,7 +2841,9 @@ range_impl! {
 		}
 	},
 	unchecked |RangeFrom { start }, slice: Self::Immut| {
-		let (data, head, bits) = slice.bitptr().raw_parts();
+		let bp = slice.bitptr();
+		bp.audit_range(start, bp.len(), "get_unchecked");
+		let (data, head, bits) = bp.raw_parts();
 
 		let (skip, new_head) = head.offset(start as isize);
 
@@ -2490,21 +2866,81 @@ range_impl! {
 	},
 	unchecked |RangeTo { end }, slice: Self::Immut| {
 		let mut bp = slice.bitptr();
+		bp.audit_range(0, end, "get_unchecked");
 		bp.set_len(end);
 		bp.into_bitslice()
 	};
 }
 
 range_impl! {
-	RangeInclusive<usize> => map |this: Self| {
+	//  Inclusive ranges are converted to exclusive ones, but an inclusive end
+	//  of `usize::max_value()` cannot be, and is always out of bounds.
+	RangeInclusive<usize> => get |this: RangeInclusive<usize>, slice: Self::Immut| {
+		let (start, end) = this.into_inner();
+		if end == !0 {
+			return None;
+		}
+		#[allow(clippy::range_plus_one)]
+		(start .. end + 1).get(slice)
+	},
+	unchecked |this: RangeInclusive<usize>, slice: Self::Immut| {
+		let (start, end) = this.into_inner();
 		#[allow(clippy::range_plus_one)]
-		(*this.start() .. *this.end() + 1)
+		(start .. end + 1).get_unchecked(slice)
 	};
 
-	RangeToInclusive<usize> => map |RangeToInclusive { end }| {
+	RangeToInclusive<usize> => get |RangeToInclusive { end }, slice: Self::Immut| {
+		if end == !0 {
+			return None;
+		}
+		#[allow(clippy::range_plus_one)]
+		(.. end + 1).get(slice)
+	},
+	unchecked |RangeToInclusive { end }, slice: Self::Immut| {
 		#[allow(clippy::range_plus_one)]
-		(.. end + 1)
+		(.. end + 1).get_unchecked(slice)
+	};
+
+	//  Arbitrary bound pairs, as produced by `RangeBounds` implementors.
+	(Bound<usize>, Bound<usize>) => get |bounds: (Bound<usize>, Bound<usize>), slice: Self::Immut| {
+		bound_range(bounds, slice.len())?.get(slice)
+	},
+	unchecked |bounds: (Bound<usize>, Bound<usize>), slice: Self::Immut| {
+		match bound_range(bounds, slice.len()) {
+			Some(range) => range.get_unchecked(slice),
+			None => core::hint::unreachable_unchecked(),
+		}
+	};
+}
+
+/// Converts a pair of range bounds into a half-open range.
+///
+/// # Parameters
+///
+/// - `(start, end)`: The bounds of the range.
+/// - `len`: The length of the slice being indexed, which is the end of the
+///   range when `end` is unbounded.
+///
+/// # Returns
+///
+/// The equivalent half-open range, or `None` if either bound cannot be
+/// represented as an exclusive `usize` end. The range is not checked against
+/// `len`.
+fn bound_range(
+	(start, end): (Bound<usize>, Bound<usize>),
+	len: usize,
+) -> Option<Range<usize>> {
+	let start = match start {
+		Bound::Included(n) => n,
+		Bound::Excluded(n) => n.checked_add(1)?,
+		Bound::Unbounded => 0,
+	};
+	let end = match end {
+		Bound::Included(n) => n.checked_add(1)?,
+		Bound::Excluded(n) => n,
+		Bound::Unbounded => len,
 	};
+	Some(start .. end)
 }
 
 /// `RangeFull` is the identity function.
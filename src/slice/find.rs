@@ -0,0 +1,363 @@
+/*! Repeated substring search over `BitSlice`s.
+
+Searching for a pattern inside a bit slice one time is cheap to express, but
+scanning many haystacks for the same needle repeats the same analysis of the
+needle on every call. `BitFinder` performs that analysis once, up front, and
+then scans each haystack in a single forward pass.
+
+The search is the two-way algorithm of Crochemore and Perrin, which is also the
+one that `str::find` uses. It needs only constant state beyond the needle, and
+it matches each part of the needle against a window of the haystack as a whole
+run, so the runs are compared 64 bits at a time. Slices in the `Msb0` or `Lsb0`
+layout load those runs a whole element at a time.
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	fields,
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+use core::{
+	cmp,
+	iter::FusedIterator,
+};
+
+/** A precomputed searcher for one needle bit slice.
+
+Construction computes a critical factorization of the needle: a split point,
+and the period of the needle around it. Each search compares the part of the
+needle after that point against a window of the haystack, then the part before
+it, and uses the period to decide how far the window can move after a mismatch.
+Each search is then linear in the length of the haystack.
+
+The haystack may use any `BitOrder` and `BitStore` parameters, independently of
+the needle.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, slice::BitFinder};
+
+let sync = bits![Msb0, u8; 0, 1, 1, 1, 1, 1, 1, 0];
+let finder = BitFinder::new(sync);
+
+let capture = [0b1101_1111u8, 0b1001_1000];
+assert_eq!(finder.find_in(capture.bits::<Msb0>()), Some(2));
+assert_eq!(finder.find_in(0u8.bits::<Msb0>()), None);
+```
+**/
+#[derive(Clone, Debug)]
+pub struct BitFinder<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The pattern being searched for.
+	needle: &'a BitSlice<O, T>,
+	/// The critical position, which splits the needle into a left and a right
+	/// part.
+	crit: usize,
+	/// The distance the window moves when the left part mismatches.
+	period: usize,
+	/// Whether `period` is the true period of the needle. When it is, a
+	/// mismatch in the left part leaves a known-matching prefix of the next
+	/// window, which the search remembers rather than re-reading.
+	periodic: bool,
+}
+
+impl<'a, O, T> BitFinder<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// Analyzes a needle for repeated searching.
+	///
+	/// # Parameters
+	///
+	/// - `needle`: The bit pattern to search for.
+	///
+	/// # Returns
+	///
+	/// A searcher that can be run against any number of haystacks.
+	pub fn new(needle: &'a BitSlice<O, T>) -> Self {
+		let len = needle.len();
+		//  The critical position is the later of the starts of the maximal
+		//  suffixes under the two orderings of the bit values.
+		let (crit_lt, period_lt) = maximal_suffix(needle, false);
+		let (crit_gt, period_gt) = maximal_suffix(needle, true);
+		let (crit, period) = if crit_lt > crit_gt {
+			(crit_lt, period_lt)
+		}
+		else {
+			(crit_gt, period_gt)
+		};
+
+		if crit + period <= len && needle[.. crit] == needle[period ..][.. crit]
+		{
+			Self {
+				needle,
+				crit,
+				period,
+				periodic: true,
+			}
+		}
+		else {
+			Self {
+				needle,
+				crit,
+				period: cmp::max(crit, len - crit) + 1,
+				periodic: false,
+			}
+		}
+	}
+
+	/// Views the needle this searcher was built for.
+	pub fn needle(&self) -> &'a BitSlice<O, T> {
+		self.needle
+	}
+
+	/// Finds the first occurrence of the needle in a haystack.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `haystack`: The bit slice to search.
+	///
+	/// # Returns
+	///
+	/// The index in `haystack` at which the first occurrence of the needle
+	/// begins, if any. An empty needle matches at index `0`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::{prelude::*, slice::BitFinder};
+	///
+	/// let finder = BitFinder::new(bits![1, 0, 1]);
+	/// assert_eq!(finder.find_in(bits![0, 1, 1, 0, 1, 0, 1]), Some(2));
+	/// assert_eq!(finder.find_in(bits![1, 1, 0, 0]), None);
+	/// ```
+	pub fn find_in<P, U>(&self, haystack: &BitSlice<P, U>) -> Option<usize>
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		self.find_iter(haystack).next()
+	}
+
+	/// Iterates over each non-overlapping occurrence of the needle in a
+	/// haystack.
+	///
+	/// Once a match is found, the search resumes at the first bit after it,
+	/// in the same manner as `str::match_indices`.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `haystack`: The bit slice to search.
+	///
+	/// # Returns
+	///
+	/// An iterator yielding the starting index of each match, in ascending
+	/// order.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::{prelude::*, slice::BitFinder};
+	///
+	/// let finder = BitFinder::new(bits![1, 1]);
+	/// let hits = finder
+	///     .find_iter(bits![1, 1, 1, 0, 1, 1, 0])
+	///     .collect::<Vec<_>>();
+	/// assert_eq!(hits, [0, 4]);
+	/// ```
+	pub fn find_iter<'b, 'h, P, U>(
+		&'b self,
+		haystack: &'h BitSlice<P, U>,
+	) -> FindIter<'b, 'a, 'h, O, T, P, U>
+	where
+		P: BitOrder,
+		U: 'h + BitStore,
+	{
+		FindIter {
+			finder: self,
+			haystack,
+			pos: 0,
+			memory: 0,
+		}
+	}
+}
+
+/** Iterator over the occurrences of a needle in a haystack.
+
+This struct is created by the [`find_iter`] method on [`BitFinder`].
+
+[`BitFinder`]: struct.BitFinder.html
+[`find_iter`]: struct.BitFinder.html#method.find_iter
+**/
+#[derive(Clone, Debug)]
+pub struct FindIter<'b, 'a, 'h, O, T, P, U>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+	P: BitOrder,
+	U: 'h + BitStore,
+{
+	/// The precomputed needle state.
+	finder: &'b BitFinder<'a, O, T>,
+	/// The slice being searched.
+	haystack: &'h BitSlice<P, U>,
+	/// The index in the haystack of the current window.
+	pos: usize,
+	/// The number of needle bits known to match at the front of the window.
+	/// This is only used when the needle is periodic.
+	memory: usize,
+}
+
+impl<O, T, P, U> Iterator for FindIter<'_, '_, '_, O, T, P, U>
+where
+	O: BitOrder,
+	T: BitStore,
+	P: BitOrder,
+	U: BitStore,
+{
+	type Item = usize;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let finder = self.finder;
+		let needle = finder.needle;
+		let len = needle.len();
+		let hay = self.haystack.len();
+
+		//  An empty needle matches at every index, including the end.
+		if len == 0 {
+			if self.pos > hay {
+				return None;
+			}
+			self.pos += 1;
+			return Some(self.pos - 1);
+		}
+
+		while hay - self.pos >= len {
+			let window = &self.haystack[self.pos ..][.. len];
+			let memory = if finder.periodic { self.memory } else { 0 };
+
+			//  Match the right part, from the critical position forwards. A
+			//  mismatch there moves the window past the matched bits.
+			let start = cmp::max(finder.crit, memory);
+			if let Some(n) = mismatch(&needle[start ..], &window[start ..]) {
+				self.pos += start + n - finder.crit + 1;
+				self.memory = 0;
+				continue;
+			}
+
+			//  Match the left part. A mismatch there moves the window by the
+			//  period, and the front of the next window is then known.
+			let left = cmp::min(memory, finder.crit) .. finder.crit;
+			if mismatch(&needle[left.clone()], &window[left]).is_some() {
+				self.pos += finder.period;
+				self.memory = len - finder.period;
+				continue;
+			}
+
+			self.pos += len;
+			self.memory = 0;
+			return Some(self.pos - len);
+		}
+		None
+	}
+}
+
+impl<O, T, P, U> FusedIterator for FindIter<'_, '_, '_, O, T, P, U>
+where
+	O: BitOrder,
+	T: BitStore,
+	P: BitOrder,
+	U: BitStore,
+{
+}
+
+/** Finds the maximal suffix of a needle under one ordering of bit values.
+
+# Parameters
+
+- `needle`: The bit slice to factorize.
+- `greater`: If this is `false`, a cleared bit orders before a set bit; if it is
+  `true`, a set bit orders before a cleared bit.
+
+# Returns
+
+The index at which the lexicographically greatest suffix begins, and the period
+of that suffix.
+**/
+fn maximal_suffix<O, T>(needle: &BitSlice<O, T>, greater: bool) -> (usize, usize)
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let len = needle.len();
+	let (mut left, mut right, mut offset, mut period) = (0, 1, 0, 1);
+	while right + offset < len {
+		let a = needle[right + offset];
+		let b = needle[left + offset];
+		if a == b {
+			//  Advance through a repetition of the current period.
+			if offset + 1 == period {
+				right += offset + 1;
+				offset = 0;
+			}
+			else {
+				offset += 1;
+			}
+		}
+		//  The bits differ, so `a < b` exactly when `b` is set.
+		else if b != greater {
+			//  The suffix is smaller, so the period is the whole prefix.
+			right += offset + 1;
+			offset = 0;
+			period = right - left;
+		}
+		else {
+			//  The suffix is larger, so start again from here.
+			left = right;
+			right += 1;
+			offset = 0;
+			period = 1;
+		}
+	}
+	(left, period)
+}
+
+/** Finds the first index at which two runs of equal length differ.
+
+The runs are compared 64 bits at a time, through the same loads that the
+`BitField` trait uses.
+
+# Returns
+
+The index of the first bit that differs between `a` and `b`, if any.
+**/
+fn mismatch<O, T, P, U>(a: &BitSlice<O, T>, b: &BitSlice<P, U>) -> Option<usize>
+where
+	O: BitOrder,
+	T: BitStore,
+	P: BitOrder,
+	U: BitStore,
+{
+	let mut start = 0;
+	for (a, b) in a.chunks(64).zip(b.chunks(64)) {
+		let diff = fields::load_msb_first(a) ^ fields::load_msb_first(b);
+		if diff != 0 {
+			//  The first bit of each run is the most significant of the low
+			//  `a.len()` bits.
+			return Some(start + diff.leading_zeros() as usize - (64 - a.len()));
+		}
+		start += a.len();
+	}
+	None
+}
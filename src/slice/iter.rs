@@ -271,6 +271,14 @@ where
 		self.inner
 	}
 
+	/// Views the bits not yet produced, without consuming the iterator.
+	///
+	/// The view borrows the iterator, so no proxy can be produced while it
+	/// lives.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		self.inner
+	}
+
 	/// Views the underlying buffer.
 	///
 	/// To avoid creating `&mut` references that alias, this is forced to
@@ -832,6 +840,482 @@ where
 {
 }
 
+/** An iterator over a slice in mutable chunks of at least `width` bits, which
+never share a memory element with each other.
+
+Each split point is moved forward to the next element boundary in memory, so
+every chunk after the first begins at the start of an element, and every chunk
+before the last ends at the end of one. A chunk may therefore be up to
+`T::BITS - 1` bits longer than `width`. The final chunk holds whatever remains.
+
+This struct is created by the [`chunks_mut_disjoint`] method on [`BitSlice`]s.
+
+[`BitSlice`]: struct.BitSlice.html
+[`chunks_mut_disjoint`]: struct.BitSlice.html#method.chunks_mut_disjoint
+**/
+#[derive(Debug)]
+pub struct ChunksMutDisjoint<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The `BitSlice` undergoing iteration.
+	pub(super) inner: &'a mut BitSlice<O, T>,
+	/// The minimum width of the produced chunks.
+	pub(super) width: usize,
+}
+
+impl<'a, O, T> ChunksMutDisjoint<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// Computes the length of the next chunk to be split off the front of a
+	/// slice, so that it ends on an element boundary.
+	fn split_point(slice: &BitSlice<O, T>, width: usize) -> usize {
+		let bits = T::BITS as usize;
+		let head = *slice.bitptr().head() as usize;
+		let end = head.saturating_add(width);
+		let end = match end % bits {
+			0 => end,
+			r => end.saturating_add(bits - r),
+		};
+		cmp::min(end - head, slice.len())
+	}
+}
+
+impl<'a, O, T> Iterator for ChunksMutDisjoint<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = &'a mut BitSlice<O, T>;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		let slice = mem::replace(&mut self.inner, BitSlice::empty_mut());
+		if slice.is_empty() {
+			return None;
+		}
+		let at = Self::split_point(slice, self.width);
+		let (head, rest) = slice.split_at_mut(at);
+		self.inner = rest;
+		Some(head)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.inner.len();
+		if len == 0 {
+			return (0, Some(0));
+		}
+		let first = Self::split_point(self.inner, self.width);
+		//  All chunks after the first begin on an element boundary, so they
+		//  all have the same width.
+		let bits = T::BITS as usize;
+		let step = self.width.saturating_add(bits - 1) / bits * bits;
+		let (n, r) = ((len - first) / step, (len - first) % step);
+		let out = 1 + n + (r > 0) as usize;
+		(out, Some(out))
+	}
+
+	#[inline]
+	fn count(self) -> usize {
+		self.len()
+	}
+}
+
+impl<O, T> ExactSizeIterator for ChunksMutDisjoint<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+impl<O, T> FusedIterator for ChunksMutDisjoint<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+/** An iterator over a slice in bit-reversed index order.
+
+The `n`th bit produced is the bit of the slice whose index, written in
+`log2(len)` binary digits, is the reverse of `n`. This is the input permutation
+of a radix-2 Fast Fourier Transform. The slice is not modified or copied.
+
+This struct is created by the [`iter_bit_reversed`] method on [`BitSlice`]s.
+
+[`BitSlice`]: struct.BitSlice.html
+[`iter_bit_reversed`]: struct.BitSlice.html#method.iter_bit_reversed
+**/
+#[derive(Clone, Debug)]
+pub struct BitReversed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The `BitSlice` undergoing iteration. Its length is a power of two.
+	pub(super) inner: &'a BitSlice<O, T>,
+	/// The next position to produce from the front.
+	pub(super) front: usize,
+	/// One past the next position to produce from the back.
+	pub(super) back: usize,
+}
+
+impl<'a, O, T> BitReversed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// Finds the index of the bit produced at a position.
+	fn source(&self, pos: usize) -> usize {
+		let len = self.inner.len();
+		if len < 2 {
+			return pos;
+		}
+		let width = 0usize.count_zeros() - len.trailing_zeros();
+		pos.reverse_bits() >> width
+	}
+}
+
+impl<'a, O, T> Iterator for BitReversed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = &'a bool;
+
+	#[inline]
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		let idx = self.source(self.front);
+		self.front += 1;
+		Some(&self.inner[idx])
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.back - self.front;
+		(len, Some(len))
+	}
+
+	#[inline]
+	fn count(self) -> usize {
+		self.len()
+	}
+
+	#[inline]
+	fn nth(&mut self, n: usize) -> Option<Self::Item> {
+		if n >= self.back - self.front {
+			self.front = self.back;
+			return None;
+		}
+		self.front += n;
+		self.next()
+	}
+
+	#[inline]
+	fn last(mut self) -> Option<Self::Item> {
+		self.next_back()
+	}
+}
+
+impl<'a, O, T> DoubleEndedIterator for BitReversed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	#[inline]
+	fn next_back(&mut self) -> Option<Self::Item> {
+		if self.front == self.back {
+			return None;
+		}
+		self.back -= 1;
+		Some(&self.inner[self.source(self.back)])
+	}
+}
+
+impl<O, T> ExactSizeIterator for BitReversed<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+impl<O, T> FusedIterator for BitReversed<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+/** An iterator over the number of set bits in overlapping windows of a slice.
+
+This struct is created by the [`popcount_windows`] method on [`BitSlice`]s.
+
+[`BitSlice`]: struct.BitSlice.html
+[`popcount_windows`]: struct.BitSlice.html#method.popcount_windows
+**/
+#[derive(Clone, Debug)]
+pub struct PopcountWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The `BitSlice` from the start of the next window onward.
+	pub(super) inner: &'a BitSlice<O, T>,
+	/// The width of the windows.
+	pub(super) width: usize,
+	/// The number of set bits in the next window.
+	pub(super) ones: usize,
+}
+
+impl<'a, O, T> Iterator for PopcountWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = usize;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let len = self.inner.len();
+		if self.width > len {
+			self.inner = BitSlice::empty();
+			return None;
+		}
+		let out = self.ones;
+		if len > self.width {
+			self.ones -= self.inner[0] as usize;
+			self.ones += self.inner[self.width] as usize;
+		}
+		self.inner = &self.inner[1 ..];
+		Some(out)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.inner.len();
+		if self.width > len {
+			(0, Some(0))
+		}
+		else {
+			let count = len - self.width + 1;
+			(count, Some(count))
+		}
+	}
+}
+
+impl<'a, O, T> ExactSizeIterator for PopcountWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+impl<'a, O, T> FusedIterator for PopcountWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+/** An iterator over the densities of overlapping windows of a slice.
+
+This struct is created by the [`density_windows`] method on [`BitSlice`]s.
+
+[`BitSlice`]: struct.BitSlice.html
+[`density_windows`]: struct.BitSlice.html#method.density_windows
+**/
+#[derive(Clone, Debug)]
+pub struct DensityWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The counts of set bits in each window.
+	pub(super) inner: PopcountWindows<'a, O, T>,
+}
+
+impl<'a, O, T> Iterator for DensityWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = f64;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let width = self.inner.width as f64;
+		self.inner.next().map(|ones| ones as f64 / width)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.inner.size_hint()
+	}
+}
+
+impl<'a, O, T> ExactSizeIterator for DensityWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+impl<'a, O, T> FusedIterator for DensityWindows<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+/** An iterator over write guards for the bits of a slice that a mask selects.
+
+This struct is created by the [`select_mut`] method on [`BitSlice`]s.
+
+[`BitSlice`]: struct.BitSlice.html
+[`select_mut`]: struct.BitSlice.html#method.select_mut
+**/
+#[derive(Debug)]
+pub struct SelectMut<'a, O, T, P, U>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+	P: BitOrder,
+	U: 'a + BitStore,
+{
+	/// The `BitSlice` not yet searched.
+	pub(super) inner: &'a mut BitSlice<O, T>,
+	/// The part of the mask that corresponds to `inner`.
+	pub(super) mask: &'a BitSlice<P, U>,
+}
+
+impl<'a, O, T, P, U> Iterator for SelectMut<'a, O, T, P, U>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+	P: BitOrder,
+	U: 'a + BitStore,
+{
+	type Item = BitMut<'a, O, T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		//  Find the next set bit of the mask 64 bits at a time, by counting the
+		//  cleared bits in front of it.
+		let mut skip = 0;
+		loop {
+			let rest = &self.mask[skip ..];
+			if rest.is_empty() {
+				self.inner = BitSlice::empty_mut();
+				self.mask = BitSlice::empty();
+				return None;
+			}
+			let chunk = &rest[.. cmp::min(rest.len(), 64)];
+			let word = fields::load_msb_first(chunk) << (64 - chunk.len());
+			if word != 0 {
+				skip += word.leading_zeros() as usize;
+				break;
+			}
+			skip += chunk.len();
+		}
+		let inner = mem::replace(&mut self.inner, BitSlice::empty_mut());
+		let (bit, rest) = inner[skip ..].split_first_mut()?;
+		self.inner = rest;
+		self.mask = &self.mask[skip + 1 ..];
+		Some(bit)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		(0, Some(self.mask.len()))
+	}
+}
+
+impl<'a, O, T, P, U> FusedIterator for SelectMut<'a, O, T, P, U>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+	P: BitOrder,
+	U: 'a + BitStore,
+{
+}
+
+/** An iterator over a slice in consecutive chunks of at most `weight` set bits
+each.
+
+This struct is created by the [`chunks_by_weight`] method on [`BitSlice`]s.
+
+[`BitSlice`]: struct.BitSlice.html
+[`chunks_by_weight`]: struct.BitSlice.html#method.chunks_by_weight
+**/
+#[derive(Clone, Debug)]
+pub struct ChunksByWeight<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The `BitSlice` not yet produced.
+	pub(super) inner: &'a BitSlice<O, T>,
+	/// The most set bits in each chunk.
+	pub(super) weight: usize,
+}
+
+impl<'a, O, T> Iterator for ChunksByWeight<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = &'a BitSlice<O, T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let len = self.inner.len();
+		if len == 0 {
+			return None;
+		}
+		let block = T::BITS as usize;
+		let (mut ones, mut end) = (0, 0);
+		//  Skip over whole blocks while they fit in the budget, then walk the
+		//  block that does not, one bit at a time.
+		while end < len {
+			let next = cmp::min(end + block, len);
+			let count = self.inner[end .. next].count_ones();
+			if ones + count > self.weight {
+				break;
+			}
+			ones += count;
+			end = next;
+		}
+		while end < len {
+			if self.inner[end] {
+				if ones == self.weight {
+					break;
+				}
+				ones += 1;
+			}
+			end += 1;
+		}
+		let (head, rest) = self.inner.split_at(end);
+		self.inner = rest;
+		Some(head)
+	}
+
+	#[inline]
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.inner.len();
+		(cmp::min(len, 1), Some(len))
+	}
+}
+
+impl<O, T> FusedIterator for ChunksByWeight<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
 /** An iterator over a slice in (non-overlapping) chunks (`width` bits at a
 time), starting at the end of the slice.
 
@@ -5,6 +5,7 @@ use super::api::BitSliceIndex;
 use crate::{
 	access::BitAccess,
 	indices::Indexable,
+	kernels,
 	order::BitOrder,
 	slice::BitSlice,
 	store::BitStore,
@@ -18,6 +19,7 @@ use core::{
 		BitAndAssign,
 		BitOrAssign,
 		BitXorAssign,
+		Bound,
 		Index,
 		IndexMut,
 		Neg,
@@ -30,8 +32,8 @@ use core::{
 		RangeToInclusive,
 		ShlAssign,
 		ShrAssign,
+		SubAssign,
 	},
-	ptr,
 };
 
 /** Performs unsigned addition in place on a `BitSlice`.
@@ -44,17 +46,16 @@ Addition proceeds from the right ends of each slice towards the left. Because
 this trait is forbidden from returning anything, the final carry-out bit is
 discarded.
 
-Note that, unlike `BitVec`, there is no subtraction implementation until I find
-a subtraction algorithm that does not require modifying the subtrahend.
-
-Subtraction can be implemented by negating the intended subtrahend yourself and
-then using addition, or by using `BitVec`s instead of `BitSlice`s.
+Subtraction is available through the `SubAssign` implementation, or through
+[`BitSlice::sub_assign_borrow`] for arbitrary bitstreams.
 
 # Type Parameters
 
 - `I: IntoIterator<Item=bool, IntoIter: DoubleEndedIterator>`: The bitstream to
   add into `self`. It must be finite and double-ended, since addition operates
   in reverse.
+
+[`BitSlice::sub_assign_borrow`]: struct.BitSlice.html#method.sub_assign_borrow
 **/
 impl<O, T, I> AddAssign<I> for BitSlice<O, T>
 where
@@ -393,6 +394,28 @@ where
 	}
 }
 
+impl<O, T> Index<(Bound<usize>, Bound<usize>)> for BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Output = Self;
+
+	fn index(&self, range: (Bound<usize>, Bound<usize>)) -> &Self {
+		range.index(self)
+	}
+}
+
+impl<O, T> IndexMut<(Bound<usize>, Bound<usize>)> for BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn index_mut(&mut self, range: (Bound<usize>, Bound<usize>)) -> &mut Self {
+		range.index_mut(self)
+	}
+}
+
 /** Performs fixed-width 2’s-complement negation of a `BitSlice`.
 
 Unlike the `!` operator (`Not` trait), the unary `-` operator treats the
@@ -629,9 +652,10 @@ where
 			self.set_all(false);
 			return;
 		}
-		//  If the slice fully owns its memory, then a fast path is available
-		//  with element-wise `memmove`.
-		if self.bitptr().domain().is_spanning() {
+		//  If the slice fully owns its memory, and the shift crosses at least
+		//  one element, then a fast path is available with element-wise
+		//  `memmove`.
+		if shamt >= T::BITS as usize && self.bitptr().domain().is_spanning() {
 			//  Compute the shift distance measured in elements.
 			let offset = shamt >> T::INDX;
 			//  Compute the number of elements that will remain.
@@ -650,20 +674,10 @@ where
 			  ^-------after-------^ 0 0 0 0 0
 			*/
 
-			//  Pointer to the front of the slice.
-			let to: *mut T = self.as_mut_ptr();
-			//  Pointer to the front of the section that will move and be
-			//  retained.
-			let from: *const T = &self.as_slice()[offset];
-			//  Pointer to the back of the slice that will be zero-filled.
-			let tail: *mut T = &mut self.as_mut_slice()[rem];
-			unsafe {
-				ptr::copy(from, to, rem);
-				ptr::write_bytes(tail, 0, offset);
-			}
+			kernels::shift_elements_down(self.as_mut_slice(), offset);
 			//  Any remaining shift amount only needs to shift the `after` block
 			//  above.
-			self[.. rem << T::INDX] <<= shamt & T::INDX as usize;
+			self[.. rem << T::INDX] <<= shamt & T::MASK as usize;
 			return;
 		}
 		//  Otherwise, crawl.
@@ -738,13 +752,12 @@ where
 			self.set_all(false);
 			return;
 		}
-		//  If the slice fully owns its memory, then a fast path is available
-		//  with element-wise `memmove`.
-		if self.bitptr().domain().is_spanning() {
+		//  If the slice fully owns its memory, and the shift crosses at least
+		//  one element, then a fast path is available with element-wise
+		//  `memmove`.
+		if shamt >= T::BITS as usize && self.bitptr().domain().is_spanning() {
 			//  Compute the shift amount measured in elements.
 			let offset = shamt >> T::INDX;
-			// Compute the number of elements that will remain.
-			let rem = self.bitptr().elements() - offset;
 
 			/* Memory model: suppose we have this slice of sixteen elements,
 			that is shifted five elements to the right. We have two pointers
@@ -757,15 +770,10 @@ where
 			[ 0 1 2 3 4 5 6 7 8 9 a b c d e f ]
 			  0 0 0 0 0 ^-------after-------^
 			*/
-			let from: *mut T = self.as_mut_ptr();
-			let to: *mut T = &mut self.as_mut_slice()[offset];
-			unsafe {
-				ptr::copy(from, to, rem);
-				ptr::write_bytes(from, 0, offset);
-			}
+			kernels::shift_elements_up(self.as_mut_slice(), offset);
 			//  Any remaining shift amount only needs to shift the `after` block
 			//  above.
-			self[offset << T::INDX ..] >>= shamt & T::INDX as usize;
+			self[offset << T::INDX ..] >>= shamt & T::MASK as usize;
 			return;
 		}
 		//  Otherwise, crawl.
@@ -777,3 +785,44 @@ where
 		self[.. shamt].set_all(false);
 	}
 }
+
+/** Performs unsigned subtraction in place on a `BitSlice`.
+
+The subtrahend is right-aligned against `self`, and zero-extended at the left if
+it is shorter. If it is longer, the excess front length is unused. The
+subtrahend is not modified.
+
+Because this trait is forbidden from returning anything, the final borrow-out
+bit is discarded, and the difference wraps. Use [`BitSlice::sub_assign_borrow`]
+to observe it.
+
+[`BitSlice::sub_assign_borrow`]: struct.BitSlice.html#method.sub_assign_borrow
+**/
+impl<O, T, P, U> SubAssign<&BitSlice<P, U>> for BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+	P: BitOrder,
+	U: BitStore,
+{
+	/// Performs unsigned wrapping subtraction in place.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut src = [0b0110_0011u8];
+	/// let bits = src.bits_mut::<Msb0>();
+	/// let (accum, step) = bits.split_at_mut(4);
+	/// *accum -= &*step;
+	/// assert_eq!(accum, bits![0, 0, 1, 1]);
+	/// *accum -= &*step;
+	/// assert_eq!(accum, bits![0, 0, 0, 0]);
+	/// *accum -= &*step;
+	/// assert_eq!(accum, bits![1, 1, 0, 1]);
+	/// ```
+	fn sub_assign(&mut self, subtrahend: &BitSlice<P, U>) {
+		self.sub_assign_borrow(subtrahend.iter().copied());
+	}
+}
@@ -24,7 +24,14 @@ use crate::{
 };
 
 use core::{
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
 	marker::PhantomData,
+	mem,
 	ops::{
 		Deref,
 		DerefMut,
@@ -60,6 +67,154 @@ where
 	pub(super) bit: bool,
 }
 
+impl<O, T> BitMut<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Gets the index of the proxied bit within its memory element.
+	///
+	/// This is the semantic index that the `BitOrder` type parameter maps to a
+	/// concrete bit position; it is not an index into the slice from which
+	/// the proxy was produced.
+	pub fn index(&self) -> BitIdx<T> {
+		self.head
+	}
+
+	/// Writes the cached bit into the referent memory, and releases the
+	/// proxy.
+	///
+	/// This is what happens when the proxy is dropped; the method exists to
+	/// make the write explicit at the point where a transaction decides to
+	/// keep it.
+	pub fn commit(self) {
+	}
+
+	/// Releases the proxy without writing the cached bit.
+	///
+	/// The referent memory keeps the value it had when the proxy was made.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// let mut bit = bits.first_mut().unwrap();
+	/// *bit = true;
+	/// bit.cancel();
+	/// assert!(bits.not_any());
+	///
+	/// let mut bit = bits.last_mut().unwrap();
+	/// *bit = true;
+	/// assert_eq!(*bit.index(), 7);
+	/// bit.commit();
+	/// assert_eq!(data, 1);
+	/// ```
+	pub fn cancel(self) {
+		mem::forget(self);
+	}
+
+	/// Lends the cached bit to a function as a `&mut bool`, then writes it
+	/// into the referent memory.
+	///
+	/// This is the proxy’s equivalent of `RefMut::map`: the function receives
+	/// an ordinary mutable borrow of the bit, so it can be passed to code that
+	/// expects `&mut bool`, and the borrow cannot outlive the proxy.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	/// - `func`: A function which receives the cached bit.
+	///
+	/// # Returns
+	///
+	/// The return value of `func`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// fn toggle(flag: &mut bool) -> bool {
+	///     *flag = !*flag;
+	///     *flag
+	/// }
+	///
+	/// let mut data = 0u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// assert!(bits.first_mut().unwrap().map(toggle));
+	/// assert_eq!(data, 0x80);
+	/// ```
+	pub fn map<F, R>(mut self, func: F) -> R
+	where F: FnOnce(&mut bool) -> R {
+		func(&mut self.bit)
+	}
+
+	/// Lends the cached bit to a fallible function as a `&mut bool`, and
+	/// writes it into the referent memory only if the function succeeds.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	/// - `func`: A function which receives the cached bit.
+	///
+	/// # Returns
+	///
+	/// The return value of `func`. If it is `Err`, the proxy is released as by
+	/// [`cancel`], and any change that `func` made to the bit is discarded.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut data = 0u8;
+	/// let bits = data.bits_mut::<Msb0>();
+	/// let res = bits.first_mut().unwrap().try_map(|bit| {
+	///     *bit = true;
+	///     Err("rolled back")
+	/// });
+	/// assert_eq!(res, Err::<(), _>("rolled back"));
+	/// assert_eq!(data, 0);
+	/// ```
+	///
+	/// [`cancel`]: #method.cancel
+	pub fn try_map<F, R, E>(mut self, func: F) -> Result<R, E>
+	where F: FnOnce(&mut bool) -> Result<R, E> {
+		let out = func(&mut self.bit);
+		if out.is_err() {
+			self.cancel();
+		}
+		out
+	}
+}
+
+impl<O, T> Debug for BitMut<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.debug_struct("BitMut")
+			.field("data", &self.data)
+			.field("head", &self.head)
+			.field("bit", &self.bit)
+			.finish()
+	}
+}
+
+impl<O, T> Display for BitMut<'_, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Display::fmt(&self.bit, fmt)
+	}
+}
+
 impl<O, T> Deref for BitMut<'_, O, T>
 where
 	O: BitOrder,
@@ -2,16 +2,35 @@
 !*/
 
 use crate::{
+	fields::{
+		BitField,
+		Overflow,
+	},
+	indices::{
+		BitIdx,
+		BitPos,
+		Indexable,
+	},
 	order::{
+		BitOrder,
 		Local,
+		Lsb0,
 		Msb0,
 	},
 	slice::{
 		AsBits,
 		BitSlice,
 	},
+	store::BitStore,
+};
+
+use alloc::{
+	borrow::ToOwned,
+	vec::Vec,
 };
 
+use core::cmp;
+
 #[test]
 fn all() {
 	assert!(BitSlice::<Local, usize>::empty().all());
@@ -110,3 +129,1299 @@ fn set_all() {
 	bits.set_all(true);
 	assert_eq!(data, [!0; 5]);
 }
+
+#[test]
+fn copy_from_slice() {
+	let src = [0xA5u8, 0x3C, 0x96, 0x0F];
+	let src = src.bits::<Msb0>();
+
+	for head in 0 .. 8 {
+		for len in 0 .. 32 - head {
+			let mut dst = [0u8; 4];
+			let dst = dst.bits_mut::<Msb0>();
+			dst[head ..][.. len].copy_from_slice(&src[head ..][.. len]);
+			assert_eq!(&dst[head ..][.. len], &src[head ..][.. len]);
+			assert!(dst[.. head].not_any());
+			assert!(dst[head + len ..].not_any());
+		}
+	}
+
+	let mut dst = [0u8; 4];
+	let dst = dst.bits_mut::<Msb0>();
+	dst[3 ..][.. 20].copy_from_slice(&src[5 ..][.. 20]);
+	assert_eq!(&dst[3 ..][.. 20], &src[5 ..][.. 20]);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn repeat() {
+	let bits = &0b1101_0011u8.bits::<Msb0>()[1 ..][.. 5];
+	for n in 0 .. 40 {
+		let rep = bits.repeat(n);
+		assert_eq!(rep.len(), 5 * n);
+		for chunk in rep.chunks(5) {
+			assert_eq!(chunk, bits);
+		}
+	}
+
+	let bits = [0xA5u8, 0x3C].bits::<Msb0>();
+	let rep = bits.repeat(9);
+	let mut expected = Vec::new();
+	for _ in 0 .. 9 {
+		expected.extend_from_slice(&[0xA5, 0x3C]);
+	}
+	assert_eq!(rep.as_slice(), &expected[..]);
+}
+
+/// Swaps the bits of each pair, so that no shift moves the bits in order.
+struct PairSwap;
+
+impl BitOrder for PairSwap {
+	const TYPENAME: &'static str = "PairSwap";
+
+	fn at<T>(place: BitIdx<T>) -> BitPos<T>
+	where T: BitStore {
+		(*place ^ 1).pos()
+	}
+}
+
+/// Checks `copy_within` against a copy through a scratch buffer, at distances
+/// that are and are not multiples of the element width, in both directions.
+#[cfg(feature = "alloc")]
+fn check_copy_within<O, T>(data: &[T])
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let bits = T::BITS as usize;
+	let counts = [1, 3, bits - 1, bits, bits + 3, 3 * bits - 5];
+	let dists = [1, 3, bits - 1, bits, bits + 1, 2 * bits + 5];
+	for &head in [0, 1, bits - 1].iter() {
+		let orig = &BitSlice::<O, T>::from_slice(data)[head ..];
+		let len = orig.len();
+		for &count in counts.iter().filter(|&&count| count <= len) {
+			for from in 0 ..= len - count {
+				for &dist in dists.iter() {
+					let dests = [from.checked_sub(dist), Some(from + dist)];
+					for &dest in dests.iter() {
+						let dest = match dest {
+							Some(dest) if dest + count <= len => dest,
+							_ => continue,
+						};
+						let mut buf = data.to_vec();
+						let view = BitSlice::<O, T>::from_slice_mut(&mut buf);
+						let view = &mut view[head ..];
+						view.copy_within(from .. from + count, dest);
+
+						let mut expected = orig.to_owned();
+						let src = orig[from ..][.. count].to_owned();
+						expected[dest ..][.. count].copy_from_slice(&src);
+						assert_eq!(*view, expected, "{} +{} to {}", from, count, dest);
+						assert_eq!(
+							BitSlice::<O, T>::from_slice(&buf)[.. head],
+							BitSlice::<O, T>::from_slice(data)[.. head],
+						);
+					}
+				}
+			}
+		}
+	}
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn copy_within_shifted() {
+	let data = [0xC5u8, 0x0E, 0x71, 0xAF, 0x38];
+	check_copy_within::<Msb0, u8>(&data);
+	check_copy_within::<Lsb0, u8>(&data);
+	check_copy_within::<PairSwap, u8>(&data);
+
+	let data = [0xA5C3u16, 0x0FF0, 0x1234, 0xFEDC];
+	check_copy_within::<Msb0, u16>(&data);
+	check_copy_within::<Lsb0, u16>(&data);
+
+	let data = [0x0123_4567u32, 0x89AB_CDEF, 0xF0E1_D2C3, 0x5A5A_A5A5];
+	check_copy_within::<Msb0, u32>(&data);
+	check_copy_within::<Lsb0, u32>(&data);
+	check_copy_within::<PairSwap, u32>(&data);
+
+	#[cfg(target_pointer_width = "64")]
+	{
+		let data = [0x0123_4567_89AB_CDEFu64, !0 << 3, 0x8000_0000_0000_0001];
+		check_copy_within::<Msb0, u64>(&data);
+		check_copy_within::<Lsb0, u64>(&data);
+	}
+}
+
+#[test]
+#[should_panic(expected = "range end overflows usize")]
+fn copy_within_end_overflow() {
+	let mut data = 0u8;
+	data.bits_mut::<Msb0>().copy_within(1 ..= usize::max_value(), 0);
+}
+
+#[test]
+fn copy_within() {
+	let data = [0xA5u8, 0x3C, 0x96, 0x0F, 0x5A];
+	let bits = data.bits::<Msb0>();
+	let len = bits.len();
+
+	for head in 0 .. 8 {
+		let tail = len - head;
+		for count in [0, 1, 5, 8, 13, 16, 21].iter().copied() {
+			for from in 0 ..= tail - count {
+				for dest in 0 ..= tail - count {
+					let mut buf = data;
+					let view = &mut buf.bits_mut::<Msb0>()[head ..];
+					view.copy_within(from .. from + count, dest);
+
+					let orig = &bits[head ..];
+					for n in 0 .. tail {
+						let expected = if (dest .. dest + count).contains(&n) {
+							orig[from + n - dest]
+						}
+						else {
+							orig[n]
+						};
+						assert_eq!(view[n], expected);
+					}
+					assert_eq!(buf.bits::<Msb0>()[.. head], bits[.. head]);
+				}
+			}
+		}
+	}
+}
+
+#[test]
+fn sub_assign_borrow_long_subtrahend() {
+	//  The subtrahend is eight bits wide, and is truncated to its low `width`
+	//  bits when subtracted from a narrower slice.
+	let sub = 0b1011_0110u8;
+	for width in 1 .. 8 {
+		let mask = (1u8 << width) - 1;
+		for value in 0 ..= mask {
+			let mut data = value;
+			let bits = &mut data.bits_mut::<Msb0>()[8 - width ..];
+			let sub_bits = sub.bits::<Msb0>().iter().copied();
+			assert_eq!(bits.sub_assign_borrow(sub_bits), sub & mask > value);
+			assert_eq!(data, value.wrapping_sub(sub) & mask);
+		}
+	}
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn bit_finder() {
+	use crate::slice::BitFinder;
+
+	let hay = [0xA5u8, 0x3C, 0x96, 0x0F, 0x5A, 0xA5];
+	let hay = hay.bits::<Msb0>();
+	let needles = [0b1010_0101u8, 0b0110_1001, 0xFF];
+	for needle in needles.iter() {
+		for len in 1 ..= 8 {
+			let needle = &needle.bits::<Lsb0>()[.. len];
+			let finder = BitFinder::new(needle);
+			let expected = hay.windows(len).position(|w| w == needle);
+			assert_eq!(finder.find_in(hay), expected);
+
+			let mut next = 0;
+			for hit in finder.find_iter(hay) {
+				let skip = hay[next ..].windows(len).position(|w| w == needle);
+				assert_eq!(Some(hit - next), skip);
+				next = hit + len;
+			}
+			assert!(hay[next ..].windows(len).all(|w| w != needle));
+		}
+	}
+
+	let empty = BitFinder::new(BitSlice::<Msb0, u8>::empty());
+	assert_eq!(empty.find_iter(&hay[.. 3]).collect::<Vec<_>>(), [0, 1, 2, 3]);
+}
+
+#[test]
+#[cfg(all(feature = "alloc", target_pointer_width = "64"))]
+fn bit_finder_long_needles() {
+	use crate::{
+		slice::BitFinder,
+		vec::BitVec,
+	};
+
+	fn naive(
+		hay: &BitSlice<Lsb0, u64>,
+		needle: &BitSlice<Lsb0, u64>,
+	) -> Vec<usize> {
+		let len = needle.len();
+		let mut out = Vec::new();
+		let mut next = 0;
+		while let Some(n) = hay[next ..].windows(len).position(|w| w == needle) {
+			out.push(next + n);
+			next += n + len;
+		}
+		out
+	}
+
+	//  A periodic haystack with a few flipped bits gives both periodic and
+	//  aperiodic needles, including needles longer than one load.
+	let mut hay = [0x0F0F_0F0F_0F0F_0F0Fu64; 6];
+	hay[2] ^= 1 << 17;
+	hay[4] ^= 3 << 40;
+	let hay = hay.bits::<Lsb0>();
+	let copy = hay.iter().copied().collect::<BitVec<Msb0, u8>>();
+	for start in (0 .. 200).step_by(13) {
+		for &len in [1, 7, 40, 64, 65, 100, 150].iter() {
+			let needle = &hay[start ..][.. len];
+			let finder = BitFinder::new(needle);
+			let expected = naive(hay, needle);
+			assert_eq!(finder.find_iter(hay).collect::<Vec<_>>(), expected);
+			assert_eq!(finder.find_iter(&copy).collect::<Vec<_>>(), expected);
+
+			let tail = naive(&hay[3 ..], needle);
+			assert_eq!(finder.find_in(&hay[3 ..]), tail.first().copied());
+		}
+	}
+}
+
+#[test]
+fn chunks_mut_disjoint() {
+	for head in 0 .. 8 {
+		for width in 1 .. 30 {
+			let mut data = [0u16; 4];
+			let bits = &mut data.bits_mut::<Msb0>()[head ..];
+			let len = bits.len();
+			let iter = bits.chunks_mut_disjoint(width);
+			let count = iter.len();
+
+			let mut seen = 0;
+			let mut last: Option<*const u16> = None;
+			let mut chunks = 0;
+			for chunk in iter {
+				assert!(chunk.len() >= width || seen + chunk.len() == len);
+				assert!(chunk.len() < width + 16);
+				let elts = chunk.as_total_slice();
+				let first = elts.as_ptr() as *const u16;
+				if let Some(prev) = last {
+					assert!(first > prev, "chunks share an element");
+				}
+				last = Some(unsafe { first.add(elts.len() - 1) });
+				seen += chunk.len();
+				chunks += 1;
+			}
+			assert_eq!(seen, len);
+			assert_eq!(chunks, count);
+		}
+	}
+}
+
+#[test]
+fn overlaps() {
+	let data = [0u16; 3];
+	let bits = data.bits::<Msb0>();
+	let len = bits.len();
+
+	for a in 0 .. len {
+		for b in a .. len {
+			for c in 0 .. len {
+				for d in c .. len {
+					let (x, y) = (&bits[a .. b], &bits[c .. d]);
+					let expected = (a .. b).any(|n| (c .. d).contains(&n));
+					assert_eq!(x.overlaps(y), expected);
+					assert_eq!(y.overlaps(x), expected);
+
+					let shared = a < b
+						&& c < d && (a / 16 ..= (b - 1) / 16)
+						.any(|n| (c / 16 ..= (d - 1) / 16).contains(&n));
+					assert_eq!(x.shares_elements(y), shared);
+				}
+			}
+		}
+	}
+
+	let other = [0u16; 3];
+	assert!(!bits.overlaps(other.bits::<Msb0>()));
+	assert!(!bits.shares_elements(other.bits::<Lsb0>()));
+}
+
+#[test]
+fn swap_element_bytes() {
+	//  A big-endian wire buffer, viewed through native `u32` elements.
+	let mut data = [
+		u32::from_ne_bytes([0x12, 0x34, 0x56, 0x78]),
+		u32::from_ne_bytes([0x9A, 0xBC, 0xDE, 0xF0]),
+	];
+	let bits = data.bits_mut::<Lsb0>();
+	bits.swap_element_bytes();
+	assert_eq!(bits[.. 32].load::<u32>(), 0x1234_5678);
+	assert_eq!(bits[32 ..].load::<u32>(), 0x9ABC_DEF0);
+	assert_eq!(bits[16 .. 48].load_be::<u32>(), 0x1234_DEF0);
+
+	bits[8 .. 56].swap_element_bytes();
+	bits.swap_element_bytes();
+	assert_eq!(data[0].to_ne_bytes(), [0x12, 0x34, 0x56, 0x78]);
+	assert_eq!(data[1].to_ne_bytes(), [0x9A, 0xBC, 0xDE, 0xF0]);
+}
+
+#[test]
+fn get_ranges() {
+	use core::ops::Bound::*;
+
+	let mut data = 0x0Fu8;
+	let bits = data.bits_mut::<Msb0>();
+	let max = !0usize;
+
+	assert_eq!(bits.get(2 .. 6).map(BitSlice::count_ones), Some(2));
+	let (start, end) = (6, 2);
+	assert!(bits.get(start .. end).is_none());
+	assert!(bits.get(4 ..= 8).is_none());
+	assert!(bits.get(4 ..= max).is_none());
+	assert!(bits.get(..= max).is_none());
+	assert_eq!(bits.get(..= 7).map(BitSlice::len), Some(8));
+	assert!(bits.get(9 ..).is_none());
+	assert!(bits.get(.. 9).is_none());
+
+	assert_eq!(bits.get((Excluded(3), Included(5))).map(BitSlice::len), Some(2));
+	assert_eq!(bits.get((Unbounded, Excluded(8))).map(BitSlice::len), Some(8));
+	assert!(bits.get((Excluded(max), Unbounded)).is_none());
+	assert!(bits.get((Included(0), Included(max))).is_none());
+	assert!(bits.get((Included(5), Excluded(4))).is_none());
+
+	if let Some(part) = bits.get_mut((Included(0), Excluded(4))) {
+		part.set_all(true);
+	}
+	assert!(bits.get_mut(..= max).is_none());
+	assert!(bits[(Unbounded, Unbounded)].all());
+	assert_eq!(data, !0);
+}
+
+#[test]
+fn split_at_mut_aligned() {
+	let mut data = [0u16; 3];
+	let all = data.bits_mut::<Lsb0>();
+	for start in 0 .. 16 {
+		for end in start .. 48 {
+			let bits = &mut all[start .. end];
+			let len = bits.len();
+			for mid in 0 ..= len {
+				let (left, right, split) = bits.split_at_mut_aligned(mid);
+				assert_eq!(left.len(), split);
+				assert_eq!(right.len(), len - split);
+				assert!(!left.shares_elements(right));
+
+				//  The split point is an element boundary, unless it is an
+				//  end of the slice.
+				let pos = start + split;
+				assert!(split == 0 || split == len || pos % 16 == 0);
+				//  No element boundary inside the slice is closer to `mid`.
+				let dist = |n: usize| cmp::max(n, mid) - cmp::min(n, mid);
+				for n in 0 ..= len {
+					if (start + n) % 16 == 0 {
+						assert!(dist(split) <= dist(n));
+					}
+				}
+			}
+		}
+	}
+}
+
+#[test]
+#[cfg(target_pointer_width = "64")]
+fn volatile_matches_plain() {
+	let mut plain = [0u8; 10];
+	let mut vol = [0u8; 10];
+	for start in 0 .. 8 {
+		for len in 1 ..= 64 {
+			let value = 0x0123_4567_89AB_CDEFu64
+				.rotate_left(start as u32 + len as u32);
+			plain.bits_mut::<Msb0>()[start ..][.. len].store(value);
+			unsafe {
+				vol.bits_mut::<Msb0>()[start ..][.. len].store_volatile(value);
+			}
+			assert_eq!(plain, vol);
+			assert_eq!(
+				unsafe {
+					vol.bits::<Msb0>()[start ..][.. len].load_volatile::<u64>()
+				},
+				plain.bits::<Msb0>()[start ..][.. len].load::<u64>(),
+			);
+
+			plain.bits_mut::<Lsb0>()[start ..][.. len].store(!value);
+			unsafe {
+				vol.bits_mut::<Lsb0>()[start ..][.. len].store_volatile(!value);
+			}
+			assert_eq!(plain, vol);
+			assert_eq!(
+				unsafe {
+					vol.bits::<Lsb0>()[start ..][.. len].load_volatile::<u64>()
+				},
+				plain.bits::<Lsb0>()[start ..][.. len].load::<u64>(),
+			);
+		}
+	}
+
+	let bits = vol.bits_mut::<Msb0>();
+	for n in 0 .. bits.len() {
+		let bit = unsafe { !bits.get_volatile(n) };
+		unsafe {
+			bits.set_volatile(n, bit);
+		}
+		assert_eq!(bits[n], bit);
+	}
+}
+
+#[test]
+fn as_int() {
+	let data = [0xA5u8, 0x3C, 0xFF];
+	let bits = data.bits::<Lsb0>();
+
+	for len in 1 ..= 16 {
+		for start in 0 .. 8 {
+			let field = &bits[start ..][.. len];
+			let unsigned = field.load::<u16>();
+			assert_eq!(field.as_uint::<u16>(), Some(unsigned));
+			let shift = 16 - len as u32;
+			let signed = ((unsigned << shift) as i16) >> shift;
+			assert_eq!(field.as_int::<i16>(), Some(signed));
+			assert_eq!(field.as_int::<i32>(), Some(signed as i32));
+		}
+	}
+
+	assert_eq!(bits[.. 9].as_uint::<u8>(), None);
+	assert_eq!(bits[.. 9].as_int::<i8>(), None);
+	assert_eq!(bits[.. 0].as_int::<i8>(), Some(0));
+}
+
+#[test]
+fn store_uint() {
+	//  Fields that start inside an element and span several of them.
+	for start in 0 .. 8 {
+		for len in 1 ..= 16 {
+			let mut data = [0xA5u8; 4];
+			let value = 0xBEEFu16 & (!0 >> (16 - len));
+			let bits = &mut data.bits_mut::<Msb0>()[start ..][.. len];
+			assert!(bits.store_uint(value).is_ok());
+			assert_eq!(bits.load::<u16>(), value);
+			assert!(bits.store_uint_le(value).is_ok());
+			assert_eq!(bits.load_le::<u16>(), value);
+			assert!(bits.store_uint_be(value).is_ok());
+			assert_eq!(bits.load_be::<u16>(), value);
+
+			let all = data.bits::<Msb0>();
+			assert_eq!(all[.. start], 0xA5A5u16.bits::<Msb0>()[.. start]);
+			assert_eq!(
+				all[start + len ..],
+				[0xA5u8; 4].bits::<Msb0>()[start + len ..],
+			);
+		}
+	}
+
+	//  Values too wide for the slice, and slices too wide for the value,
+	//  leave the slice unchanged.
+	let mut data = [0x0Fu8, 0xF0];
+	let bits = &mut data.bits_mut::<Lsb0>()[3 .. 13];
+	assert_eq!(bits.store_uint(0x400u16), Err(Overflow));
+	assert_eq!(bits.store_uint_le(0xFFFFu16), Err(Overflow));
+	assert_eq!(bits.store_uint_be(0x401u16), Err(Overflow));
+	assert_eq!(bits.store_uint(0u8), Err(Overflow));
+	assert_eq!(data, [0x0F, 0xF0]);
+	assert!(data.bits_mut::<Lsb0>()[3 .. 13].store_uint(0x3FFu16).is_ok());
+	assert_eq!(data, [0xFF, 0xFF]);
+
+	//  An empty slice holds only zero.
+	let empty = &mut data.bits_mut::<Msb0>()[5 .. 5];
+	assert!(empty.store_uint(0u32).is_ok());
+	assert_eq!(empty.store_uint(1u32), Err(Overflow));
+}
+
+#[test]
+fn gray_code() {
+	for n in 0 .. 256u16 {
+		let mut data = n as u8;
+		let bits = data.bits_mut::<Msb0>();
+		bits.to_gray();
+		assert_eq!(data, (n ^ (n >> 1)) as u8);
+		data.bits_mut::<Msb0>().from_gray();
+		assert_eq!(data, n as u8);
+	}
+
+	let mut data = [0u16, 0x0ABC];
+	let bits = &mut data.bits_mut::<Lsb0>()[5 .. 27];
+	let before = bits.to_owned();
+	bits.to_gray();
+	for i in 1 .. bits.len() {
+		assert_eq!(bits[i], before[i] ^ before[i - 1]);
+	}
+	bits.from_gray();
+	assert_eq!(bits, &before);
+
+	//  A run of ones encodes as its leading edge, and alternating bits as a
+	//  run of ones, including across the element boundary.
+	let mut data = [0xFFFFu16, 0x5555];
+	data.bits_mut::<Msb0>().to_gray();
+	assert_eq!(data, [0x8000, 0xFFFF]);
+
+	//  Set and cleared bits at the element edges carry between elements.
+	let src = [0x8001u16, 0xFFFF, 0x5555, 0x00F0];
+	for &(start, len) in &[(0, 64), (3, 9), (5, 50), (16, 33), (7, 0)] {
+		let mut data = src;
+		let bits = &mut data.bits_mut::<Msb0>()[start ..][.. len];
+		let before = src.bits::<Msb0>()[start ..][.. len].to_owned();
+		bits.to_gray();
+		assert_eq!(bits.first(), before.first());
+		for i in 1 .. len {
+			assert_eq!(bits[i], before[i] ^ before[i - 1]);
+		}
+		assert_eq!(data.bits::<Msb0>()[.. start], src.bits::<Msb0>()[.. start]);
+
+		let mut data = src;
+		let bits = &mut data.bits_mut::<Lsb0>()[start ..][.. len];
+		bits.to_gray();
+		bits.from_gray();
+		assert_eq!(data, src);
+	}
+}
+
+#[test]
+fn bit_reversal() {
+	let data = [0x1234_5678u32, 0x9ABC_DEF0];
+	let bits = data.bits::<Lsb0>();
+
+	let forward: Vec<bool> = bits.iter_bit_reversed().copied().collect();
+	for (n, bit) in forward.iter().enumerate() {
+		let idx = BitStore::reverse_bits(n) >> (0usize.count_zeros() - 6);
+		assert_eq!(*bit, bits[idx]);
+	}
+	let mut backward: Vec<bool> =
+		bits.iter_bit_reversed().rev().copied().collect();
+	backward.reverse();
+	assert_eq!(forward, backward);
+	assert_eq!(bits.iter_bit_reversed().nth(1), Some(&bits[32]));
+	assert_eq!(bits[.. 1].iter_bit_reversed().count(), 1);
+	assert_eq!(bits[.. 0].iter_bit_reversed().count(), 0);
+
+	let mut data = [0x0Fu8, 0x01, 0x80];
+	data.bits_mut::<Lsb0>()[4 ..].reverse_bits_per_element();
+	assert_eq!(data, [0x0F, 0x80, 0x01]);
+}
+
+#[test]
+#[should_panic]
+fn bit_reversal_needs_power_of_two() {
+	let data = 0u8;
+	data.bits::<Lsb0>()[.. 6].iter_bit_reversed();
+}
+
+#[test]
+fn test_and_modify() {
+	let mut data = [0u16; 2];
+	let bits = &mut data.bits_mut::<Msb0>()[3 .. 29];
+	assert!(!bits.replace(13, true));
+	assert!(bits.toggle(13));
+	assert!(!bits.toggle(25));
+	assert!(!bits.set_if(0, true, |old| old));
+	assert!(!bits.set_if(1, true, |old| !old));
+	assert!(bits.set_if(1, false, |_| false));
+	assert_eq!(bits.count_ones(), 2);
+	assert_eq!(data, [0x0800, 0x0008]);
+}
+
+#[test]
+fn common_prefix_len() {
+	let a = [0u16, 0x1234, 0x5678, 0x9ABC];
+	let b = [0u16, 0x1234, 0x5678, 0x9ABD];
+	let (a, b) = (a.bits::<Lsb0>(), b.bits::<Lsb0>());
+	//  The first difference is the lowest bit of the last element.
+	assert_eq!(a.common_prefix_len(b), 48);
+	assert_eq!(a[3 ..].common_prefix_len(&b[3 ..]), 45);
+	assert_eq!(a[3 .. 40].common_prefix_len(&b[3 ..]), 37);
+	assert_eq!(a[5 .. 20].common_prefix_len(&b[5 .. 50]), 15);
+	assert_eq!(a[48 ..].common_prefix_len(&b[48 ..]), 0);
+	assert_eq!(a[49 ..].common_prefix_len(&b[49 ..]), 15);
+	//  Misaligned slices agree with a bit-by-bit count.
+	for shift in 1 .. 4 {
+		let (x, y) = (&a[20 ..], &b[20 + shift ..]);
+		let naive = x.iter().zip(y).take_while(|(l, r)| l == r).count();
+		assert_eq!(x.common_prefix_len(y), naive);
+	}
+
+	assert_eq!(a.strip_prefix(&b[.. 48]), Some(&a[48 ..]));
+	assert!(a.strip_prefix(&b[.. 49]).is_none());
+	assert_eq!(a.strip_suffix(&a[30 ..]), Some(&a[.. 30]));
+	assert!(a.strip_suffix(&b[30 ..]).is_none());
+}
+
+#[test]
+fn increment_decrement() {
+	let mut data = [0xFFu8, 0x7F, 0xFF, 0xFF];
+	let bits = &mut data.bits_mut::<Msb0>()[4 .. 30];
+	assert!(!bits.increment());
+	assert_eq!(data, [0xFF, 0x80, 0x00, 0x03]);
+
+	let bits = &mut data.bits_mut::<Msb0>()[4 .. 30];
+	assert!(!bits.decrement());
+	assert_eq!(data, [0xFF, 0x7F, 0xFF, 0xFF]);
+
+	let bits = &mut data.bits_mut::<Msb0>()[9 .. 30];
+	assert!(bits.increment());
+	assert!(bits.not_any());
+	assert!(bits.decrement());
+	assert!(bits.all());
+
+	let mut empty = 0u8;
+	assert!(empty.bits_mut::<Lsb0>()[.. 0].increment());
+	assert!(empty.bits_mut::<Lsb0>()[.. 0].decrement());
+}
+
+#[test]
+fn fused_counts() {
+	//  Each pair of elements overlaps differently, so the three counts differ.
+	let a = [!0u32, 0, 0xFFFF_0000, 0x0F0F_0F0F];
+	let b = [0xAAAA_AAAAu32, !0, 0x00FF_FF00, 0x3333_3333];
+	let (a, b) = (a.bits::<Msb0>(), b.bits::<Msb0>());
+	assert_eq!(a.and_count_ones(b), 16 + 8 + 8);
+	assert_eq!(a.or_count_ones(b), 32 + 32 + 24 + 24);
+	assert_eq!(a.xor_count_ones(b), 16 + 32 + 16 + 16);
+	//  Same heads take the element path; different heads take the bit path.
+	for &(ra, rb) in &[(0, 0), (3, 3), (5, 36), (40, 9)] {
+		for &len in &[0, 7, 27, 60, 87] {
+			let (l, r) = (&a[ra .. ra + len], &b[rb .. rb + len]);
+			let count = |f: fn(bool, bool) -> bool| {
+				l.iter().zip(r.iter()).filter(|(x, y)| f(**x, **y)).count()
+			};
+			assert_eq!(l.and_count_ones(r), count(|x, y| x & y));
+			assert_eq!(l.or_count_ones(r), count(|x, y| x | y));
+			assert_eq!(l.xor_count_ones(r), count(|x, y| x ^ y));
+		}
+	}
+}
+
+#[test]
+fn combine_many() {
+	let mut src = [[0u16; 80]; 3];
+	for (n, elt) in src.iter_mut().flat_map(|s| s.iter_mut()).enumerate() {
+		*elt = (n as u16).wrapping_mul(0x9E37).rotate_left(n as u32);
+	}
+	//  The operands span several blocks, and some of them are misaligned
+	//  against the destination.
+	for &(from, len) in &[(0, 1271), (3, 1200), (7, 5), (11, 1269)] {
+		let ops = [
+			&src[0].bits::<Msb0>()[from .. from + len],
+			&src[1].bits::<Msb0>()[9 .. 9 + len],
+			&src[2].bits::<Msb0>()[from .. from + len],
+		];
+		let span = from .. from + len;
+		let mut fast = [0x5A5Au16; 80];
+		let mut slow = fast;
+		fast.bits_mut::<Msb0>()[span.clone()].and_many(ops.iter().copied());
+		for op in &ops {
+			slow.bits_mut::<Msb0>()[span.clone()] &= op.iter().copied();
+		}
+		assert_eq!(&fast[..], &slow[..]);
+
+		fast.bits_mut::<Msb0>()[span.clone()].or_many(ops.iter().copied());
+		for op in &ops {
+			slow.bits_mut::<Msb0>()[span.clone()] |= op.iter().copied();
+		}
+		assert_eq!(&fast[..], &slow[..]);
+	}
+}
+
+#[test]
+#[should_panic]
+fn combine_many_lengths() {
+	let (a, b) = ([0u8; 2], [0u8; 2]);
+	let mut dst = [0u8; 2];
+	let ops = [a.bits::<Msb0>(), &b.bits::<Msb0>()[1 ..]];
+	dst.bits_mut::<Msb0>().or_many(ops.iter().copied());
+}
+
+#[test]
+fn zip_words_mut() {
+	//  The majority of each triple of elements differs from all three.
+	let x = [0xFFFF_0000u32, 0x0F0F_0F0F, !0];
+	let y = [0xFF00_FF00u32, 0x3333_3333, 0];
+	let z = [0xF0F0_F0F0u32, 0x5555_5555, 0x8000_0001];
+	let mut out = x;
+	out.bits_mut::<Msb0>().zip_words_mut(
+		y.bits::<Msb0>(),
+		z.bits::<Msb0>(),
+		|x, y, z| x & y | x & z | y & z,
+	);
+	assert_eq!(out, [0xFFF0_F000, 0x1717_1717, 0x8000_0001]);
+	for &(from, len) in &[(0, 96), (0, 0), (5, 20), (5, 80), (32, 40)] {
+		let span = from .. from + len;
+		let mut out = x;
+		out.bits_mut::<Msb0>()[span.clone()].zip_words_mut(
+			&y.bits::<Msb0>()[span.clone()],
+			&z.bits::<Msb0>()[span.clone()],
+			|x, y, z| x & y | x & z | y & z,
+		);
+		let (xb, yb) = (x.bits::<Msb0>(), y.bits::<Msb0>());
+		let zb = z.bits::<Msb0>();
+		for (idx, bit) in out.bits::<Msb0>().iter().enumerate() {
+			let vote = (xb[idx] as u8 + yb[idx] as u8 + zb[idx] as u8) >= 2;
+			let want = if span.contains(&idx) { vote } else { xb[idx] };
+			assert_eq!(*bit, want, "{:?} at {}", span, idx);
+		}
+	}
+}
+
+#[test]
+#[should_panic]
+fn zip_words_mut_misaligned() {
+	let (a, b) = ([0u8; 2], [0u8; 2]);
+	let mut dst = [0u8; 2];
+	dst.bits_mut::<Msb0>()[.. 8].zip_words_mut(
+		&a.bits::<Msb0>()[.. 8],
+		&b.bits::<Msb0>()[1 .. 9],
+		|d, _, _| d,
+	);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn try_from_bits() {
+	use crate::vec::BitVec;
+	use core::convert::TryFrom;
+
+	let data = [0xA5u8, 0x0F, 0x3C];
+	let bits = &data.bits::<Msb0>()[3 .. 20];
+	let bytes = <[u8; 3]>::try_from(bits).unwrap();
+	assert_eq!(&bytes.bits::<Msb0>()[.. 17], bits);
+	assert!(bytes.bits::<Msb0>()[17 ..].not_any());
+	assert_eq!(<[u8; 2]>::try_from(bits).unwrap_err().source_len(), 17);
+
+	let bv = BitVec::<Msb0, u16>::from(bytes);
+	assert_eq!(&bv[.. 17], bits);
+	assert_eq!(<[u8; 3]>::try_from(bv.as_bitslice()), Ok(bytes));
+
+	let bv = BitVec::<Lsb0, u8>::from(0x1234u16);
+	assert_eq!(bv.len(), 16);
+	assert_eq!(u16::try_from(bv.as_bitslice()), Ok(0x1234));
+	assert_eq!(u32::try_from(&bv[.. 0]), Ok(0));
+	assert!(u8::try_from(bv.as_bitslice()).is_err());
+}
+
+#[test]
+fn shift_spanning() {
+	//  Slices that own whole elements take an element-wise fast path, which
+	//  must agree with shifting the same bits in a partial slice.
+	let src = [0x1234u16, 0x5678, 0x9ABC, 0xDEF0];
+	for &shamt in &[1, 4, 13, 16, 17, 35] {
+		let mut whole = src;
+		let mut part = [0u16; 5];
+		part.bits_mut::<Msb0>()[8 .. 72].copy_from_slice(src.bits::<Msb0>());
+		*whole.bits_mut::<Msb0>() <<= shamt;
+		part.bits_mut::<Msb0>()[8 .. 72] <<= shamt;
+		assert_eq!(whole.bits::<Msb0>(), &part.bits::<Msb0>()[8 .. 72]);
+
+		let mut whole = src;
+		part.bits_mut::<Msb0>()[8 .. 72].copy_from_slice(src.bits::<Msb0>());
+		*whole.bits_mut::<Msb0>() >>= shamt;
+		part.bits_mut::<Msb0>()[8 .. 72] >>= shamt;
+		assert_eq!(whole.bits::<Msb0>(), &part.bits::<Msb0>()[8 .. 72]);
+	}
+}
+
+#[test]
+fn shift_through_carry() {
+	fn check<O>(range: core::ops::Range<usize>)
+	where O: crate::order::BitOrder {
+		let src = [0x0123_4567u32, 0x89AB_CDEF, 0xF0E1_D2C3];
+		let mut data = src;
+		let bits = &mut data.bits_mut::<O>()[range.clone()];
+		let len = bits.len();
+		let first = bits[0];
+		assert_eq!(bits.shl_through_carry(true), first);
+		let mut expected = src;
+		let reference = &mut expected.bits_mut::<O>()[range.clone()];
+		*reference <<= 1;
+		reference.set(len - 1, true);
+		assert_eq!(data, expected);
+
+		let last = data.bits::<O>()[range.clone()][len - 1];
+		let bits = &mut data.bits_mut::<O>()[range.clone()];
+		assert_eq!(bits.shr_through_carry(first), last);
+		assert_eq!(data, src);
+	}
+	for range in &[0 .. 96, 3 .. 96, 0 .. 77, 5 .. 90, 9 .. 30, 33 .. 34] {
+		check::<Msb0>(range.clone());
+		check::<Lsb0>(range.clone());
+	}
+	//  An empty slice passes its carry straight through.
+	let mut data = 0u8;
+	assert!(data.bits_mut::<Msb0>()[.. 0].shl_through_carry(true));
+	assert!(!data.bits_mut::<Lsb0>()[.. 0].shr_through_carry(false));
+}
+
+#[test]
+fn smear_until() {
+	use crate::order::BitOrder;
+
+	fn check<O: BitOrder>(start: usize, mark: usize, len: usize) {
+		let src = [0x8000_0401u32, 0x0002_0000, 0x0100_0010, 0x0000_4000];
+		let markers = [0x0000_3000u32, 0x4000_0010, 0x0000_0400, 0x0800_0000];
+		let mut right = src;
+		let mut left = src;
+		let markers = &markers.bits::<O>()[mark ..][.. len];
+		right.bits_mut::<O>()[start ..][.. len].smear_right_until(markers);
+		left.bits_mut::<O>()[start ..][.. len].smear_left_until(markers);
+
+		let bits = &src.bits::<O>()[start ..][.. len];
+		let (mut r, mut l) = (bits.to_owned(), bits.to_owned());
+		for n in 1 .. len {
+			let bit = r[n] || r[n - 1] && !markers[n];
+			r.set(n, bit);
+			let m = len - 1 - n;
+			let bit = l[m] || l[m + 1] && !markers[m];
+			l.set(m, bit);
+		}
+		assert_eq!(right.bits::<O>()[start ..][.. len], r);
+		assert_eq!(left.bits::<O>()[start ..][.. len], l);
+		for edit in [right, left].iter() {
+			let (edit, src) = (edit.bits::<O>(), src.bits::<O>());
+			assert_eq!(edit[.. start], src[.. start]);
+			assert_eq!(edit[start + len ..], src[start + len ..]);
+		}
+	}
+
+	for &(start, len) in &[(0, 120), (3, 9), (5, 100), (32, 64), (7, 0)] {
+		//  Equally aligned markers take the element path, and the others
+		//  take the bit path.
+		for &mark in &[start, (start + 1) % 8] {
+			check::<Msb0>(start, mark, len);
+			check::<Lsb0>(start, mark, len);
+		}
+	}
+}
+
+#[test]
+fn chunks_by_weight() {
+	let data = [0x8000_0001u32, 0, 0xFFFF_0000, 0x0000_0003];
+	let bits = &data.bits::<Msb0>()[1 ..];
+	for weight in 1 .. 20 {
+		let count = bits.chunks_by_weight(weight).count();
+		let mut total = 0;
+		for (n, chunk) in bits.chunks_by_weight(weight).enumerate() {
+			total += chunk.len();
+			if n + 1 < count {
+				assert_eq!(chunk.count_ones(), weight);
+			}
+			assert!(chunk.count_ones() <= weight);
+			assert!(n == 0 || chunk[0]);
+		}
+		assert_eq!(total, bits.len());
+	}
+	//  The set bits are at 30, 63 through 78, 125, and 126.
+	let lens = bits.chunks_by_weight(3).map(|chunk| chunk.len());
+	assert!(lens.eq([65, 3, 3, 3, 3, 49, 1].iter().copied()));
+	assert_eq!(0u8.bits::<Lsb0>()[.. 0].chunks_by_weight(1).count(), 0);
+}
+
+#[test]
+fn prefix_scans() {
+	use crate::order::BitOrder;
+
+	fn check<O: BitOrder>(data: [u16; 3], range: core::ops::Range<usize>) {
+		let (mut xor, mut or, mut left) = (data, data, data);
+		let mut fill = data;
+		xor.bits_mut::<O>()[range.clone()].prefix_xor();
+		fill.bits_mut::<O>()[range.clone()].fill_between_markers();
+		or.bits_mut::<O>()[range.clone()].prefix_or();
+		left.bits_mut::<O>()[range.clone()].smear_left();
+		let last = range.clone().rev().find(|&n| data.bits::<O>()[n]);
+		let (mut px, mut po) = (false, false);
+		for n in 0 .. 48 {
+			let bit = data.bits::<O>()[n];
+			let (x, o, l) = if range.contains(&n) {
+				px ^= bit;
+				po |= bit;
+				(px, po, last >= Some(n))
+			}
+			else {
+				(bit, bit, bit)
+			};
+			assert_eq!(xor.bits::<O>()[n], x);
+			assert_eq!(fill.bits::<O>()[n], x || bit);
+			assert_eq!(or.bits::<O>()[n], o);
+			assert_eq!(left.bits::<O>()[n], l);
+		}
+	}
+
+	let data = [0x1020u16, 0x0C80, 0x4001];
+	for range in &[0 .. 48, 3 .. 13, 5 .. 40, 16 .. 32, 7 .. 7, 33 .. 45] {
+		check::<Lsb0>(data, range.clone());
+		check::<Msb0>(data, range.clone());
+		check::<Local>([0, 0, 0x8000], range.clone());
+	}
+}
+
+#[test]
+fn secure_clear() {
+	//  Without the `atomic` feature, the edge elements are cleared through
+	//  volatile accesses to their `Cell`s rather than atomic operations.
+	for &(start, len) in &[(0, 96), (3, 4), (5, 80), (32, 40), (13, 0)] {
+		let mut data = [0xA5C3_3C5Au32; 3];
+		data.bits_mut::<Msb0>()[start ..][.. len].secure_clear();
+		let bits = data.bits::<Msb0>();
+		assert!(bits[start ..][.. len].not_any());
+		let before = [0xA5C3_3C5Au32; 3];
+		let before = before.bits::<Msb0>();
+		assert_eq!(bits[.. start], before[.. start]);
+		assert_eq!(bits[start + len ..], before[start + len ..]);
+	}
+}
+
+#[test]
+fn map_elements() {
+	let mut data = [0x1234u16, 0x5678, 0x9ABC, 0xDEF0];
+	let (mut masks, mut calls) = ([0u16; 3], 0);
+	data.bits_mut::<Lsb0>()[5 .. 40].map_elements(|elt, mask| {
+		masks[calls] = mask;
+		calls += 1;
+		assert_eq!(elt & !mask, 0);
+		!elt
+	});
+	//  The head, body, and tail are visited in slice order.
+	assert_eq!(masks, [0xFFE0, 0xFFFF, 0x00FF]);
+	assert_eq!(data, [0x1234 ^ 0xFFE0, !0x5678, 0x9ABC ^ 0x00FF, 0xDEF0]);
+
+	let mut calls = 0;
+	0u8.bits_mut::<Msb0>()[3 .. 3].map_elements(|elt, _| {
+		calls += 1;
+		elt
+	});
+	assert_eq!(calls, 0);
+	let mut data = 0x0Fu8;
+	data.bits_mut::<Msb0>()[2 .. 6].map_elements(|elt, mask| {
+		assert_eq!(mask, 0x3C);
+		elt ^ mask
+	});
+	assert_eq!(data, 0x33);
+}
+
+#[test]
+fn assign_masked() {
+	//  Writing set bits into a cleared slice copies the mask.
+	let mut data = [0u32; 2];
+	let mask = [0xF0F0_F0F0u32, 0x0000_FFFF];
+	data.bits_mut::<Msb0>()
+		.assign_masked([!0u32; 2].bits::<Msb0>(), mask.bits::<Msb0>());
+	assert_eq!(data, mask);
+
+	//  The mask selects both set and cleared source bits over set and
+	//  cleared destination bits.
+	let src = [!0u32, 0, 0xAAAA_AAAA];
+	let mask = [0xFF00_FF00u32, 0x0FF0_0FF0, !0];
+	for &(at, from) in &[(3, 3), (3, 0), (0, 17)] {
+		let mut data = [0x0123_4567u32, 0x89AB_CDEF, 0xFEDC_BA98];
+		let before = data;
+		let len = 70;
+		let src = &src.bits::<Msb0>()[from ..][.. len];
+		let mask = &mask.bits::<Msb0>()[at ..][.. len];
+		data.bits_mut::<Msb0>()[at ..][.. len].assign_masked(src, mask);
+
+		let (data, before) = (data.bits::<Msb0>(), before.bits::<Msb0>());
+		assert_eq!(data[.. at], before[.. at]);
+		assert_eq!(data[at + len ..], before[at + len ..]);
+		for idx in 0 .. len {
+			let expected = if mask[idx] { src[idx] } else { before[at + idx] };
+			assert_eq!(data[at + idx], expected);
+		}
+	}
+}
+
+#[test]
+fn density() {
+	let data = [0x0F0F_00FFu32, 0x1357_9BDF];
+	let bits = &data.bits::<Lsb0>()[5 .. 61];
+	assert_eq!(bits.density(), bits.count_ones() as f64 / 56.0);
+	assert_eq!(bits[.. 0].density(), 0.0);
+	for &width in &[1, 7, 32, 56, 57] {
+		let mut iter = bits.density_windows(width);
+		let mut counts = bits.popcount_windows(width);
+		assert_eq!(iter.len(), bits.windows(width).len());
+		for window in bits.windows(width) {
+			let ones = window.count_ones();
+			assert_eq!(counts.next(), Some(ones));
+			assert_eq!(iter.next(), Some(ones as f64 / width as f64));
+		}
+		assert_eq!((iter.next(), counts.next()), (None, None));
+	}
+}
+
+#[test]
+fn run_statistics() {
+	fn check<O: crate::order::BitOrder>(bits: &BitSlice<O, u16>) {
+		let transitions = bits.windows(2).filter(|w| w[0] != w[1]).count();
+		assert_eq!(bits.count_transitions(), transitions);
+		for &value in &[false, true] {
+			let (mut best, mut start) = (0, 0);
+			for from in 0 .. bits.len() {
+				let rest = bits[from ..].iter();
+				let run = rest.take_while(|b| **b == value).count();
+				if run > best {
+					best = run;
+					start = from;
+				}
+			}
+			assert_eq!(bits.longest_run(value), (best, start));
+		}
+	}
+
+	let data = [0xFFF0u16, 0xFFFF, 0x0F00, 0x0000, 0x8001];
+	for &(from, to) in &[(0, 80), (3, 9), (4, 52), (17, 31), (30, 30), (2, 79)] {
+		check(&data.bits::<Lsb0>()[from .. to]);
+		check(&data.bits::<Msb0>()[from .. to]);
+	}
+}
+
+#[test]
+fn fold_elements() {
+	let data = [0x8421_F00Fu32, 0x0FF0_1248, 0xAAAA_5555];
+	let bits = &data.bits::<Msb0>()[7 .. 85];
+	let mut seen = [(0u32, 0u32); 4];
+	let count = bits.fold_elements(0, |n, elt, mask| {
+		seen[n] = (elt, mask);
+		n + 1
+	});
+	assert_eq!(count, 3);
+	assert_eq!(seen, [
+		(0x8421_F00F & 0x01FF_FFFF, 0x01FF_FFFF),
+		(0x0FF0_1248, !0),
+		(0xAAAA_5555 & 0xFFFF_F800, 0xFFFF_F800),
+		(0, 0),
+	]);
+
+	let bits = &data.bits::<Lsb0>()[33 .. 40];
+	let ones = bits.fold_elements(0, |n, elt, _| n + elt.count_ones());
+	assert_eq!(ones as usize, bits.count_ones());
+	assert_eq!(bits[.. 0].fold_elements(5, |_, _, _| 0), 5);
+}
+
+#[test]
+fn try_traversal() {
+	let mut data = [0x0F0F_0F0Fu32, 0xF0F0_F0F0];
+	let bits = &mut data.bits_mut::<Lsb0>()[4 ..];
+	let res: Result<(), usize> = bits.try_for_each(|_, bit| Ok(!bit));
+	assert!(res.is_ok());
+	assert_eq!(data, [0xF0F0_F0FF, 0x0F0F_0F0F]);
+
+	let bits = &mut data.bits_mut::<Lsb0>()[4 ..];
+	let res = bits.try_for_each(|idx, bit| {
+		if idx < 30 { Ok(true) } else { Err(bit) }
+	});
+	assert_eq!(res, Err(true));
+	assert_eq!(data, [!0, 0x0F0F_0F0F]);
+
+	let bits = data.bits::<Lsb0>();
+	let ones = bits.try_fold(0, |n, _, bit| Ok::<_, ()>(n + bit as usize));
+	assert_eq!(ones, Ok(bits.count_ones()));
+	let first = bits.try_fold((), |(), idx, bit| {
+		if bit { Ok(()) } else { Err(idx) }
+	});
+	assert_eq!(first, Err(36));
+	assert_eq!(bits[.. 0].try_fold(7, |_, _, _| Err(())), Ok(7));
+}
+
+#[test]
+fn iter_mut_ends() {
+	let mut data = [0u16; 3];
+	let bits = &mut data.bits_mut::<Msb0>()[5 .. 43];
+	let mut iter = bits.iter_mut();
+	let mut n = 0;
+	while let (Some(mut front), Some(mut back)) = (iter.next(), iter.next_back())
+	{
+		*front = n % 3 == 0;
+		*back = true;
+		n += 1;
+		assert_eq!(iter.len(), 38 - 2 * n);
+		if n == 10 {
+			break;
+		}
+	}
+	assert_eq!(iter.as_bitslice().len(), 18);
+	assert!(iter.as_bitslice().not_any());
+	assert!(iter.into_bitslice().not_any());
+	assert_eq!(bits.count_ones(), 14);
+	assert!(bits[28 ..].all());
+}
+
+#[test]
+fn select_mut() {
+	let old = [0x1234_5678u32, 0x9ABC_DEF0];
+	let mask = [0xF00Fu16, 0x0F0F, 0x8001, 0x00FF];
+	let mask = &mask.bits::<Msb0>()[3 .. 64];
+	let mut data = old;
+	let bits = &mut data.bits_mut::<Lsb0>()[3 ..];
+	let mut count = 0;
+	for mut bit in bits.select_mut(mask) {
+		*bit = !*bit;
+		count += 1;
+	}
+	assert_eq!(count, mask.count_ones());
+	let old = &old.bits::<Lsb0>()[3 ..];
+	for ((bit, old), sel) in bits.iter().zip(old).zip(mask) {
+		assert_eq!(*bit, old ^ sel);
+	}
+	assert_eq!(data[0] & 7, 0);
+	assert_eq!(0u8.bits_mut::<Msb0>().select_mut(0u8.bits::<Lsb0>()).count(), 0);
+
+	//  Gaps in the mask longer than one 64-bit load are skipped whole.
+	#[cfg(target_pointer_width = "64")]
+	{
+		let mut mask = [0u64; 4];
+		let mask = &mut mask.bits_mut::<Lsb0>()[5 .. 205];
+		for &idx in [0, 130, 199].iter() {
+			mask.set(idx, true);
+		}
+		let mut data = [0u8; 26];
+		let bits = &mut data.bits_mut::<Msb0>()[1 .. 201];
+		for mut bit in bits.select_mut(mask) {
+			*bit = true;
+		}
+		assert_eq!(bits.count_ones(), 3);
+		assert!(bits[0] && bits[130] && bits[199]);
+	}
+}
+
+#[test]
+#[cfg(any(
+	feature = "debug-unchecked",
+	all(feature = "unchecked-release", debug_assertions),
+))]
+#[should_panic(expected = "Unchecked access violation in `set_unchecked`")]
+fn debug_unchecked_audit() {
+	let mut data = 0u8;
+	let bits = &mut data.bits_mut::<Msb0>()[2 .. 4];
+	unsafe {
+		bits.set_unchecked(2, true);
+	}
+}
+
+#[test]
+#[cfg(any(
+	feature = "debug-unchecked",
+	all(feature = "unchecked-release", debug_assertions),
+))]
+#[should_panic(expected = "overflows")]
+fn debug_unchecked_copy_overflow() {
+	let mut data = 0u8;
+	let bits = data.bits_mut::<Msb0>();
+	unsafe {
+		bits.copy_within_unchecked(0 .. 2, !0);
+	}
+}
+
+#[test]
+#[cfg(any(
+	feature = "debug-unchecked",
+	all(feature = "unchecked-release", debug_assertions),
+))]
+#[should_panic(expected = "Unchecked access violation in `toggle_unchecked`")]
+fn debug_unchecked_toggle() {
+	let mut data = 0u8;
+	let bits = &mut data.bits_mut::<Lsb0>()[4 ..];
+	unsafe {
+		assert!(!bits.replace_unchecked(3, true));
+		assert!(bits.toggle_unchecked(3));
+		bits.toggle_unchecked(4);
+	}
+}
+
+#[test]
+fn recast_order() {
+	let mut data = [0x1234_5678u32, 0x9ABC_DEF0];
+	let bits = data.bits::<Msb0>();
+	let lsb0 = bits.recast_order::<Lsb0>().unwrap();
+	assert_eq!(lsb0.len(), 64);
+	for idx in 0 .. 64 {
+		assert_eq!(lsb0[idx], bits[idx / 32 * 32 + 31 - idx % 32]);
+	}
+	assert!(bits[32 ..].recast_order::<Lsb0>().is_some());
+	assert!(bits[1 ..].recast_order::<Lsb0>().is_none());
+	assert!(bits[.. 63].recast_order::<Lsb0>().is_none());
+	assert!(bits[3 .. 9].recast_order::<Lsb0>().is_none());
+	assert!(bits[5 .. 5].recast_order::<Lsb0>().unwrap().is_empty());
+
+	let bits = data.bits_mut::<Lsb0>();
+	bits.recast_order_mut::<Msb0>().unwrap().set(3, false);
+	assert!(bits[.. 40].recast_order_mut::<Msb0>().is_none());
+	assert_eq!(data, [0x0234_5678, 0x9ABC_DEF0]);
+}
+
+#[test]
+fn hash_words() {
+	use core::hash::{
+		Hash,
+		Hasher,
+	};
+
+	//  Records the words written to it, in order.
+	#[derive(Default)]
+	struct Words {
+		words: [usize; 8],
+		len: usize,
+	}
+
+	impl Hasher for Words {
+		fn finish(&self) -> u64 {
+			self.len as u64
+		}
+
+		fn write(&mut self, _: &[u8]) {
+			unreachable!("Bit slices only write words");
+		}
+
+		fn write_usize(&mut self, word: usize) {
+			self.words[self.len] = word;
+			self.len += 1;
+		}
+	}
+
+	fn words<O, T>(bits: &BitSlice<O, T>) -> Words
+	where
+		O: crate::order::BitOrder,
+		T: crate::store::BitStore,
+	{
+		let mut hasher = Words::default();
+		bits.hash(&mut hasher);
+		hasher
+	}
+
+	//  The first bit of the slice is the least significant bit of the word,
+	//  and the length follows the words.
+	let packed = words(&0xB0u8.bits::<Msb0>()[.. 4]);
+	assert_eq!(&packed.words[.. packed.len], &[0b1101, 4]);
+
+	let width = <usize as crate::store::BitStore>::BITS as usize;
+	//  Set bits at both edges of each element show where the words split.
+	let data = [0x8000_0001u32, 0xF0F0_0F0F, 0x0000_FFFF, 0xC000_0003];
+	for &(start, end) in &[(0, 0), (0, 128), (3, 11), (5, 96), (31, 65)] {
+		let src = &data.bits::<Msb0>()[start .. end];
+		let expected = words(src);
+		let full = src.len() / width + cmp::min(src.len() % width, 1);
+		assert_eq!(expected.len, full + 1);
+		assert_eq!(expected.words[expected.len - 1], src.len());
+		for (idx, bit) in src.iter().enumerate() {
+			let word = expected.words[idx / width];
+			assert_eq!(word >> (idx % width) & 1 == 1, *bit);
+		}
+
+		for head in 0 .. 9 {
+			let mut bytes = [0u8; 18];
+			let bits = &mut bytes.bits_mut::<Lsb0>()[head ..][.. src.len()];
+			bits.clone_from_slice(src);
+			let actual = words(bits);
+			assert_eq!(actual.words, expected.words);
+			assert_eq!(actual.len, expected.len);
+
+			let mut shorts = [0u16; 9];
+			let bits = &mut shorts.bits_mut::<Msb0>()[head ..][.. src.len()];
+			bits.clone_from_slice(src);
+			assert_eq!(words(bits).words, expected.words);
+		}
+	}
+	assert_ne!(
+		words(&0u8.bits::<Lsb0>()[.. 3]).words,
+		words(&0u8.bits::<Lsb0>()[.. 4]).words,
+	);
+}
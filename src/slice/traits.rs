@@ -5,13 +5,19 @@ The operator traits are defined in the `ops` module.
 
 use crate::{
 	access::BitAccess,
+	fields::BitField,
 	order::BitOrder,
-	slice::BitSlice,
+	slice::{
+		AsBits,
+		BitSlice,
+		TryFromBitSliceError,
+	},
 	store::BitStore,
 };
 
 use core::{
 	cmp::Ordering,
+	convert::TryFrom,
 	fmt::{
 		self,
 		Binary,
@@ -284,6 +290,74 @@ where
 	}
 }
 
+macro_rules! try_from_bits {
+	($( $t:ty ),* ) => { $(
+		/// Loads a slice of no more than the integer’s width into an integer.
+		///
+		/// This uses [`BitField::load`], so the `BitOrder` parameter determines
+		/// which bit of the slice is least significant. An empty slice loads
+		/// as zero.
+		///
+		/// [`BitField::load`]: ../fields/trait.BitField.html#method.load
+		impl<'a, O, T> TryFrom<&'a BitSlice<O, T>> for $t
+		where
+			O: BitOrder,
+			T: 'a + BitStore,
+			BitSlice<O, T>: BitField,
+		{
+			type Error = TryFromBitSliceError;
+
+			fn try_from(src: &'a BitSlice<O, T>) -> Result<Self, Self::Error> {
+				let max = <$t as BitStore>::BITS as usize;
+				if src.len() > max {
+					return Err(TryFromBitSliceError::new(src.len(), max));
+				}
+				if src.is_empty() {
+					return Ok(0);
+				}
+				Ok(src.load())
+			}
+		}
+	)* };
+}
+
+try_from_bits!(u8, u16, u32, usize);
+
+#[cfg(target_pointer_width = "64")]
+try_from_bits!(u64);
+
+macro_rules! try_from_bits_for {
+	($( $n:expr ),* ) => { $(
+		/// Copies a slice of no more than the array’s width into a byte array.
+		///
+		/// The bits are written in order into the array, viewed as a
+		/// `BitSlice` with the same `BitOrder` as the source. Bits of the array
+		/// past the end of the source are cleared.
+		impl<'a, O, T> TryFrom<&'a BitSlice<O, T>> for [u8; $n]
+		where
+			O: BitOrder,
+			T: 'a + BitStore,
+		{
+			type Error = TryFromBitSliceError;
+
+			fn try_from(src: &'a BitSlice<O, T>) -> Result<Self, Self::Error> {
+				let max = $n * 8;
+				if src.len() > max {
+					return Err(TryFromBitSliceError::new(src.len(), max));
+				}
+				let mut out = [0u8; $n];
+				out.bits_mut::<O>()[.. src.len()].clone_from_slice(src);
+				Ok(out)
+			}
+		}
+	)* };
+}
+
+try_from_bits_for![
+	0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+	21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+];
+
 macro_rules! fmt {
 	($trait:ident, $base:expr, $pfx:expr, $blksz:expr) => {
 		/// Write out the contents of a `BitSlice` as a numeric format.
@@ -460,7 +534,23 @@ impl Debug for RenderPart<'_> {
 	}
 }
 
-/// Writes the contents of the `BitSlice`, in semantic bit order, into a hasher.
+/** Writes the contents of the `BitSlice`, in semantic bit order, into a hasher.
+
+Slices compare equal when their bits are equal, whatever their `BitOrder` and
+`BitStore` parameters, and so they hash only their bits. The bits are packed
+into `usize` words, with bit `n` of the slice at bit `n % usize::BITS` of word
+`n / usize::BITS`, counting from the least significant bit, and with the unused
+bits of the last word cleared. Each word is written with `Hasher::write_usize`,
+and the length of the slice follows them. `BitBox` and `BitVec` hash as their
+slices do.
+
+This layout is part of the crate’s interface, and will not change without a
+major version. Note that it does depend on the width of `usize`, as the standard
+library’s own `Hash` implementations do.
+
+When `O` is `Lsb0` or `Msb0`, or behaves as one of them, the words are built a
+whole element at a time.
+**/
 impl<O, T> Hash for BitSlice<O, T>
 where
 	O: BitOrder,
@@ -468,12 +558,78 @@ where
 {
 	fn hash<H>(&self, hasher: &mut H)
 	where H: Hasher {
-		for bit in self {
-			hasher.write_u8(*bit as u8);
+		let mut words = WordHasher {
+			hasher,
+			word: 0,
+			fill: 0,
+		};
+		match Self::linear_order() {
+			Some(lsb0) => self.fold_elements((), |(), elt, mask| {
+				//  Put the first live bit of the element at the bottom.
+				let (elt, mask) = if lsb0 {
+					(elt, mask)
+				}
+				else {
+					(elt.reverse_bits(), mask.reverse_bits())
+				};
+				let (elt, mask) = (to_usize(elt), to_usize(mask));
+				words.push(elt >> mask.trailing_zeros(), mask.count_ones());
+			}),
+			None => {
+				for bit in self {
+					words.push(*bit as usize, 1);
+				}
+			},
+		}
+		if words.fill > 0 {
+			words.hasher.write_usize(words.word);
 		}
+		words.hasher.write_usize(self.len());
 	}
 }
 
+/** Packs runs of bits into `usize` words, and writes each full word into a
+hasher.
+**/
+struct WordHasher<'a, H>
+where H: Hasher
+{
+	hasher: &'a mut H,
+	/// The bits packed so far, in the low `fill` bits.
+	word: usize,
+	/// The number of bits in `word`.
+	fill: u32,
+}
+
+impl<H> WordHasher<'_, H>
+where H: Hasher
+{
+	/// Appends the low `count` bits of `bits`, which has no other bits set.
+	fn push(&mut self, bits: usize, count: u32) {
+		let width = <usize as BitStore>::BITS as u32;
+		self.word |= bits << self.fill;
+		self.fill += count;
+		if self.fill >= width {
+			self.hasher.write_usize(self.word);
+			self.fill -= width;
+			//  Keep the bits that did not fit in the full word.
+			self.word = if self.fill == 0 {
+				0
+			}
+			else {
+				bits >> (count - self.fill)
+			};
+		}
+	}
+}
+
+/// Widens an element to `usize`.
+fn to_usize<T>(elt: T) -> usize
+where T: BitStore {
+	elt.try_into()
+		.unwrap_or_else(|_| unreachable!("This conversion is infallible"))
+}
+
 /** `BitSlice` is safe to move across thread boundaries, when atomic operations
 are enabled.
 
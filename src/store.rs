@@ -248,6 +248,103 @@ pub trait BitStore:
 		//  invert (0 becomes 1, 1 becomes 0), zero-extend, count ones
 		<Self as BitStore>::count_ones(!self)
 	}
+
+	/// Counts the cleared bits below the least significant set bit of `self`.
+	///
+	/// This defers to the inherent `trailing_zeros` method of the fundamental.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	///
+	/// # Returns
+	///
+	/// The number of trailing `0` bits in `self`, which is `Self::BITS` when
+	/// `self` is zero. This is a `usize` for the same reason as in
+	/// [`count_ones`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::BitStore;
+	/// assert_eq!(BitStore::trailing_zeros(0x28u8), 3);
+	/// assert_eq!(BitStore::trailing_zeros(0u16), 16);
+	/// ```
+	///
+	/// [`count_ones`]: #method.count_ones
+	fn trailing_zeros(self) -> usize;
+
+	/// Counts the cleared bits above the most significant set bit of `self`.
+	///
+	/// This defers to the inherent `leading_zeros` method of the fundamental.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	///
+	/// # Returns
+	///
+	/// The number of leading `0` bits in `self`, which is `Self::BITS` when
+	/// `self` is zero. This is a `usize` for the same reason as in
+	/// [`count_ones`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::BitStore;
+	/// assert_eq!(BitStore::leading_zeros(0x28u8), 2);
+	/// assert_eq!(BitStore::leading_zeros(0u16), 16);
+	/// ```
+	///
+	/// [`count_ones`]: #method.count_ones
+	fn leading_zeros(self) -> usize;
+
+	/// Reverses the byte order of `self`.
+	///
+	/// This defers to the inherent `swap_bytes` method of the fundamental.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	///
+	/// # Returns
+	///
+	/// `self`, with its bytes in the reverse order. Single-byte types are
+	/// returned unchanged.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::BitStore;
+	/// assert_eq!(BitStore::swap_bytes(0x1234u16), 0x3412);
+	/// assert_eq!(BitStore::swap_bytes(0xA5u8), 0xA5);
+	/// ```
+	fn swap_bytes(self) -> Self;
+
+	/// Reverses the bit order of `self`.
+	///
+	/// The inherent `reverse_bits` method of the fundamentals requires Rust
+	/// 1.37, so this reverses the bytes of `self`, and then the bits within
+	/// each byte, with shifts and masks.
+	///
+	/// # Parameters
+	///
+	/// - `self`
+	///
+	/// # Returns
+	///
+	/// `self`, with its most significant bit moved to the least significant
+	/// position, and so on.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::BitStore;
+	/// assert_eq!(BitStore::reverse_bits(0x01u8), 0x80);
+	/// assert_eq!(BitStore::reverse_bits(0x00F0u16), 0x0F00);
+	/// assert_eq!(BitStore::reverse_bits(0x0123_4567u32), 0xE6A2_C480);
+	/// ```
+	fn reverse_bits(self) -> Self;
 }
 
 /** Compute the number of elements required to store a number of bits.
@@ -288,6 +385,34 @@ macro_rules! bitstore {
 			fn count_ones(self) -> usize {
 				Self::count_ones(self) as usize
 			}
+
+			#[inline(always)]
+			fn trailing_zeros(self) -> usize {
+				Self::trailing_zeros(self) as usize
+			}
+
+			#[inline(always)]
+			fn leading_zeros(self) -> usize {
+				Self::leading_zeros(self) as usize
+			}
+
+			#[inline(always)]
+			fn swap_bytes(self) -> Self {
+				Self::swap_bytes(self)
+			}
+
+			#[inline]
+			fn reverse_bits(self) -> Self {
+				//  The masks select the low nibble of each byte, the low pair of
+				//  each nibble, and the low bit of each pair.
+				let nibbles: Self = !0 / 0x11;
+				let pairs: Self = !0 / 0x05;
+				let bits: Self = !0 / 0x03;
+				let mut out = self.swap_bytes();
+				out = ((out >> 4) & nibbles) | ((out & nibbles) << 4);
+				out = ((out >> 2) & pairs) | ((out & pairs) << 2);
+				((out >> 1) & bits) | ((out & bits) << 1)
+			}
 		}
 	)* };
 }
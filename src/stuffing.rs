@@ -0,0 +1,227 @@
+/*! Bit stuffing
+
+Framed serial protocols reserve some bit pattern, such as the HDLC flag
+`01111110`, to mark the edges of a frame. So that the payload can never contain
+that pattern, the transmitter inserts an extra bit after every long enough run
+of identical bits, and the receiver removes it again. HDLC and USB insert a `0`
+after every five or six consecutive `1`s, respectively.
+
+This module adds both directions of the transform to `BitSlice`, as
+[`stuff_bits`] and [`unstuff_bits`]. The runs are counted across element
+boundaries, so the input may begin and end anywhere in memory.
+
+[`stuff_bits`]: ../slice/struct.BitSlice.html#method.stuff_bits
+[`unstuff_bits`]: ../slice/struct.BitSlice.html#method.unstuff_bits
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::fmt::{
+	self,
+	Display,
+	Formatter,
+};
+
+impl<O, T> BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Inserts a stuffing bit after every run of `max_run` bits that differ
+	/// from it.
+	///
+	/// The stuffing bit ends the run that caused it, so counting begins again
+	/// with the bit after it. A run that ends exactly at the end of the slice
+	/// is also followed by a stuffing bit.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `max_run`: The longest run of `!stuff_bit` that may appear in the
+	///   output.
+	/// - `stuff_bit`: The value of the inserted bits.
+	///
+	/// # Returns
+	///
+	/// A copy of `self` with stuffing bits inserted.
+	///
+	/// # Panics
+	///
+	/// This panics if `max_run` is zero.
+	///
+	/// # Examples
+	///
+	/// HDLC stuffing, which inserts a `0` after five `1`s:
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let data = bits![Msb0, u8; 0, 1, 1, 1, 1, 1, 1, 1, 0];
+	/// let sent = data.stuff_bits(5, false);
+	/// assert_eq!(sent, bitvec![Msb0, u8; 0, 1, 1, 1, 1, 1, 0, 1, 1, 0]);
+	/// assert_eq!(sent.unstuff_bits(5, false).unwrap(), data);
+	/// ```
+	pub fn stuff_bits(&self, max_run: usize, stuff_bit: bool) -> BitVec<O, T> {
+		assert!(max_run > 0, "Bit stuffing requires a run of at least one bit");
+		let mut out = BitVec::with_capacity(self.len() + self.len() / max_run);
+		//  Each run is copied into the output whole, along with everything
+		//  before it that has not been copied yet.
+		let mut start = 0;
+		let mut run = 0;
+		for (idx, bit) in self.iter().copied().enumerate() {
+			if bit == stuff_bit {
+				run = 0;
+				continue;
+			}
+			run += 1;
+			if run == max_run {
+				out.extend_from_slice(&self[start .. idx + 1]);
+				out.push(stuff_bit);
+				start = idx + 1;
+				run = 0;
+			}
+		}
+		out.extend_from_slice(&self[start ..]);
+		out
+	}
+
+	/// Removes the stuffing bits inserted by [`stuff_bits`].
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `max_run`: The `max_run` given to `stuff_bits`.
+	/// - `stuff_bit`: The `stuff_bit` given to `stuff_bits`.
+	///
+	/// # Returns
+	///
+	/// A copy of `self` with the stuffing bits removed, or an error if a run of
+	/// `max_run` bits is not followed by a stuffing bit. In HDLC, such a run is
+	/// a flag or an abort sequence, rather than data.
+	///
+	/// # Panics
+	///
+	/// This panics if `max_run` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let sent = bits![Msb0, u8; 1, 1, 1, 0, 1, 0, 1, 1, 1, 0, 1];
+	/// let data = sent.unstuff_bits(3, false).unwrap();
+	/// assert_eq!(data, bitvec![Msb0, u8; 1, 1, 1, 1, 0, 1, 1, 1, 1]);
+	///
+	/// //  The run at the end is missing its stuffing bit.
+	/// let cut = &sent[.. 9];
+	/// assert_eq!(cut.unstuff_bits(3, false).unwrap_err().index(), 9);
+	/// ```
+	///
+	/// [`stuff_bits`]: #method.stuff_bits
+	pub fn unstuff_bits(
+		&self,
+		max_run: usize,
+		stuff_bit: bool,
+	) -> Result<BitVec<O, T>, StuffError>
+	{
+		assert!(max_run > 0, "Bit stuffing requires a run of at least one bit");
+		let mut out = BitVec::with_capacity(self.len());
+		let mut start = 0;
+		let mut run = 0;
+		let mut iter = self.iter().copied().enumerate();
+		while let Some((idx, bit)) = iter.next() {
+			if bit == stuff_bit {
+				run = 0;
+				continue;
+			}
+			run += 1;
+			if run == max_run {
+				match iter.next() {
+					Some((next, bit)) if bit == stuff_bit => {
+						out.extend_from_slice(&self[start .. next]);
+						start = next + 1;
+						run = 0;
+					},
+					Some((next, _)) => return Err(StuffError { index: next }),
+					None => return Err(StuffError { index: idx + 1 }),
+				}
+			}
+		}
+		out.extend_from_slice(&self[start ..]);
+		Ok(out)
+	}
+}
+
+/** The error produced when a bit sequence is not validly stuffed.
+
+This is returned by [`BitSlice::unstuff_bits`] when a run of the maximum length
+is followed by a bit other than the stuffing bit, or by the end of the input.
+
+[`BitSlice::unstuff_bits`]: ../slice/struct.BitSlice.html#method.unstuff_bits
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct StuffError {
+	/// The index at which a stuffing bit was expected.
+	index: usize,
+}
+
+impl StuffError {
+	/// Gets the index in the input at which a stuffing bit was expected. This
+	/// is the length of the input when the input ended too early.
+	pub fn index(&self) -> usize {
+		self.index
+	}
+}
+
+impl Display for StuffError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(fmt, "Missing stuffing bit at index {}", self.index)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for StuffError {
+}
+
+#[cfg(test)]
+mod tests {
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+
+	#[test]
+	fn round_trip() {
+		let data = [0xFFu16, 0xFFFF, 0x7FFF, 0xF0F0, 0xFFFF];
+		//  Runs cross element boundaries, and the slices start part of the
+		//  way through an element.
+		for &from in &[0, 3, 9, 31] {
+			let bits = &data.bits::<Msb0>()[from ..];
+			for &max in &[1, 5, 6, 17] {
+				for &stuff in &[false, true] {
+					let sent = bits.stuff_bits(max, stuff);
+					let mut run = 0;
+					for bit in sent.iter().copied() {
+						run = if bit == stuff { 0 } else { run + 1 };
+						assert!(run <= max);
+					}
+					assert_eq!(sent.unstuff_bits(max, stuff).unwrap(), bits);
+				}
+			}
+		}
+	}
+
+	#[test]
+	fn invalid_run() {
+		let flag = [0b0111_1110u8];
+		let err = flag.bits::<Msb0>().unstuff_bits(5, false).unwrap_err();
+		assert_eq!(err.index(), 6);
+	}
+}
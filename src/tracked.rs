@@ -0,0 +1,388 @@
+/*! Bit vectors that track their own population
+
+Allocators that keep a bitmap of used slots ask two questions of it on every
+allocation: how many slots are in use, and where the first free one is. Asking
+a `BitVec` either question scans the whole map.
+
+[`TrackedBitVec`] wraps a `BitVec` and keeps both answers current as it is
+modified, so that [`len_set`] takes constant time, and [`first_zero`] reads a
+cached index. It dereferences to `BitSlice` only immutably, and all writes go
+through its own methods, so that the cached values cannot fall out of date.
+
+[`TrackedBitVec`]: struct.TrackedBitVec.html
+[`first_zero`]: struct.TrackedBitVec.html#method.first_zero
+[`len_set`]: struct.TrackedBitVec.html#method.len_set
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
+	hash::{
+		Hash,
+		Hasher,
+	},
+	ops::{
+		Deref,
+		Range,
+	},
+};
+
+/** A bit vector which tracks its count of set bits, and its first unset bit.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering of bits within each storage element.
+- `T: BitStore`: The storage element type.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, tracked::TrackedBitVec};
+
+let mut map = TrackedBitVec::<Msb0, u8>::repeat(false, 20);
+map.set_range(0 .. 6, true);
+assert_eq!(map.len_set(), 6);
+assert_eq!(map.first_zero(), Some(6));
+
+map.set(2, false);
+assert_eq!(map.first_zero(), Some(2));
+map.set(2, true);
+assert_eq!(map.first_zero(), Some(6));
+assert_eq!(map.len_clear(), 14);
+```
+**/
+#[derive(Clone, Eq, PartialEq)]
+pub struct TrackedBitVec<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The bits.
+	bits: BitVec<O, T>,
+	/// The number of set bits in `bits`.
+	ones: usize,
+	/// The index of the first unset bit in `bits`, or its length if it has
+	/// none.
+	zero: usize,
+}
+
+impl<O, T> TrackedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Constructs an empty vector.
+	pub fn new() -> Self {
+		Self::from_bitvec(BitVec::new())
+	}
+
+	/// Constructs a vector of `len` copies of `bit`.
+	pub fn repeat(bit: bool, len: usize) -> Self {
+		Self {
+			bits: BitVec::repeat(bit, len),
+			ones: if bit { len } else { 0 },
+			zero: if bit { len } else { 0 },
+		}
+	}
+
+	/// Wraps a bit vector, counting its set bits once.
+	pub fn from_bitvec(bits: BitVec<O, T>) -> Self {
+		let mut out = Self {
+			ones: bits.count_ones(),
+			bits,
+			zero: 0,
+		};
+		out.zero = out.next_zero(0);
+		out
+	}
+
+	/// Unwraps the bit vector.
+	pub fn into_bitvec(self) -> BitVec<O, T> {
+		self.bits
+	}
+
+	/// Views the bits.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		self.bits.as_bitslice()
+	}
+
+	/// Counts the set bits, in constant time.
+	pub fn len_set(&self) -> usize {
+		self.ones
+	}
+
+	/// Counts the unset bits, in constant time.
+	pub fn len_clear(&self) -> usize {
+		self.bits.len() - self.ones
+	}
+
+	/// Finds the first unset bit, in constant time.
+	///
+	/// The index is kept current by the methods that modify the vector. Those
+	/// that set the bit at this index search forward for the next unset bit,
+	/// so the search cost is spread across the writes that make it necessary.
+	///
+	/// # Returns
+	///
+	/// The index of the first unset bit, or `None` if every bit is set.
+	pub fn first_zero(&self) -> Option<usize> {
+		if self.zero < self.bits.len() {
+			Some(self.zero)
+		}
+		else {
+			None
+		}
+	}
+
+	/// Writes a bit.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The bit to write.
+	/// - `value`: The value to write into it.
+	///
+	/// # Returns
+	///
+	/// The value that the bit held before the write.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is out of bounds.
+	pub fn set(&mut self, index: usize, value: bool) -> bool {
+		let old = self.bits[index];
+		if old != value {
+			self.bits.set(index, value);
+			if value {
+				self.ones += 1;
+				if index == self.zero {
+					self.zero = self.next_zero(index + 1);
+				}
+			}
+			else {
+				self.ones -= 1;
+				self.zero = cmp::min(self.zero, index);
+			}
+		}
+		old
+	}
+
+	/// Writes a bit into each index in a range.
+	///
+	/// # Panics
+	///
+	/// This panics if `range` is out of bounds.
+	pub fn set_range(&mut self, range: Range<usize>, value: bool) {
+		let Range { start, end } = range;
+		let span = &mut self.bits[start .. end];
+		let ones = span.count_ones();
+		span.set_all(value);
+		if start == end {
+			return;
+		}
+		if value {
+			self.ones += (end - start) - ones;
+			if (start .. end).contains(&self.zero) {
+				self.zero = self.next_zero(end);
+			}
+		}
+		else {
+			self.ones -= ones;
+			self.zero = cmp::min(self.zero, start);
+		}
+	}
+
+	/// Writes a value into every bit.
+	pub fn set_all(&mut self, value: bool) {
+		let len = self.bits.len();
+		self.set_range(0 .. len, value);
+	}
+
+	/// Appends a bit to the back of the vector.
+	pub fn push(&mut self, value: bool) {
+		let len = self.bits.len();
+		self.bits.push(value);
+		if value {
+			self.ones += 1;
+			if self.zero == len {
+				self.zero += 1;
+			}
+		}
+	}
+
+	/// Removes the last bit of the vector, and returns it.
+	pub fn pop(&mut self) -> Option<bool> {
+		let out = self.bits.pop()?;
+		if out {
+			self.ones -= 1;
+		}
+		self.zero = cmp::min(self.zero, self.bits.len());
+		Some(out)
+	}
+
+	/// Shortens the vector to `len` bits, if it is longer.
+	pub fn truncate(&mut self, len: usize) {
+		if len < self.bits.len() {
+			self.ones -= self.bits[len ..].count_ones();
+			self.bits.truncate(len);
+			self.zero = cmp::min(self.zero, len);
+		}
+	}
+
+	/// Resizes the vector to `new_len` bits, filling any new bits with
+	/// `value`.
+	pub fn resize(&mut self, new_len: usize, value: bool) {
+		let len = self.bits.len();
+		if new_len <= len {
+			return self.truncate(new_len);
+		}
+		self.bits.resize(new_len, value);
+		if value {
+			self.ones += new_len - len;
+			if self.zero == len {
+				self.zero = new_len;
+			}
+		}
+	}
+
+	/// Finds the first unset bit at or after `from`, or the length of the
+	/// vector if there is none.
+	///
+	/// The bits are tested a storage element’s width at a time, and only the
+	/// first run that is not entirely set is searched bit by bit.
+	fn next_zero(&self, from: usize) -> usize {
+		let width = T::BITS as usize;
+		for (n, chunk) in self.bits[from ..].chunks(width).enumerate() {
+			if !chunk.all() {
+				let idx = chunk.iter().take_while(|bit| **bit).count();
+				return from + n * width + idx;
+			}
+		}
+		self.bits.len()
+	}
+}
+
+impl<O, T> Debug for TrackedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("TrackedBitVec<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str(", ")?;
+		fmt.write_str(T::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+impl<O, T> Default for TrackedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<O, T> Deref for TrackedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Target = BitSlice<O, T>;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<O, T> From<BitVec<O, T>> for TrackedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from(bits: BitVec<O, T>) -> Self {
+		Self::from_bitvec(bits)
+	}
+}
+
+impl<O, T> Hash for TrackedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Writes only the bits, as the cached counts follow from them.
+	fn hash<H: Hasher>(&self, hasher: &mut H) {
+		self.bits.hash(hasher)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::{
+		Lsb0,
+		Msb0,
+	};
+
+	#[test]
+	fn tracking() {
+		let mut map = TrackedBitVec::<Lsb0, u8>::new();
+		let mut state = 0x2545_F491u32;
+		for _ in 0 .. 2000 {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			let len = map.len();
+			let at = if len == 0 { 0 } else { state as usize % len };
+			let value = state & 0x100 != 0;
+			match (state >> 9) % 8 {
+				0 | 1 => map.push(value),
+				2 => {
+					map.pop();
+				},
+				3 => map.set_range(at .. cmp::min(len, at + 13), value),
+				4 => map.resize(len + (state as usize >> 20) % 20, value),
+				5 => map.truncate(len.saturating_sub(3)),
+				_ if len > 0 => {
+					let old = map[at];
+					assert_eq!(map.set(at, value), old);
+				},
+				_ => {},
+			}
+			assert_eq!(map.len_set(), map.count_ones());
+			assert_eq!(map.first_zero(), map.iter().position(|bit| !*bit));
+		}
+		map.set_all(true);
+		assert_eq!((map.len_clear(), map.first_zero()), (0, None));
+		let map = TrackedBitVec::from(map.into_bitvec());
+		assert_eq!(map.len_set(), map.len());
+	}
+
+	#[test]
+	fn default() {
+		let map = TrackedBitVec::<Msb0, u8>::default();
+		assert!(map.is_empty());
+		assert_eq!((map.len_set(), map.first_zero()), (0, None));
+	}
+}
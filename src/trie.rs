@@ -0,0 +1,390 @@
+/*! Prefix trees keyed by bit sequences
+
+Routing tables, address allocation maps, and prefix-code dictionaries all map
+variable-length bit strings to values, and answer queries for the longest stored
+key that is a prefix of some probe. [`BitTrie`] is a binary radix tree that
+stores its keys as `BitSlice` edge labels, so that lookups compare whole runs of
+bits instead of descending once per bit.
+
+[`BitTrie`]: struct.BitTrie.html
+!*/
+
+#![cfg(feature = "alloc")]
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::{
+	borrow::ToOwned,
+	boxed::Box,
+};
+
+use core::mem;
+
+/** A map from bit sequences to values, supporting longest-prefix lookup.
+
+Keys of any length, including zero, may be stored. Each key is one value; keys
+that are prefixes of other keys are independent entries.
+
+# Type Parameters
+
+- `V`: The type of the stored values.
+- `O: BitOrder`: The ordering of bits within each storage element of the keys.
+- `T: BitStore`: The storage element type of the keys.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, trie::BitTrie};
+
+let mut routes = BitTrie::<&str, Msb0, u8>::new();
+//  10.0.0.0/8 and 10.1.0.0/16
+routes.insert(&[10u8].bits::<Msb0>()[..], "wan");
+routes.insert(&[10u8, 1].bits::<Msb0>()[..], "lan");
+
+let addr = [10u8, 1, 2, 3];
+let (prefix, via) = routes.longest_prefix(addr.bits::<Msb0>()).unwrap();
+assert_eq!(prefix.len(), 16);
+assert_eq!(*via, "lan");
+
+let addr = [10u8, 2, 2, 3];
+assert_eq!(routes.longest_prefix(addr.bits::<Msb0>()).unwrap().0.len(), 8);
+```
+**/
+#[derive(Clone, Debug)]
+pub struct BitTrie<V, O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The node for the empty key. Its label is always empty.
+	root: Node<V, O, T>,
+	/// The number of stored keys.
+	len: usize,
+}
+
+/// A node in the tree, reached by following its parent’s child for the first
+/// bit of `label`, and then matching the rest of `label`.
+#[derive(Clone, Debug)]
+struct Node<V, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The bits that lead from the parent node to this one. These are never
+	/// empty, except at the root.
+	label: BitVec<O, T>,
+	/// The value stored for the key that ends at this node.
+	value: Option<V>,
+	/// The subtrees for a next bit of `0` and `1`.
+	children: [Option<Box<Node<V, O, T>>>; 2],
+}
+
+impl<V, O, T> BitTrie<V, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Constructs an empty map.
+	pub fn new() -> Self {
+		Self {
+			root: Node::new(BitVec::new(), None),
+			len: 0,
+		}
+	}
+
+	/// Counts the stored keys.
+	pub fn len(&self) -> usize {
+		self.len
+	}
+
+	/// Tests if the map has no keys.
+	pub fn is_empty(&self) -> bool {
+		self.len == 0
+	}
+
+	/// Removes every key from the map.
+	pub fn clear(&mut self) {
+		*self = Self::new();
+	}
+
+	/// Stores a value under a key.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `key`: The key. It is copied into the map.
+	/// - `value`: The value to store.
+	///
+	/// # Returns
+	///
+	/// The value previously stored under `key`, if any.
+	pub fn insert(&mut self, key: &BitSlice<O, T>, value: V) -> Option<V> {
+		let out = self.root.insert(key, value);
+		if out.is_none() {
+			self.len += 1;
+		}
+		out
+	}
+
+	/// Looks up the value stored under exactly `key`.
+	pub fn get(&self, key: &BitSlice<O, T>) -> Option<&V> {
+		self.find(key).and_then(|node| node.value.as_ref())
+	}
+
+	/// Looks up the value stored under exactly `key`, for modification.
+	pub fn get_mut(&mut self, key: &BitSlice<O, T>) -> Option<&mut V> {
+		let mut node = &mut self.root;
+		let mut key = key;
+		while !key.is_empty() {
+			let child = node.children[key[0] as usize].as_mut()?;
+			if !key.starts_with(&child.label) {
+				return None;
+			}
+			key = &key[child.label.len() ..];
+			node = child;
+		}
+		node.value.as_mut()
+	}
+
+	/// Tests if a value is stored under exactly `key`.
+	pub fn contains_key(&self, key: &BitSlice<O, T>) -> bool {
+		self.get(key).is_some()
+	}
+
+	/// Finds the longest stored key that is a prefix of a probe.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	/// - `key`: The probe sequence.
+	///
+	/// # Returns
+	///
+	/// The front of `key` that matched a stored key, and the value stored
+	/// under it, or `None` if no stored key is a prefix of `key`. The empty
+	/// key, if stored, is a prefix of every probe.
+	pub fn longest_prefix<'a>(
+		&self,
+		key: &'a BitSlice<O, T>,
+	) -> Option<(&'a BitSlice<O, T>, &V)>
+	{
+		let mut node = &self.root;
+		let mut depth = 0;
+		let mut best = node.value.as_ref().map(|v| (0, v));
+		while depth < key.len() {
+			let child = match &node.children[key[depth] as usize] {
+				Some(child) => child,
+				None => break,
+			};
+			if !key[depth ..].starts_with(&child.label) {
+				break;
+			}
+			depth += child.label.len();
+			node = child;
+			if let Some(value) = node.value.as_ref() {
+				best = Some((depth, value));
+			}
+		}
+		best.map(|(len, value)| (&key[.. len], value))
+	}
+
+	/// Removes the value stored under a key.
+	///
+	/// # Returns
+	///
+	/// The value that was stored under `key`, if any.
+	pub fn remove(&mut self, key: &BitSlice<O, T>) -> Option<V> {
+		let out = self.root.remove(key);
+		if out.is_some() {
+			self.len -= 1;
+		}
+		out
+	}
+
+	/// Finds the node at which `key` ends, if there is one.
+	fn find(&self, key: &BitSlice<O, T>) -> Option<&Node<V, O, T>> {
+		let mut node = &self.root;
+		let mut key = key;
+		while !key.is_empty() {
+			let child = node.children[key[0] as usize].as_ref()?;
+			if !key.starts_with(&child.label) {
+				return None;
+			}
+			key = &key[child.label.len() ..];
+			node = child;
+		}
+		Some(node)
+	}
+}
+
+impl<V, O, T> Default for BitTrie<V, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<V, O, T> Node<V, O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn new(label: BitVec<O, T>, value: Option<V>) -> Self {
+		Self {
+			label,
+			value,
+			children: [None, None],
+		}
+	}
+
+	/// Stores a value under `key`, which is relative to the end of this
+	/// node’s label.
+	fn insert(&mut self, key: &BitSlice<O, T>, value: V) -> Option<V> {
+		if key.is_empty() {
+			return self.value.replace(value);
+		}
+		let slot = &mut self.children[key[0] as usize];
+		let child = match slot {
+			Some(child) => child,
+			None => {
+				*slot = Some(Box::new(Self::new(key.to_owned(), Some(value))));
+				return None;
+			},
+		};
+		let common = child.label.common_prefix_len(key);
+		//  The key leaves the child’s label part of the way through, so the
+		//  label is split with a new node at the fork.
+		if common < child.label.len() {
+			let tail = child.label.split_off(common);
+			let head = mem::replace(&mut child.label, tail);
+			let old = mem::replace(&mut **child, Self::new(head, None));
+			let bit = old.label[0] as usize;
+			child.children[bit] = Some(Box::new(old));
+		}
+		child.insert(&key[common ..], value)
+	}
+
+	/// Removes the value stored under `key`, which is relative to the end of
+	/// this node’s label, and merges any node left with no value and only one
+	/// child into that child.
+	fn remove(&mut self, key: &BitSlice<O, T>) -> Option<V> {
+		if key.is_empty() {
+			return self.value.take();
+		}
+		let bit = key[0] as usize;
+		let child = self.children[bit].as_mut()?;
+		if !key.starts_with(&child.label) {
+			return None;
+		}
+		let out = child.remove(&key[child.label.len() ..]);
+		if out.is_some() && child.value.is_none() {
+			match child.children.iter().filter(|c| c.is_some()).count() {
+				0 => self.children[bit] = None,
+				1 => {
+					let next = child
+						.children
+						.iter_mut()
+						.find_map(Option::take)
+						.expect("Counted one child");
+					//  `mem::take` is not available in the minimum Rust version.
+					#[allow(clippy::mem_replace_with_default)]
+					let mut label =
+						mem::replace(&mut child.label, BitVec::new());
+					label.extend_from_slice(&next.label);
+					**child = *next;
+					child.label = label;
+				},
+				_ => {},
+			}
+		}
+		out
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+
+	type Trie = BitTrie<u32, Msb0, u8>;
+
+	#[test]
+	fn insert_get_remove() {
+		let data = [0b1011_0110u8, 0b0100_0000];
+		let bits = data.bits::<Msb0>();
+		let mut trie = Trie::new();
+		for &len in &[0, 3, 4, 7, 12, 16] {
+			assert!(trie.insert(&bits[.. len], len as u32).is_none());
+		}
+		//  A key that forks from a stored key, in the middle of an edge.
+		assert!(trie.insert(bits![Msb0, u8; 1, 0, 1, 1, 1], 99).is_none());
+		assert_eq!(trie.len(), 7);
+		assert_eq!(trie.insert(&bits[.. 4], 40), Some(4));
+
+		for &len in &[0, 3, 7, 12, 16] {
+			assert_eq!(trie.get(&bits[.. len]), Some(&(len as u32)));
+		}
+		assert_eq!(trie.get(&bits[.. 4]), Some(&40));
+		assert!(trie.get(&bits[.. 5]).is_none());
+		assert_eq!(trie.get(bits![Msb0, u8; 1, 0, 1, 1, 1]), Some(&99));
+
+		*trie.get_mut(&bits[.. 7]).unwrap() += 1;
+		assert_eq!(trie.remove(&bits[.. 7]), Some(8));
+		assert!(trie.remove(&bits[.. 7]).is_none());
+		assert_eq!(trie.remove(&bits[.. 4]), Some(40));
+		assert_eq!(trie.get(&bits[.. 12]), Some(&12));
+		assert_eq!(trie.get(bits![Msb0, u8; 1, 0, 1, 1, 1]), Some(&99));
+		assert_eq!(trie.len(), 5);
+
+		trie.clear();
+		assert!(trie.is_empty());
+		assert!(trie.get(&bits[.. 0]).is_none());
+	}
+
+	#[test]
+	fn longest_prefix() {
+		let data = [0xC0u8, 0xA8, 0x01, 0x07];
+		let addr = data.bits::<Msb0>();
+		let mut trie = Trie::new();
+		assert!(trie.longest_prefix(addr).is_none());
+
+		trie.insert(&addr[.. 16], 16);
+		trie.insert(&addr[.. 24], 24);
+		trie.insert(&addr[.. 30], 30);
+		assert_eq!(
+			trie.longest_prefix(addr).map(|(k, v)| (k.len(), *v)),
+			Some((30, 30)),
+		);
+		assert_eq!(
+			trie.longest_prefix(&addr[.. 28]).map(|(k, v)| (k.len(), *v)),
+			Some((24, 24)),
+		);
+
+		trie.insert(&addr[.. 0], 0);
+		let other = [0x0Au8];
+		assert_eq!(
+			trie.longest_prefix(other.bits::<Msb0>()).map(|(k, _)| k.len()),
+			Some(0),
+		);
+
+		trie.remove(&addr[.. 24]);
+		assert_eq!(
+			trie.longest_prefix(&addr[.. 28]).map(|(k, v)| (k.len(), *v)),
+			Some((16, 16)),
+		);
+	}
+}
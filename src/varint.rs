@@ -0,0 +1,483 @@
+/*! LEB128 variable-length integers inside bit streams
+
+Protocol Buffers, DWARF, WebAssembly, and many index formats store integers as
+LEB128 varints: seven bits of the value in each byte, least significant group
+first, with the high bit of each byte set when another byte follows. Formats
+that mix bit-packed fields with byte-oriented headers place these varints at
+bit offsets that need not fall on byte boundaries.
+
+The functions in this module read and write varints at the front of any
+`BitSlice<O, u8>`, and report the number of bits they consumed, so that a
+parser can step past a varint and continue with the bits after it. Each byte of
+a varint occupies eight consecutive bits of the slice, which hold its bits in
+the same places that `O` gives them within a memory byte. A varint at a byte
+boundary therefore reads exactly as it does in byte-oriented code.
+
+Unsigned values are written as ULEB128, which is also the Protocol Buffers
+`varint` wire type. Signed values are written as SLEB128. Protocol Buffers
+`sint` fields instead zigzag-encode their values into unsigned varints, which
+[`zigzag`] and [`unzigzag`] provide.
+
+[`unzigzag`]: fn.unzigzag.html
+[`zigzag`]: fn.zigzag.html
+!*/
+
+use crate::{
+	indices::Indexable,
+	order::BitOrder,
+	slice::BitSlice,
+};
+
+use core::{
+	cmp,
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
+	iter::FusedIterator,
+};
+
+/// The largest number of bytes that a varint of a 64-bit value occupies.
+pub const MAX_BYTES: usize = 10;
+
+/** The reasons that a varint can fail to be read or written.
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum VarintError {
+	/// The slice ended before the last byte of the varint.
+	Truncated {
+		/// The number of bits that the slice held.
+		available: usize,
+	},
+	/// The varint encodes a value too large for 64 bits.
+	Overflow,
+	/// The slice is too short to hold the encoded value.
+	TooShort {
+		/// The number of bits that the encoding requires.
+		needed: usize,
+		/// The number of bits that the slice held.
+		available: usize,
+	},
+}
+
+impl Display for VarintError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		match self {
+			VarintError::Truncated { available } => write!(
+				fmt,
+				"Varint is truncated after {} bits",
+				available,
+			),
+			VarintError::Overflow => {
+				fmt.write_str("Varint does not fit in 64 bits")
+			},
+			VarintError::TooShort { needed, available } => write!(
+				fmt,
+				"Varint needs {} bits, but only {} are available",
+				needed, available,
+			),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for VarintError {
+}
+
+/// Reads an unsigned varint from the front of a bit slice.
+///
+/// # Returns
+///
+/// The decoded value, and the number of bits that its encoding occupied.
+///
+/// # Errors
+///
+/// This fails if the slice ends inside the varint, or if the varint encodes
+/// more than 64 bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{prelude::*, varint};
+///
+/// //  300 is encoded as the bytes 0xAC, 0x02, here after three other bits.
+/// let data = [0b101_10101u8, 0b100_00000, 0b010_00000];
+/// let bits = data.bits::<Msb0>();
+/// assert_eq!(varint::read_uleb128(&bits[3 ..]), Ok((300, 16)));
+/// ```
+pub fn read_uleb128<O>(
+	bits: &BitSlice<O, u8>,
+) -> Result<(u64, usize), VarintError>
+where
+	O: BitOrder,
+{
+	let mut value = 0u64;
+	for n in 0 .. MAX_BYTES {
+		let byte = load_byte(bits, n)?;
+		//  The last byte holds only the top bit of a 64-bit value.
+		if n == MAX_BYTES - 1 && byte > 1 {
+			return Err(VarintError::Overflow);
+		}
+		value |= ((byte & 0x7F) as u64) << (7 * n);
+		if byte & 0x80 == 0 {
+			return Ok((value, 8 * (n + 1)));
+		}
+	}
+	Err(VarintError::Overflow)
+}
+
+/// Reads a signed varint from the front of a bit slice.
+///
+/// # Returns
+///
+/// The decoded value, and the number of bits that its encoding occupied.
+///
+/// # Errors
+///
+/// This fails if the slice ends inside the varint, or if the varint encodes
+/// more than 64 bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{prelude::*, varint};
+///
+/// let data = [0x7Fu8, 0x80, 0x7F];
+/// let bits = data.bits::<Lsb0>();
+/// assert_eq!(varint::read_sleb128(bits), Ok((-1, 8)));
+/// assert_eq!(varint::read_sleb128(&bits[8 ..]), Ok((-128, 16)));
+/// ```
+pub fn read_sleb128<O>(
+	bits: &BitSlice<O, u8>,
+) -> Result<(i64, usize), VarintError>
+where
+	O: BitOrder,
+{
+	let mut value = 0i64;
+	for n in 0 .. MAX_BYTES {
+		let byte = load_byte(bits, n)?;
+		let shift = 7 * n;
+		//  The last byte holds only the sign bit of a 64-bit value, which
+		//  must be copied through its payload.
+		if n == MAX_BYTES - 1 && byte != 0x00 && byte != 0x7F {
+			return Err(VarintError::Overflow);
+		}
+		value |= ((byte & 0x7F) as i64) << shift;
+		if byte & 0x80 == 0 {
+			if shift + 7 < 64 && byte & 0x40 != 0 {
+				value |= !0 << (shift + 7);
+			}
+			return Ok((value, 8 * (n + 1)));
+		}
+	}
+	Err(VarintError::Overflow)
+}
+
+/// Writes an unsigned varint into the front of a bit slice.
+///
+/// # Returns
+///
+/// The number of bits written. Any bits after them are untouched.
+///
+/// # Errors
+///
+/// This fails, without writing any bits, if the slice is shorter than the
+/// encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::{prelude::*, varint};
+///
+/// let mut data = [0u8; 3];
+/// let bits = &mut data.bits_mut::<Msb0>()[4 ..];
+/// assert_eq!(varint::write_uleb128(bits, 300), Ok(16));
+/// assert_eq!(data, [0x0A, 0xC0, 0x20]);
+/// ```
+pub fn write_uleb128<O>(
+	bits: &mut BitSlice<O, u8>,
+	value: u64,
+) -> Result<usize, VarintError>
+where
+	O: BitOrder,
+{
+	let needed = uleb128_len(value);
+	check_len(bits, needed)?;
+	let mut value = value;
+	for n in 0 .. needed / 8 {
+		let more = if n + 1 < needed / 8 { 0x80 } else { 0 };
+		store_byte(bits, n, (value & 0x7F) as u8 | more);
+		value >>= 7;
+	}
+	Ok(needed)
+}
+
+/// Writes a signed varint into the front of a bit slice.
+///
+/// # Returns
+///
+/// The number of bits written. Any bits after them are untouched.
+///
+/// # Errors
+///
+/// This fails, without writing any bits, if the slice is shorter than the
+/// encoding.
+pub fn write_sleb128<O>(
+	bits: &mut BitSlice<O, u8>,
+	value: i64,
+) -> Result<usize, VarintError>
+where
+	O: BitOrder,
+{
+	let needed = sleb128_len(value);
+	check_len(bits, needed)?;
+	let mut value = value;
+	for n in 0 .. needed / 8 {
+		let more = if n + 1 < needed / 8 { 0x80 } else { 0 };
+		store_byte(bits, n, (value & 0x7F) as u8 | more);
+		value >>= 7;
+	}
+	Ok(needed)
+}
+
+/// Counts the bits that the unsigned varint encoding of a value occupies.
+pub fn uleb128_len(value: u64) -> usize {
+	let significant = 64 - value.leading_zeros() as usize;
+	8 * cmp::max(1, groups(significant))
+}
+
+/// Counts the bits that the signed varint encoding of a value occupies.
+pub fn sleb128_len(value: i64) -> usize {
+	//  The encoding must also hold the sign bit above the significant bits.
+	let redundant = if value < 0 {
+		(!value).leading_zeros()
+	}
+	else {
+		value.leading_zeros()
+	};
+	8 * groups(65 - redundant as usize)
+}
+
+/// Maps a signed value onto an unsigned one, so that values of small
+/// magnitude have short varint encodings.
+///
+/// This is the encoding of Protocol Buffers `sint32` and `sint64` fields:
+/// `0, -1, 1, -2, …` become `0, 1, 2, 3, …`.
+pub fn zigzag(value: i64) -> u64 {
+	((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Reverses [`zigzag`].
+///
+/// [`zigzag`]: fn.zigzag.html
+pub fn unzigzag(value: u64) -> i64 {
+	(value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+/** An iterator over a sequence of unsigned varints packed end to end.
+
+This is produced by [`Varints::new`]. It stops at the end of the slice, or at
+the first error, which it yields before stopping.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, varint::Varints};
+
+let data = [0x01u8, 0xAC, 0x02, 0x7F, 0x80];
+let mut varints = Varints::new(data.bits::<Lsb0>());
+assert_eq!(varints.next(), Some(Ok(1)));
+assert_eq!(varints.next(), Some(Ok(300)));
+assert_eq!(varints.next(), Some(Ok(127)));
+assert_eq!(varints.consumed(), 32);
+assert!(varints.next().unwrap().is_err());
+assert_eq!(varints.next(), None);
+```
+
+[`Varints::new`]: #method.new
+**/
+#[derive(Clone, Debug)]
+pub struct Varints<'a, O>
+where O: BitOrder
+{
+	/// The bits that have not been read.
+	bits: &'a BitSlice<O, u8>,
+	/// The number of bits read so far.
+	consumed: usize,
+}
+
+impl<'a, O> Varints<'a, O>
+where O: BitOrder
+{
+	/// Begins reading varints from the front of a bit slice.
+	pub fn new(bits: &'a BitSlice<O, u8>) -> Self {
+		Self { bits, consumed: 0 }
+	}
+
+	/// Counts the bits occupied by the varints read so far.
+	pub fn consumed(&self) -> usize {
+		self.consumed
+	}
+
+	/// Views the bits after the varints read so far.
+	pub fn remainder(&self) -> &'a BitSlice<O, u8> {
+		self.bits
+	}
+}
+
+impl<'a, O> Iterator for Varints<'a, O>
+where O: BitOrder
+{
+	type Item = Result<u64, VarintError>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.bits.is_empty() {
+			return None;
+		}
+		match read_uleb128(self.bits) {
+			Ok((value, len)) => {
+				self.bits = &self.bits[len ..];
+				self.consumed += len;
+				Some(Ok(value))
+			},
+			Err(err) => {
+				self.bits = BitSlice::empty();
+				Some(Err(err))
+			},
+		}
+	}
+}
+
+impl<'a, O> FusedIterator for Varints<'a, O>
+where O: BitOrder
+{
+}
+
+/// Counts the seven-bit groups that hold `bits` bits.
+fn groups(bits: usize) -> usize {
+	bits / 7 + cmp::min(bits % 7, 1)
+}
+
+/// Reads the `n`th byte of a varint from the front of a bit slice.
+fn load_byte<O>(bits: &BitSlice<O, u8>, n: usize) -> Result<u8, VarintError>
+where O: BitOrder {
+	let available = bits.len();
+	if available < 8 * (n + 1) {
+		return Err(VarintError::Truncated { available });
+	}
+	let byte = &bits[8 * n .. 8 * (n + 1)];
+	Ok((0 .. 8u8).fold(0, |accum, idx| {
+		let bit = unsafe { *byte.get_unchecked(idx as usize) } as u8;
+		accum | bit << *O::at::<u8>(idx.idx())
+	}))
+}
+
+/// Writes the `n`th byte of a varint into the front of a bit slice.
+fn store_byte<O>(bits: &mut BitSlice<O, u8>, n: usize, value: u8)
+where O: BitOrder {
+	let byte = &mut bits[8 * n .. 8 * (n + 1)];
+	for idx in 0 .. 8u8 {
+		let bit = value >> *O::at::<u8>(idx.idx()) & 1 != 0;
+		unsafe {
+			byte.set_unchecked(idx as usize, bit);
+		}
+	}
+}
+
+/// Checks that a destination slice can hold `needed` bits.
+fn check_len<O>(
+	bits: &BitSlice<O, u8>,
+	needed: usize,
+) -> Result<(), VarintError>
+where
+	O: BitOrder,
+{
+	let available = bits.len();
+	if available < needed {
+		Err(VarintError::TooShort { needed, available })
+	}
+	else {
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+
+	#[test]
+	fn round_trip() {
+		let values = [
+			0,
+			1,
+			127,
+			128,
+			300,
+			0x3FFF,
+			0x4000,
+			!0u32 as u64,
+			1 << 63,
+			!0,
+		];
+		let mut data = [0u8; 24];
+		for &value in &values {
+			for &offset in &[0, 3, 8, 13] {
+				let bits = &mut data.bits_mut::<Msb0>()[offset ..];
+				let len = write_uleb128(bits, value).unwrap();
+				assert_eq!(len, uleb128_len(value));
+				assert_eq!(read_uleb128(bits), Ok((value, len)));
+				assert_eq!(
+					read_uleb128(&bits[.. len - 1]),
+					Err(VarintError::Truncated { available: len - 1 }),
+				);
+
+				let signed = value as i64;
+				let bits = &mut data.bits_mut::<Lsb0>()[offset ..];
+				let len = write_sleb128(bits, signed).unwrap();
+				assert_eq!(len, sleb128_len(signed));
+				assert_eq!(read_sleb128(bits), Ok((signed, len)));
+				assert_eq!(unzigzag(zigzag(signed)), signed);
+			}
+		}
+		assert_eq!(uleb128_len(!0), 8 * MAX_BYTES);
+		assert_eq!(sleb128_len(-1 << 63), 8 * MAX_BYTES);
+		assert_eq!((sleb128_len(63), sleb128_len(64)), (8, 16));
+		assert_eq!((sleb128_len(-64), sleb128_len(-65)), (8, 16));
+		assert_eq!((zigzag(-1), zigzag(1), zigzag(-2)), (1, 2, 3));
+
+		let bits = &mut data.bits_mut::<Msb0>()[.. 20];
+		assert_eq!(
+			write_uleb128(bits, 1 << 14),
+			Err(VarintError::TooShort {
+				needed: 24,
+				available: 20,
+			}),
+		);
+	}
+
+	#[test]
+	fn overflow() {
+		let data = [0xFFu8; 11];
+		let bits = data.bits::<Msb0>();
+		assert_eq!(read_uleb128(bits), Err(VarintError::Overflow));
+		assert_eq!(read_sleb128(bits), Err(VarintError::Overflow));
+
+		let mut data = [0xFFu8; 10];
+		data[9] = 0x01;
+		assert_eq!(
+			read_uleb128(data.bits::<Lsb0>()),
+			Ok((!0, 80)),
+		);
+		data[9] = 0x02;
+		let bits = data.bits::<Lsb0>();
+		assert_eq!(read_uleb128(bits), Err(VarintError::Overflow));
+	}
+}
@@ -12,6 +12,8 @@ slice and vector types.
 use crate::{
 	access::BitAccess,
 	boxed::BitBox,
+	domain::Domain,
+	fields::BitField,
 	indices::Indexable,
 	order::{
 		BitOrder,
@@ -23,19 +25,31 @@ use crate::{
 };
 
 use alloc::{
-	borrow::ToOwned,
+	borrow::{
+		Cow,
+		ToOwned,
+	},
 	vec::Vec,
 };
 
 use core::{
+	cmp,
+	fmt::{
+		self,
+		Display,
+		Formatter,
+	},
 	marker::PhantomData,
 	mem,
+	ops::Range,
+	ptr,
+	slice,
 };
 
 /** A compact [`Vec`] of bits, whose order and storage type can be customized.
 
-`BitVec` is a newtype wrapper over `Vec`, and as such is exactly three words in
-size on the stack.
+`BitVec` is a wrapper over `Vec`, with room for two more words of bits, and as
+such is exactly five words in size on the stack.
 
 # Examples
 
@@ -175,27 +189,32 @@ about its design. This ensures that it is as low-overhead as possible in the
 general case, and can be correctly manipulated in fundamental ways by `unsafe`
 code.
 
-Most fundamentally, `BitVec` is and always will be a `([`BitPtr`], capacity)`
-doublet. No more, no less. The order of these fields is unspecified, and you
-should **only** interact with the members through the provided APIs. Note that
-`BitPtr` is ***not directly manipulable***, and must ***never*** be written or
-interpreted as anything but opaque binary data by user code.
+Most fundamentally, `BitVec` is a `([`BitPtr`], capacity)` doublet, followed by
+two `usize`s of inline storage. The order of these fields is unspecified, and
+you should **only** interact with the members through the provided APIs. Note
+that `BitPtr` is ***not directly manipulable***, and must ***never*** be written
+or interpreted as anything but opaque binary data by user code.
 
 When a `BitVec` has allocated memory, then the memory to which it points is on
 the heap (as defined by the allocator Rust is configured to use by default), and
 its pointer points to [`len`] initialized bits in order of the [`BitOrder`] type
 parameter, followed by `capacity - len` logically uninitialized bits.
 
-`BitVec` will never perform a “small optimization” where elements are stored in
-its handle representation, for two reasons:
+## Inline Storage
 
-- It would make it more difficult for user code to correctly manipulate a
-  `BitVec`. The contents of the `BitVec` would not have a stable address if the
-  handle were moved, and it would be more difficult to determine if a `BitVec`
-  had allocated memory.
+`BitVec` performs a “small optimization”: a vector whose elements fit in two
+`usize`s keeps them in its handle, and does not allocate. Programs that hold
+many tiny vectors, such as a null mask for each row of a table, need no
+allocation for each one. The vector moves its contents to the heap the first
+time it grows past the inline storage, and [`shrink_to_fit`] moves them back if
+they fit again. [`is_inline`] reports where the contents currently are.
 
-- It would penalize the general, heap-allocated, case by incurring a branch on
-  every access.
+The contents of an inline vector do not have a stable address: moving the
+handle moves them. Pointers and slices drawn from a `BitVec` are only valid
+while it is borrowed, as the borrow checker already enforces for safe code.
+Methods that hand out the buffer beyond the life of the handle, such as
+[`leak`], [`into_iter`], and [`into_vec`], move inline contents to the heap
+first.
 
 `BitVec` will never automatically shrink itself, even if it is emptied. This
 ensures that no unnecessary allocations or deallocations occur. Emptying a
@@ -226,8 +245,8 @@ space, then increasing the length to match, is always valid.
 
 # Safety
 
-The `BitVec` handle has the same *size* as standard Rust `Vec` handles, but it
-is ***extremely binary incompatible*** with them. Attempting to treat
+The `BitVec` handle is larger than standard Rust `Vec` handles, and is
+***extremely binary incompatible*** with them. Attempting to treat
 `BitVec<_, T>` as `Vec<T>` in any manner except through the provided APIs is
 ***catastrophically*** unsafe and unsound.
 
@@ -240,6 +259,10 @@ is ***extremely binary incompatible*** with them. Attempting to treat
 [`Vec`]: https://doc.rust-lang.org/stable/std/vec/struct.Vec.html
 [`bitvec!`]: ../macro.bitvec.html
 [`clear_on_drop`]: https://docs.rs/clear_on_drop
+[`into_iter`]: #method.into_iter
+[`into_vec`]: #method.into_vec
+[`is_inline`]: #method.is_inline
+[`leak`]: #method.leak
 [`len`]: #method.len
 [`shrink_to_fit`]: #method.shrink_to_fit
 [`&str`]: https://doc.rust-lang.org/stable/std/primitive.str.html
@@ -253,17 +276,51 @@ where
 {
 	/// Phantom `BitOrder` member to satisfy the constraint checker.
 	_order: PhantomData<O>,
-	/// Slice pointer over the owned memory.
+	/// Slice pointer over the owned memory. While the vector is inline, its
+	/// address is stale, and is replaced by the address of `inline` on use.
 	pointer: BitPtr<T>,
-	/// The number of *elements* this vector has allocated.
+	/// The number of *elements* this vector has allocated. This is zero while
+	/// the vector is inline.
 	capacity: usize,
+	/// Storage for the elements of a vector that has not allocated.
+	inline: [usize; 2],
 }
 
+/** A clone-on-write bit sequence, which either borrows a `BitSlice` or owns a
+`BitVec`.
+
+`BitVec` borrows as `BitSlice`, and hashes and compares exactly as its slice
+does, so maps keyed by `BitCow` or by `BitVec` can be searched with a borrowed
+`&BitSlice` key.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, vec::BitCow};
+use std::collections::HashMap;
+
+let data = [0b1010_0000u8];
+let key: &BitSlice<Msb0, u8> = &data.bits::<Msb0>()[.. 4];
+
+let mut map = HashMap::<BitCow<Msb0, u8>, &str>::new();
+map.insert(key.to_cow(), "borrowed");
+map.insert(BitCow::Owned(bitvec![Msb0, u8; 1, 1]), "owned");
+
+assert_eq!(map.get(key), Some(&"borrowed"));
+assert_eq!(map.get(bits![Msb0, u8; 1, 1]), Some(&"owned"));
+```
+**/
+pub type BitCow<'a, O = Local, T = usize> = Cow<'a, BitSlice<O, T>>;
+
 impl<O, T> BitVec<O, T>
 where
 	O: BitOrder,
 	T: BitStore,
 {
+	/// The number of elements that fit in the inline storage.
+	const INLINE_ELTS: usize =
+		mem::size_of::<[usize; 2]>() / mem::size_of::<T>();
+
 	/// Constructs a `BitVec` from a value repeated many times.
 	///
 	/// This function is equivalent to the `bitvec![O, T; bit; len]` macro call,
@@ -308,9 +365,7 @@ where
 	/// ```
 	#[inline]
 	pub fn from_element(elt: T) -> Self {
-		let mut v = Vec::with_capacity(1);
-		v.push(elt);
-		Self::from_vec(v)
+		Self::from_slice(&[elt])
 	}
 
 	/// Constructs a `BitVec` from a slice of elements.
@@ -339,7 +394,164 @@ where
 	/// ```
 	#[inline]
 	pub fn from_slice(slice: &[T]) -> Self {
-		Self::from_vec(slice.to_owned())
+		Self::from_bitslice(BitSlice::from_slice(slice))
+	}
+
+	/// Encodes an integer into a new `BitVec` of a given width.
+	///
+	/// The value is written as by [`BitField::store`]: the `BitOrder`
+	/// parameter determines which bit of the vector is the least significant,
+	/// and the local CPU byte order determines the order of elements in
+	/// multi-element vectors. Use [`from_uint_le`] or [`from_uint_be`] to name
+	/// the element order explicitly.
+	///
+	/// # Parameters
+	///
+	/// - `value`: The integer to encode.
+	/// - `width`: The number of bits in the produced vector.
+	///
+	/// # Returns
+	///
+	/// A `width`-bit vector holding `value`, or `None` if `value` does not fit
+	/// in `width` bits, or `width` is wider than `U`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = BitVec::<Msb0, u8>::from_uint(0b101u8, 3).unwrap();
+	/// assert_eq!(bv, bitvec![1, 0, 1]);
+	/// assert!(BitVec::<Msb0, u8>::from_uint(8u8, 3).is_none());
+	/// ```
+	///
+	/// [`BitField::store`]: ../fields/trait.BitField.html#method.store
+	/// [`from_uint_be`]: #method.from_uint_be
+	/// [`from_uint_le`]: #method.from_uint_le
+	pub fn from_uint<U>(value: U, width: usize) -> Option<Self>
+	where
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		Self::encode_uint(value, width, BitSlice::store_uint)
+	}
+
+	/// Encodes an integer into a new `BitVec` of a given width, with the least
+	/// significant element first.
+	///
+	/// This is [`from_uint`], using [`BitField::store_le`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = BitVec::<Msb0, u8>::from_uint_le(0x1234u16, 16).unwrap();
+	/// assert_eq!(bv.as_slice(), &[0x34, 0x12]);
+	/// assert!(BitVec::<Msb0, u8>::from_uint_le(0x1234u16, 12).is_none());
+	/// ```
+	///
+	/// [`BitField::store_le`]: ../fields/trait.BitField.html#tymethod.store_le
+	/// [`from_uint`]: #method.from_uint
+	pub fn from_uint_le<U>(value: U, width: usize) -> Option<Self>
+	where
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		Self::encode_uint(value, width, BitSlice::store_uint_le)
+	}
+
+	/// Encodes an integer into a new `BitVec` of a given width, with the most
+	/// significant element first.
+	///
+	/// This is [`from_uint`], using [`BitField::store_be`].
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = BitVec::<Msb0, u8>::from_uint_be(0x1234u16, 16).unwrap();
+	/// assert_eq!(bv.as_slice(), &[0x12, 0x34]);
+	/// ```
+	///
+	/// [`BitField::store_be`]: ../fields/trait.BitField.html#tymethod.store_be
+	/// [`from_uint`]: #method.from_uint
+	pub fn from_uint_be<U>(value: U, width: usize) -> Option<Self>
+	where
+		U: BitStore,
+		BitSlice<O, T>: BitField,
+	{
+		Self::encode_uint(value, width, BitSlice::store_uint_be)
+	}
+
+	/// Parses a string of digits into a `BitVec`.
+	///
+	/// Each digit produces `log2(radix)` bits, most significant first, so that
+	/// `"A"` in radix 16 is `1010`. The digits may be separated by `_`, `,`,
+	/// or whitespace, which are skipped, and the whole string may be wrapped
+	/// in `[` and `]`. This accepts the output of the `Display` implementation
+	/// when `radix` is 2.
+	///
+	/// The [`FromStr`] implementation calls this with a radix of 2.
+	///
+	/// # Parameters
+	///
+	/// - `src`: The text to parse.
+	/// - `radix`: The base of the digits: 2, 8, or 16.
+	///
+	/// # Returns
+	///
+	/// The parsed bits, or an error naming the first character that is neither
+	/// a separator nor a digit in `radix`.
+	///
+	/// # Panics
+	///
+	/// This panics if `radix` is not 2, 8, or 16.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let bv = BitVec::<Msb0, u8>::from_str_radix("a_7", 16).unwrap();
+	/// assert_eq!(bv, bitvec![1, 0, 1, 0, 0, 1, 1, 1]);
+	/// let bv = BitVec::<Msb0, u8>::from_str_radix("7 0", 8).unwrap();
+	/// assert_eq!(bv, bitvec![1, 1, 1, 0, 0, 0]);
+	/// let err = BitVec::<Msb0, u8>::from_str_radix("018", 8).unwrap_err();
+	/// assert_eq!(err.index(), 2);
+	/// ```
+	///
+	/// [`FromStr`]: https://doc.rust-lang.org/core/str/trait.FromStr.html
+	pub fn from_str_radix(src: &str, radix: u32) -> Result<Self, ParseError> {
+		let width = match radix {
+			2 => 1,
+			8 => 3,
+			16 => 4,
+			_ => panic!("Radix {} must be 2, 8, or 16", radix),
+		};
+		//  Brackets are only stripped as a pair, and error indices are kept
+		//  relative to the original text.
+		let (offset, body) = if src.starts_with('[') && src.ends_with(']') {
+			(1, &src[1 .. src.len() - 1])
+		}
+		else {
+			(0, src)
+		};
+		let mut out = Self::with_capacity(body.len() * width);
+		for (idx, ch) in body.char_indices() {
+			if ch == '_' || ch == ',' || ch.is_whitespace() {
+				continue;
+			}
+			let digit = ch.to_digit(radix).ok_or(ParseError {
+				index: offset + idx,
+				found: ch,
+			})?;
+			for n in (0 .. width).rev() {
+				out.push(digit >> n & 1 == 1);
+			}
+		}
+		Ok(out)
 	}
 
 	/// Consumes a `Vec<T>` and creates a `BitVec<C, T>` from it.
@@ -380,11 +592,38 @@ where
 		let pointer = bs.bitptr();
 		let capacity = vec.capacity();
 		mem::forget(vec);
-		Self {
-			_order: PhantomData,
-			pointer,
-			capacity,
-		}
+		unsafe { Self::from_raw_parts(pointer, capacity) }
+	}
+
+	/// Gathers one bit plane of an integer buffer into a new `BitVec`.
+	///
+	/// This is [`plane::extract_bit_plane`]; see the [`plane`] module for
+	/// decomposing a buffer into all of its planes at once.
+	///
+	/// # Parameters
+	///
+	/// - `src`: The buffer whose plane is taken.
+	/// - `plane`: The significance of the bit to take from each element.
+	///
+	/// # Panics
+	///
+	/// This panics if `plane` is not less than the bit width of `U`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let src = [0x0100u16, 0x0003, 0xFFFF];
+	/// let bv = BitVec::<Lsb0, u8>::from_bit_plane(&src, 8);
+	/// assert_eq!(bv, bits![1, 0, 1]);
+	/// ```
+	///
+	/// [`plane`]: ../plane/index.html
+	/// [`plane::extract_bit_plane`]: ../plane/fn.extract_bit_plane.html
+	pub fn from_bit_plane<U>(src: &[U], plane: u8) -> Self
+	where U: BitStore {
+		crate::plane::extract_bit_plane(src, plane)
 	}
 
 	/// Clones a `&BitSlice` into a `BitVec`.
@@ -429,28 +668,228 @@ where
 		let mut pointer = slice.bitptr();
 		let source = pointer.as_access_slice();
 
+		/* The copy keeps the source pointer’s length and offset information.
+		This enables `BitVec` to efficiently lift from any `&BitSlice`, without
+		having to reälign the source per-bit.
+
+		The copy must be done per-element, so that atomic systems will
+		correctly synchronize.
+		*/
+		if source.len() <= Self::INLINE_ELTS {
+			let mut out = Self::new();
+			for (slot, elt) in out.inline_elements().iter_mut().zip(source) {
+				*slot = elt.load();
+			}
+			//  The stale address in `pointer` is never used while inline.
+			out.pointer = pointer;
+			return out;
+		}
+
 		//  Create a blank buffer into which the source will be copied.
 		let mut v = Vec::with_capacity(source.len());
-
-		//  Copy the source into the buffer. This must be done per-element, so
-		//  that atomic systems will correctly synchronize.
 		source.iter().for_each(|elt| v.push(elt.load()));
 
-		/* Target the copied pointer to the buffer’s region, preserving its
-		length and offset information. This enables `BitVec` to efficiently
-		lift from any `&BitSlice`, without having to reälign the source per-bit.
-		*/
+		//  Target the copied pointer to the buffer’s region.
 		unsafe {
 			pointer.set_pointer(v.as_ptr() as *const T);
 		}
 
 		let capacity = v.capacity();
 		mem::forget(v);
-		Self {
-			_order: PhantomData,
-			pointer,
-			capacity,
+		unsafe { Self::from_raw_parts(pointer, capacity) }
+	}
+
+	/// Flattens a sequence of bit slices into a single `BitVec`.
+	///
+	/// This is the equivalent of `[&[T]]::concat`. The output buffer is
+	/// allocated once, at the sum of the source lengths, and each source is
+	/// then copied into place. Sources that share their starting bit index
+	/// with their destination region are copied element-wise rather than
+	/// bit-wise.
+	///
+	/// # Parameters
+	///
+	/// - `slices`: The bit slices to concatenate, in order.
+	///
+	/// # Returns
+	///
+	/// A `BitVec` containing each slice in `slices`, one after another.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = bits![Msb0, u8; 1, 0];
+	/// let b = bits![Msb0, u8; 1, 1, 1];
+	/// let bv = BitVec::concat(&[a, b, a]);
+	/// assert_eq!(bv, bitvec![Msb0, u8; 1, 0, 1, 1, 1, 1, 0]);
+	/// ```
+	pub fn concat(slices: &[&BitSlice<O, T>]) -> Self {
+		let len = slices.iter().map(|s| s.len()).sum();
+		let mut out = Self::with_capacity(len);
+		for slice in slices {
+			out.append_slice(slice);
 		}
+		out
+	}
+
+	/// Flattens a sequence of bit slices into a single `BitVec`, placing a
+	/// separator between each one.
+	///
+	/// This is the equivalent of `[&[T]]::join`. As with [`concat`], the output
+	/// buffer is allocated exactly once.
+	///
+	/// # Parameters
+	///
+	/// - `slices`: The bit slices to join, in order.
+	/// - `separator`: A bit slice copied between each pair of adjacent
+	///   `slices`. It is not written before the first or after the last slice.
+	///
+	/// # Returns
+	///
+	/// A `BitVec` containing each slice in `slices`, interspersed with
+	/// `separator`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = bits![Msb0, u8; 1, 1];
+	/// let b = bits![Msb0, u8; 1];
+	/// let sep = bits![Msb0, u8; 0, 0];
+	/// let bv = BitVec::join(&[a, b, a], sep);
+	/// assert_eq!(bv, bitvec![Msb0, u8; 1, 1, 0, 0, 1, 0, 0, 1, 1]);
+	/// ```
+	///
+	/// [`concat`]: #method.concat
+	pub fn join(slices: &[&BitSlice<O, T>], separator: &BitSlice<O, T>) -> Self {
+		let seps = slices.len().saturating_sub(1);
+		let len = slices.iter().map(|s| s.len()).sum::<usize>()
+			+ seps * separator.len();
+		let mut out = Self::with_capacity(len);
+		for (idx, slice) in slices.iter().enumerate() {
+			if idx > 0 {
+				out.append_slice(separator);
+			}
+			out.append_slice(slice);
+		}
+		out
+	}
+
+	/// Takes a bitwise majority vote of several bit slices.
+	///
+	/// Each bit of the output is set if more than half of the inputs have that
+	/// bit set. This is [`threshold`] with `k` equal to one more than half the
+	/// number of inputs; a tie among an even number of inputs is not a
+	/// majority.
+	///
+	/// # Parameters
+	///
+	/// - `slices`: The inputs, which must all have the same length.
+	///
+	/// # Returns
+	///
+	/// A vector of the same length as the inputs, holding the vote. With no
+	/// inputs, this is empty.
+	///
+	/// # Panics
+	///
+	/// This panics if the inputs have different lengths.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = bits![Msb0, u8; 1, 1, 0, 0, 1];
+	/// let b = bits![Msb0, u8; 1, 0, 1, 0, 1];
+	/// let c = bits![Msb0, u8; 0, 1, 1, 0, 1];
+	/// let bv = BitVec::majority(&[a, b, c]);
+	/// assert_eq!(bv, bitvec![Msb0, u8; 1, 1, 1, 0, 1]);
+	/// ```
+	///
+	/// [`threshold`]: #method.threshold
+	pub fn majority(slices: &[&BitSlice<O, T>]) -> Self {
+		Self::threshold(slices.len() / 2 + 1, slices)
+	}
+
+	/// Sets each bit that is set in at least `k` of several bit slices.
+	///
+	/// The inputs are counted a storage element at a time. For each element of
+	/// the output, a bit-sliced counter is kept in a few elements: one for each
+	/// binary digit of the count, with the count of bit `n` spread across bit
+	/// `n` of each of them. Each input element is added into the counter with
+	/// a chain of carry-save half adders, so every input costs a handful of
+	/// word operations per element rather than one operation per bit.
+	///
+	/// Inputs that begin at the start of an element are read an element at a
+	/// time. Inputs that do not are gathered bit by bit, which is much slower.
+	///
+	/// # Parameters
+	///
+	/// - `k`: The number of inputs in which a bit must be set for it to be set
+	///   in the output.
+	/// - `slices`: The inputs, which must all have the same length.
+	///
+	/// # Returns
+	///
+	/// A vector of the same length as the inputs. When `k` is zero, every bit
+	/// is set; when `k` is greater than the number of inputs, none are.
+	///
+	/// # Panics
+	///
+	/// This panics if the inputs have different lengths.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let a = bits![Msb0, u8; 1, 1, 1, 0];
+	/// let b = bits![Msb0, u8; 1, 1, 0, 0];
+	/// let c = bits![Msb0, u8; 1, 0, 0, 0];
+	/// let d = bits![Msb0, u8; 1, 1, 0, 1];
+	/// let bv = BitVec::threshold(3, &[a, b, c, d]);
+	/// assert_eq!(bv, bitvec![Msb0, u8; 1, 1, 0, 0]);
+	/// ```
+	pub fn threshold(k: usize, slices: &[&BitSlice<O, T>]) -> Self {
+		let len = slices.first().map_or(0, |s| s.len());
+		for slice in slices {
+			assert_eq!(
+				slice.len(),
+				len,
+				"Voting requires inputs of equal lengths",
+			);
+		}
+		let mut out = Self::repeat(k == 0, len);
+		if k == 0 || k > slices.len() {
+			return out;
+		}
+		//  One counter plane for each binary digit of the number of inputs.
+		let digits = (0usize.count_zeros() - slices.len().leading_zeros())
+			as usize;
+		let mut planes = [T::FALSE; 64];
+		let planes = &mut planes[.. digits];
+		let width = T::BITS as usize;
+		for (idx, elt) in out.as_mut_slice().iter_mut().enumerate() {
+			let span = idx * width .. cmp::min(len, (idx + 1) * width);
+			planes.iter_mut().for_each(|p| *p = T::FALSE);
+			for slice in slices {
+				let mut carry = Self::gather(&slice[span.clone()]);
+				for plane in planes.iter_mut() {
+					if carry == T::FALSE {
+						break;
+					}
+					let sum = (*plane | carry) & !(*plane & carry);
+					carry = *plane & carry;
+					*plane = sum;
+				}
+			}
+			*elt = Self::at_least(planes, k);
+		}
+		out
 	}
 
 	/// Converts a frozen `BitBox` allocation into a growable `BitVec`.
@@ -503,7 +942,9 @@ where
 	/// - `pointer`’s element count needs to be less than or equal to the
 	///   original allocation capacity.
 	/// - `capacity` needs to be the original allocation capacity for the
-	///   vector. This is *not* the value produced by `.capacity()`.
+	///   vector. This is *not* the value produced by `.capacity()`. A
+	///   `capacity` of zero marks `pointer` as unallocated; it must then have
+	///   no elements, and the vector begins in its inline storage.
 	///
 	/// Violating these ***will*** cause problems, like corrupting the handle’s
 	/// concept of memory, the allocator’s internal data structures, and the
@@ -521,6 +962,7 @@ where
 			_order: PhantomData,
 			pointer,
 			capacity,
+			inline: [0; 2],
 		}
 	}
 
@@ -546,7 +988,7 @@ where
 	/// ```
 	#[inline]
 	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
-		self.pointer.into_bitslice()
+		self.bitptr().into_bitslice()
 	}
 
 	/// Produces a mutable `BitSlice` containing the entire vector.
@@ -571,7 +1013,7 @@ where
 	/// ```
 	#[inline]
 	pub fn as_mut_bitslice(&mut self) -> &mut BitSlice<O, T> {
-		self.pointer.into_bitslice_mut()
+		self.bitptr_mut().into_bitslice_mut()
 	}
 
 	/// Sets the backing storage to the provided element.
@@ -611,6 +1053,207 @@ where
 			.for_each(|elt| *elt = element);
 	}
 
+	/// Reverses the byte order of every live element in the backing store.
+	///
+	/// Unlike [`BitSlice::swap_element_bytes`], which skips partially-owned
+	/// edge elements, this includes the elements at either end of the
+	/// `BitVec`, as the vector owns them entirely. It operates on the same
+	/// elements as `self.as_slice()`.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = BitVec::<Msb0, u16>::from_vec(vec![0x0102, 0x0304]);
+	/// bv.truncate(20);
+	/// bv.swap_element_bytes();
+	/// assert_eq!(bv.as_slice(), &[0x0201, 0x0403]);
+	/// ```
+	///
+	/// [`BitSlice::swap_element_bytes`]: ../slice/struct.BitSlice.html#method.swap_element_bytes
+	#[inline]
+	pub fn swap_element_bytes(&mut self) {
+		self.as_mut_slice()
+			.iter_mut()
+			.for_each(|elt| *elt = elt.swap_bytes());
+	}
+
+	/// Reverses the bit order of every live element in the backing store.
+	///
+	/// Unlike [`BitSlice::reverse_bits_per_element`], which skips
+	/// partially-owned edge elements, this includes the elements at either end
+	/// of the `BitVec`, as the vector owns them entirely. It operates on the
+	/// same elements as `self.as_slice()`.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![Msb0, u8; 1, 1, 0];
+	/// bv.reverse_bits_per_element();
+	/// assert_eq!(bv.as_slice()[0] & 0b11, 0b11);
+	/// ```
+	///
+	/// [`BitSlice::reverse_bits_per_element`]: ../slice/struct.BitSlice.html#method.reverse_bits_per_element
+	#[inline]
+	pub fn reverse_bits_per_element(&mut self) {
+		self.as_mut_slice()
+			.iter_mut()
+			.for_each(|elt| *elt = elt.reverse_bits());
+	}
+
+	/// Widens a 2’s-complement integer by copying its sign bit.
+	///
+	/// As with the arithmetic operators, the first bit of the vector is the
+	/// most significant, so the new bits are inserted at the front. The
+	/// numeric value of `self`, read as a signed integer, is unchanged.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `new_len`: The new width of `self`. If this is not greater than the
+	///   current width, `self` is unchanged.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![1, 0, 1];
+	/// bv.sign_extend(5);
+	/// assert_eq!(bv, bitvec![1, 1, 1, 0, 1]);
+	/// ```
+	pub fn sign_extend(&mut self, new_len: usize) {
+		let sign = self.first().copied().unwrap_or(false);
+		self.extend_front(new_len, sign);
+	}
+
+	/// Widens an unsigned integer by inserting zeros at its most significant
+	/// end.
+	///
+	/// As with the arithmetic operators, the first bit of the vector is the
+	/// most significant, so the new bits are inserted at the front.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `new_len`: The new width of `self`. If this is not greater than the
+	///   current width, `self` is unchanged.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![1, 0, 1];
+	/// bv.zero_extend(5);
+	/// assert_eq!(bv, bitvec![0, 0, 1, 0, 1]);
+	/// ```
+	pub fn zero_extend(&mut self, new_len: usize) {
+		self.extend_front(new_len, false);
+	}
+
+	/// Narrows an unsigned integer, if its value fits in the new width.
+	///
+	/// The bits removed are the most significant, at the front of the vector.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `new_len`: The new width of `self`. If this is not less than the
+	///   current width, `self` is unchanged.
+	///
+	/// # Returns
+	///
+	/// `Err(Overflow)` if any of the bits that would be removed are set. In
+	/// that case, `self` is unchanged.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![0, 0, 1, 1];
+	/// assert!(bv.truncate_checked(2).is_ok());
+	/// assert_eq!(bv, bitvec![1, 1]);
+	/// assert!(bv.truncate_checked(1).is_err());
+	/// assert_eq!(bv, bitvec![1, 1]);
+	/// ```
+	pub fn truncate_checked(&mut self, new_len: usize) -> Result<(), Overflow> {
+		let len = self.len();
+		if new_len >= len {
+			return Ok(());
+		}
+		if self[.. len - new_len].any() {
+			return Err(Overflow);
+		}
+		self.remove_front(len - new_len);
+		Ok(())
+	}
+
+	/// Narrows a 2’s-complement integer, if its value fits in the new width.
+	///
+	/// The bits removed are the most significant, at the front of the vector.
+	/// They must all be copies of the sign bit of the narrowed value.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `new_len`: The new width of `self`. If this is not less than the
+	///   current width, `self` is unchanged. Narrowing to zero bits only
+	///   succeeds if the value is zero.
+	///
+	/// # Returns
+	///
+	/// `Err(Overflow)` if the value of `self`, read as a signed integer, does
+	/// not fit in `new_len` bits. In that case, `self` is unchanged.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![1, 1, 1, 0];
+	/// assert!(bv.truncate_signed_checked(2).is_ok());
+	/// assert_eq!(bv, bitvec![1, 0]);
+	/// assert!(bv.truncate_signed_checked(1).is_err());
+	/// ```
+	pub fn truncate_signed_checked(
+		&mut self,
+		new_len: usize,
+	) -> Result<(), Overflow>
+	{
+		let len = self.len();
+		if new_len >= len {
+			return Ok(());
+		}
+		let cut = len - new_len;
+		let fits = if new_len == 0 {
+			self.not_any()
+		}
+		else if self[cut] {
+			self[.. cut].all()
+		}
+		else {
+			self[.. cut].not_any()
+		};
+		if !fits {
+			return Err(Overflow);
+		}
+		self.remove_front(cut);
+		Ok(())
+	}
+
 	/// Performs “reverse” addition (left to right instead of right to left).
 	///
 	/// This addition traverses the addends from left to right, performing
@@ -720,9 +1363,14 @@ where
 	/// the desired order type.
 	pub fn change_order<P>(self) -> BitVec<P, T>
 	where P: BitOrder {
-		let (bp, cap) = (self.pointer, self.capacity);
+		let out = BitVec {
+			_order: PhantomData,
+			pointer: self.pointer,
+			capacity: self.capacity,
+			inline: self.inline,
+		};
 		mem::forget(self);
-		unsafe { BitVec::from_raw_parts(bp, cap) }
+		out
 	}
 
 	/// Degrades a `BitVec` to a `BitBox`, freezing its size.
@@ -755,8 +1403,11 @@ where
 	/// # Returns
 	///
 	/// The plain vector underlying the `BitVec`.
-	pub fn into_vec(self) -> Vec<T> {
-		let slice = self.pointer.as_mut_slice();
+	pub fn into_vec(mut self) -> Vec<T> {
+		if self.is_inline() {
+			return self.as_slice().to_vec();
+		}
+		let slice = self.bitptr_mut().as_mut_slice();
 		let out = unsafe {
 			Vec::from_raw_parts(slice.as_mut_ptr(), slice.len(), self.capacity)
 		};
@@ -764,6 +1415,38 @@ where
 		out
 	}
 
+	/// Tests if the vector holds its elements in its own handle, rather than
+	/// in a heap allocation.
+	///
+	/// Vectors whose elements fit in two `usize`s begin inline, and move to
+	/// the heap when they grow past that.
+	///
+	/// # Parameters
+	///
+	/// - `&self`
+	///
+	/// # Returns
+	///
+	/// Whether the vector has no heap allocation.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![Msb0, u8; 0; 10];
+	/// assert!(bv.is_inline());
+	/// bv.resize(500, true);
+	/// assert!(!bv.is_inline());
+	/// bv.truncate(10);
+	/// bv.shrink_to_fit();
+	/// assert!(bv.is_inline());
+	/// ```
+	#[inline]
+	pub fn is_inline(&self) -> bool {
+		self.capacity == 0
+	}
+
 	/// Ensures that the live region of the underlying memory begins at the `0`
 	/// bit position.
 	///
@@ -810,6 +1493,311 @@ where
 		}
 	}
 
+	/// Removes the bits at each of a sequence of indices.
+	///
+	/// Unlike calling [`remove`] once per index, which shifts the entire tail
+	/// of the vector each time, this moves each surviving bit at most once, in
+	/// a single front-to-back pass.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `indices`: The indices to remove, as measured in the vector before
+	///   any removal takes place. These must be strictly increasing.
+	///
+	/// # Panics
+	///
+	/// This panics if any index is out of bounds, or is not greater than the
+	/// index before it. The vector is left in an unspecified, but valid, state.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![0, 1, 1, 0, 1, 0, 0, 1];
+	/// bv.remove_indices(vec![1, 2, 6]);
+	/// assert_eq!(bv, bitvec![0, 0, 1, 0, 1]);
+	/// ```
+	///
+	/// [`remove`]: #method.remove
+	pub fn remove_indices<I>(&mut self, indices: I)
+	where I: IntoIterator<Item = usize> {
+		let len = self.len();
+		//  The next bit to be read, and the next slot to be written.
+		let (mut from, mut to) = (0, 0);
+		for idx in indices {
+			assert!(idx < len, "Index {} out of bounds: {}", idx, len);
+			assert!(
+				idx >= from,
+				"Index {} is not after the previously removed index",
+				idx,
+			);
+			self.shift_down(from .. idx, to);
+			to += idx - from;
+			from = idx + 1;
+		}
+		self.shift_down(from .. len, to);
+		unsafe {
+			self.set_len(to + len - from);
+		}
+	}
+
+	/// Removes every bit whose corresponding bit in a mask is unset.
+	///
+	/// The surviving bits keep their order, and are each moved at most once,
+	/// in a single front-to-back pass. Each run of kept bits is moved as one
+	/// block.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `mask`: A bit slice of the same length as `self`. Positions where
+	///   `mask` is set are kept; positions where it is unset are removed.
+	///
+	/// # Panics
+	///
+	/// This panics if `mask` is not the same length as `self`.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![Msb0, u8; 0, 1, 1, 0, 1, 0, 0, 1];
+	/// bv.compact(0b1010_1010u8.bits::<Msb0>());
+	/// assert_eq!(bv, bitvec![0, 1, 1, 0]);
+	/// ```
+	pub fn compact<P, U>(&mut self, mask: &BitSlice<P, U>)
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		let len = self.len();
+		assert_eq!(len, mask.len(), "Compaction requires equal lengths");
+		//  The start of the current run of kept bits, and the next slot to be
+		//  written.
+		let (mut from, mut to) = (0, 0);
+		for (idx, &keep) in mask.iter().enumerate() {
+			if !keep {
+				self.shift_down(from .. idx, to);
+				to += idx - from;
+				from = idx + 1;
+			}
+		}
+		self.shift_down(from .. len, to);
+		unsafe {
+			self.set_len(to + len - from);
+		}
+	}
+
+	/// Writes a batch of bits at scattered indices.
+	///
+	/// The updates are sorted by index and grouped by the storage element
+	/// that holds each bit, so that each touched element is read and written
+	/// only once, however many of its bits the batch changes.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `updates`: Pairs of an index and the value to write into it. They may
+	///   come in any order. When an index appears more than once, the last
+	///   value given for it is the one written.
+	///
+	/// # Panics
+	///
+	/// This panics if any index is out of bounds. The check happens before
+	/// any bit is written, so the vector is unchanged if it fails.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![Msb0, u8; 0; 12];
+	/// bv.assign_batch(vec![(9, true), (1, true), (3, true), (1, false)]);
+	/// assert_eq!(bv, bits![0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0]);
+	/// ```
+	pub fn assign_batch<I>(&mut self, updates: I)
+	where I: IntoIterator<Item = (usize, bool)> {
+		let len = self.len();
+		let mut updates = updates.into_iter().collect::<Vec<_>>();
+		for &(idx, _) in &updates {
+			assert!(idx < len, "Index {} out of bounds: {}", idx, len);
+		}
+		//  The sort is stable, so repeated indices keep their order.
+		updates.sort_by_key(|&(idx, _)| idx);
+		let width = T::BITS as usize;
+		let head = *self.bitptr().head() as usize;
+		let elts = self.as_mut_slice();
+		let mut rest = &updates[..];
+		while let Some(&(first, _)) = rest.first() {
+			let elt = (head + first) / width;
+			let (mut set, mut clear) = (T::FALSE, T::FALSE);
+			let mut count = 0;
+			for &(idx, bit) in rest {
+				let pos = head + idx;
+				if pos / width != elt {
+					break;
+				}
+				let mask = *O::mask::<T>(((pos % width) as u8).idx());
+				if bit {
+					set |= mask;
+					clear &= !mask;
+				}
+				else {
+					clear |= mask;
+					set &= !mask;
+				}
+				count += 1;
+			}
+			elts[elt] = (elts[elt] & !clear) | set;
+			rest = &rest[count ..];
+		}
+	}
+
+	/// Opens a cursor that drains the vector from the front into destination
+	/// slices.
+	///
+	/// Each call to [`BitReader::read_bits_into`] copies as many bits as fit
+	/// into the destination, and advances past them. The bits that were read
+	/// are removed from the vector when the reader is dropped, in a single
+	/// move, rather than on each read.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![Msb0, u8; 1, 0, 1, 1, 0, 0, 1];
+	/// let mut dst = 0u8;
+	/// {
+	///   let mut reader = bv.reader();
+	///   assert_eq!(reader.read_bits_into(&mut dst.bits_mut()[.. 3]), 3);
+	///   assert_eq!(reader.len(), 4);
+	///   assert_eq!(reader.read_bits_into(&mut dst.bits_mut()[3 .. 5]), 2);
+	/// }
+	/// assert_eq!(dst, 0b1011_0000);
+	/// assert_eq!(bv, bits![0, 1]);
+	///
+	/// let mut reader = bv.reader();
+	/// assert_eq!(reader.read_bits_into(dst.bits_mut()), 2);
+	/// assert_eq!(reader.read_bits_into(dst.bits_mut()), 0);
+	/// ```
+	///
+	/// [`BitReader::read_bits_into`]: struct.BitReader.html#method.read_bits_into
+	pub fn reader(&mut self) -> BitReader<'_, O, T> {
+		BitReader {
+			bitvec: self,
+			head: 0,
+		}
+	}
+
+	/// Drains the vector from the front in frames of a fixed width.
+	///
+	/// Each frame is a new `BitBox` of exactly `width` bits, and producing one
+	/// costs one allocation of that size and one copy of `width` bits, however
+	/// long the vector is. The bits left after the last whole frame stay in
+	/// the vector, unless [`DrainFixed::padded`] is called to yield them as a
+	/// final frame filled out with zeros.
+	///
+	/// Every frame is removed from the vector when the iterator is dropped,
+	/// even if it was not consumed. Only the fewer than `width` bits that
+	/// remain are then moved, to the front of the vector.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `width`: The number of bits in each frame.
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is zero.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![Msb0, u8; 1, 0, 1, 1, 0, 0, 1, 1];
+	/// let frames = bv.drain_fixed(3).collect::<Vec<_>>();
+	/// assert_eq!(frames.len(), 2);
+	/// assert_eq!(frames[0].as_bitslice(), bits![1, 0, 1]);
+	/// assert_eq!(frames[1].as_bitslice(), bits![1, 0, 0]);
+	/// assert_eq!(bv, bits![1, 1]);
+	/// assert_eq!(bv.drain_fixed(3).len(), 0);
+	///
+	/// let last = bv.drain_fixed(3).padded().next().unwrap();
+	/// assert_eq!(last.as_bitslice(), bits![1, 1, 0]);
+	/// assert!(bv.is_empty());
+	/// ```
+	///
+	/// [`DrainFixed::padded`]: struct.DrainFixed.html#method.padded
+	pub fn drain_fixed(&mut self, width: usize) -> DrainFixed<'_, O, T> {
+		assert!(width > 0, "Cannot drain frames of zero bits");
+		DrainFixed {
+			bitvec: self,
+			head: 0,
+			width,
+			pad: false,
+		}
+	}
+
+	/// Stores an integer into a new vector of a given width, with one of the
+	/// checked `BitSlice::store_uint` methods.
+	fn encode_uint<U, F>(value: U, width: usize, store: F) -> Option<Self>
+	where
+		U: BitStore,
+		F: FnOnce(&mut BitSlice<O, T>, U) -> Result<(), Overflow>,
+	{
+		if width > U::BITS as usize {
+			return None;
+		}
+		let mut out = Self::repeat(false, width);
+		store(&mut out, value).ok()?;
+		Some(out)
+	}
+
+	/// Grows the vector to `new_len` by inserting `bit` at the front.
+	fn extend_front(&mut self, new_len: usize, bit: bool) {
+		let len = self.len();
+		if new_len <= len {
+			return;
+		}
+		self.resize(new_len, bit);
+		self.rotate_right(new_len - len);
+	}
+
+	/// Removes the first `count` bits of the vector.
+	fn remove_front(&mut self, count: usize) {
+		let len = self.len();
+		self.shift_down(count .. len, 0);
+		self.truncate(len - count);
+	}
+
+	/// Moves a run of bits down to a lower, or equal, starting index.
+	///
+	/// The run is moved with [`BitSlice::copy_within_unchecked`], which moves
+	/// whole elements at once when the distance is a multiple of the element
+	/// width.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `range`: The bits to move. This must be within the live region.
+	/// - `to`: The index to which `range.start` is moved. This must not be
+	///   greater than `range.start`.
+	///
+	/// [`BitSlice::copy_within_unchecked`]: ../slice/struct.BitSlice.html#method.copy_within_unchecked
+	fn shift_down(&mut self, range: Range<usize>, to: usize) {
+		if range.start == to {
+			return;
+		}
+		unsafe {
+			self.copy_within_unchecked(range, to);
+		}
+	}
+
 	/// Permits a function to modify the `Vec<T>` underneath a `BitVec<_, T>`.
 	///
 	/// This produces a `Vec<T>` structure referring to the same data region as
@@ -828,11 +1816,23 @@ where
 	///   closure) which receives a mutable borrow of a `Vec<T>`.
 	///
 	/// - `R`: The return value from the called function or closure.
+	///
+	/// An inline vector is first moved onto the heap, and stays there unless
+	/// the function releases the whole allocation.
 	fn with_vec<F, R>(&mut self, func: F) -> R
 	where F: FnOnce(&mut Vec<T>) -> R {
-		let slice = self.pointer.as_mut_slice();
-		let mut v = unsafe {
-			Vec::from_raw_parts(slice.as_mut_ptr(), slice.len(), self.capacity)
+		let mut v = if self.is_inline() {
+			self.as_slice().to_vec()
+		}
+		else {
+			let slice = self.bitptr_mut().as_mut_slice();
+			unsafe {
+				Vec::from_raw_parts(
+					slice.as_mut_ptr(),
+					slice.len(),
+					self.capacity,
+				)
+			}
 		};
 		let out = func(&mut v);
 		//  The only change is that the pointer might relocate. The region data
@@ -846,12 +1846,190 @@ where
 		mem::forget(v);
 		out
 	}
+
+	/// Produces the vector’s pointer, aimed at wherever its elements are.
+	///
+	/// Inline elements move with the handle, so the pointer is only valid
+	/// while `self` is borrowed.
+	pub(crate) fn bitptr(&self) -> BitPtr<T> {
+		let mut out = self.pointer;
+		if self.is_inline() {
+			unsafe {
+				out.set_pointer(self.inline.as_ptr() as *const T);
+			}
+		}
+		out
+	}
+
+	/// Produces the vector’s pointer, with write provenance over the inline
+	/// storage.
+	fn bitptr_mut(&mut self) -> BitPtr<T> {
+		let mut out = self.pointer;
+		if self.is_inline() {
+			unsafe {
+				out.set_pointer(self.inline.as_mut_ptr() as *mut T);
+			}
+		}
+		out
+	}
+
+	/// Views the whole inline storage as elements.
+	fn inline_elements(&mut self) -> &mut [T] {
+		let data = self.inline.as_mut_ptr() as *mut T;
+		unsafe { slice::from_raw_parts_mut(data, Self::INLINE_ELTS) }
+	}
+
+	/// Produces the address and length, in elements, of each region of memory
+	/// in which the vector may have held bits.
+	///
+	/// These are the inline storage, which keeps its contents after the vector
+	/// moves onto the heap, and the whole heap allocation, including its spare
+	/// capacity. The heap region is empty while the vector is inline.
+	#[cfg(feature = "zeroize")]
+	pub(crate) fn storage_regions(&mut self) -> [(*mut T, usize); 2] {
+		let inline = self.inline.as_mut_ptr() as *mut T;
+		let heap = self.bitptr_mut().as_mut_slice().as_mut_ptr();
+		[(inline, Self::INLINE_ELTS), (heap, self.capacity)]
+	}
+
+	/// Moves inline elements onto the heap, so that they keep their address
+	/// when the handle moves.
+	fn spill(&mut self) {
+		if self.is_inline() {
+			self.with_vec(|_| ());
+		}
+	}
+
+	/// Appends a bit slice of the same type onto the vector.
+	///
+	/// Unlike [`extend_from_slice`], this requires the source to match the
+	/// vector’s type parameters, which permits the copy to run through
+	/// `copy_from_slice` and use whole-element moves where possible.
+	///
+	/// [`extend_from_slice`]: #method.extend_from_slice
+	fn append_slice(&mut self, other: &BitSlice<O, T>) {
+		let len = self.len();
+		let olen = other.len();
+		self.reserve(olen);
+		//  `copy_from_slice` reads the partial elements at the edges of its
+		//  destination, so the spare elements it will cover must be written
+		//  before they become live.
+		let elts = self.pointer.elements();
+		let (total, _) = self.pointer.head().span(len + olen);
+		let base = self.bitptr_mut().pointer().w();
+		for idx in elts .. total {
+			unsafe {
+				ptr::write(base.add(idx), T::FALSE);
+			}
+		}
+		unsafe {
+			self.set_len(len + olen);
+		}
+		self[len ..].copy_from_slice(other);
+	}
+
+	/// Reads up to one element’s worth of bits into an element, with bit `n`
+	/// of the slice at position `n` of the element, and any remaining bits
+	/// cleared.
+	fn gather(bits: &BitSlice<O, T>) -> T {
+		if *bits.bitptr().head() == 0 {
+			return match bits.domain() {
+				Domain::Enclave(elt) => elt.load(),
+				Domain::Region { body, tail, .. } => body
+					.first()
+					.copied()
+					.or_else(|| tail.map(|elt| elt.load()))
+					.unwrap_or(T::FALSE),
+			};
+		}
+		let mut out = T::FALSE;
+		for (n, bit) in bits.iter().enumerate() {
+			out.set::<O>((n as u8).idx(), *bit);
+		}
+		out
+	}
+
+	/// Compares a bit-sliced counter against `k`, producing an element with
+	/// each bit set whose count is at least `k`.
+	///
+	/// The planes are the binary digits of the count, least significant
+	/// first, and `k` must fit in that many digits.
+	fn at_least(planes: &[T], k: usize) -> T {
+		//  Walk the digits from the most significant, tracking the bits whose
+		//  count is already known to be greater, and those equal so far.
+		let mut greater = T::FALSE;
+		let mut equal = T::TRUE;
+		for (digit, &plane) in planes.iter().enumerate().rev() {
+			if k >> digit & 1 == 1 {
+				equal &= plane;
+			}
+			else {
+				greater |= equal & plane;
+				equal &= !plane;
+			}
+		}
+		greater | equal
+	}
+}
+
+/** The error produced when parsing a `BitVec` from text fails.
+
+This is returned by [`BitVec::from_str_radix`], and by the `FromStr`
+implementation, when the text holds a character that is neither a separator nor
+a digit.
+
+[`BitVec::from_str_radix`]: struct.BitVec.html#method.from_str_radix
+**/
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ParseError {
+	/// The byte index of the invalid character.
+	index: usize,
+	/// The invalid character.
+	found: char,
+}
+
+impl ParseError {
+	/// Gets the byte index in the text of the invalid character.
+	pub fn index(&self) -> usize {
+		self.index
+	}
+
+	/// Gets the invalid character.
+	pub fn found(&self) -> char {
+		self.found
+	}
+}
+
+impl Display for ParseError {
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		write!(
+			fmt,
+			"Invalid digit {:?} at index {}",
+			self.found,
+			self.index,
+		)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {
 }
 
 mod api;
+mod builder;
 mod iter;
 mod ops;
+mod set;
+mod shared;
 mod traits;
 
+pub use crate::fields::Overflow;
+
 pub use api::*;
+pub use builder::*;
 pub use iter::*;
+pub use set::*;
+pub use shared::*;
+
+#[cfg(test)]
+mod tests;
@@ -16,6 +16,7 @@ use alloc::{
 use core::{
 	cmp,
 	hint::unreachable_unchecked,
+	mem,
 	ops::RangeBounds,
 	ptr::NonNull,
 };
@@ -41,7 +42,8 @@ where
 	/// Constructs a new, empty `BitVec<C, T>` with the specified capacity.
 	///
 	/// The vector will be able to hold at least `capacity` bits without
-	/// reallocating. If `capacity` is 0, the vector will not allocate.
+	/// reallocating. If `capacity` fits in the inline storage, which holds two
+	/// `usize`s, the vector will not allocate.
 	///
 	/// It is important to note that although the returned vector has the
 	/// *capacity* specified, the vector will have a zero *length*. For an
@@ -53,16 +55,18 @@ where
 		//  Get the number of `T` elements needed to store the requested bit
 		//  capacity.
 		let (elts, _) = 0u8.idx::<T>().span(capacity);
+		//  Small vectors use the inline storage. The pointer only needs to be
+		//  well aligned until it is aimed at that storage.
+		if elts <= Self::INLINE_ELTS {
+			let ptr = NonNull::<T>::dangling().as_ptr();
+			return unsafe { Self::from_raw_parts(BitPtr::uninhabited(ptr), 0) };
+		}
 		//  Allocate a buffer that can hold that many elements.
 		let v = Vec::with_capacity(elts);
 		let (ptr, cap) = (v.as_ptr(), v.capacity());
 		//  Disarm the `Vec` destructor.
 		mem::forget(v);
-		Self {
-			_order: PhantomData,
-			pointer: BitPtr::uninhabited(ptr),
-			capacity: cap,
-		}
+		unsafe { Self::from_raw_parts(BitPtr::uninhabited(ptr), cap) }
 	}
 
 	/// Returns the number of bits the vector can hold without reallocating.
@@ -75,8 +79,13 @@ where
 	/// assert!(bv.capacity() >= 100);
 	#[inline]
 	pub fn capacity(&self) -> usize {
-		self.capacity
-			.checked_mul(T::BITS as usize)
+		let elts = if self.is_inline() {
+			Self::INLINE_ELTS
+		}
+		else {
+			self.capacity
+		};
+		elts.checked_mul(T::BITS as usize)
 			.expect("Vector capacity overflow")
 	}
 
@@ -107,6 +116,9 @@ where
 			BitPtr::<T>::MAX_BITS,
 		);
 		let (total_elts, _) = self.pointer.head().span(newlen);
+		if self.is_inline() && total_elts <= Self::INLINE_ELTS {
+			return;
+		}
 		if let Some(extra) = total_elts.checked_sub(self.pointer.elements()) {
 			self.with_vec(|v| v.reserve(extra));
 		}
@@ -142,14 +154,20 @@ where
 			BitPtr::<T>::MAX_BITS,
 		);
 		let (total_elts, _) = self.pointer.head().span(newlen);
-		let extra = total_elts - self.capacity;
-		self.with_vec(|v| v.reserve_exact(extra));
+		if self.is_inline() && total_elts <= Self::INLINE_ELTS {
+			return;
+		}
+		if let Some(extra) = total_elts.checked_sub(self.pointer.elements()) {
+			self.with_vec(|v| v.reserve_exact(extra));
+		}
 	}
 
 	/// Shrinks the capacity of the vector as much as possible.
 	///
 	/// It will drop down as close as possible to the length but the allocator
 	/// may still inform the vector that there is space for a few more elements.
+	/// A vector that fits in the inline storage releases its allocation, and
+	/// moves back into its handle.
 	///
 	/// # Examples
 	///
@@ -163,6 +181,17 @@ where
 	/// ```
 	#[inline]
 	pub fn shrink_to_fit(&mut self) {
+		if self.is_inline() {
+			return;
+		}
+		let elts = self.pointer.elements();
+		if elts <= Self::INLINE_ELTS {
+			let ptr = self.pointer.pointer().w();
+			let heap = unsafe { Vec::from_raw_parts(ptr, elts, self.capacity) };
+			self.inline_elements()[.. elts].copy_from_slice(&heap);
+			self.capacity = 0;
+			return;
+		}
 		self.with_vec(Vec::shrink_to_fit);
 	}
 
@@ -270,7 +299,7 @@ where
 	/// [`BitSlice::as_slice`]: ../slice/struct.BitSlice.html#method.as_slice
 	#[inline]
 	pub fn as_slice(&self) -> &[T] {
-		self.pointer.as_slice()
+		self.bitptr().as_slice()
 	}
 
 	/// Extracts a mutable slice of the entire vector.
@@ -294,7 +323,7 @@ where
 	/// ../slice/struct.BitSlice.html#method.as_mut_slice
 	#[inline]
 	pub fn as_mut_slice(&mut self) -> &mut [T] {
-		self.pointer.as_mut_slice()
+		self.bitptr_mut().as_mut_slice()
 	}
 
 	/// Forces the length of the vector to `new_len`.
@@ -485,7 +514,13 @@ where
 		//  If self is empty *or* tail is at the back edge of an element, push
 		//  an element onto the vector.
 		if self.is_empty() || *self.pointer.tail() == T::BITS {
-			self.with_vec(|v| v.push(T::FALSE));
+			let elts = self.pointer.elements();
+			if self.is_inline() && elts < Self::INLINE_ELTS {
+				self.inline_elements()[elts] = T::FALSE;
+			}
+			else {
+				self.with_vec(|v| v.push(T::FALSE));
+			}
 		}
 		//  At this point, it is always safe to increment the tail, and then
 		//  write to the newly live bit.
@@ -720,6 +755,38 @@ where
 		}
 	}
 
+	/// Consumes and leaks the `BitVec`, returning a mutable reference to the
+	/// contents, `&'a mut BitSlice<O, T>`. Note that the memory region `[T]`
+	/// must outlive the chosen lifetime `'a`. If the lifetime is `'static`,
+	/// the region lives for the remainder of the program’s life.
+	///
+	/// This function is similar to the [`leak`] function on [`BitBox`]. It
+	/// does not reallocate or shrink the `BitVec`, so the leaked allocation
+	/// may include spare capacity which is not part of the returned slice.
+	///
+	/// This function is mainly useful for bit regions that live for the
+	/// remainder of the program’s life, such as bitmaps built at startup.
+	/// Dropping the returned reference will cause a memory leak.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// # use bitvec::prelude::*;
+	/// let bv = bitvec![Local, u8; 0; 20];
+	/// let static_ref: &'static mut BitSlice<Local, u8> = bv.leak();
+	/// static_ref.set(3, true);
+	/// assert_eq!(static_ref.count_ones(), 1);
+	/// ```
+	///
+	/// [`BitBox`]: ../boxed/struct.BitBox.html
+	/// [`leak`]: ../boxed/struct.BitBox.html#method.leak
+	pub fn leak<'a>(mut self) -> &'a mut BitSlice<O, T> {
+		self.spill();
+		let out = self.pointer;
+		mem::forget(self);
+		out.into_bitslice_mut()
+	}
+
 	/// Resizes the `BitVec` in place so that `len` is equal to `new_len`.
 	///
 	/// If `new_len` is greater than `len`, the `BitVec` is extended by the
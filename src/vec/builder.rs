@@ -0,0 +1,247 @@
+/*! A bit vector built from variable-width fields.
+
+Encoders for compressed formats emit most of their output as fields of a few
+bits each: Huffman codes, Elias-gamma lengths, Rice remainders. Pushing each bit
+of each field into a `BitVec` costs an index computation and a read-modify-write
+of memory for every bit. `BitVecBuilder` stages fields in a register instead,
+and only touches memory once a whole storage element has been produced.
+!*/
+
+use crate::{
+	fields,
+	indices::Indexable,
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::{
+		self,
+		BitStore,
+	},
+	vec::BitVec,
+};
+
+use alloc::vec::Vec;
+
+use core::{
+	marker::PhantomData,
+	mem,
+};
+
+/** Builds a `BitVec` by appending fields of up to 64 bits.
+
+As with the integers in the [`pack`] module, the most significant bit of each
+appended field is the first one written. Bits are staged in a register, and are
+moved into the vector’s storage a whole element at a time.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, vec::BitVecBuilder};
+
+let mut builder = BitVecBuilder::<Msb0, u8>::new();
+builder.append_bits(0b101, 3);
+builder.append_bits(0x3C, 8);
+builder.append_slice(bits![1, 1]);
+assert_eq!(builder.len(), 13);
+
+let bv = builder.finish();
+assert_eq!(bv.as_slice(), &[0b1010_0111, 0b1001_1000]);
+assert_eq!(bv.len(), 13);
+```
+
+[`pack`]: ../pack/index.html
+**/
+#[derive(Clone, Debug)]
+pub struct BitVecBuilder<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The completed storage elements.
+	elts: Vec<T>,
+	/// The bits not yet moved into `elts`, in the low `staged` bits. The first
+	/// of them is the most significant, and the unused bits are zero.
+	reg: u128,
+	/// The number of staged bits. This is always less than `T::BITS`.
+	staged: u8,
+	/// The result of `BitSlice::<O, T>::linear_order`.
+	linear: Option<bool>,
+	_order: PhantomData<O>,
+}
+
+impl<O, T> BitVecBuilder<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Constructs an empty builder.
+	pub fn new() -> Self {
+		Self::with_capacity(0)
+	}
+
+	/// Constructs an empty builder, with room for at least `bits` bits before
+	/// it reallocates.
+	pub fn with_capacity(bits: usize) -> Self {
+		Self {
+			elts: Vec::with_capacity(store::elts::<T>(bits)),
+			reg: 0,
+			staged: 0,
+			linear: BitSlice::<O, T>::linear_order(),
+			_order: PhantomData,
+		}
+	}
+
+	/// Counts the bits appended so far.
+	pub fn len(&self) -> usize {
+		self.elts.len() * T::BITS as usize + self.staged as usize
+	}
+
+	/// Tests if no bits have been appended.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+
+	/// Appends the low bits of an integer.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `value`: The integer whose low `width` bits are appended, most
+	///   significant first. Its higher bits are ignored.
+	/// - `width`: The number of bits to append.
+	///
+	/// # Panics
+	///
+	/// This panics if `width` is more than 64.
+	pub fn append_bits(&mut self, value: u64, width: u8) {
+		assert!(
+			width <= 64,
+			"Cannot append {} bits from a 64-bit value",
+			width,
+		);
+		let mask = (1u128 << width) - 1;
+		self.reg = (self.reg << width) | (value as u128 & mask);
+		self.staged += width;
+		while self.staged >= T::BITS {
+			self.staged -= T::BITS;
+			let elt = (self.reg >> self.staged) as u64;
+			self.reg &= (1u128 << self.staged) - 1;
+			let elt = self.element(elt);
+			self.elts.push(elt);
+		}
+	}
+
+	/// Appends one bit.
+	pub fn append_bit(&mut self, value: bool) {
+		self.append_bits(value as u64, 1);
+	}
+
+	/// Appends the bits of a slice, in order.
+	///
+	/// The slice is read 64 bits at a time, and each run is appended as one
+	/// field. Slices in the `Msb0` or `Lsb0` layout are read a whole element
+	/// at a time, rather than bit by bit.
+	pub fn append_slice<P, U>(&mut self, bits: &BitSlice<P, U>)
+	where
+		P: BitOrder,
+		U: BitStore,
+	{
+		for chunk in bits.chunks(64) {
+			self.append_bits(fields::load_msb_first(chunk), chunk.len() as u8);
+		}
+	}
+
+	/// Produces the built vector.
+	///
+	/// The bits of the last element past the end of the vector are zero.
+	pub fn finish(mut self) -> BitVec<O, T> {
+		let len = self.len();
+		if self.staged > 0 {
+			let pad = T::BITS - self.staged;
+			self.append_bits(0, pad);
+		}
+		let mut out = BitVec::from_vec(self.elts);
+		out.truncate(len);
+		out
+	}
+
+	/// Lays out the low `T::BITS` bits of a register, first bit most
+	/// significant, as a storage element.
+	fn element(&self, bits: u64) -> T {
+		//  Assemble the element a byte at a time, from the most significant.
+		let bytes = mem::size_of::<T>();
+		let byte = |n: usize| T::from((bits >> (n * 8)) as u8);
+		let elt = (0 .. bytes - 1)
+			.rev()
+			.fold(byte(bytes - 1), |elt, n| (elt << 8u8) | byte(n));
+		match self.linear {
+			Some(false) => elt,
+			Some(true) => elt.reverse_bits(),
+			None => (0 .. T::BITS)
+				.filter(|&n| (bits >> (T::MASK - n)) & 1 != 0)
+				.fold(T::FALSE, |out, n| out | *O::mask::<T>(n.idx())),
+		}
+	}
+}
+
+impl<O, T> Default for BitVecBuilder<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::order::{
+		Lsb0,
+		Msb0,
+	};
+	use alloc::borrow::ToOwned;
+
+	fn check<O, T>()
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		let fields = [
+			(0x1234_5678_9ABC_DEF0u64, 64),
+			(0b101, 3),
+			(!0, 0),
+			(0xFFFF_FFFF_FFFF_FFF5, 4),
+			(0x2A, 7),
+			(!0, 64),
+			(0x0F0F, 13),
+		];
+		let mut builder = BitVecBuilder::<O, T>::new();
+		let mut expected = BitVec::<O, T>::new();
+		for &(value, width) in &fields {
+			builder.append_bits(value, width);
+			for n in (0 .. width).rev() {
+				expected.push((value >> n) & 1 != 0);
+			}
+			assert_eq!(builder.len(), expected.len());
+		}
+		let tail = expected[3 .. 150].to_owned();
+		builder.append_slice(&tail);
+		builder.append_bit(true);
+		expected.extend_from_slice(&tail);
+		expected.push(true);
+		assert_eq!(builder.finish(), expected);
+	}
+
+	#[test]
+	fn fields() {
+		check::<Msb0, u8>();
+		check::<Lsb0, u16>();
+		check::<Msb0, u32>();
+		check::<Lsb0, usize>();
+		assert!(BitVecBuilder::<Local, u8>::default().finish().is_empty());
+	}
+}
@@ -10,6 +10,7 @@ use crate::{
 use alloc::vec::Vec;
 
 use core::{
+	cmp,
 	iter::{
 		FromIterator,
 		FusedIterator,
@@ -59,6 +60,40 @@ where
 	}
 }
 
+/** Writes a stream of sparse updates into a `BitVec`.
+
+Unlike `Extend<bool>`, this does not lengthen the vector. Each item names an
+existing index and the value to write into it. This is [`BitVec::assign_batch`],
+so that collecting updates into a vector can use the standard trait.
+
+[`BitVec::assign_batch`]: struct.BitVec.html#method.assign_batch
+**/
+impl<O, T> Extend<(usize, bool)> for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Writes each value into its index.
+	///
+	/// # Panics
+	///
+	/// This panics if any index is out of bounds, before writing any bit.
+	///
+	/// # Examples
+	///
+	/// ```rust
+	/// use bitvec::prelude::*;
+	///
+	/// let mut bv = bitvec![Lsb0, u16; 0; 20];
+	/// bv.extend((0 .. 20).step_by(3).map(|n| (n, true)));
+	/// assert_eq!(bv.count_ones(), 7);
+	/// assert_eq!(bv.len(), 20);
+	/// ```
+	fn extend<I: IntoIterator<Item = (usize, bool)>>(&mut self, src: I) {
+		self.assign_batch(src);
+	}
+}
+
 /// Permits the construction of a `BitVec` by using `.collect()` on an iterator
 /// of `bool`.
 impl<O, T> FromIterator<bool> for BitVec<O, T>
@@ -118,7 +153,10 @@ where
 	/// }
 	/// assert_eq!(count, 4);
 	/// ```
-	fn into_iter(self) -> Self::IntoIter {
+	fn into_iter(mut self) -> Self::IntoIter {
+		//  The iterator holds a pointer into the vector’s storage, which must
+		//  not move along with the handle.
+		self.spill();
 		IntoIter {
 			region: self.pointer,
 			bitvec: self,
@@ -313,6 +351,208 @@ where
 	}
 }
 
+/** A cursor that drains a `BitVec` from the front into destination slices.
+
+This is produced by [`BitVec::reader`]. The bits that it reads are removed from
+the vector when it is dropped.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering type of the underlying vector.
+- `T: 'a + BitStore`: The storage type of the underlying vector.
+
+# Lifetimes
+
+- `'a`: The lifetime of the underlying vector.
+
+[`BitVec::reader`]: struct.BitVec.html#method.reader
+**/
+pub struct BitReader<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The vector being drained.
+	pub(super) bitvec: &'a mut BitVec<O, T>,
+	/// The index of the next bit to be read.
+	pub(super) head: usize,
+}
+
+impl<'a, O, T> BitReader<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// Copies bits from the front of the vector into a slice.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `dst`: The slice into which bits are copied, from its front.
+	///
+	/// # Returns
+	///
+	/// The number of bits copied, which is the lesser of the lengths of `dst`
+	/// and of the unread bits. The bits of `dst` past that number are not
+	/// modified.
+	pub fn read_bits_into(&mut self, dst: &mut BitSlice<O, T>) -> usize {
+		let len = cmp::min(dst.len(), self.len());
+		let head = self.head;
+		dst[.. len].copy_from_slice(&self.bitvec[head .. head + len]);
+		self.head += len;
+		len
+	}
+
+	/// Views the bits that have not yet been read.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		&self.bitvec[self.head ..]
+	}
+
+	/// Counts the bits that have not yet been read.
+	pub fn len(&self) -> usize {
+		self.bitvec.len() - self.head
+	}
+
+	/// Tests if every bit has been read.
+	pub fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+}
+
+impl<'a, O, T> Drop for BitReader<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	fn drop(&mut self) {
+		let (head, len) = (self.head, self.bitvec.len());
+		self.bitvec.shift_down(head .. len, 0);
+		unsafe {
+			self.bitvec.set_len(len - head);
+		}
+	}
+}
+
+/** An iterator that drains a `BitVec` from the front in fixed-width frames.
+
+This is produced by [`BitVec::drain_fixed`]. Every frame it can yield is removed
+from the vector when it is dropped.
+
+# Type Parameters
+
+- `O: BitOrder`: The ordering type of the underlying vector.
+- `T: 'a + BitStore`: The storage type of the underlying vector.
+
+# Lifetimes
+
+- `'a`: The lifetime of the underlying vector.
+
+[`BitVec::drain_fixed`]: struct.BitVec.html#method.drain_fixed
+**/
+pub struct DrainFixed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// The vector being drained.
+	pub(super) bitvec: &'a mut BitVec<O, T>,
+	/// The index of the first bit of the next frame.
+	pub(super) head: usize,
+	/// The number of bits in each frame.
+	pub(super) width: usize,
+	/// Whether the bits after the last whole frame are yielded, and removed.
+	pub(super) pad: bool,
+}
+
+impl<'a, O, T> DrainFixed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	/// Yields the bits after the last whole frame as a final frame, with zeros
+	/// after them, and removes them from the vector.
+	pub fn padded(mut self) -> Self {
+		self.pad = true;
+		self
+	}
+
+	/// Counts the bits from the next frame to the end of the vector.
+	fn remaining(&self) -> usize {
+		self.bitvec.len() - self.head
+	}
+
+	/// Finds the index after the last bit that the iterator removes.
+	fn end(&self) -> usize {
+		let len = self.bitvec.len();
+		if self.pad {
+			len
+		}
+		else {
+			len - len % self.width
+		}
+	}
+}
+
+impl<'a, O, T> Iterator for DrainFixed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	type Item = BitBox<O, T>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let (head, width) = (self.head, self.width);
+		let rest = self.remaining();
+		if rest >= width {
+			self.head += width;
+			Some(BitBox::from_bitslice(&self.bitvec[head .. head + width]))
+		}
+		else if rest > 0 && self.pad {
+			let mut frame = BitVec::repeat(false, width);
+			frame[.. rest].copy_from_slice(&self.bitvec[head ..]);
+			self.head += rest;
+			Some(frame.into_boxed_bitslice())
+		}
+		else {
+			None
+		}
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let frames = (self.end() - self.head) / self.width
+			+ cmp::min((self.end() - self.head) % self.width, 1);
+		(frames, Some(frames))
+	}
+}
+
+impl<'a, O, T> ExactSizeIterator for DrainFixed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+impl<'a, O, T> FusedIterator for DrainFixed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+}
+
+impl<'a, O, T> Drop for DrainFixed<'a, O, T>
+where
+	O: BitOrder,
+	T: 'a + BitStore,
+{
+	fn drop(&mut self) {
+		let (end, len) = (self.end(), self.bitvec.len());
+		self.bitvec.shift_down(end .. len, 0);
+		unsafe {
+			self.bitvec.set_len(len - end);
+		}
+	}
+}
+
 /// A consuming iterator for `BitVec`.
 #[repr(C)]
 pub struct IntoIter<O, T>
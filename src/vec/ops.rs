@@ -405,6 +405,10 @@ where
 {
 	/// Rebuild the interior `Vec` and let it run the deallocator.
 	fn drop(&mut self) {
+		//  Inline vectors have nothing to release.
+		if self.is_inline() {
+			return;
+		}
 		let bp = mem::replace(&mut self.pointer, BitPtr::empty());
 		//  Build a Vec<T> out of the elements, and run its destructor.
 		let (ptr, cap) = (bp.pointer(), self.capacity);
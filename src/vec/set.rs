@@ -0,0 +1,298 @@
+/*! A set of small integers, stored as a bit vector.
+
+Bit vectors are frequently used as sets of indices: free lists in allocators,
+ready queues in schedulers, dirty-page maps. `BitIndexSet` gives that usage a
+set-shaped API, so that callers do not have to manage the growth and scanning
+of the underlying `BitVec` themselves.
+!*/
+
+use crate::{
+	indices::Indexable,
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+/** A set of `usize` indices, where index `n` is a member if bit `n` is set.
+
+The set grows its backing `BitVec` as needed to hold the largest index inserted,
+and never shrinks it on removal. Searches for the least and greatest members
+skip over wholly-clear storage elements, and find the member in the first
+non-clear element with one count of its zero bits, so they run in time
+proportional to the number of elements scanned.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, vec::BitIndexSet};
+
+let mut free = BitIndexSet::<Msb0, u8>::new();
+for slot in &[3, 9, 20] {
+    free.insert(*slot);
+}
+assert_eq!(free.len(), 3);
+assert_eq!(free.pop_min(), Some(3));
+assert_eq!(free.min(), Some(9));
+assert_eq!(free.max(), Some(20));
+assert!(!free.contains(3));
+```
+**/
+#[derive(Clone, Debug)]
+pub struct BitIndexSet<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Membership bits. This is always aligned to the front of its first
+	/// element, so that storage element `k` holds indices `k * T::BITS ..`.
+	bits: BitVec<O, T>,
+}
+
+impl<O, T> BitIndexSet<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Constructs a new, empty, set.
+	///
+	/// This does not allocate until an index is inserted.
+	pub fn new() -> Self {
+		Self { bits: BitVec::new() }
+	}
+
+	/// Constructs a new, empty, set that can hold indices below `bound`
+	/// without reallocating.
+	///
+	/// # Parameters
+	///
+	/// - `bound`: One past the largest index the set is expected to hold.
+	pub fn with_capacity(bound: usize) -> Self {
+		Self { bits: BitVec::with_capacity(bound) }
+	}
+
+	/// Adds an index to the set.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The index to insert. The set grows to hold it if necessary.
+	///
+	/// # Returns
+	///
+	/// `true` if the index was not already in the set.
+	///
+	/// # Panics
+	///
+	/// This panics if `index` is `usize::max_value()`, as the set cannot grow
+	/// to hold it, or if growing the set exceeds the capacity of a `BitVec`.
+	pub fn insert(&mut self, index: usize) -> bool {
+		if index >= self.bits.len() {
+			let len = index
+				.checked_add(1)
+				.expect("Cannot insert usize::max_value() into a set");
+			self.bits.resize(len, false);
+		}
+		let prev = self.bits[index];
+		self.bits.set(index, true);
+		!prev
+	}
+
+	/// Removes an index from the set.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	/// - `index`: The index to remove.
+	///
+	/// # Returns
+	///
+	/// `true` if the index was in the set.
+	pub fn remove(&mut self, index: usize) -> bool {
+		let prev = self.contains(index);
+		if prev {
+			self.bits.set(index, false);
+		}
+		prev
+	}
+
+	/// Tests whether an index is in the set.
+	pub fn contains(&self, index: usize) -> bool {
+		self.bits.get(index).copied().unwrap_or(false)
+	}
+
+	/// Counts the members of the set.
+	pub fn len(&self) -> usize {
+		self.bits.count_ones()
+	}
+
+	/// Tests whether the set has no members.
+	pub fn is_empty(&self) -> bool {
+		self.bits.not_any()
+	}
+
+	/// Removes all members from the set, keeping its allocation.
+	pub fn clear(&mut self) {
+		self.bits.clear();
+	}
+
+	/// Finds the least member of the set.
+	///
+	/// # Returns
+	///
+	/// The smallest index in the set, or `None` if it is empty.
+	pub fn min(&self) -> Option<usize> {
+		let width = T::BITS as usize;
+		let elts = self.bits.as_slice();
+		let (k, elt) = elts.iter().enumerate().find(|(_, e)| **e != T::FALSE)?;
+		let live = self.live_bits(k);
+		first_one::<O, T>(*elt & live_mask::<O, T>(live)).map(|n| k * width + n)
+	}
+
+	/// Finds the greatest member of the set.
+	///
+	/// # Returns
+	///
+	/// The largest index in the set, or `None` if it is empty.
+	pub fn max(&self) -> Option<usize> {
+		let width = T::BITS as usize;
+		for (k, elt) in self.bits.as_slice().iter().enumerate().rev() {
+			let live = self.live_bits(k);
+			if let Some(n) = last_one::<O, T>(*elt & live_mask::<O, T>(live)) {
+				return Some(k * width + n);
+			}
+		}
+		None
+	}
+
+	/// Removes and returns the least member of the set.
+	///
+	/// This is the usual allocation step when the set is a free list.
+	///
+	/// # Returns
+	///
+	/// The smallest index in the set, or `None` if it is empty.
+	pub fn pop_min(&mut self) -> Option<usize> {
+		let min = self.min()?;
+		self.bits.set(min, false);
+		Some(min)
+	}
+
+	/// Views the membership bits of the set.
+	///
+	/// Bit `n` of the returned slice is set if, and only if, `n` is in the set.
+	/// The slice may be longer than `self.max() + 1`.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		&self.bits
+	}
+
+	/// Unwraps the set into its membership bits.
+	pub fn into_bitvec(self) -> BitVec<O, T> {
+		self.bits
+	}
+
+	/// Counts the bits of storage element `k` that are inside the vector.
+	fn live_bits(&self, k: usize) -> u8 {
+		let width = T::BITS as usize;
+		let len = self.bits.len();
+		if len - k * width >= width {
+			T::BITS
+		}
+		else {
+			(len % width) as u8
+		}
+	}
+}
+
+impl<O, T> Default for BitIndexSet<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<O, T> From<BitVec<O, T>> for BitIndexSet<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Uses a bit vector as a set, where the indices of its set bits are the
+	/// members.
+	fn from(mut bits: BitVec<O, T>) -> Self {
+		bits.force_align();
+		Self { bits }
+	}
+}
+
+impl<O, T> Extend<usize> for BitIndexSet<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn extend<I>(&mut self, iter: I)
+	where I: IntoIterator<Item = usize> {
+		for index in iter {
+			self.insert(index);
+		}
+	}
+}
+
+/// Produces a mask of the first `live` bits of an element, under an ordering.
+fn live_mask<O, T>(live: u8) -> T
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	if live == T::BITS {
+		return T::TRUE;
+	}
+	match BitSlice::<O, T>::linear_order() {
+		Some(true) => !(T::TRUE << live),
+		Some(false) => !(T::TRUE >> live),
+		None => (0 .. live).fold(T::FALSE, |mask, n| mask | *O::mask(n.idx())),
+	}
+}
+
+/// Finds the index, under an ordering, of the first set bit of an element.
+fn first_one<O, T>(elt: T) -> Option<usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	if elt == T::FALSE {
+		return None;
+	}
+	match BitSlice::<O, T>::linear_order() {
+		Some(true) => Some(elt.trailing_zeros()),
+		Some(false) => Some(elt.leading_zeros()),
+		None => (0 .. T::BITS)
+			.find(|&n| elt.get::<O>(n.idx()))
+			.map(|n| n as usize),
+	}
+}
+
+/// Finds the index, under an ordering, of the last set bit of an element.
+fn last_one<O, T>(elt: T) -> Option<usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	if elt == T::FALSE {
+		return None;
+	}
+	let last = T::MASK as usize;
+	match BitSlice::<O, T>::linear_order() {
+		Some(true) => Some(last - elt.leading_zeros()),
+		Some(false) => Some(last - elt.trailing_zeros()),
+		None => (0 .. T::BITS)
+			.rev()
+			.find(|&n| elt.get::<O>(n.idx()))
+			.map(|n| n as usize),
+	}
+}
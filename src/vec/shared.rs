@@ -0,0 +1,320 @@
+/*! A reference-counted, copy-on-write bit vector.
+
+Snapshots of a bitmap, such as the visibility mask of a table as of some query,
+are read far more often than they are changed, and are often handed to other
+threads. Cloning a `BitVec` for each snapshot copies the whole buffer.
+`SharedBitVec` keeps the buffer behind an atomic reference count instead, so
+that clones share it, and only copies it when a shared buffer is modified.
+!*/
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+	},
+	slice::BitSlice,
+	store::BitStore,
+	vec::BitVec,
+};
+
+use alloc::sync::Arc;
+
+use core::{
+	borrow::Borrow,
+	cmp::Ordering,
+	fmt::{
+		self,
+		Debug,
+		Display,
+		Formatter,
+	},
+	hash::{
+		Hash,
+		Hasher,
+	},
+	ops::Deref,
+};
+
+/** An immutable, shared bit vector, which copies its buffer on write.
+
+Cloning a `SharedBitVec` increments a reference count, rather than copying the
+bits. The bits are read through `Deref` to `BitSlice`, and modified through
+[`make_mut`], which copies the buffer first if any other handle shares it.
+
+The reference count is atomic, so handles can be sent to, and shared between,
+threads.
+
+# Examples
+
+```rust
+use bitvec::{prelude::*, vec::SharedBitVec};
+
+let live = SharedBitVec::from(bitvec![Msb0, u8; 1, 1, 0, 1]);
+let snapshot = live.clone();
+assert!(SharedBitVec::ptr_eq(&live, &snapshot));
+
+//  Writing to one handle detaches it from the other.
+let mut live = live;
+live.make_mut().set(0, false);
+assert!(!SharedBitVec::ptr_eq(&live, &snapshot));
+assert_eq!(live.as_bitslice(), bits![0, 1, 0, 1]);
+assert_eq!(snapshot.as_bitslice(), bits![1, 1, 0, 1]);
+```
+
+[`make_mut`]: #method.make_mut
+**/
+#[derive(Clone)]
+pub struct SharedBitVec<O = Local, T = usize>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// The shared buffer.
+	inner: Arc<BitVec<O, T>>,
+}
+
+impl<O, T> SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	/// Constructs a new, empty, shared vector.
+	pub fn new() -> Self {
+		Self::from(BitVec::new())
+	}
+
+	/// Copies a bit slice into a new shared vector.
+	pub fn from_bitslice(slice: &BitSlice<O, T>) -> Self {
+		Self::from(BitVec::from_bitslice(slice))
+	}
+
+	/// Views the shared bits.
+	pub fn as_bitslice(&self) -> &BitSlice<O, T> {
+		self.inner.as_bitslice()
+	}
+
+	/// Gets mutable access to the vector, copying it first if any other handle
+	/// shares it.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	///
+	/// # Returns
+	///
+	/// The vector, which no other handle shares.
+	pub fn make_mut(&mut self) -> &mut BitVec<O, T> {
+		Arc::make_mut(&mut self.inner)
+	}
+
+	/// Gets mutable access to the vector, if no other handle shares it.
+	///
+	/// # Parameters
+	///
+	/// - `&mut self`
+	///
+	/// # Returns
+	///
+	/// The vector, or `None` if it is shared, in which case nothing is copied.
+	pub fn get_mut(&mut self) -> Option<&mut BitVec<O, T>> {
+		Arc::get_mut(&mut self.inner)
+	}
+
+	/// Unwraps the shared vector into an owned one, copying it if any other
+	/// handle shares it.
+	pub fn into_bitvec(self) -> BitVec<O, T> {
+		Arc::try_unwrap(self.inner).unwrap_or_else(|arc| (*arc).clone())
+	}
+
+	/// Tests if two handles share the same buffer.
+	pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+		Arc::ptr_eq(&this.inner, &other.inner)
+	}
+
+	/// Counts the handles that share the buffer, including `this`.
+	pub fn strong_count(this: &Self) -> usize {
+		Arc::strong_count(&this.inner)
+	}
+}
+
+impl<O, T> AsRef<BitSlice<O, T>> for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn as_ref(&self) -> &BitSlice<O, T> {
+		self.as_bitslice()
+	}
+}
+
+impl<O, T> Borrow<BitSlice<O, T>> for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn borrow(&self) -> &BitSlice<O, T> {
+		self.as_bitslice()
+	}
+}
+
+impl<O, T> Debug for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		fmt.write_str("SharedBitVec<")?;
+		fmt.write_str(O::TYPENAME)?;
+		fmt.write_str(", ")?;
+		fmt.write_str(T::TYPENAME)?;
+		fmt.write_str("> ")?;
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+impl<O, T> Default for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<O, T> Deref for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Target = BitSlice<O, T>;
+
+	fn deref(&self) -> &Self::Target {
+		self.as_bitslice()
+	}
+}
+
+impl<O, T> Display for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+		Display::fmt(self.as_bitslice(), fmt)
+	}
+}
+
+impl<O, T> Eq for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+}
+
+impl<O, T> From<BitVec<O, T>> for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from(bv: BitVec<O, T>) -> Self {
+		Self {
+			inner: Arc::new(bv),
+		}
+	}
+}
+
+impl<O, T> From<&BitSlice<O, T>> for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn from(slice: &BitSlice<O, T>) -> Self {
+		Self::from_bitslice(slice)
+	}
+}
+
+/// Hashes as the shared `BitSlice` does, so that `Borrow` lookups agree.
+impl<O, T> Hash for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn hash<H>(&self, hasher: &mut H)
+	where H: Hasher {
+		self.as_bitslice().hash(hasher)
+	}
+}
+
+impl<O, T> Ord for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.as_bitslice().cmp(other.as_bitslice())
+	}
+}
+
+impl<O, T> PartialEq for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn eq(&self, other: &Self) -> bool {
+		Self::ptr_eq(self, other) || self.as_bitslice() == other.as_bitslice()
+	}
+}
+
+impl<O, T> PartialOrd for SharedBitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		order::Msb0,
+		slice::AsBits,
+	};
+
+	#[test]
+	fn copy_on_write() {
+		//  Every write sets a bit, so a write that reaches a shared buffer
+		//  shows up in the other handle.
+		let data = [0u32; 2];
+		let bits = &data.bits::<Msb0>()[3 .. 61];
+		let mut a = SharedBitVec::from(bits);
+		let b = a.clone();
+		assert_eq!(SharedBitVec::strong_count(&a), 2);
+		assert!(a.get_mut().is_none());
+
+		a.make_mut().push(true);
+		assert_eq!(SharedBitVec::strong_count(&b), 1);
+		assert_eq!(a[.. 58], *bits);
+		assert_eq!(a.len(), 59);
+		assert_eq!(b.as_bitslice(), bits);
+		assert!(b.not_any());
+
+		//  A unique handle is modified in place.
+		let addr = a.as_bitslice().as_ptr();
+		a.get_mut().unwrap().set(0, true);
+		assert_eq!(a.as_bitslice().as_ptr(), addr);
+		let bv = a.into_bitvec();
+		assert_eq!(bv.count_ones(), 2);
+		assert!(b.not_any());
+
+		let c = b.clone();
+		assert_eq!(b, c);
+		assert_eq!(c.into_bitvec(), bits);
+
+		let mut d = SharedBitVec::<Msb0, u8>::default();
+		assert!(d.is_empty());
+		assert!(d.get_mut().is_some());
+	}
+}
@@ -0,0 +1,402 @@
+/*! Unit tests for the `vec` module.
+!*/
+
+use crate::{
+	order::{
+		BitOrder,
+		Local,
+		Lsb0,
+		Msb0,
+	},
+	slice::{
+		AsBits,
+		BitSlice,
+	},
+	store::BitStore,
+	vec::{
+		BitIndexSet,
+		BitVec,
+	},
+};
+
+use alloc::vec::Vec;
+
+/// A vector with a mix of set and cleared bits in every element.
+fn pattern(len: usize) -> BitVec<Msb0, u8> {
+	(0 .. len).map(|n| n % 3 == 0 || n % 7 == 1).collect()
+}
+
+#[test]
+fn remove_indices() {
+	//  The gaps vary in width, so that the runs between them move by both
+	//  whole and partial elements.
+	for &gap in [1, 3, 8, 16, 17].iter() {
+		let mut bv = pattern(200);
+		let expected = bv
+			.iter()
+			.enumerate()
+			.filter(|&(n, _)| n % 50 >= gap)
+			.map(|(_, &bit)| bit)
+			.collect::<BitVec<Msb0, u8>>();
+		bv.remove_indices((0 .. 200).filter(|n| n % 50 < gap));
+		assert_eq!(bv, expected);
+	}
+
+	let mut bv = pattern(20);
+	bv.remove_indices(Vec::new());
+	assert_eq!(bv, pattern(20));
+	bv.remove_indices(0 .. 20);
+	assert!(bv.is_empty());
+}
+
+#[test]
+fn compact() {
+	let mask = [0xFF00_FFF0u32, 0x0F0F_0FFF, 0xFFFF_0000, 0x8000_0001];
+	let mask = mask.bits::<Lsb0>();
+	for len in [0, 1, 40, 95, 128].iter().copied() {
+		let mut bv = pattern(len);
+		let expected = bv
+			.iter()
+			.zip(mask)
+			.filter(|&(_, &keep)| keep)
+			.map(|(&bit, _)| bit)
+			.collect::<BitVec<Msb0, u8>>();
+		bv.compact(&mask[.. len]);
+		assert_eq!(bv, expected);
+	}
+}
+
+#[test]
+fn reader_drop() {
+	for &read in [0, 5, 8, 24, 29].iter() {
+		let mut bv = pattern(100);
+		let mut dst = [0u8; 4];
+		{
+			let mut reader = bv.reader();
+			let dst = &mut dst.bits_mut::<Msb0>()[.. read];
+			assert_eq!(reader.read_bits_into(dst), read);
+		}
+		assert_eq!(&dst.bits::<Msb0>()[.. read], &pattern(100)[.. read]);
+		assert_eq!(bv, pattern(100)[read ..]);
+	}
+}
+
+#[test]
+fn index_set() {
+	fn check<O>()
+	where O: BitOrder {
+		let mut set = BitIndexSet::<O, u16>::default();
+		assert_eq!(set.min(), None);
+		assert_eq!(set.max(), None);
+		assert_eq!(set.pop_min(), None);
+
+		for &idx in [70, 3, 40, 16, 15, 3].iter() {
+			set.insert(idx);
+		}
+		assert_eq!(set.len(), 5);
+		assert_eq!(set.min(), Some(3));
+		assert_eq!(set.max(), Some(70));
+		assert!(set.remove(70));
+		assert!(!set.remove(70));
+		assert_eq!(set.max(), Some(40));
+		assert_eq!(set.pop_min(), Some(3));
+		assert_eq!(set.pop_min(), Some(15));
+		assert_eq!(set.min(), Some(16));
+
+		//  Bits past the end of the vector are not members, even when the
+		//  storage still holds them.
+		let mut bits = BitVec::<O, u16>::repeat(true, 32);
+		bits.truncate(20);
+		let mut set = BitIndexSet::from(bits);
+		assert_eq!(set.max(), Some(19));
+		set.clear();
+		set.insert(2);
+		assert_eq!(set.min(), Some(2));
+		assert_eq!(set.max(), Some(2));
+		assert!(!set.contains(5));
+	}
+
+	check::<Msb0>();
+	check::<Lsb0>();
+	check::<Local>();
+}
+
+#[test]
+#[should_panic(expected = "Cannot insert usize::max_value() into a set")]
+fn index_set_overflow() {
+	BitIndexSet::<Msb0, u8>::new().insert(!0);
+}
+
+#[test]
+fn drain_fixed_partial_frame() {
+	let src = pattern(45);
+	//  Without padding, the last seven bits stay in the vector.
+	let mut bv = src.clone();
+	let frames = bv.drain_fixed(19).collect::<Vec<_>>();
+	assert_eq!(frames.len(), 2);
+	assert_eq!(frames[0].as_bitslice(), &src[.. 19]);
+	assert_eq!(frames[1].as_bitslice(), &src[19 .. 38]);
+	assert_eq!(bv, src[38 ..]);
+
+	//  With padding, they are yielded with zeros after them.
+	let mut bv = src.clone();
+	let mut iter = bv.drain_fixed(19).padded();
+	assert_eq!(iter.len(), 3);
+	let last = iter.nth(2).unwrap();
+	assert_eq!(&last[.. 7], &src[38 ..]);
+	assert!(last[7 ..].not_any());
+	assert_eq!(last.len(), 19);
+	drop(iter);
+	assert!(bv.is_empty());
+
+	//  Frames that are never consumed are still removed.
+	let mut bv = src.clone();
+	assert_eq!(bv.drain_fixed(10).len(), 4);
+	assert_eq!(bv, src[40 ..]);
+}
+
+#[test]
+fn drain_fixed_misaligned() {
+	//  The bytes count up from zero, so every frame is distinct, and the
+	//  last frame from each head is cut short by the end of the vector.
+	let data = [0x0302_0100u32, 0x0706_0504, 0x0B0A_0908];
+	for head in [1, 7, 13, 31].iter().copied() {
+		let bits = &data.bits::<Lsb0>()[head ..];
+		let mut bv = BitVec::from_bitslice(bits);
+		let mut iter = bv.drain_fixed(24);
+		for n in 0 .. bits.len() / 24 {
+			let frame = iter.next().unwrap();
+			assert_eq!(frame.as_bitslice(), &bits[n * 24 ..][.. 24]);
+		}
+		assert!(iter.next().is_none());
+		drop(iter);
+		assert_eq!(bv, bits[bits.len() - bits.len() % 24 ..]);
+	}
+}
+
+#[test]
+fn drain_fixed_empty() {
+	let mut bv = BitVec::<Msb0, u8>::new();
+	assert_eq!(bv.drain_fixed(8).len(), 0);
+	assert!(bv.drain_fixed(8).next().is_none());
+	assert!(bv.drain_fixed(8).padded().next().is_none());
+	assert!(bv.is_empty());
+
+	//  A vector shorter than one frame yields nothing until it is padded.
+	let mut bv = pattern(5);
+	assert!(bv.drain_fixed(8).next().is_none());
+	assert_eq!(bv, pattern(5));
+	let frame = bv.drain_fixed(8).padded().next().unwrap();
+	assert_eq!(&frame[.. 5], &pattern(5)[..]);
+	assert!(bv.is_empty());
+}
+
+/// Sets each bit that is set in at least `k` of the inputs, one bit at a time.
+fn naive_threshold<O, T>(k: usize, slices: &[&BitSlice<O, T>]) -> Vec<bool>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	let len = slices.first().map_or(0, |s| s.len());
+	(0 .. len)
+		.map(|n| slices.iter().filter(|s| s[n]).count() >= k)
+		.collect()
+}
+
+#[test]
+fn threshold_multi_element() {
+	//  The first element of the inputs holds every combination of their votes
+	//  once, and the others hold runs that end at different bits.
+	let data = [
+		[0x5555_5555u32, 0xAAAA_AAAA, 0x8000_0001, 0xFFFF_FFFF],
+		[0x3333_3333, 0xCCCC_CCCC, 0xC000_0003, 0x01FF_FFFF],
+		[0x0F0F_0F0F, 0xF0F0_F0F0, 0xE000_0007, 0x0003_FFFF],
+		[0x00FF_00FF, 0xFF00_FF00, 0xF000_000F, 0x0000_07FF],
+		[0x0000_FFFF, 0xFFFF_0000, 0xF800_001F, 0x0000_000F],
+	];
+	let firsts = data
+		.iter()
+		.map(|elts| &elts.bits::<Msb0>()[.. 32])
+		.collect::<Vec<_>>();
+	//  Of the 32 combinations of five votes, 31 have at least one, 26 at
+	//  least two, 16 at least three, 6 at least four, and 1 has all five.
+	for (k, &ones) in [31, 26, 16, 6, 1].iter().enumerate() {
+		assert_eq!(BitVec::threshold(k + 1, &firsts).count_ones(), ones);
+	}
+	for &len in [1, 31, 32, 33, 100, 128].iter() {
+		let slices = data
+			.iter()
+			.map(|elts| &elts.bits::<Msb0>()[.. len])
+			.collect::<Vec<_>>();
+		for n in 1 ..= slices.len() {
+			let inputs = &slices[.. n];
+			for k in 1 ..= n {
+				let bv = BitVec::threshold(k, inputs);
+				assert_eq!(bv.len(), len);
+				assert!(bv.iter().copied().eq(naive_threshold(k, inputs)));
+			}
+			let vote = BitVec::majority(inputs);
+			assert!(vote.iter().copied().eq(naive_threshold(n / 2 + 1, inputs)));
+		}
+	}
+}
+
+#[test]
+fn threshold_misaligned() {
+	let data = [
+		[0xFFFFu16, 0x0000, 0xFF00, 0x00FF, 0xAAAA, 0x5555, 0xF0F0],
+		[0x0000, 0xFFFF, 0x0FF0, 0xF00F, 0xCCCC, 0x3333, 0x8001],
+		[0x0123, 0x4567, 0x89AB, 0xCDEF, 0xFEDC, 0xBA98, 0x7654],
+	];
+	//  Each input begins at a different bit, and some cross more element
+	//  boundaries than others.
+	for &len in [1, 15, 16, 17, 60, 90].iter() {
+		let slices = data
+			.iter()
+			.zip([3usize, 0, 11].iter())
+			.map(|(elts, &head)| &elts.bits::<Lsb0>()[head ..][.. len])
+			.collect::<Vec<_>>();
+		for k in 1 ..= 3 {
+			let bv = BitVec::threshold(k, &slices);
+			assert!(bv.iter().copied().eq(naive_threshold(k, &slices)));
+		}
+		let vote = BitVec::majority(&slices);
+		assert!(vote.iter().copied().eq(naive_threshold(2, &slices)));
+
+		//  A vote with itself, from an unaligned start, is the input.
+		let same = BitVec::majority(&[slices[0], slices[0]]);
+		assert_eq!(same, slices[0]);
+	}
+}
+
+#[test]
+fn threshold_edges() {
+	let a = [0x0Fu8, 0xF0, 0xAA];
+	let b = [0x33u8, 0x00, 0xFF];
+	let slices = [&a.bits::<Msb0>()[2 ..], &b.bits::<Msb0>()[2 ..]];
+
+	//  Every bit is set in at least zero inputs.
+	let all = BitVec::threshold(0, &slices);
+	assert_eq!(all.len(), 22);
+	assert!(all.all());
+
+	//  No bit is set in more inputs than there are.
+	let none = BitVec::threshold(3, &slices);
+	assert_eq!(none.len(), 22);
+	assert!(none.not_any());
+	assert!(BitVec::threshold(!0, &slices).not_any());
+
+	//  A tie between two inputs is not a majority.
+	let vote = BitVec::majority(&slices);
+	assert!(vote.iter().copied().eq(naive_threshold(2, &slices)));
+
+	let empty: [&BitSlice<Msb0, u8>; 0] = [];
+	assert!(BitVec::threshold(0, &empty).is_empty());
+	assert!(BitVec::majority(&empty).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "Voting requires inputs of equal lengths")]
+fn threshold_unequal() {
+	let data = [0u8; 2];
+	let bits = data.bits::<Msb0>();
+	BitVec::threshold(1, &[&bits[.. 9], &bits[.. 10]]);
+}
+
+/// Scattered updates, with repeated indices, for a vector of `len` bits.
+fn batch(len: usize) -> Vec<(usize, bool)> {
+	let mut state = 0x2545_F491u32;
+	(0 .. len * 2)
+		.map(|_| {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+			(state as usize % len, state & 0x100 != 0)
+		})
+		.collect()
+}
+
+#[test]
+fn assign_batch_misaligned() {
+	//  Runs and alternations, so that each update is as likely to change a
+	//  bit as to keep it.
+	let data = [0xFFFFu16, 0x0000, 0xAAAA, 0x5555, 0xFF00, 0x00FF];
+	for &head in [0, 1, 7, 13].iter() {
+		let bits = &data.bits::<Lsb0>()[head ..];
+		let updates = batch(bits.len());
+
+		let mut expected = BitVec::<Lsb0, u16>::from_bitslice(bits);
+		for &(idx, bit) in &updates {
+			expected.set(idx, bit);
+		}
+
+		let mut bv = BitVec::from_bitslice(bits);
+		assert_eq!(*bv.bitptr().head() as usize, head);
+		bv.assign_batch(updates.iter().copied());
+		assert_eq!(bv, expected);
+
+		let mut bv = BitVec::from_bitslice(bits);
+		bv.extend(updates.iter().rev().copied().rev());
+		assert_eq!(bv, expected);
+	}
+}
+
+#[test]
+fn assign_batch_repeated() {
+	//  The last write to an index wins, even when the writes to it are split
+	//  by writes to other elements.
+	let mut bv = BitVec::<Msb0, u8>::repeat(false, 40);
+	bv.assign_batch(vec![
+		(3, true),
+		(35, true),
+		(3, false),
+		(20, true),
+		(3, true),
+		(35, false),
+		(20, true),
+	]);
+	assert_eq!(bv.count_ones(), 2);
+	assert!(bv[3] && bv[20] && !bv[35]);
+
+	bv.extend((0 .. 40).map(|n| (n, true)).chain((0 .. 40).map(|n| (n, false))));
+	assert!(bv.not_any());
+	bv.assign_batch(Vec::new());
+	assert!(bv.not_any());
+}
+
+#[test]
+fn assign_batch_spanning() {
+	//  Every update lands in a different element, across the whole vector.
+	let mut bv = pattern(200);
+	let mut expected = bv.clone();
+	let updates = (0 .. 200).step_by(9).map(|n| (n, n % 2 == 0));
+	for (idx, bit) in updates.clone() {
+		expected.set(idx, bit);
+	}
+	bv.assign_batch(updates);
+	assert_eq!(bv, expected);
+}
+
+#[test]
+#[should_panic(expected = "Index 40 out of bounds: 40")]
+fn assign_batch_out_of_bounds() {
+	let mut bv = BitVec::<Msb0, u8>::repeat(false, 40);
+	bv.assign_batch(vec![(0, true), (40, true)]);
+}
+
+#[test]
+fn concat_reused_allocation() {
+	//  Release a dirtied buffer of the same size so that the allocator is
+	//  likely to hand it back to `concat`.
+	drop(vec![!0u8; 64]);
+	let src = pattern(509);
+	let bv = BitVec::concat(&[&src[.. 250], &src[250 ..]]);
+	assert_eq!(bv, src);
+	//  The dead bits of the partial last element were zeroed, not inherited.
+	assert_eq!(*bv.as_slice().last().unwrap() & 0b111, 0);
+
+	drop(vec![!0u8; 64]);
+	let bv = BitVec::join(&[&src[.. 250], &src[251 ..]], &src[250 .. 251]);
+	assert_eq!(bv, src);
+	assert_eq!(*bv.as_slice().last().unwrap() & 0b111, 0);
+}
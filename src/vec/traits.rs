@@ -6,7 +6,9 @@ The operator traits are defined in the `ops` module.
 use super::*;
 
 use crate::{
+	fields::BitField,
 	order::BitOrder,
+	slice::AsBits,
 	store::BitStore,
 };
 
@@ -14,7 +16,6 @@ use alloc::{
 	borrow::{
 		Borrow,
 		BorrowMut,
-		ToOwned,
 	},
 	boxed::Box,
 	vec::Vec,
@@ -36,8 +37,7 @@ use core::{
 		Hash,
 		Hasher,
 	},
-	marker::PhantomData,
-	mem,
+	str::FromStr,
 };
 
 /// Signifies that `BitSlice` is the borrowed form of `BitVec`.
@@ -110,38 +110,22 @@ where
 	T: BitStore,
 {
 	fn clone(&self) -> Self {
-		let new_vec = self.as_slice().to_owned();
-		let capacity = new_vec.capacity();
-		let mut pointer = self.pointer;
-		unsafe {
-			pointer.set_pointer(new_vec.as_ptr());
-		}
-		mem::forget(new_vec);
-		Self {
-			_order: PhantomData,
-			pointer, // unsafe { BitPtr::new_unchecked(ptr, e, h, t) },
-			capacity,
-		}
+		Self::from_bitslice(self)
 	}
 
 	fn clone_from(&mut self, other: &Self) {
-		let slice = other.pointer.as_slice();
+		let slice = other.as_slice();
+		//  Reuse the existing storage, growing it if the other vector needs
+		//  more elements than it holds.
 		self.clear();
-		//  Copy the other data region into the underlying vector, then grab its
-		//  pointer and capacity values.
-		let (ptr, capacity) = self.with_vec(|v| {
-			v.copy_from_slice(slice);
-			(v.as_ptr(), v.capacity())
-		});
-		//  Copy the other `BitPtr<T>`,
+		self.reserve(slice.len() * T::BITS as usize);
+		//  Take the other vector’s head and length, and copy its elements.
 		let mut pointer = other.pointer;
-		//  Then set it to aim at the copied pointer.
 		unsafe {
-			pointer.set_pointer(ptr);
+			pointer.set_pointer(self.bitptr().pointer());
 		}
-		//  And set the new pointer/capacity.
 		self.pointer = pointer;
-		self.capacity = capacity;
+		self.as_mut_slice().copy_from_slice(slice);
 	}
 }
 
@@ -526,6 +510,59 @@ where
 	}
 }
 
+macro_rules! from_uint {
+	($( $t:ty ),* ) => { $(
+		/// Encodes an integer into a vector of the integer’s full width.
+		///
+		/// This is [`BitVec::from_uint`], and the inverse of the `TryFrom`
+		/// conversion from `&BitSlice` into the integer.
+		///
+		/// [`BitVec::from_uint`]: struct.BitVec.html#method.from_uint
+		impl<O, T> From<$t> for BitVec<O, T>
+		where
+			O: BitOrder,
+			T: BitStore,
+			BitSlice<O, T>: BitField,
+		{
+			fn from(src: $t) -> Self {
+				let width = <$t as BitStore>::BITS as usize;
+				Self::from_uint(src, width).expect("Integers fit their width")
+			}
+		}
+	)* };
+}
+
+from_uint!(u8, u16, u32, usize);
+
+#[cfg(target_pointer_width = "64")]
+from_uint!(u64);
+
+macro_rules! from_bytes_for {
+	($( $n:expr ),* ) => { $(
+		/// Copies the bits of a byte array into a vector, in the order given by
+		/// the vector’s `BitOrder`.
+		///
+		/// This is the inverse of the `TryFrom` conversion from `&BitSlice`
+		/// into the array.
+		impl<O, T> From<[u8; $n]> for BitVec<O, T>
+		where
+			O: BitOrder,
+			T: BitStore,
+		{
+			fn from(src: [u8; $n]) -> Self {
+				let mut out = Self::with_capacity($n * 8);
+				out.extend_from_slice(src.bits::<O>());
+				out
+			}
+		}
+	)* };
+}
+
+from_bytes_for![
+	0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20,
+	21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32
+];
+
 impl<O, T> Default for BitVec<O, T>
 where
 	O: BitOrder,
@@ -648,13 +685,44 @@ where
 	}
 }
 
+/** Parses a `BitVec` from a string of `0` and `1` characters.
+
+Separators and brackets are accepted as described in [`from_str_radix`], so the
+output of `Display` parses back into an equal vector.
+
+# Examples
+
+```rust
+use bitvec::prelude::*;
+
+let bv: BitVec<Msb0, u8> = "[01001011, 01]".parse().unwrap();
+assert_eq!(bv, bitvec![0, 1, 0, 0, 1, 0, 1, 1, 0, 1]);
+assert_eq!(format!("{}", bv).parse::<BitVec<Msb0, u8>>().unwrap(), bv);
+assert!("0120".parse::<BitVec>().is_err());
+```
+
+[`from_str_radix`]: struct.BitVec.html#method.from_str_radix
+**/
+impl<O, T> FromStr for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	type Err = ParseError;
+
+	fn from_str(src: &str) -> Result<Self, Self::Err> {
+		Self::from_str_radix(src, 2)
+	}
+}
+
 /// Writes the contents of the `BitVec`, in semantic bit order, into a hasher.
 impl<O, T> Hash for BitVec<O, T>
 where
 	O: BitOrder,
 	T: BitStore,
 {
-	/// Writes each bit of the `BitVec`, as a full `bool`, into the hasher.
+	/// Writes the bits of the `BitVec` into the hasher, as its `BitSlice`
+	/// does.
 	///
 	/// # Parameters
 	///
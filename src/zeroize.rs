@@ -0,0 +1,206 @@
+/*! Scrubbing of secret bits
+
+The [`zeroize`] crate defines a trait for types that can be reliably set to
+zero, with writes that the compiler may not remove even when the memory is
+never read again. This module implements it for `BitSlice` and
+`&mut BitSlice`, and for `BitBox` and `BitVec`.
+
+A `BitSlice` is cleared with [`BitSlice::secure_clear`], which leaves the bits
+of its edge elements that lie outside it untouched. The owning types clear
+more than their live bits: `BitBox` clears every bit of its elements, including
+those before its head and after its tail, and `BitVec` also clears the spare
+capacity of its allocation and its inline storage before it becomes empty.
+
+Memory that a `BitVec` has released by reallocating is not cleared. Reserve
+enough capacity before placing secrets in a vector that will grow.
+
+[`BitSlice::secure_clear`]: ../slice/struct.BitSlice.html#method.secure_clear
+[`zeroize`]: https://docs.rs/zeroize/1/zeroize/
+!*/
+
+#![cfg(feature = "zeroize")]
+
+use crate::{
+	order::BitOrder,
+	slice::BitSlice,
+	store::BitStore,
+};
+
+#[cfg(feature = "alloc")]
+use crate::{
+	boxed::BitBox,
+	vec::BitVec,
+};
+
+#[cfg(feature = "alloc")]
+use core::{
+	ptr,
+	sync::atomic::{
+		self,
+		Ordering,
+	},
+};
+
+use ::zeroize::Zeroize;
+
+/// Clears the bits of the slice, but not those of its edge elements that are
+/// outside it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::prelude::*;
+/// use zeroize::Zeroize;
+///
+/// let mut key = [!0u16; 2];
+/// key.bits_mut::<Lsb0>()[4 .. 28].zeroize();
+/// assert_eq!(key, [0x000F, 0xF000]);
+/// ```
+impl<O, T> Zeroize for BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn zeroize(&mut self) {
+		self.secure_clear();
+	}
+}
+
+/// Clears the bits of the borrowed slice, as `BitSlice::zeroize` does. This
+/// allows a mutable borrow to be passed where a sized `Zeroize` type is
+/// required.
+impl<O, T> Zeroize for &mut BitSlice<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn zeroize(&mut self) {
+		self.secure_clear();
+	}
+}
+
+/// Clears every bit of the box’s elements, and keeps its length.
+#[cfg(feature = "alloc")]
+impl<O, T> Zeroize for BitBox<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn zeroize(&mut self) {
+		let elts = self.as_mut_slice();
+		clear_elements(elts.as_mut_ptr(), elts.len());
+	}
+}
+
+/// Clears every element the vector can hold, including its spare capacity and
+/// inline storage, and then empties it.
+///
+/// # Examples
+///
+/// ```rust
+/// use bitvec::prelude::*;
+/// use zeroize::Zeroize;
+///
+/// let mut key = bitvec![Msb0, u8; 1; 40];
+/// let capacity = key.capacity();
+/// key.zeroize();
+/// assert!(key.is_empty());
+/// assert_eq!(key.capacity(), capacity);
+/// ```
+#[cfg(feature = "alloc")]
+impl<O, T> Zeroize for BitVec<O, T>
+where
+	O: BitOrder,
+	T: BitStore,
+{
+	fn zeroize(&mut self) {
+		for &(base, len) in &self.storage_regions() {
+			clear_elements(base, len);
+		}
+		self.clear();
+	}
+}
+
+/// Writes zero into each of `len` elements with a volatile store, and fences
+/// the stores against later accesses.
+#[cfg(feature = "alloc")]
+fn clear_elements<T>(base: *mut T, len: usize)
+where T: BitStore {
+	for idx in 0 .. len {
+		unsafe {
+			ptr::write_volatile(base.add(idx), T::FALSE);
+		}
+	}
+	atomic::compiler_fence(Ordering::SeqCst);
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+	use super::*;
+	use crate::{
+		order::{
+			Lsb0,
+			Msb0,
+		},
+		slice::AsBits,
+	};
+	use alloc::vec::Vec;
+
+	/// Reads every element in the vector’s storage.
+	fn storage<O, T>(bv: &mut BitVec<O, T>) -> Vec<T>
+	where
+		O: BitOrder,
+		T: BitStore,
+	{
+		bv.storage_regions()
+			.iter()
+			.flat_map(|&(base, len)| {
+				(0 .. len).map(move |idx| unsafe { ptr::read(base.add(idx)) })
+			})
+			.collect()
+	}
+
+	#[test]
+	fn scrub() {
+		let mut data = [!0u32; 3];
+		data.bits_mut::<Msb0>()[5 .. 90].zeroize();
+		assert_eq!(data, [0xF800_0000, 0, 0x0000_003F]);
+		data.bits_mut::<Lsb0>()[3 .. 4].zeroize();
+		assert_eq!(data[0], 0xF800_0000);
+		data.bits_mut::<Lsb0>()[27 .. 28].zeroize();
+		assert_eq!(data[0], 0xF000_0000);
+
+		fn wipe<Z: Zeroize>(mut secret: Z) {
+			secret.zeroize();
+		}
+		wipe(&mut data.bits_mut::<Msb0>()[4 .. 8]);
+		assert_eq!(data[0], 0xF000_0000);
+		wipe(&mut data.bits_mut::<Msb0>()[.. 2]);
+		assert_eq!(data[0], 0x3000_0000);
+
+		let bb = bitbox![Lsb0, u8; 1; 20];
+		let mut bb = BitBox::<Lsb0, u8>::from_bitslice(&bb[3 ..]);
+		bb.zeroize();
+		assert_eq!(bb.len(), 17);
+		assert!(bb.as_slice().iter().all(|&elt| elt == 0));
+
+		//  Bits written while the vector was inline remain in its inline
+		//  storage after it moves onto the heap.
+		let mut bv = BitVec::<Msb0, u8>::new();
+		bv.extend_from_slice([!0u8; 2].bits::<Msb0>());
+		assert!(bv.is_inline());
+		for _ in 0 .. 10 {
+			bv.extend_from_slice([!0u8; 8].bits::<Msb0>());
+		}
+		assert!(!bv.is_inline());
+		bv.truncate(8);
+		bv.zeroize();
+		assert!(bv.is_empty());
+		assert!(storage(&mut bv).iter().all(|&elt| elt == 0));
+
+		let mut bv = bitvec![Lsb0, u16; 1; 20];
+		assert!(bv.is_inline());
+		bv.zeroize();
+		assert!(storage(&mut bv).iter().all(|&elt| elt == 0));
+	}
+}
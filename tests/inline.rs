@@ -0,0 +1,77 @@
+/*! Exercise the inline storage of small `BitVec`s.
+
+Vectors whose elements fit in two `usize`s live inside their handle, so their
+contents move whenever the handle does. These tests move vectors across the
+boundary between inline and heap storage, and move the handles themselves, to
+check that no pointer into the old location survives.
+!*/
+
+#![cfg(feature = "alloc")]
+
+use bitvec::prelude::*;
+
+/// Moves a value through an opaque function, so that it changes address.
+#[inline(never)]
+fn relocate<T>(value: T) -> Box<T> {
+	Box::new(value)
+}
+
+#[test]
+fn spill_and_return() {
+	let inline_bits = 16 * std::mem::size_of::<usize>();
+	let mut bv = BitVec::<Msb0, u8>::new();
+	assert!(bv.is_inline());
+	for n in 0 .. inline_bits {
+		bv.push(n % 3 == 0);
+	}
+	assert!(bv.is_inline());
+	assert_eq!(bv.capacity(), inline_bits);
+
+	let moved = relocate(bv);
+	let ones = (0 .. inline_bits).filter(|n| n % 3 == 0).count();
+	assert_eq!(moved.count_ones(), ones);
+	let mut bv = *moved;
+
+	bv.push(true);
+	assert!(!bv.is_inline());
+	assert_eq!(bv.len(), inline_bits + 1);
+	for (n, bit) in bv[.. inline_bits].iter().enumerate() {
+		assert_eq!(*bit, n % 3 == 0);
+	}
+
+	bv.truncate(5);
+	bv.shrink_to_fit();
+	assert!(bv.is_inline());
+	let bv = *relocate(bv);
+	assert_eq!(bv, bits![1, 0, 0, 1, 0]);
+}
+
+#[test]
+fn copies() {
+	//  A misaligned region is copied with its head index intact.
+	let data = [0x0123_4567_89AB_CDEFu64, !0];
+	let region = &data.bits::<Lsb0>()[3 .. 70];
+	let bv = region.to_owned();
+	assert!(bv.is_inline());
+	assert_eq!(bv, region);
+
+	let clone = relocate(bv.clone());
+	assert_eq!(*clone, region);
+
+	//  Cloning into a vector reuses its storage, wherever that is.
+	let mut small = bitvec![Lsb0, u64; 1; 5];
+	small.clone_from(&bv);
+	assert_eq!(small, region);
+	let mut large = BitVec::<Lsb0, u64>::repeat(false, 1000);
+	large.clone_from(&bv);
+	assert_eq!(large, region);
+	large.clone_from(&BitVec::repeat(true, 800));
+	assert_eq!(large.count_ones(), 800);
+
+	//  Consuming iterators outlive the handle they came from.
+	let iter = relocate(bv.into_iter());
+	assert!(iter.eq(region.iter().copied()));
+
+	let leaked: &'static mut BitSlice<Lsb0, u64> = region.to_owned().leak();
+	assert_eq!(leaked, region);
+}